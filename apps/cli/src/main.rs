@@ -3,26 +3,61 @@
 //! Headless CLI for initializing a minimal world and validating invariants.
 
 use anyhow::Result;
-use chrono::{Datelike, NaiveDate};
+use chrono::NaiveDate;
 use persistence::{self, TelemetryRow};
 use sim_core::*;
 use tracing::{info, Level};
 use tracing_subscriber::EnvFilter;
 
-fn parse_args() -> (
-    Option<String>,
-    Option<u32>,
-    Option<String>,
-    Option<String>,
-    bool,
-    bool,
-) {
+/// Parsed command-line flags.
+struct CliArgs {
+    scenario: Option<String>,
+    years: Option<u32>,
+    campaign: Option<String>,
+    export_path: Option<String>,
+    /// Event log file for `--export-campaign`; a sidecar next to the export by default.
+    export_events: Option<String>,
+    export_dry_run: bool,
+    show_version: bool,
+    plan_trace: Option<String>,
+    sweep: Option<String>,
+    sweep_out: Option<String>,
+    calibrate: Option<String>,
+    calibrate_out: Option<String>,
+    backcast: Option<String>,
+    /// Recorded lockstep frames to fit tactics to, and where to write them.
+    clone_tactics: Option<String>,
+    clone_out: Option<String>,
+    /// Start seed for `--clone-tactics`; the campaign run's by default.
+    seed: Option<u64>,
+    /// Write telemetry and `--export-campaign` money columns in start-of-run prices.
+    real_prices: bool,
+    /// Custom start from `--sandbox` and its flags, instead of a campaign file.
+    sandbox: Option<scenario_pack::SandboxSetup>,
+    /// Strategy script playing the player in `--campaign` and `--sandbox` runs.
+    script: Option<String>,
+}
+
+fn parse_args() -> CliArgs {
     let mut scenario: Option<String> = None;
     let mut years: Option<u32> = None;
     let mut campaign: Option<String> = None;
     let mut export_path: Option<String> = None;
+    let mut export_events: Option<String> = None;
     let mut export_dry_run: bool = true; // default to dry-run for export
     let mut show_version: bool = false;
+    let mut plan_trace: Option<String> = None;
+    let mut sweep: Option<String> = None;
+    let mut sweep_out: Option<String> = None;
+    let mut calibrate: Option<String> = None;
+    let mut calibrate_out: Option<String> = None;
+    let mut backcast: Option<String> = None;
+    let mut clone_tactics: Option<String> = None;
+    let mut clone_out: Option<String> = None;
+    let mut seed: Option<u64> = None;
+    let mut real_prices = false;
+    let mut sandbox: Option<scenario_pack::SandboxSetup> = None;
+    let mut script: Option<String> = None;
     let mut it = std::env::args().skip(1);
     while let Some(arg) = it.next() {
         match arg.as_str() {
@@ -30,6 +65,7 @@ fn parse_args() -> (
             "--years" => years = it.next().and_then(|s| s.parse().ok()),
             "--campaign" => campaign = it.next(),
             "--export-campaign" => export_path = it.next(),
+            "--export-events" => export_events = it.next(),
             "--export-dry-run" => {
                 if let Some(v) = it.next() {
                     export_dry_run = v == "1" || v.eq_ignore_ascii_case("true");
@@ -38,17 +74,123 @@ fn parse_args() -> (
             "--version" | "-V" => {
                 show_version = true;
             }
+            "--plan-trace" => plan_trace = it.next(),
+            "--sweep" => sweep = it.next(),
+            "--sweep-out" => sweep_out = it.next(),
+            "--calibrate" => calibrate = it.next(),
+            "--calibrate-out" => calibrate_out = it.next(),
+            "--backcast" => backcast = it.next(),
+            "--clone-tactics" => clone_tactics = it.next(),
+            "--clone-out" => clone_out = it.next(),
+            "--seed" => seed = it.next().and_then(|s| s.parse().ok()),
+            "--real-prices" => real_prices = true,
+            "--script" => script = it.next(),
+            "--sandbox" => {
+                sandbox.get_or_insert_with(Default::default);
+            }
+            "--start-year" => {
+                if let Some(v) = it.next().and_then(|s| s.parse().ok()) {
+                    sandbox.get_or_insert_with(Default::default).start_year = v;
+                }
+            }
+            "--start-cash-cents" => {
+                if let Some(v) = it.next().and_then(|s| s.replace('_', "").parse().ok()) {
+                    sandbox
+                        .get_or_insert_with(Default::default)
+                        .player_cash_cents = v;
+                }
+            }
+            "--rivals" => {
+                if let Some(v) = it.next().and_then(|s| s.parse().ok()) {
+                    sandbox.get_or_insert_with(Default::default).competitors = v;
+                }
+            }
+            "--segments" => {
+                if let Some(v) = it.next() {
+                    sandbox.get_or_insert_with(Default::default).segments =
+                        v.split(',').map(|s| s.trim().to_string()).collect();
+                }
+            }
+            "--difficulty" => {
+                if let Some(v) = it.next() {
+                    sandbox.get_or_insert_with(Default::default).difficulty = Some(v);
+                }
+            }
             _ => {}
         }
     }
-    (
+    CliArgs {
         scenario,
         years,
         campaign,
         export_path,
+        export_events,
         export_dry_run,
         show_version,
-    )
+        plan_trace,
+        sweep,
+        sweep_out,
+        calibrate,
+        calibrate_out,
+        backcast,
+        clone_tactics,
+        clone_out,
+        seed,
+        real_prices,
+        sandbox,
+        script,
+    }
+}
+
+/// Write the last quarterly planner trace as pretty JSON.
+fn write_plan_trace(path: &str, trace: Option<&sim_runtime::LastPlanTrace>) -> Result<()> {
+    let Some(t) = trace.and_then(|t| t.0.as_ref()) else {
+        eprintln!("No planner trace recorded (run at least one quarter)");
+        return Ok(());
+    };
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    std::fs::write(path, serde_json::to_string_pretty(t)?)?;
+    println!(
+        "Planner trace written: {} ({} quarters)",
+        path,
+        t.quarters.len()
+    );
+    Ok(())
+}
+
+/// Let the strategy script at `path` play the player.
+fn attach_script(world: &mut sim_runtime::World, path: Option<&str>) -> Result<()> {
+    if let Some(path) = path {
+        let script = sim_runtime::StrategyScript::load(path)
+            .map_err(|e| anyhow::anyhow!("script {path}: {e}"))?;
+        sim_runtime::attach_player_script(world, script);
+    }
+    Ok(())
+}
+
+/// Summarize what the attached strategy script did, if one played.
+fn print_script_log(world: &sim_runtime::World) {
+    if let Some(log) = world.get_resource::<sim_runtime::PlayerScriptLog>() {
+        println!(
+            "Script | applied: {} | refused: {}",
+            log.applied, log.refused
+        );
+        for e in log.entries.iter().filter(|e| e.error.is_some()) {
+            println!(
+                "  {} {} -> {}",
+                e.date,
+                e.action.as_deref().unwrap_or("(script)"),
+                e.error.as_deref().unwrap_or_default()
+            );
+        }
+        for w in log.entries.iter().flat_map(|e| &e.warnings) {
+            println!("  {} warning {:?}: {}", w.date, w.code, w.message);
+        }
+    }
 }
 
 fn minimal_world() -> World {
@@ -97,26 +239,22 @@ fn minimal_world() -> World {
     }
 }
 
-#[derive(serde::Deserialize)]
-struct CampaignScenario {
-    start_date: String,
-    end_date: String,
-    player_start_cash_cents: I64OrStr,
+fn campaign_scenario_path(camp: &str) -> String {
+    if camp == "1990s" {
+        "assets/scenarios/campaign_1990s.yaml".to_string()
+    } else {
+        camp.to_string()
+    }
 }
 
-#[derive(serde::Deserialize)]
-#[serde(untagged)]
-enum I64OrStr {
-    I(i64),
-    S(String),
-}
-impl I64OrStr {
-    fn val(&self) -> i64 {
-        match self {
-            I64OrStr::I(i) => *i,
-            I64OrStr::S(s) => s.replace('_', "").parse::<i64>().unwrap_or(0),
-        }
-    }
+/// Build a runtime world for a campaign scenario file on the shipped game data; returns it
+/// with the campaign length in months.
+fn load_campaign(path: &str, seed: u64) -> Result<(sim_runtime::World, u32)> {
+    let text = std::fs::read_to_string(path)?;
+    let content = scenario_pack::content().map_err(anyhow::Error::msg)?;
+    content
+        .campaign_world(&text, seed)
+        .map_err(|e| anyhow::anyhow!("{path}: {e}"))
 }
 
 fn main() -> Result<()> {
@@ -127,7 +265,27 @@ fn main() -> Result<()> {
         .with_max_level(Level::INFO)
         .init();
 
-    let (scenario, years, campaign, export_path, export_dry_run, show_version) = parse_args();
+    let CliArgs {
+        scenario,
+        years,
+        campaign,
+        export_path,
+        export_events,
+        export_dry_run,
+        show_version,
+        plan_trace,
+        sweep,
+        sweep_out,
+        calibrate,
+        calibrate_out,
+        backcast,
+        clone_tactics,
+        clone_out,
+        seed,
+        real_prices,
+        sandbox,
+        script,
+    } = parse_args();
     if show_version {
         let ver = env!("CARGO_PKG_VERSION");
         let sha = option_env!("GIT_SHA").unwrap_or("unknown");
@@ -135,40 +293,205 @@ fn main() -> Result<()> {
         println!("chip-tycoon cli v{} ({} {})", ver, sha, date);
         return Ok(());
     }
-    info!(?scenario, ?years, ?campaign, ?export_path, "starting CLI");
+    info!(
+        ?scenario,
+        ?years,
+        ?campaign,
+        ?export_path,
+        ?plan_trace,
+        ?sweep,
+        ?calibrate,
+        ?backcast,
+        ?clone_tactics,
+        "starting CLI"
+    );
 
-    if let Some(camp) = campaign {
-        // Load campaign and run to completion
-        let path = if camp == "1990s" {
-            "assets/scenarios/campaign_1990s.yaml".to_string()
-        } else {
-            camp
+    if let Some(markets_path) = &backcast {
+        let cfg = sim_runtime::MarketConfigRes::from_yaml_file(markets_path)
+            .map_err(anyhow::Error::msg)?;
+        let rows = sim_runtime::backcast_demand(&cfg, 1990, years.unwrap_or(10));
+        print!("{}", sim_runtime::demand_backcast_report(&rows));
+        let failed = rows.iter().filter(|r| !r.violations.is_empty()).count();
+        if failed > 0 {
+            anyhow::bail!(
+                "demand backcast: {failed} segment(s) outside their units_per_year bounds"
+            );
+        }
+        println!("Demand backcast ok: {} segments", rows.len());
+        return Ok(());
+    }
+
+    if let Some(log_path) = &clone_tactics {
+        let frames: Vec<sim_runtime::CommandFrame> =
+            serde_json::from_str(&std::fs::read_to_string(log_path)?)?;
+        let mut ecs = match (&sandbox, &campaign) {
+            (Some(setup), _) => {
+                let mut setup = setup.clone();
+                if let Some(s) = seed {
+                    setup.seed = s;
+                }
+                let content = scenario_pack::content().map_err(anyhow::Error::msg)?;
+                setup.build(&content).map_err(anyhow::Error::msg)?.0
+            }
+            (None, Some(camp)) => {
+                load_campaign(&campaign_scenario_path(camp), seed.unwrap_or(42))?.0
+            }
+            (None, None) => anyhow::bail!(
+                "--clone-tactics needs the recorded game's start: --campaign or --sandbox"
+            ),
         };
-        let text = std::fs::read_to_string(&path)?;
-        let sc: CampaignScenario = serde_yaml::from_str(&text)?;
-        let start = chrono::NaiveDate::parse_from_str(&sc.start_date, "%Y-%m-%d")?;
-        let end = chrono::NaiveDate::parse_from_str(&sc.end_date, "%Y-%m-%d")?;
-        let months = ((end.year() - start.year()) * 12
-            + (end.month() as i32 - start.month() as i32))
-            .max(0) as u32;
-        let mut world = minimal_world();
-        world.macro_state.date = start;
-        if let Some(c) = world.companies.first_mut() {
-            c.cash_usd = persistence::cents_i64_to_decimal(sc.player_start_cash_cents.val());
+        let prior = ecs.resource::<sim_runtime::AiConfig>().0.tactics.clone();
+        let samples =
+            sim_runtime::tactics_samples(&mut ecs, &frames).map_err(anyhow::Error::msg)?;
+        let fit = sim_ai::fit_tactics(&samples, &prior).map_err(anyhow::Error::msg)?;
+        println!(
+            "Cloned tactics | months: {} | agreement: {:.0}% | share drop: {:.3} | cut: {:.1}% | shortage: {:.2} | raise: {:.1}% | margin floor: {:.1}%",
+            fit.samples,
+            fit.agreement * 100.0,
+            fit.tactics.share_drop_delta,
+            fit.tactics.price_epsilon_frac * 100.0,
+            fit.tactics.shortage_raise_threshold,
+            fit.tactics.shortage_raise_epsilon_frac * 100.0,
+            fit.tactics.min_margin_frac * 100.0,
+        );
+        // Shaped like the `tactics` block of an AI config, ready to swap in
+        let yaml = serde_yaml::to_string(&std::collections::BTreeMap::from([(
+            "tactics",
+            &fit.tactics,
+        )]))?;
+        let out_path = clone_out.unwrap_or_else(|| "telemetry/cloned_tactics.yaml".to_string());
+        if let Some(parent) = std::path::Path::new(&out_path).parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+        std::fs::write(&out_path, yaml)?;
+        println!("Cloned tactics written: {}", out_path);
+        return Ok(());
+    }
+
+    if let Some(spec_path) = &calibrate {
+        let spec =
+            scenario_pack::CalibrationSpec::from_yaml_str(&std::fs::read_to_string(spec_path)?)?;
+        let levels = scenario_pack::calibrate_difficulty(&spec).map_err(anyhow::Error::msg)?;
+        for l in &levels {
+            println!(
+                "Calibrated {} | target: {:.0}% | measured: {:.0}% (shipped {:.0}%) | {} trials",
+                l.level,
+                l.target * 100.0,
+                l.win_rate * 100.0,
+                l.current_win_rate * 100.0,
+                l.trials.len()
+            );
+        }
+        let yaml =
+            scenario_pack::calibration_proposal_yaml(&spec, &levels).map_err(anyhow::Error::msg)?;
+        let out_path =
+            calibrate_out.unwrap_or_else(|| "telemetry/difficulty_proposal.yaml".to_string());
+        if let Some(parent) = std::path::Path::new(&out_path).parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
         }
-        let cfg = SimConfig {
-            tick_days: 30,
-            rng_seed: 42,
+        std::fs::write(&out_path, yaml)?;
+        println!("Difficulty proposal written: {}", out_path);
+        return Ok(());
+    }
+
+    if let Some(spec_path) = &sweep {
+        let spec = sim_runtime::SweepSpec::from_yaml_str(&std::fs::read_to_string(spec_path)?)?;
+        let campaign_path = campaign.as_deref().map(campaign_scenario_path);
+        let setup = |seed: u64| match &campaign_path {
+            Some(p) => load_campaign(p, seed)
+                .map(|(w, _)| w)
+                .map_err(|e| format!("sweep: failed to load campaign {p}: {e}")),
+            None => Ok(sim_runtime::init_world(
+                minimal_world(),
+                SimConfig {
+                    tick_days: 30,
+                    rng_seed: seed,
+                },
+            )),
         };
-        let mut ecs = sim_runtime::init_world(world, cfg);
-        // Load 1990s assets into runtime for richer balance
-        let markets =
-            sim_runtime::MarketConfigRes::from_yaml_file("assets/data/markets_1990s.yaml")
-                .unwrap_or_default();
-        ecs.insert_resource(markets);
-        ecs.insert_resource(sim_runtime::load_market_events_yaml(
-            "assets/events/campaign_1990s.yaml",
-        ));
+        // Loading the base world up front fails a bad campaign before any run starts; grid
+        // values override whatever AiConfig it starts with
+        let base = setup(0)
+            .map_err(anyhow::Error::msg)?
+            .resource::<sim_runtime::AiConfig>()
+            .0
+            .clone();
+        let results = sim_runtime::run_ai_sweep(&spec, &base, setup).map_err(anyhow::Error::msg)?;
+        let rows: Vec<persistence::SweepRow> = results
+            .iter()
+            .map(|r| persistence::SweepRow {
+                combo_index: r.combo_index,
+                params: r.params.clone(),
+                runs: r.runs,
+                win_rate: r.win_rate as f64,
+                mean_cash_cents: r.mean_cash_cents,
+                min_cash_cents: r.min_cash_cents,
+                mean_profit_cents: r.mean_profit_cents,
+                mean_share: r.mean_share as f64,
+            })
+            .collect();
+        let out_path = sweep_out.unwrap_or_else(|| {
+            format!(
+                "telemetry/ai_sweep_{}.parquet",
+                chrono::Utc::now().format("%Y%m%d_%H%M%S")
+            )
+        });
+        persistence::write_sweep_parquet(&out_path, &rows)?;
+        if let Some(best) = results
+            .iter()
+            .max_by(|a, b| a.win_rate.total_cmp(&b.win_rate))
+        {
+            println!(
+                "Sweep best | win_rate: {:.0}% | mean cash: ${:.2} | {}",
+                best.win_rate * 100.0,
+                (best.mean_cash_cents as f64) / 100.0,
+                best.params
+            );
+        }
+        println!("Sweep written: {} ({} combinations)", out_path, rows.len());
+        return Ok(());
+    }
+
+    if let Some(mut setup) = sandbox {
+        if let Some(y) = years {
+            setup.years = y;
+        }
+        let content = scenario_pack::content().map_err(anyhow::Error::msg)?;
+        let (mut ecs, months) = setup.build(&content).map_err(anyhow::Error::msg)?;
+        if plan_trace.is_some() {
+            ecs.resource_mut::<sim_runtime::AiConfig>().0.planner.trace = true;
+        }
+        attach_script(&mut ecs, script.as_deref())?;
+        let (snap, _t) = sim_runtime::run_months_in_place(&mut ecs, months);
+        println!(
+            "Sandbox | start: {} | rivals: {} | months: {} | cash: ${:.2} | profit: ${:.2} | share: {:.1}% | state: {}",
+            setup.start_year,
+            setup.competitors,
+            snap.months_run,
+            (snap.cash_cents as f64) / 100.0,
+            (snap.profit_cents as f64) / 100.0,
+            snap.market_share * 100.0,
+            snap.state_hash,
+        );
+        print_script_log(&ecs);
+        if let Some(tp) = &plan_trace {
+            write_plan_trace(tp, ecs.get_resource::<sim_runtime::LastPlanTrace>())?;
+        }
+        return Ok(());
+    }
+
+    if let Some(camp) = campaign {
+        // Load campaign and run to completion
+        let path = campaign_scenario_path(&camp);
+        let (mut ecs, months) = load_campaign(&path, 42)?;
+        if plan_trace.is_some() {
+            ecs.resource_mut::<sim_runtime::AiConfig>().0.planner.trace = true;
+        }
+        attach_script(&mut ecs, script.as_deref())?;
         // Export monthly timeline if requested
         if let Some(path) = &export_path {
             #[derive(serde::Serialize)]
@@ -184,6 +507,18 @@ fn main() -> Result<()> {
                 share: f32,
                 output_units: u64,
                 inventory_units: u64,
+                price_delta_frac: f32,
+                capacity_requested_wafers: u64,
+                tapeouts_scheduled: u32,
+                rd_budget_cents: i64,
+                promo_units: u64,
+                promo_asp_cents: i64,
+                cannibalized_units: u64,
+                /// Sales of each product generation on sale
+                products: Vec<sim_runtime::ProductMonth>,
+                /// Share of available wafers whose output sold
+                utilization: f32,
+                idle_capacity_cents: i64,
                 active_mods: Vec<String>,
                 goals: Vec<String>,
             }
@@ -195,12 +530,23 @@ fn main() -> Result<()> {
                 None
             };
             for _ in 0..months {
-                if let Some(ref mut w) = target_world {
-                    let (_snap, _t) = sim_runtime::run_months_in_place(w, 1);
+                let (_snap, t) = if let Some(ref mut w) = target_world {
+                    sim_runtime::run_months_in_place(w, 1)
                 } else {
-                    let (_snap, _t) = sim_runtime::run_months_in_place(&mut ecs, 1);
-                }
+                    sim_runtime::run_months_in_place(&mut ecs, 1)
+                };
+                let month = t.into_iter().next().unwrap_or_default();
+                let month = if real_prices { month.real() } else { month };
                 let world_ref = target_world.as_ref();
+                // `--real-prices` deflates the world's money columns like the telemetry's
+                let prices = world_ref
+                    .unwrap_or(&ecs)
+                    .get_resource::<sim_runtime::PriceIndex>()
+                    .filter(|_| real_prices);
+                let cents = |usd: rust_decimal::Decimal| {
+                    persistence::decimal_to_cents_i64(prices.map_or(usd, |p| p.deflate(usd)))
+                        .unwrap_or(0)
+                };
                 let (dom, stats, pricing) = if let Some(w) = world_ref {
                     (
                         w.resource::<sim_runtime::DomainWorld>(),
@@ -217,25 +563,20 @@ fn main() -> Result<()> {
                 let date = dom.0.macro_state.date;
                 // Active mods summary
                 let mut active_list: Vec<String> = Vec::new();
-                if let Some(me) = if let Some(w) = world_ref {
-                    w.get_non_send_resource::<sim_runtime::ModEngineRes>()
-                } else {
-                    ecs.get_non_send_resource::<sim_runtime::ModEngineRes>()
-                } {
-                    for (id, start, end) in me.engine.active_effects_summary() {
-                        if date >= start && date < end {
-                            active_list.push(id.clone());
-                        }
-                    }
-                }
-                if let Some(mm) = if let Some(w) = world_ref {
-                    w.get_resource::<sim_runtime::MarketModEffects>()
+                if let Some(fx) = if let Some(w) = world_ref {
+                    w.get_resource::<sim_runtime::Effects>()
                 } else {
-                    ecs.get_resource::<sim_runtime::MarketModEffects>()
+                    ecs.get_resource::<sim_runtime::Effects>()
                 } {
-                    for e in &mm.0 {
-                        if date >= e.start && date < e.end {
-                            active_list.push(e.id.clone());
+                    for m in &fx.modifiers {
+                        if !matches!(
+                            m.source,
+                            sim_runtime::EffectSource::Difficulty
+                                | sim_runtime::EffectSource::Macro
+                        ) && m.active_at(date)
+                            && !active_list.contains(&m.id)
+                        {
+                            active_list.push(m.id.clone());
                         }
                     }
                 }
@@ -253,18 +594,26 @@ fn main() -> Result<()> {
                 rows.push(Row {
                     date: date.to_string(),
                     month_index: stats.months_run,
-                    cash_cents: persistence::decimal_to_cents_i64(dom.0.companies[0].cash_usd)
-                        .unwrap_or(0),
-                    revenue_cents: persistence::decimal_to_cents_i64(stats.revenue_usd)
-                        .unwrap_or(0),
-                    cogs_cents: persistence::decimal_to_cents_i64(stats.cogs_usd).unwrap_or(0),
-                    profit_cents: persistence::decimal_to_cents_i64(stats.profit_usd).unwrap_or(0),
-                    asp_cents: persistence::decimal_to_cents_i64(pricing.asp_usd).unwrap_or(0),
-                    unit_cost_cents: persistence::decimal_to_cents_i64(pricing.unit_cost_usd)
-                        .unwrap_or(0),
+                    cash_cents: cents(dom.0.companies[0].cash_usd),
+                    revenue_cents: cents(stats.revenue_usd),
+                    cogs_cents: cents(stats.cogs_usd),
+                    profit_cents: cents(stats.profit_usd),
+                    asp_cents: cents(pricing.asp_usd),
+                    unit_cost_cents: cents(pricing.unit_cost_usd),
                     share: stats.market_share,
                     output_units: stats.output_units,
                     inventory_units: stats.inventory_units,
+                    price_delta_frac: month.price_delta_frac,
+                    capacity_requested_wafers: month.capacity_requested_wafers,
+                    tapeouts_scheduled: month.tapeouts_scheduled,
+                    rd_budget_cents: month.rd_budget_cents,
+                    promo_units: month.promo_units,
+                    promo_asp_cents: persistence::decimal_to_cents_i64(month.promo_asp_usd)
+                        .unwrap_or(0),
+                    cannibalized_units: month.cannibalized_units,
+                    products: month.products,
+                    utilization: month.utilization,
+                    idle_capacity_cents: month.idle_capacity_cents,
                     active_mods: active_list,
                     goals,
                 });
@@ -294,6 +643,13 @@ fn main() -> Result<()> {
                         unit_cost_cents: r.unit_cost_cents,
                         margin_cents: r.profit_cents,
                         revenue_cents: r.revenue_cents,
+                        price_delta_frac: r.price_delta_frac as f64,
+                        capacity_requested_wafers: r.capacity_requested_wafers,
+                        tapeouts_scheduled: r.tapeouts_scheduled,
+                        rd_budget_cents: r.rd_budget_cents,
+                        promo_units: r.promo_units,
+                        promo_asp_cents: r.promo_asp_cents,
+                        cannibalized_units: r.cannibalized_units,
                     });
                 }
                 persistence::write_telemetry_parquet(path, &trows)?;
@@ -308,11 +664,36 @@ fn main() -> Result<()> {
                     path
                 );
             }
+            let events_path = match &export_events {
+                Some(p) => Some(std::path::PathBuf::from(p)),
+                None if path.ends_with(".json") || path.ends_with(".parquet") => {
+                    Some(persistence::event_log_path(path))
+                }
+                None => None,
+            };
+            if let Some(events_path) = events_path {
+                // Event log, joinable to the rows on month_index
+                let w = target_world.as_ref().unwrap_or(&ecs);
+                let events = w
+                    .get_resource::<sim_runtime::EventLog>()
+                    .map(|l| l.rows())
+                    .unwrap_or_default();
+                persistence::write_event_log(&events_path, &events)?;
+                println!(
+                    "Event log exported: {} ({} events)",
+                    events_path.display(),
+                    events.len()
+                );
+            }
+            if let Some(tp) = &plan_trace {
+                let w = target_world.as_ref().unwrap_or(&ecs);
+                write_plan_trace(tp, w.get_resource::<sim_runtime::LastPlanTrace>())?;
+            }
         } else {
             // Default run: just to completion
             let (snap, _t) = sim_runtime::run_months_in_place(&mut ecs, months);
             println!(
-                "KPI | date: {} | months: {} | cash: ${:.2} | profit: ${:.2}",
+                "KPI | date: {} | months: {} | cash: ${:.2} | profit: ${:.2} | state: {}",
                 ecs.resource::<sim_runtime::DomainWorld>()
                     .0
                     .macro_state
@@ -320,6 +701,7 @@ fn main() -> Result<()> {
                 snap.months_run,
                 (snap.cash_cents as f64) / 100.0,
                 (snap.profit_cents as f64) / 100.0,
+                snap.state_hash,
             );
             println!(
                 "Campaign 1990s — result: {}",
@@ -329,6 +711,10 @@ fn main() -> Result<()> {
                     "InProgress"
                 }
             );
+            print_script_log(&ecs);
+            if let Some(tp) = &plan_trace {
+                write_plan_trace(tp, ecs.get_resource::<sim_runtime::LastPlanTrace>())?;
+            }
         }
         return Ok(());
     }
@@ -345,6 +731,13 @@ fn main() -> Result<()> {
         rng_seed: 42,
     };
     let mut ecs_world = sim_runtime::init_world(world, cfg);
+    if plan_trace.is_some() {
+        ecs_world
+            .resource_mut::<sim_runtime::AiConfig>()
+            .0
+            .planner
+            .trace = true;
+    }
     let (snap, telemetry) = sim_runtime::run_months_in_place(&mut ecs_world, months);
 
     println!(
@@ -352,7 +745,7 @@ fn main() -> Result<()> {
         n_companies, n_nodes, n_segments
     );
     println!(
-        "KPI | date: {} | months: {} | cash: ${:.2} | revenue: ${:.2} | cogs: ${:.2} | contract_costs: ${:.2} | profit: ${:.2} | asp: ${:.2} | unit_cost: ${:.2} | share: {:.1}% | R&D: {:.1}% | output: {} | defects: {} | inv: {}",
+        "KPI | date: {} | months: {} | cash: ${:.2} | revenue: ${:.2} | cogs: ${:.2} | contract_costs: ${:.2} | profit: ${:.2} | asp: ${:.2} | unit_cost: ${:.2} | share: {:.1}% | R&D: {:.1}% | output: {} | defects: {} | inv: {} | state: {}",
         ecs_world.resource::<sim_runtime::DomainWorld>().0.macro_state.date,
         snap.months_run,
         (snap.cash_cents as f64) / 100.0,
@@ -366,12 +759,14 @@ fn main() -> Result<()> {
         snap.rd_progress * 100.0,
         snap.output_units,
         snap.defect_units,
-        snap.inventory_units
+        snap.inventory_units,
+        snap.state_hash
     );
 
     // Write telemetry parquet
     let rows: Vec<TelemetryRow> = telemetry
         .into_iter()
+        .map(|t| if real_prices { t.real() } else { t })
         .map(|t| TelemetryRow {
             month_index: t.month_index,
             output_units: t.output_units,
@@ -380,6 +775,13 @@ fn main() -> Result<()> {
             unit_cost_cents: persistence::decimal_to_cents_i64(t.unit_cost_usd).unwrap_or(0),
             margin_cents: persistence::decimal_to_cents_i64(t.margin_usd).unwrap_or(0),
             revenue_cents: persistence::decimal_to_cents_i64(t.revenue_usd).unwrap_or(0),
+            price_delta_frac: t.price_delta_frac as f64,
+            capacity_requested_wafers: t.capacity_requested_wafers,
+            tapeouts_scheduled: t.tapeouts_scheduled,
+            rd_budget_cents: t.rd_budget_cents,
+            promo_units: t.promo_units,
+            promo_asp_cents: persistence::decimal_to_cents_i64(t.promo_asp_usd).unwrap_or(0),
+            cannibalized_units: t.cannibalized_units,
         })
         .collect();
     let ts = chrono::Utc::now().format("%Y%m%d_%H%M%S");
@@ -389,6 +791,9 @@ fn main() -> Result<()> {
     } else {
         println!("Telemetry written: {}", out_path);
     }
+    if let Some(tp) = &plan_trace {
+        write_plan_trace(tp, ecs_world.get_resource::<sim_runtime::LastPlanTrace>())?;
+    }
 
     Ok(())
 }
@@ -2,6 +2,7 @@
 #![deny(warnings)]
 
 use chrono::Datelike;
+use tauri::Emitter; // for AppHandle.emit()
 use tauri::Manager; // for AppHandle.path()
 use once_cell::sync::Lazy;
 use schemars::JsonSchema;
@@ -9,6 +10,8 @@ use serde::{Deserialize, Serialize};
 use sqlx::Row;
 use sim_core as core;
 use sim_runtime as runtime;
+use sim_runtime::DEFAULT_SESSION;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 mod embedded;
 
@@ -51,7 +54,28 @@ fn from_yaml_with_coerce<T: for<'de> Deserialize<'de>>(yaml_text: &str) -> Resul
     serde_json::from_value::<T>(j2).map_err(|e| e.to_string())
 }
 
-fn validate_yaml<T: for<'de> Deserialize<'de> + JsonSchema>(
+/// Marks YAML text that passed `T`'s schema, so a reset with unchanged assets skips
+/// rebuilding and recompiling the schema.
+struct SchemaChecked<T>(std::marker::PhantomData<fn() -> T>);
+
+impl<T> Clone for SchemaChecked<T> {
+    fn clone(&self) -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+fn validate_yaml<T: for<'de> Deserialize<'de> + JsonSchema + 'static>(
+    yaml_text: &str,
+    schema_name: &str,
+) -> Result<(), String> {
+    runtime::cached_config(yaml_text, |text| {
+        check_yaml_schema::<T>(text, schema_name)?;
+        Ok(SchemaChecked::<T>(std::marker::PhantomData))
+    })
+    .map(|_| ())
+}
+
+fn check_yaml_schema<T: for<'de> Deserialize<'de> + JsonSchema>(
     yaml_text: &str,
     _schema_name: &str,
 ) -> Result<(), String> {
@@ -102,8 +126,243 @@ struct SimState {
     autosave: bool,
 }
 
-static SIM_STATE: Lazy<Arc<RwLock<Option<SimState>>>> = Lazy::new(|| Arc::new(RwLock::new(None)));
-static TICK_QUEUE: Lazy<Arc<Mutex<()>>> = Lazy::new(|| Arc::new(Mutex::new(())));
+/// One game: its sim state plus a queue that serializes ticks within the session.
+#[derive(Default)]
+struct Session {
+    state: RwLock<Option<SimState>>,
+    tick_queue: Mutex<()>,
+    /// Set by `sim_run_cancel` to stop a background `sim_run` after the current month.
+    cancel_run: AtomicBool,
+    audit: Mutex<AuditLog>,
+    /// Auto-advance loop holding the live world while it runs (`sim_auto_start`).
+    auto: Mutex<Option<runtime::AutoDriver>>,
+    /// Read-only view for spectators, rebuilt after every tick; `None` until first read after
+    /// a new or loaded game.
+    spectator: RwLock<Option<SpectatorView>>,
+    /// Spectator reads in the current second: (second, calls).
+    spectator_window: Mutex<(i64, u32)>,
+    /// Crash journal since the last autosave; closed while auto-advance runs.
+    journal: Mutex<Option<runtime::Journal>>,
+}
+
+// ------- Spectator
+
+/// Spectator reads allowed per session per second; further reads are refused.
+const SPECTATOR_MAX_PER_SEC: u32 = 20;
+/// Headlines kept in the spectator view.
+const SPECTATOR_NEWS: usize = 10;
+
+/// What a second window or stream overlay may see of a game; no command takes it back.
+#[derive(Serialize, Debug, Clone)]
+struct SpectatorView {
+    date: String,
+    month_index: u32,
+    state_hash: String,
+    companies: Vec<DtoCompany>,
+    segments: Vec<DtoSegment>,
+    kpi: DtoKpi,
+    regions: Vec<DtoRegion>,
+    reputation: f32,
+    /// Latest headlines, newest last.
+    news: Vec<runtime::NewsItem>,
+}
+
+fn spectator_view(st: &SimState) -> SpectatorView {
+    let dto = build_sim_state_dto(st);
+    let skip = dto.news.len().saturating_sub(SPECTATOR_NEWS);
+    SpectatorView {
+        date: dto.date,
+        month_index: dto.month_index,
+        state_hash: format!("{:016x}", runtime::world_state_hash(&st.world)),
+        companies: dto.companies,
+        segments: dto.segments,
+        kpi: dto.kpi,
+        regions: dto.regions,
+        reputation: dto.reputation,
+        news: dto.news.into_iter().skip(skip).collect(),
+    }
+}
+
+/// Rebuild the spectator view; called with the state already locked after a tick.
+fn refresh_spectator(sess: &Session, st: &SimState) {
+    *sess.spectator.write().unwrap() = Some(spectator_view(st));
+}
+
+/// Move the spectator view on to an auto-advanced month, whose world lives on the driver
+/// thread: only the date, KPIs and checksum change.
+fn spectator_auto_tick(sess: &Session, snap: &runtime::SimSnapshot, date: chrono::NaiveDate) {
+    if let Some(v) = sess.spectator.write().unwrap().as_mut() {
+        v.date = date.to_string();
+        v.month_index = snap.months_run;
+        v.state_hash = snap.state_hash.clone();
+        v.kpi = DtoKpi {
+            cash_cents: snap.cash_cents,
+            revenue_cents: snap.revenue_cents,
+            cogs_cents: snap.cogs_cents,
+            contract_costs_cents: snap.contract_costs_cents,
+            profit_cents: snap.profit_cents,
+            share: snap.market_share,
+            rd_pct: snap.rd_progress,
+            output_units: snap.output_units,
+            inventory_units: snap.inventory_units,
+            // Not in the snapshot; keep the last measured value
+            utilization: v.kpi.utilization,
+        };
+    }
+}
+
+/// Count a spectator read against the per-second limit.
+fn spectator_admit(window: &mut (i64, u32), now_ms: i64) -> Result<(), String> {
+    let sec = now_ms / 1000;
+    if window.0 != sec {
+        *window = (sec, 0);
+    }
+    if window.1 >= SPECTATOR_MAX_PER_SEC {
+        return Err("spectator rate limit reached; try again shortly".to_string());
+    }
+    window.1 += 1;
+    Ok(())
+}
+
+/// Cached spectator view of a session, built once from the world if no tick has yet.
+fn spectator_read(session_id: Option<String>) -> Result<SpectatorView, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let now_ms = PROCESS_START.elapsed().as_millis() as i64;
+    spectator_admit(&mut sess.spectator_window.lock().unwrap(), now_ms)?;
+    if let Some(v) = sess.spectator.read().unwrap().as_ref() {
+        return Ok(v.clone());
+    }
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "no game to watch yet".to_string())?;
+    let view = spectator_view(st);
+    *sess.spectator.write().unwrap() = Some(view.clone());
+    Ok(view)
+}
+
+/// Read-only view of the game as of its last tick, for a second window or overlay.
+#[tauri::command]
+fn spectator_state(session_id: Option<String>) -> Result<SpectatorView, String> {
+    spectator_read(session_id)
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct SpectatorKpi {
+    date: String,
+    month_index: u32,
+    state_hash: String,
+    kpi: DtoKpi,
+}
+
+/// Just the date, checksum and KPIs, for frequent polling.
+#[tauri::command]
+fn spectator_kpi(session_id: Option<String>) -> Result<SpectatorKpi, String> {
+    let v = spectator_read(session_id)?;
+    Ok(SpectatorKpi {
+        date: v.date,
+        month_index: v.month_index,
+        state_hash: v.state_hash,
+        kpi: v.kpi,
+    })
+}
+
+// ------- IPC audit
+
+/// Audited calls kept per command per second; further calls are only counted.
+const AUDIT_MAX_PER_SEC: u32 = 10;
+/// Buffered entries kept per session between saves (oldest dropped first).
+const AUDIT_BUFFER_CAP: usize = 5_000;
+
+static PROCESS_START: Lazy<std::time::Instant> = Lazy::new(std::time::Instant::now);
+
+/// Rate-limited trail of IPC audit rows for one session; every save stores the trail so far,
+/// and loading a save continues from its stored trail.
+#[derive(Default)]
+struct AuditLog {
+    rows: std::collections::VecDeque<persistence::AuditRow>,
+    /// Per command: (second window, calls recorded in it, calls suppressed since last row).
+    windows: std::collections::BTreeMap<String, (i64, u32, i64)>,
+}
+
+impl AuditLog {
+    /// Record `row` unless its command already hit the per-second limit; returns whether it
+    /// was kept.
+    fn record(&mut self, mut row: persistence::AuditRow) -> bool {
+        let sec = row.at_ms / 1000;
+        let w = self
+            .windows
+            .entry(row.command.clone())
+            .or_insert((sec, 0, 0));
+        if w.0 != sec {
+            w.0 = sec;
+            w.1 = 0;
+        }
+        if w.1 >= AUDIT_MAX_PER_SEC {
+            w.2 += 1;
+            return false;
+        }
+        w.1 += 1;
+        row.suppressed = std::mem::take(&mut w.2);
+        if self.rows.len() >= AUDIT_BUFFER_CAP {
+            self.rows.pop_front();
+        }
+        self.rows.push_back(row);
+        true
+    }
+
+    /// The trail so far, oldest first, as a save stores it.
+    fn to_vec(&self) -> Vec<persistence::AuditRow> {
+        self.rows.iter().cloned().collect()
+    }
+
+    fn restore(&mut self, rows: Vec<persistence::AuditRow>) {
+        self.rows = rows.into();
+        self.windows.clear();
+    }
+}
+
+/// Record one IPC command (arguments, duration, outcome) in the session's audit log.
+fn audit_ipc<T>(
+    sess: &Session,
+    session_id: Option<&str>,
+    command: &str,
+    args: serde_json::Value,
+    started: std::time::Instant,
+    res: &Result<T, String>,
+) {
+    let duration_ms = started.elapsed().as_millis() as i64;
+    tracing::debug!(target: "ipc.audit", command, %args, duration_ms, ok = res.is_ok(), "ipc");
+    let _ = sess.audit.lock().unwrap().record(persistence::AuditRow {
+        session_id: session_id.unwrap_or(DEFAULT_SESSION).to_string(),
+        command: command.to_string(),
+        args_json: args.to_string(),
+        duration_ms,
+        ok: res.is_ok(),
+        error: res.as_ref().err().cloned(),
+        suppressed: 0,
+        at_ms: PROCESS_START.elapsed().as_millis() as i64,
+    });
+}
+
+/// Run a synchronous command body and audit it.
+fn audited<T>(
+    session_id: Option<String>,
+    command: &str,
+    args: serde_json::Value,
+    f: impl FnOnce() -> Result<T, String>,
+) -> Result<T, String> {
+    let started = std::time::Instant::now();
+    let res = f();
+    if let Ok(sess) = SESSIONS.get(session_id.as_deref()) {
+        audit_ipc(&sess, session_id.as_deref(), command, args, started, &res);
+    }
+    res
+}
+
+/// Independent games keyed by session id, each with its own lock so one session ticking
+/// never blocks another.
+static SESSIONS: Lazy<runtime::Sessions<Session>> = Lazy::new(Default::default);
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct PlanSummary {
@@ -112,12 +371,19 @@ struct PlanSummary {
 }
 
 #[tauri::command]
-async fn sim_tick(app: tauri::AppHandle, months: u32) -> Result<runtime::SimSnapshot, String> {
-    tracing::info!(target: "ipc", months, "sim_tick");
+async fn sim_tick(
+    app: tauri::AppHandle,
+    months: u32,
+    session_id: Option<String>,
+) -> Result<runtime::SimSnapshot, String> {
+    tracing::info!(target: "ipc", months, session = ?session_id, "sim_tick");
+    let started = std::time::Instant::now();
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let main_sess = sess.clone();
     let (tx, rx) = std::sync::mpsc::channel();
     let _ = app.run_on_main_thread(move || {
-        let state = SIM_STATE.clone();
-        let queue = TICK_QUEUE.clone();
+        let state = &main_sess.state;
+        let queue = &main_sess.tick_queue;
         let res = (|| {
             let _q = queue.lock().unwrap();
             {
@@ -134,6 +400,8 @@ async fn sim_tick(app: tauri::AppHandle, months: u32) -> Result<runtime::SimSnap
                 let mut guard = state.write().unwrap();
                 let st = guard.as_mut().unwrap();
                 let (snap, _t) = runtime::run_months_in_place(&mut st.world, months);
+                journal(&main_sess, |j| j.month(&st.world));
+                refresh_spectator(&main_sess, st);
                 snap
             };
             {
@@ -145,20 +413,44 @@ async fn sim_tick(app: tauri::AppHandle, months: u32) -> Result<runtime::SimSnap
         })();
         let _ = tx.send(res);
     });
-    let snap = rx.recv().map_err(|e| e.to_string())??;
+    let res = rx.recv().map_err(|e| e.to_string()).and_then(|r| r);
+    audit_ipc(
+        &sess,
+        session_id.as_deref(),
+        "sim_tick",
+        serde_json::json!({ "months": months }),
+        started,
+        &res,
+    );
+    let snap = res?;
     tracing::info!(target: "ipc", months_run = snap.months_run, "sim_tick: ok");
     Ok(snap)
 }
 
+/// Quarter tick result: the usual snapshot plus the turn's "what happened" digest.
+#[derive(Serialize, Debug, Clone)]
+struct QuarterTick {
+    #[serde(flatten)]
+    snapshot: runtime::SimSnapshot,
+    summary: runtime::TurnSummary,
+}
+
 #[tauri::command]
-async fn sim_tick_quarter(app: tauri::AppHandle) -> Result<runtime::SimSnapshot, String> {
-    tracing::info!(target: "ipc", "sim_tick_quarter");
+async fn sim_tick_quarter(
+    app: tauri::AppHandle,
+    session_id: Option<String>,
+) -> Result<QuarterTick, String> {
+    tracing::info!(target: "ipc", session = ?session_id, "sim_tick_quarter");
+    let started = std::time::Instant::now();
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let main_sess = sess.clone();
     // Precompute autosave DB URL (to avoid borrowing `app` inside main-thread closure)
     let db_url_opt = saves_db_url(&app).ok();
+    let journal_file = journal_path(&app, session_id.as_deref()).ok();
     let (tx, rx) = std::sync::mpsc::channel();
     let _ = app.run_on_main_thread(move || {
-        let state = SIM_STATE.clone();
-        let queue = TICK_QUEUE.clone();
+        let state = &main_sess.state;
+        let queue = &main_sess.tick_queue;
         let res = (|| {
             let _q = queue.lock().unwrap();
             {
@@ -174,9 +466,12 @@ async fn sim_tick_quarter(app: tauri::AppHandle) -> Result<runtime::SimSnapshot,
             let snap = {
                 let mut guard = state.write().unwrap();
                 let st = guard.as_mut().unwrap();
+                let turn = runtime::TurnStart::capture(&st.world);
                 let (_s1, _t1) = runtime::run_months_in_place(&mut st.world, 1);
                 let (_s2, _t2) = runtime::run_months_in_place(&mut st.world, 1);
                 let (s3, _t3) = runtime::run_months_in_place(&mut st.world, 1);
+                journal(&main_sess, |j| j.month(&st.world));
+                refresh_spectator(&main_sess, st);
                 if st.autosave {
                     let date = st
                         .world
@@ -185,17 +480,37 @@ async fn sim_tick_quarter(app: tauri::AppHandle) -> Result<runtime::SimSnapshot,
                         .macro_state
                         .date;
                     let name = format!("auto-{}{:02}", date.year(), date.month());
-                    let dom_clone = st.dom.clone();
-                    let world_clone = runtime::clone_world_state(&st.world);
-                    if let Some(db_url) = db_url_opt.clone() {
-                        tauri::async_runtime::spawn(async move {
-                            let _ = save_now(db_url, name, dom_clone, world_clone).await;
-                        });
-                    } else {
-                        tracing::error!(target: "ipc", "autosave: db url error");
+                    let audit = main_sess.audit.lock().unwrap().to_vec();
+                    match (db_url_opt.clone(), runtime::save_data(&st.world, &name, audit)) {
+                        (Some(db_url), Ok(data)) => {
+                            let mark = main_sess
+                                .journal
+                                .lock()
+                                .unwrap()
+                                .as_ref()
+                                .map(|j| (j.base_hash().to_string(), j.len()));
+                            let sess = main_sess.clone();
+                            let path = journal_file.clone();
+                            tauri::async_runtime::spawn(async move {
+                                let save_id = match write_save_data(db_url, data.clone()).await {
+                                    Ok(id) => id,
+                                    Err(e) => {
+                                        // The old journal keeps covering play since the last good save
+                                        tracing::error!(target: "ipc", error = %e, "autosave not written");
+                                        return;
+                                    }
+                                };
+                                rebase_journal(&sess, path, &data, save_id, mark);
+                            });
+                        }
+                        (None, _) => tracing::error!(target: "ipc", "autosave: db url error"),
+                        (_, Err(e)) => tracing::error!(target: "ipc", error = %e, "autosave failed"),
                     }
                 }
-                s3
+                QuarterTick {
+                    summary: runtime::TurnSummary::build(&st.world, &turn),
+                    snapshot: s3,
+                }
             };
             {
                 let mut guard = state.write().unwrap();
@@ -206,17 +521,340 @@ async fn sim_tick_quarter(app: tauri::AppHandle) -> Result<runtime::SimSnapshot,
         })();
         let _ = tx.send(res);
     });
-    let snap = rx.recv().map_err(|e| e.to_string())??;
-    tracing::info!(target: "ipc", months_run = snap.months_run, "sim_tick_quarter: ok");
-    Ok(snap)
+    let res = rx.recv().map_err(|e| e.to_string()).and_then(|r| r);
+    audit_ipc(
+        &sess,
+        session_id.as_deref(),
+        "sim_tick_quarter",
+        serde_json::json!({}),
+        started,
+        &res,
+    );
+    let tick = res?;
+    tracing::info!(target: "ipc", months_run = tick.snapshot.months_run, "sim_tick_quarter: ok");
+    Ok(tick)
+}
+
+/// Event emitted by `sim_run` after each completed month.
+const RUN_PROGRESS_EVENT: &str = "sim-run-progress";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct RunProgress {
+    session_id: String,
+    months_done: u32,
+    months_total: u32,
+    pct: f32,
+    date: String,
+}
+
+/// Long run result: the snapshot after the last completed month, and whether
+/// `sim_run_cancel` stopped the run, or a pending decision paused it, before all its months.
+#[derive(Serialize, Debug, Clone)]
+struct RunResult {
+    #[serde(flatten)]
+    snapshot: runtime::SimSnapshot,
+    months_done: u32,
+    cancelled: bool,
+    paused: bool,
+}
+
+/// Long run in the background: each month is ticked in its own main-thread hop so the UI
+/// stays responsive, a `sim-run-progress` event follows every month, and `sim_run_cancel`
+/// stops the run early. A decision waiting for the player pauses the run where it stands,
+/// so `sim_decide` can resolve it. A run of 0 months returns the current snapshot.
+#[tauri::command]
+async fn sim_run(
+    app: tauri::AppHandle,
+    months: u32,
+    session_id: Option<String>,
+) -> Result<RunResult, String> {
+    tracing::info!(target: "ipc", months, session = ?session_id, "sim_run");
+    let started = std::time::Instant::now();
+    let sid = session_id.unwrap_or_else(|| DEFAULT_SESSION.to_string());
+    let sess = SESSIONS.get(Some(sid.as_str()))?;
+    let run = {
+        let mut guard = sess.state.write().unwrap();
+        let st = guard
+            .as_mut()
+            .ok_or_else(|| "sim not initialized".to_string())?;
+        if st.busy {
+            return Err("busy".to_string());
+        }
+        st.busy = true;
+        runtime::MonthRun::new(&st.world, months)
+    };
+    sess.cancel_run.store(false, Ordering::SeqCst);
+    let mut res: Result<runtime::MonthRun, String> = Ok(run);
+    loop {
+        // The run crosses to the main thread and back with each month
+        let mut run = match res {
+            Ok(run) if !run.is_over() => run,
+            _ => break,
+        };
+        let (tx, rx) = std::sync::mpsc::channel();
+        let s = sess.clone();
+        let _ = app.run_on_main_thread(move || {
+            let _q = s.tick_queue.lock().unwrap();
+            let mut guard = s.state.write().unwrap();
+            let out = match guard.as_mut() {
+                Some(st) => {
+                    let ran = run.step(&mut st.world, &s.cancel_run);
+                    if ran {
+                        journal(&s, |j| j.month(&st.world));
+                        refresh_spectator(&s, st);
+                    }
+                    let date = st
+                        .world
+                        .resource::<runtime::DomainWorld>()
+                        .0
+                        .macro_state
+                        .date;
+                    Ok((run, ran.then_some(date)))
+                }
+                None => Err("sim not initialized".to_string()),
+            };
+            let _ = tx.send(out);
+        });
+        res = rx
+            .recv()
+            .map_err(|e| e.to_string())
+            .and_then(|r| r)
+            .map(|(run, date)| {
+                if let Some(date) = date {
+                    let _ = app.emit(
+                        RUN_PROGRESS_EVENT,
+                        RunProgress {
+                            session_id: sid.clone(),
+                            months_done: run.months_done,
+                            months_total: months,
+                            pct: run.months_done as f32 * 100.0 / months as f32,
+                            date: date.to_string(),
+                        },
+                    );
+                }
+                run
+            });
+    }
+    if let Some(st) = sess.state.write().unwrap().as_mut() {
+        st.busy = false;
+    }
+    let res = res.map(|run| {
+        if run.cancelled {
+            tracing::info!(target: "ipc", months_done = run.months_done, "sim_run: cancelled");
+        } else if run.paused {
+            tracing::info!(target: "ipc", months_done = run.months_done, "sim_run: paused for a decision");
+        }
+        RunResult {
+            snapshot: run.snapshot,
+            months_done: run.months_done,
+            cancelled: run.cancelled,
+            paused: run.paused,
+        }
+    });
+    audit_ipc(
+        &sess,
+        Some(&sid),
+        "sim_run",
+        serde_json::json!({ "months": months }),
+        started,
+        &res,
+    );
+    let run = res?;
+    tracing::info!(target: "ipc", months_run = run.snapshot.months_run, cancelled = run.cancelled, paused = run.paused, "sim_run: ok");
+    Ok(run)
+}
+
+/// Ask a running `sim_run` in this session to stop after the month in progress.
+#[tauri::command]
+fn sim_run_cancel(session_id: Option<String>) -> Result<(), String> {
+    tracing::info!(target: "ipc", session = ?session_id, "sim_run_cancel");
+    SESSIONS
+        .get(session_id.as_deref())?
+        .cancel_run
+        .store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Event emitted by the auto-advance loop after each month or failed player action.
+const AUTO_TICK_EVENT: &str = "sim-auto-tick";
+
+#[derive(Serialize, Debug, Clone)]
+struct AutoTick {
+    session_id: String,
+    /// Date after the month; null for a failed action.
+    date: Option<String>,
+    snapshot: Option<runtime::SimSnapshot>,
+    /// Why a queued player action failed.
+    error: Option<String>,
+}
+
+/// Event emitted by the auto-advance loop when it stops running months until the pending
+/// decisions are resolved with `sim_decide`.
+const AUTO_PAUSED_EVENT: &str = "sim-auto-paused";
+
+#[derive(Serialize, Debug, Clone)]
+struct AutoPaused {
+    session_id: String,
+    pending_decisions: Vec<DtoDecision>,
+}
+
+/// Event emitted by the auto-advance loop when the hint engine spots a player mistake.
+const HINT_EVENT: &str = "sim-hint";
+
+#[derive(Serialize, Debug, Clone)]
+struct HintEvent {
+    session_id: String,
+    hint: runtime::Hint,
+}
+
+/// Watch the sim run: hand the session world to a runtime thread that advances
+/// `ticks_per_second` months a second, emitting `sim-auto-tick` after each, until
+/// `sim_auto_stop`. Ticks are refused meanwhile and player actions are queued between months.
+#[tauri::command]
+fn sim_auto_start(
+    app: tauri::AppHandle,
+    ticks_per_second: f32,
+    session_id: Option<String>,
+) -> Result<(), String> {
+    tracing::info!(target: "ipc", ticks_per_second, session = ?session_id, "sim_auto_start");
+    let sid = session_id.unwrap_or_else(|| DEFAULT_SESSION.to_string());
+    let sess = SESSIONS.get(Some(sid.as_str()))?;
+    let mut auto = sess.auto.lock().unwrap();
+    if let Some(driver) = auto.as_ref() {
+        return driver.set_rate(ticks_per_second);
+    }
+    if !(ticks_per_second > 0.0 && ticks_per_second <= runtime::MAX_AUTO_TICKS_PER_SECOND) {
+        return Err(format!(
+            "ticks per second must be within (0, {}]",
+            runtime::MAX_AUTO_TICKS_PER_SECOND
+        ));
+    }
+    let mut guard = sess.state.write().unwrap();
+    let st = guard
+        .as_mut()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    if st.busy {
+        return Err("busy".to_string());
+    }
+    // Months on the auto thread are not journaled; recovery stops where it took over
+    sess.journal.lock().unwrap().take();
+    // The session keeps a copy to read from until the world comes back
+    let view = runtime::clone_world_state(&st.world);
+    let driver = runtime::start_auto(std::mem::replace(&mut st.world, view), ticks_per_second)?;
+    st.busy = true;
+    let events = driver.subscribe();
+    let view_sess = sess.clone();
+    std::thread::spawn(move || {
+        for ev in events {
+            let tick = match ev {
+                runtime::AutoEvent::Tick { snapshot, date } => {
+                    spectator_auto_tick(&view_sess, &snapshot, date);
+                    AutoTick {
+                        session_id: sid.clone(),
+                        date: Some(date.to_string()),
+                        snapshot: Some(snapshot),
+                        error: None,
+                    }
+                }
+                runtime::AutoEvent::CommandFailed(e) => AutoTick {
+                    session_id: sid.clone(),
+                    date: None,
+                    snapshot: None,
+                    error: Some(e),
+                },
+                runtime::AutoEvent::Paused(pending) => {
+                    let _ = app.emit(
+                        AUTO_PAUSED_EVENT,
+                        AutoPaused {
+                            session_id: sid.clone(),
+                            pending_decisions: pending.iter().map(decision_dto).collect(),
+                        },
+                    );
+                    continue;
+                }
+                runtime::AutoEvent::Hint(hint) => {
+                    let _ = app.emit(
+                        HINT_EVENT,
+                        HintEvent {
+                            session_id: sid.clone(),
+                            hint,
+                        },
+                    );
+                    continue;
+                }
+            };
+            let _ = app.emit(AUTO_TICK_EVENT, tick);
+        }
+    });
+    *auto = Some(driver);
+    Ok(())
+}
+
+/// Stop auto-advance after the month in progress and return the session world.
+#[tauri::command]
+fn sim_auto_stop(session_id: Option<String>) -> Result<SimStateDto, String> {
+    tracing::info!(target: "ipc", session = ?session_id, "sim_auto_stop");
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let driver = sess
+        .auto
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or_else(|| "auto-advance is not running".to_string())?;
+    let world = driver.stop_auto()?;
+    let mut guard = sess.state.write().unwrap();
+    let st = guard
+        .as_mut()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    st.world = world;
+    st.dom = st.world.resource::<runtime::DomainWorld>().0.clone();
+    st.busy = false;
+    refresh_spectator(&sess, st);
+    Ok(build_sim_state_dto(st))
 }
 
+/// Lockstep frame for this session's next month: its month index and state hash plus the
+/// given commands, ready to send to every instance of the game.
 #[tauri::command]
-async fn sim_plan_quarter() -> Result<PlanSummary, String> {
-    let guard = SIM_STATE.read().unwrap();
+fn sim_lockstep_frame(
+    commands: Vec<runtime::SeatCommand>,
+    session_id: Option<String>,
+) -> Result<runtime::CommandFrame, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
     let st = guard
         .as_ref()
         .ok_or_else(|| "sim not initialized".to_string())?;
+    Ok(runtime::command_frame(&st.world, commands))
+}
+
+/// Apply a lockstep frame and run its month; refused, with the world untouched, when the
+/// frame was built against a different state.
+#[tauri::command]
+fn sim_lockstep_step(
+    frame: runtime::CommandFrame,
+    session_id: Option<String>,
+) -> Result<runtime::LockstepOutcome, String> {
+    let args = serde_json::to_value(&frame).unwrap_or_default();
+    audited(session_id.clone(), "sim_lockstep_step", args, || {
+        let sess = SESSIONS.get(session_id.as_deref())?;
+        let _q = sess.tick_queue.lock().unwrap();
+        let mut guard = sess.state.write().unwrap();
+        let st = guard
+            .as_mut()
+            .ok_or_else(|| "sim not initialized".to_string())?;
+        if st.busy {
+            return Err("busy".to_string());
+        }
+        let out = runtime::step_lockstep(&mut st.world, &frame)?;
+        st.dom = st.world.resource::<runtime::DomainWorld>().0.clone();
+        refresh_spectator(&sess, st);
+        Ok(out)
+    })
+}
+
+/// Planner inputs derived from the live sim state.
+fn planner_kpis(st: &SimState) -> sim_ai::CurrentKpis {
     let world = &st.world;
     let dom = &st.dom;
     // Derive current KPIs for planner
@@ -227,7 +865,7 @@ async fn sim_plan_quarter() -> Result<PlanSummary, String> {
         .get_resource::<runtime::Capacity>()
         .map(|c| c.wafers_per_month * 50 - (c.wafers_per_month * 50) / 20)
         .unwrap_or(1_000_000);
-    let current = sim_ai::CurrentKpis {
+    sim_ai::CurrentKpis {
         asp_usd: pricing.asp_usd,
         unit_cost_usd: pricing.unit_cost_usd,
         capacity_units_per_month: cap,
@@ -243,11 +881,58 @@ async fn sim_plan_quarter() -> Result<PlanSummary, String> {
             .unwrap_or(rust_decimal::Decimal::ZERO),
         share: stats.market_share,
         rd_progress: stats.rd_progress,
-    };
+        committed_outflow_usd: persistence::cents_i64_to_decimal(runtime::committed_outflow_cents(
+            world.resource::<runtime::CapacityBook>(),
+            world.resource::<runtime::RnDBudgetCents>().0,
+            dom.macro_state.date,
+        )),
+        portfolio_div: runtime::portfolio_diversification(world),
+        underused_capacity: world
+            .get_resource::<runtime::CapacityUtilization>()
+            .is_some_and(|u| u.sustained_low()),
+        launch: runtime::planner_launch_cadence(world),
+    }
+}
+
+/// Have the planner age appeal the way the running game does.
+fn plan_for_staleness(cfg: &mut sim_ai::PlannerConfig, st: &SimState, cfg_ai: &sim_ai::AiConfig) {
+    cfg.staleness = Some(cfg_ai.staleness);
+    if let Some(t) = st.world.get_resource::<runtime::RnDTracks>() {
+        cfg.tapeout_lead_months = t.tapeout_months();
+    }
+}
+
+/// Scenario objective for the planned company, falling back to global weights.
+fn planner_weights(st: &SimState, cfg_ai: &sim_ai::AiConfig) -> sim_ai::ScoreWeights {
+    match (
+        st.world.get_resource::<runtime::CompanyObjectives>(),
+        st.dom.companies.first(),
+    ) {
+        (Some(o), Some(c)) => o.weights_for(&c.name, &cfg_ai.weights),
+        _ => cfg_ai.weights,
+    }
+}
+
+#[tauri::command]
+async fn sim_plan_quarter(session_id: Option<String>) -> Result<PlanSummary, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    let world = &st.world;
+    let current = planner_kpis(st);
     let cfg_ai = world.resource::<runtime::AiConfig>().0.clone();
+    let weights = planner_weights(st, &cfg_ai);
     let mut cfg = cfg_ai.planner.clone();
     cfg.months = 3; // plan a quarter horizon
-    let plan = sim_ai::plan_horizon(&st.dom, &current, &cfg_ai.weights, &cfg);
+    cfg.budget = runtime::planner_budget(world);
+    plan_for_staleness(&mut cfg, st, &cfg_ai);
+    let oem_target = runtime::oem_investment_target(world);
+    if oem_target.is_none() {
+        cfg.relationship_step_cents = 0;
+    }
+    let plan = sim_ai::plan_horizon(&st.dom, &current, &weights, &cfg);
     // Convert first few decisions to strings
     let mut decisions = Vec::new();
     for d in plan.decisions.iter().take(5) {
@@ -261,6 +946,44 @@ async fn sim_plan_quarter() -> Result<PlanSummary, String> {
             sim_ai::PlanAction::AdjustPriceFrac(_) => "ASP±0%".into(),
             sim_ai::PlanAction::RequestCapacity(u) => format!("Capacity+{}u/mo", u),
             sim_ai::PlanAction::AllocateRndBoost(_b) => "R&D boost".into(),
+            sim_ai::PlanAction::AdjustSegmentPriceFrac { segment, frac } => {
+                let name = st
+                    .dom
+                    .segments
+                    .get(segment)
+                    .map(|s| s.name.as_str())
+                    .unwrap_or("?");
+                format!("ASP[{}]{:+}%", name, (frac * 100.0).round())
+            }
+            sim_ai::PlanAction::ShiftAllocation { from, to, frac } => {
+                let seg = |i: usize| {
+                    st.dom
+                        .segments
+                        .get(i)
+                        .map(|s| s.name.clone())
+                        .unwrap_or_else(|| "?".into())
+                };
+                format!(
+                    "Alloc {}%: {}→{}",
+                    (frac * 100.0).round(),
+                    seg(from),
+                    seg(to)
+                )
+            }
+            sim_ai::PlanAction::InvestRelationship { cents } => format!(
+                "Invest ${}k in {}",
+                cents / 100_000,
+                oem_target.as_deref().unwrap_or("OEM")
+            ),
+            sim_ai::PlanAction::FundProcessRnd { cents } => {
+                format!("Process R&D+${}k/mo", cents / 100_000)
+            }
+            sim_ai::PlanAction::FundDesignRnd { cents } => {
+                format!("Design R&D+${}k/mo", cents / 100_000)
+            }
+            sim_ai::PlanAction::FundQualityControl { cents } => {
+                format!("QC+${}k/mo", cents / 100_000)
+            }
             sim_ai::PlanAction::ScheduleTapeout { expedite } => {
                 if expedite {
                     "Tapeout (expedite)".into()
@@ -277,7 +1000,27 @@ async fn sim_plan_quarter() -> Result<PlanSummary, String> {
     })
 }
 
-#[derive(Deserialize, Debug)]
+/// Re-run the quarterly planner with tracing on and return its search trace.
+#[tauri::command]
+async fn sim_plan_trace(session_id: Option<String>) -> Result<sim_ai::PlanTrace, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    let current = planner_kpis(st);
+    let cfg_ai = st.world.resource::<runtime::AiConfig>().0.clone();
+    let weights = planner_weights(st, &cfg_ai);
+    let mut cfg = cfg_ai.planner.clone();
+    cfg.trace = true;
+    cfg.budget = runtime::planner_budget(&st.world);
+    plan_for_staleness(&mut cfg, st, &cfg_ai);
+    let plan = sim_ai::plan_horizon(&st.dom, &current, &weights, &cfg);
+    plan.trace
+        .ok_or_else(|| "planner returned no trace".to_string())
+}
+
+#[derive(Serialize, Deserialize, Debug)]
 struct OverrideReq {
     price_delta_frac: Option<f32>,
     rd_delta_cents: Option<i64>,
@@ -285,20 +1028,41 @@ struct OverrideReq {
     tapeout: Option<TapeoutReq>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 struct CapacityReq {
     wafers_per_month: u32,
     months: u16,
     billing_cents_per_wafer: Option<i64>,
     take_or_pay_frac: Option<f32>,
+    /// Committed wafers for the first months; omitted takes the going ramp.
+    #[serde(default)]
+    ramp_wafers: Option<Vec<u32>>,
+    #[serde(default)]
+    min_order_wafers: Option<u32>,
+    /// Bank paid-for wafers left unused as credits.
+    #[serde(default)]
+    rollover: bool,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 struct TapeoutReq {
     perf_index: f32,
     die_area_mm2: f32,
     tech_node: String,
     expedite: Option<bool>,
+    /// Months to pull the tapeout in; quoted by `sim_expedite_quote`.
+    #[serde(default)]
+    expedite_months: Option<u8>,
+    /// Defaults to CPU.
+    kind: Option<core::ProductKind>,
+    microarch: Option<core::MicroArch>,
+    tdp_w: Option<f32>,
+    /// Market segment ids the design is tuned for; empty or absent targets the whole market.
+    target_segments: Option<Vec<String>>,
+    /// Clock bin as a multiple of the node's baseline frequency; defaults to 1.
+    clock_frac: Option<f32>,
+    /// Product family; the design becomes its next generation.
+    family: Option<String>,
 }
 
 #[derive(Serialize, Debug, Default)]
@@ -328,6 +1092,11 @@ struct DtoSegment {
     elasticity: f32,
     trend_pct: f32,
     sold_units: u64,
+    regions: Vec<runtime::RegionTrend>,
+    /// Below 1 while the segment is still emerging or in decline.
+    lifecycle_frac: f32,
+    /// How far demand, elasticity and trend may be off, as a fraction; 0 when exact.
+    estimate_error_frac: f32,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -347,26 +1116,82 @@ struct DtoKpi {
     rd_pct: f32,
     output_units: u64,
     inventory_units: u64,
+    /// Last month's share of available wafers whose output sold.
+    #[serde(default)]
+    utilization: Option<f32>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct DtoContract {
+    /// Stable id, kept across saves.
+    id: String,
     foundry_id: String,
     wafers_per_month: u32,
     billing_cents_per_wafer: i64,
     take_or_pay_frac: f32,
     start: String,
     end: String,
+    compensation_cents_per_wafer: i64,
+    /// Node the capacity is bound to; `None` for general capacity.
+    node: Option<String>,
+    /// Wafers committed in the first months before `wafers_per_month`.
+    ramp_wafers: Vec<u32>,
+    min_order_wafers: u32,
+    rollover: bool,
+    /// Unused paid wafers banked as credits under the rollover clause.
+    banked_wafers: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct DtoFoundry {
+    id: String,
+    score: f32,
+    months_observed: u32,
+    misses: u32,
+    preferred: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct DtoPromo {
+    id: u32,
+    segment_id: String,
+    discount_frac: f32,
+    reach_frac: f32,
+    start: String,
+    end: String,
+    active: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct DtoBudget {
+    mode: runtime::BudgetMode,
+    board_confidence: f32,
+    quarter_to_date: Vec<runtime::BudgetVariance>,
+    history: Vec<runtime::BudgetVariance>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct DtoInsurance {
+    products: Vec<runtime::InsuranceProduct>,
+    policies: Vec<runtime::InsurancePolicy>,
+    premiums_paid_cents: i64,
+    claims: Vec<runtime::InsuranceClaim>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct DtoTapeoutReq {
+    /// Stable id, shared with the product once released.
+    id: String,
     tech_node: String,
     start: String,
     ready: String,
     expedite: bool,
     expedite_cost_cents: i64,
     perf_index: f32,
+    /// Target clock: the node's baseline frequency times the design's clock bin.
+    clock_ghz: Option<f32>,
+    /// Marketing name, e.g. "Falcon II", for a product in a family.
+    name: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -390,67 +1215,295 @@ struct SimStateDto {
     pricing: DtoPricing,
     kpi: DtoKpi,
     contracts: Vec<DtoContract>,
+    foundries: Vec<DtoFoundry>,
     pipeline: DtoPipeline,
     ai_plan: PlanSummary,
     config: DtoConfig,
     campaign: Option<DtoCampaign>,
+    pending_decisions: Vec<DtoDecision>,
+    distress: DtoDistress,
+    forecast: Option<DtoForecast>,
+    finance: DtoFinance,
+    /// Most recent headlines, newest last.
+    news: Vec<runtime::NewsItem>,
+    /// Accepted subsidy grants with their obligations and status.
+    grants: Vec<runtime::GrantCommitment>,
+    /// Last month's shipments, share and logistics cost per region.
+    regions: Vec<DtoRegion>,
+    /// Relationship with each major OEM customer and what it unlocks.
+    oems: Vec<DtoOem>,
+    /// Market reputation (0-100), moved by keeping or slipping roadmap promises.
+    reputation: f32,
+    /// Announced roadmap items and whether each was hit or slipped.
+    roadmap: Vec<runtime::RoadmapAnnouncement>,
+    /// Press reception of each release, oldest first.
+    reviews: Vec<runtime::LaunchReview>,
+    /// Process/design R&D budgets and track progress.
+    rnd: runtime::RnDTracks,
+    /// Quality-control budget, defect rate and field-failure effect.
+    quality: runtime::QualityReport,
+    /// Product generations on sale, oldest first, with last month's sales of each.
+    products: Vec<runtime::ProductOnSale>,
+    /// Patent filings, infringement cases and rival portfolios.
+    patents: DtoPatents,
+    /// This month's wafer price and status of every available node.
+    capacity_market: Vec<runtime::NodeCapacityQuote>,
+    /// Console generation RFQs, the annuity won and any lockout.
+    consoles: DtoConsoles,
+    /// How wafers are split between product lines.
+    wafer_allocation: DtoWaferAllocation,
+    /// Rival restructurings and exits, oldest first.
+    company_exits: Vec<runtime::CompanyExit>,
+    /// Rivals that entered mid-game, oldest first.
+    entrants: Vec<runtime::EntrantEvent>,
+    /// Technology licenses sold to rivals, with royalties collected so far.
+    licenses: Vec<runtime::LicenseDeal>,
+    /// Last month's segment demand split across companies; empty unless the market clears it.
+    clearing: Vec<runtime::SegmentClearing>,
+    /// Research subscriptions and this month's reports on the covered segments.
+    market_research: DtoMarketResearch,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-struct SimListsDto {
-    tech_nodes: Vec<String>,
-    foundries: Vec<String>,
-    segments: Vec<String>,
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct DtoWaferAllocation {
+    /// No player plan: shares follow the open demand each line reaches.
+    automatic: bool,
+    lines: Vec<DtoAllocationLine>,
 }
 
-// -------- Campaign DTOs --------
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct DtoAllocationLine {
+    line: String,
+    /// Marketing name of the line's current product, for a product family.
+    product: Option<String>,
+    /// Player-set share; null when allocation is automatic.
+    planned_share: Option<f32>,
+    /// Share, wafers and good units from last month's production.
+    share: f32,
+    wafers: u64,
+    units: u64,
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-struct DtoGoal {
-    kind: String,
-    desc: String,
-    progress: f32,
-    deadline: String,
-    done: bool,
+struct DtoRegion {
+    #[serde(flatten)]
+    sales: runtime::RegionSales,
+    /// Player override of the global ASP in this region.
+    price_override_cents: Option<i64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-struct DtoCampaign {
-    status: String,
-    goals: Vec<DtoGoal>,
-    start: String,
-    end: String,
-    difficulty: Option<String>,
+struct DtoOem {
+    #[serde(flatten)]
+    relationship: runtime::OemRelationship,
+    /// Deal and design-win thresholds from the catalog.
+    terms: Option<runtime::OemCustomer>,
 }
 
-// -------- Tutorial DTOs --------
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct DtoPatents {
+    #[serde(flatten)]
+    state: runtime::Patents,
+    /// Player portfolio strength (points per IP asset).
+    strength: f32,
+    filing_cost_cents: i64,
+    rivals: Vec<runtime::PatentRival>,
+}
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-struct DtoTutStep {
-    id: String,
-    desc: String,
-    hint: String,
-    nav_page: String,
-    nav_label: String,
-    done: bool,
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct DtoMarketResearch {
+    #[serde(flatten)]
+    state: runtime::MarketResearch,
+    cents_per_month: i64,
+    max_months: u32,
+    reports: Vec<runtime::MarketResearchReport>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-struct DtoTutorial {
-    active: bool,
-    current_step: u8,
-    steps: Vec<DtoTutStep>,
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct DtoConsoles {
+    #[serde(flatten)]
+    state: runtime::ConsoleGenerations,
+    /// The era's console cycle, if it has one.
+    cycle: Option<runtime::ConsoleCycleCfg>,
+    next_rfq: Option<String>,
+    /// Score of the current bid (a bid at the ASP before one is placed) and the score
+    /// needed to win.
+    score: f32,
+    needed: f32,
 }
 
-#[derive(Clone, Debug, serde::Deserialize, JsonSchema)]
-struct TutorialCfg {
-    #[serde(default)]
-    cash_threshold_cents_month24: i64,
-    #[serde(default)]
-    steps: Vec<TutorialStepCfg>,
+/// Player debt, credit rating and borrowing terms.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct DtoFinance {
+    debt_cents: i64,
+    rating: String,
+    spread_bps: u32,
+    /// Annual rate charged on new debt (base rate plus spread).
+    rate_on_new_debt: f64,
+    credit_line_cents: i64,
+    drawn_cents: i64,
+    available_cents: i64,
+    leverage: f64,
+    /// `None` when there is no debt to cover.
+    interest_coverage: Option<f64>,
+    volatility: f64,
+    /// Annual rate earned on positive cash.
+    deposit_rate: f64,
+    /// Annual rate on debt not booked as a loan (base rate plus spread).
+    debt_rate: f64,
+    interest_income_cents: i64,
+    interest_expense_cents: i64,
+    /// Warranty reserve against the claims still expected on units sold.
+    warranty: runtime::WarrantyReport,
 }
 
-#[derive(Clone, Debug, serde::Deserialize, JsonSchema)]
+/// One forecast month for chart overlays; money series in cents, demand in units.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct DtoForecastPoint {
+    month_index: u32,
+    mean: i64,
+    lower: i64,
+    upper: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct DtoForecast {
+    demand_units: Vec<DtoForecastPoint>,
+    revenue_cents: Vec<DtoForecastPoint>,
+    cash_cents: Vec<DtoForecastPoint>,
+}
+
+/// Forecast horizon shown in the UI, in months.
+const FORECAST_HORIZON_MONTHS: usize = 12;
+/// Headlines carried in each state snapshot.
+const NEWS_IN_STATE: usize = 20;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct DtoDecisionChoice {
+    id: String,
+    label: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct DtoDecision {
+    event_id: String,
+    name: String,
+    prompt: String,
+    raised: String,
+    default_choice: String,
+    choices: Vec<DtoDecisionChoice>,
+}
+
+fn decision_dto(d: &runtime::PendingDecision) -> DtoDecision {
+    DtoDecision {
+        event_id: d.event_id.clone(),
+        name: d.name.clone(),
+        prompt: d.prompt.clone(),
+        raised: d.raised.to_string(),
+        default_choice: d.default_choice.clone(),
+        choices: d
+            .choices
+            .iter()
+            .map(|c| DtoDecisionChoice {
+                id: c.id.clone(),
+                label: c.label.clone(),
+            })
+            .collect(),
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct DtoDistress {
+    active: bool,
+    months_left: u32,
+    loan_offer_cents: Option<i64>,
+    failed: bool,
+    warning: Option<DtoSolvencyWarning>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct DtoSolvencyWarning {
+    months_ahead: u32,
+    projected_cash_cents: i64,
+    lower_cash_cents: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct SimListsDto {
+    tech_nodes: Vec<String>,
+    foundries: Vec<String>,
+    segments: Vec<String>,
+    /// Market segments a tapeout can target, with their TDP envelopes.
+    design_segments: Vec<DesignSegmentDto>,
+    /// Economics and addressable segments of each configured product kind.
+    product_kinds: Vec<ProductKindDto>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct ProductKindDto {
+    kind: core::ProductKind,
+    unit_cost_pct: f32,
+    perf_pct: f32,
+    appeal: f32,
+    segments: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct DesignSegmentDto {
+    id: String,
+    name: String,
+    tdp_min_w: Option<f32>,
+    tdp_max_w: Option<f32>,
+}
+
+// -------- Campaign DTOs --------
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct DtoGoal {
+    kind: String,
+    desc: String,
+    progress: f32,
+    deadline: String,
+    done: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct DtoCampaign {
+    status: String,
+    goals: Vec<DtoGoal>,
+    start: String,
+    end: String,
+    difficulty: Option<String>,
+}
+
+// -------- Tutorial DTOs --------
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct DtoTutStep {
+    id: String,
+    desc: String,
+    hint: String,
+    nav_page: String,
+    nav_label: String,
+    done: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct DtoTutorial {
+    active: bool,
+    current_step: u8,
+    steps: Vec<DtoTutStep>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, JsonSchema)]
+struct TutorialCfg {
+    #[serde(default)]
+    cash_threshold_cents_month24: i64,
+    #[serde(default)]
+    steps: Vec<TutorialStepCfg>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, JsonSchema)]
 struct TutorialStepCfg {
     id: String,
     desc: String,
@@ -468,15 +1521,39 @@ struct TutorialNavCfg {
 
 #[derive(Clone, Debug, serde::Deserialize, JsonSchema)]
 struct CampaignScenario {
+    /// Campaign id the session was reset to; rebuilds the same start for the report baseline.
+    #[serde(skip)]
+    id: String,
     start_date: String,
     end_date: String,
+    #[allow(dead_code)]
     #[serde(deserialize_with = "de_underscore_int")]
     player_start_cash_cents: i64,
+    /// Rival companies, each starting with the player's cash.
     #[allow(dead_code)]
+    #[serde(default)]
     ai_companies: usize,
     goals: Vec<YamlGoal>,
     fail_conditions: Vec<YamlFail>,
+    #[allow(dead_code)]
     events_yaml: String,
+    /// Per-company planner weights keyed by company name.
+    #[allow(dead_code)]
+    #[serde(default)]
+    ai_objectives: std::collections::BTreeMap<String, YamlObjective>,
+    /// Whether the player starts with an own fab (process R&D can then pull nodes forward).
+    #[allow(dead_code)]
+    #[serde(default)]
+    owns_fab: bool,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, JsonSchema)]
+#[allow(dead_code)]
+struct YamlObjective {
+    share: f32,
+    margin: f32,
+    liquidity: f32,
+    portfolio: f32,
 }
 
 #[derive(Clone, Debug, serde::Deserialize, JsonSchema)]
@@ -494,9 +1571,20 @@ enum YamlGoal {
     ProfitTarget { #[serde(deserialize_with = "de_underscore_int")] profit_cents: i64, deadline: String },
     #[serde(rename = "survive_event")]
     SurviveEvent { event_id: String, deadline: String },
+    /// Goal DSL expression, e.g. `share >= 30% and min(profit_month, 6) > 0`.
+    #[serde(rename = "condition")]
+    Condition {
+        expr: String,
+        #[serde(default)]
+        deadline: Option<String>,
+    },
+    /// Done once `count` rivals have gone bankrupt and left the market.
+    #[serde(rename = "outlast_rivals")]
+    OutlastRivals { count: u32, deadline: String },
 }
 
 #[derive(Clone, Debug, serde::Deserialize, JsonSchema)]
+#[allow(dead_code)]
 #[serde(tag = "type")]
 enum YamlFail {
     #[serde(rename = "cash_below")]
@@ -507,6 +1595,8 @@ enum YamlFail {
         min_share: f32,
         deadline: String,
     },
+    #[serde(rename = "condition")]
+    Condition { expr: String },
 }
 
 // -------- Asset schema DTOs --------
@@ -514,6 +1604,8 @@ enum YamlFail {
 #[allow(dead_code)]
 struct MarketsRoot {
     segments: Vec<MarketSegSchema>,
+    #[serde(default)]
+    regions: Vec<MarketRegionSchema>,
 }
 
 #[derive(Clone, Debug, serde::Deserialize, JsonSchema)]
@@ -553,9 +1645,53 @@ struct MarketSegSchema {
     base_demand_units_1990: U64OrStr,
     base_asp_cents_1990: I64OrStrV,
     elasticity: f32,
+    #[serde(default)]
     annual_growth_pct: f32,
     #[serde(default)]
     step_events: Vec<MarketStepSchema>,
+    #[serde(default)]
+    demand_model: Option<DemandModelSchema>,
+    #[serde(default)]
+    regions: std::collections::BTreeMap<String, SegmentRegionSchema>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, JsonSchema)]
+#[serde(untagged)]
+#[allow(dead_code)]
+enum SegmentRegionSchema {
+    Share(f32),
+    Full {
+        share: f32,
+        #[serde(default)]
+        annual_growth_pct: Option<f32>,
+        #[serde(default)]
+        elasticity: Option<f32>,
+    },
+}
+
+#[derive(Clone, Debug, serde::Deserialize, JsonSchema)]
+#[allow(dead_code)]
+struct MarketRegionSchema {
+    id: String,
+    name: String,
+    #[serde(default)]
+    tariff_pct: f32,
+    #[serde(default)]
+    freight_cents_per_unit: i64,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[allow(dead_code)]
+enum DemandModelSchema {
+    Growth,
+    Bass {
+        innovation: f64,
+        imitation: f64,
+        market_size_units: U64OrStr,
+        #[serde(default)]
+        launch: Option<String>,
+    },
 }
 
 #[derive(Clone, Debug, serde::Deserialize, JsonSchema)]
@@ -631,6 +1767,7 @@ fn build_sim_state_dto(st: &SimState) -> SimStateDto {
         rd_pct: stats.rd_progress,
         output_units: stats.output_units,
         inventory_units: stats.inventory_units,
+        utilization: stats.utilization(),
     };
     let companies = dom
         .companies
@@ -642,21 +1779,51 @@ fn build_sim_state_dto(st: &SimState) -> SimStateDto {
         })
         .collect();
     let trends = world.resource::<runtime::MarketTrends>().0.clone();
+    let markets = world.get_resource::<runtime::MarketConfigRes>();
+    let date = dom.macro_state.date;
+    let estimates = runtime::market_estimates(world);
+    // Segments appear once born and drop out once they have died
     let segments = dom
         .segments
         .iter()
-        .enumerate()
-        .map(|(i, s)| {
-            let t = trends.get(i);
+        .filter(|s| {
+            markets
+                .and_then(|m| m.segments.iter().find(|c| c.name == s.name))
+                .map_or(true, |c| c.lifecycle.frac(date).is_some())
+        })
+        .map(|s| {
+            let t = trends.iter().find(|t| t.name == s.name);
+            // The player sees estimates rather than the configured values while fog is on
+            let est = t.and_then(|t| estimates.iter().find(|e| e.segment == t.id));
+            let (df, ef) = est.map_or((1.0, 1.0), |e| (e.demand_factor, e.elasticity_factor));
+            let demand = |u: u64| (u as f64 * f64::from(df)).round() as u64;
             DtoSegment {
                 name: s.name.clone(),
-                base_demand_units: s.base_demand_units,
-                price_elasticity: s.price_elasticity,
-                base_demand_t: t.map(|x| x.base_demand_t).unwrap_or(s.base_demand_units),
+                base_demand_units: demand(s.base_demand_units),
+                price_elasticity: s.price_elasticity * ef,
+                base_demand_t: demand(t.map(|x| x.base_demand_t).unwrap_or(s.base_demand_units)),
                 ref_price_t_cents: t.map(|x| x.ref_price_t_cents).unwrap_or(0),
-                elasticity: t.map(|x| x.elasticity).unwrap_or(s.price_elasticity),
-                trend_pct: t.map(|x| x.trend_pct).unwrap_or(0.0),
+                elasticity: t.map(|x| x.elasticity).unwrap_or(s.price_elasticity) * ef,
+                trend_pct: est
+                    .map(|e| e.trend_pct)
+                    .or(t.map(|x| x.trend_pct))
+                    .unwrap_or(0.0),
                 sold_units: t.map(|x| x.sold_units).unwrap_or(0),
+                regions: t
+                    .map(|x| {
+                        x.regions
+                            .iter()
+                            .map(|r| runtime::RegionTrend {
+                                base_demand_t: demand(r.base_demand_t),
+                                elasticity: r.elasticity * ef,
+                                demand_units: demand(r.demand_units),
+                                ..r.clone()
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                lifecycle_frac: t.map(|x| x.lifecycle_frac).unwrap_or(1.0),
+                estimate_error_frac: est.map_or(0.0, |e| e.error_frac),
             }
         })
         .collect();
@@ -665,31 +1832,227 @@ fn build_sim_state_dto(st: &SimState) -> SimStateDto {
         .contracts
         .iter()
         .map(|c| DtoContract {
+            id: c.id.clone(),
             foundry_id: c.foundry_id.clone(),
             wafers_per_month: c.wafers_per_month,
             billing_cents_per_wafer: c.billing_cents_per_wafer,
             take_or_pay_frac: c.take_or_pay_frac,
             start: c.start.to_string(),
             end: c.end.to_string(),
+            compensation_cents_per_wafer: c.compensation_cents_per_wafer,
+            node: c.node.clone(),
+            ramp_wafers: c.ramp_wafers.clone(),
+            min_order_wafers: c.min_order_wafers,
+            rollover: c.rollover,
+            banked_wafers: c.banked_wafers,
+        })
+        .collect();
+    let rel = world.resource::<runtime::FoundryReliability>();
+    let preferred = rel.preferred_foundry();
+    let foundries = rel
+        .profiles
+        .iter()
+        .map(|p| {
+            let learned = rel.scores.iter().find(|s| s.foundry_id == p.id);
+            DtoFoundry {
+                id: p.id.clone(),
+                score: rel.score(&p.id),
+                months_observed: learned.map(|s| s.months_observed).unwrap_or(0),
+                misses: learned.map(|s| s.misses).unwrap_or(0),
+                preferred: p.id == preferred,
+            }
         })
         .collect();
     let pipe = world.resource::<runtime::Pipeline>();
     let mut queue: Vec<DtoTapeoutReq> = Vec::new();
     for t in &pipe.0.queue {
         queue.push(DtoTapeoutReq {
+            id: t.product.id.clone(),
             tech_node: t.tech_node.0.clone(),
             start: t.start.to_string(),
             ready: t.ready.to_string(),
             expedite: t.expedite,
             expedite_cost_cents: t.expedite_cost_cents,
             perf_index: t.product.perf_index,
+            clock_ghz: dom
+                .tech_tree
+                .iter()
+                .find(|n| n.id == t.tech_node)
+                .and_then(|n| rust_decimal::prelude::ToPrimitive::to_f32(&n.freq_ghz_baseline))
+                .map(|f| f * t.product.clock_frac),
+            name: t.product.marketing_name(),
         });
     }
     let released = pipe.0.released.clone();
     let ai_cfg = world.resource::<runtime::AiConfig>().0.clone();
     let asp_cents = persistence::decimal_to_cents_i64(pricing.asp_usd).unwrap_or(0);
     let unit_cost_cents = persistence::decimal_to_cents_i64(pricing.unit_cost_usd).unwrap_or(0);
-    let campaign = st.scenario.as_ref().map(|sc| build_campaign_dto(st, sc));
+    let campaign = build_campaign_dto(st, st.scenario.as_ref());
+    let pending_decisions = world
+        .get_resource::<runtime::DecisionQueue>()
+        .map(|q| q.pending.iter().map(decision_dto).collect())
+        .unwrap_or_default();
+    let interest = world
+        .get_resource::<runtime::InterestConfig>()
+        .copied()
+        .unwrap_or_default();
+    let finance = world
+        .get_resource::<runtime::CreditState>()
+        .map(|c| DtoFinance {
+            debt_cents: st
+                .dom
+                .companies
+                .first()
+                .and_then(|c| persistence::decimal_to_cents_i64(c.debt_usd).ok())
+                .unwrap_or(0),
+            rating: c.rating.label().to_string(),
+            spread_bps: c.rating.spread_bps(),
+            rate_on_new_debt: c.rate_on_new_debt(st.dom.macro_state.interest_rate),
+            credit_line_cents: c.credit_line_cents,
+            drawn_cents: c.drawn_cents(),
+            available_cents: c.available_cents(),
+            leverage: c.metrics.leverage,
+            interest_coverage: c
+                .metrics
+                .interest_coverage
+                .is_finite()
+                .then_some(c.metrics.interest_coverage),
+            volatility: c.metrics.volatility,
+            deposit_rate: f64::from(interest.deposit_rate),
+            debt_rate: interest.debt_rate(st.dom.macro_state.interest_rate),
+            interest_income_cents: c.last_interest_income_cents,
+            interest_expense_cents: c.last_interest_expense_cents,
+            warranty: runtime::warranty_report(world),
+        })
+        .unwrap_or_default();
+    let news = world
+        .get_resource::<runtime::NewsFeed>()
+        .map(|n| n.items[n.items.len().saturating_sub(NEWS_IN_STATE)..].to_vec())
+        .unwrap_or_default();
+    let grants = world
+        .get_resource::<runtime::GrantState>()
+        .map(|g| g.commitments.clone())
+        .unwrap_or_default();
+    let overrides = world.get_resource::<runtime::RegionalPricing>();
+    let regions = world
+        .get_resource::<runtime::RegionalSales>()
+        .map(|r| {
+            r.0.iter()
+                .map(|s| DtoRegion {
+                    price_override_cents: overrides.and_then(|o| o.0.get(&s.id).copied()),
+                    sales: s.clone(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let oem_catalog = world.get_resource::<runtime::OemCatalog>();
+    let oems = world
+        .get_resource::<runtime::OemRelationships>()
+        .map(|o| {
+            o.0.iter()
+                .map(|r| DtoOem {
+                    terms: oem_catalog.and_then(|c| c.customer(&r.id).cloned()),
+                    relationship: r.clone(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let reputation = world
+        .get_resource::<runtime::Reputation>()
+        .map_or(runtime::REPUTATION_START, |r| r.score);
+    let roadmap = world
+        .get_resource::<runtime::Roadmap>()
+        .map(|r| r.announcements.clone())
+        .unwrap_or_default();
+    let rnd = world
+        .get_resource::<runtime::RnDTracks>()
+        .cloned()
+        .unwrap_or_default();
+    let patent_catalog = world.get_resource::<runtime::PatentCatalog>();
+    let patents = DtoPatents {
+        state: world
+            .get_resource::<runtime::Patents>()
+            .cloned()
+            .unwrap_or_default(),
+        strength: runtime::player_patent_strength(world),
+        filing_cost_cents: patent_catalog.map_or(0, |c| c.rules.filing_cost_cents),
+        rivals: patent_catalog.map(|c| c.rivals.clone()).unwrap_or_default(),
+    };
+    let console_state = world
+        .get_resource::<runtime::ConsoleGenerations>()
+        .cloned()
+        .unwrap_or_default();
+    let console_cycle = world
+        .get_resource::<runtime::MarketConfigRes>()
+        .and_then(|m| m.console_cycle.clone());
+    let bid = console_state
+        .rfq
+        .as_ref()
+        .and_then(|r| r.bid_price_frac)
+        .unwrap_or(1.0);
+    let (score, needed) = runtime::console_bid_score(world, bid).unwrap_or_default();
+    let consoles = DtoConsoles {
+        next_rfq: console_cycle
+            .as_ref()
+            .map(|c| runtime::next_console_rfq(c, dom.macro_state.date).to_string()),
+        state: console_state,
+        cycle: console_cycle,
+        score,
+        needed,
+    };
+    let alloc = world
+        .get_resource::<runtime::WaferAllocation>()
+        .cloned()
+        .unwrap_or_default();
+    let wafer_allocation = DtoWaferAllocation {
+        automatic: alloc.plan.is_empty(),
+        lines: runtime::product_lines(&world.resource::<runtime::Pipeline>().0.released)
+            .into_iter()
+            .map(|(line, p)| {
+                let last = alloc.last.iter().find(|a| a.line == line);
+                DtoAllocationLine {
+                    product: p.marketing_name(),
+                    planned_share: (!alloc.plan.is_empty())
+                        .then(|| alloc.plan.get(&line).copied().unwrap_or(0.0)),
+                    share: last.map_or(0.0, |a| a.share),
+                    wafers: last.map_or(0, |a| a.wafers),
+                    units: last.map_or(0, |a| a.units),
+                    line,
+                }
+            })
+            .collect(),
+    };
+    let distress = world
+        .get_resource::<runtime::DistressState>()
+        .map(|d| DtoDistress {
+            active: d.active,
+            months_left: d.months_left,
+            loan_offer_cents: d.loan_offer_cents,
+            failed: d.failed,
+            warning: d.warning.map(|w| DtoSolvencyWarning {
+                months_ahead: w.months_ahead,
+                projected_cash_cents: w.projected_cash_cents,
+                lower_cash_cents: w.lower_cash_cents,
+            }),
+        })
+        .unwrap_or_default();
+    let forecast = runtime::kpi_forecast(world, FORECAST_HORIZON_MONTHS).map(|f| {
+        let points = |pts: &[runtime::ForecastPoint], scale: f64| {
+            pts.iter()
+                .map(|p| DtoForecastPoint {
+                    month_index: stats.months_run + p.step as u32,
+                    mean: (p.mean * scale).round() as i64,
+                    lower: (p.lower * scale).round() as i64,
+                    upper: (p.upper * scale).round() as i64,
+                })
+                .collect::<Vec<_>>()
+        };
+        DtoForecast {
+            demand_units: points(&f.demand, 1.0),
+            revenue_cents: points(&f.revenue, 100.0),
+            cash_cents: points(&f.cash, 100.0),
+        }
+    });
     SimStateDto {
         date,
         month_index: stats.months_run,
@@ -701,6 +2064,7 @@ fn build_sim_state_dto(st: &SimState) -> SimStateDto {
         },
         kpi,
         contracts,
+        foundries,
         pipeline: DtoPipeline { queue, released },
         ai_plan: PlanSummary {
             decisions: vec!["n/a".into()],
@@ -711,10 +2075,61 @@ fn build_sim_state_dto(st: &SimState) -> SimStateDto {
             product_cost: ai_cfg.product_cost,
         },
         campaign,
+        pending_decisions,
+        distress,
+        forecast,
+        finance,
+        news,
+        grants,
+        regions,
+        oems,
+        reputation,
+        roadmap,
+        reviews: world
+            .get_resource::<runtime::LaunchReviews>()
+            .map(|r| r.log.clone())
+            .unwrap_or_default(),
+        rnd,
+        quality: runtime::quality_report(world),
+        products: world
+            .get_resource::<runtime::ProductSales>()
+            .map(|s| s.products.clone())
+            .unwrap_or_default(),
+        patents,
+        capacity_market: runtime::node_capacity_quotes(&world.resource::<runtime::DomainWorld>().0),
+        consoles,
+        wafer_allocation,
+        company_exits: world
+            .get_resource::<runtime::RivalBooks>()
+            .map(|r| r.log.clone())
+            .unwrap_or_default(),
+        entrants: world
+            .get_resource::<runtime::EntrantState>()
+            .map(|e| e.log.clone())
+            .unwrap_or_default(),
+        licenses: world
+            .get_resource::<runtime::Licenses>()
+            .map(|l| l.deals.clone())
+            .unwrap_or_default(),
+        clearing: world
+            .get_resource::<runtime::MarketClearing>()
+            .map(|c| c.0.clone())
+            .unwrap_or_default(),
+        market_research: DtoMarketResearch {
+            state: world
+                .get_resource::<runtime::MarketResearch>()
+                .cloned()
+                .unwrap_or_default(),
+            cents_per_month: runtime::MARKET_RESEARCH_CENTS_PER_MONTH,
+            max_months: runtime::MARKET_RESEARCH_MAX_MONTHS,
+            reports: runtime::market_research_reports(world),
+        },
     }
 }
 
-fn build_campaign_dto(st: &SimState, sc: &CampaignScenario) -> DtoCampaign {
+/// Campaign goals and status; from the runtime campaign state when present (which is all a
+/// loaded save has), else from the scenario YAML.
+fn build_campaign_dto(st: &SimState, sc: Option<&CampaignScenario>) -> Option<DtoCampaign> {
     let world = &st.world;
     let stats = world.resource::<runtime::Stats>();
     let mut goals: Vec<DtoGoal> = Vec::new();
@@ -726,13 +2141,25 @@ fn build_campaign_dto(st: &SimState, sc: &CampaignScenario) -> DtoCampaign {
         for (i, g) in cfg.goals.iter().enumerate() {
             let (desc, progress) = match g {
                 runtime::GoalKind::ReachShare {
-                    segment: _s,
+                    segment,
                     min_share,
                     deadline: _,
-                } => (
-                    format!("Reach share ≥ {}%", (min_share * 100.0).round()),
-                    (stats.market_share / (*min_share + 1e-6)).clamp(0.0, 1.0),
-                ),
+                } => {
+                    let live = runtime::segment_on_market(
+                        world.get_resource::<runtime::MarketConfigRes>(),
+                        &world.resource::<runtime::MarketTrends>().0,
+                        segment,
+                    );
+                    let desc = format!("Reach share ≥ {}%", (min_share * 100.0).round());
+                    if live {
+                        (
+                            desc,
+                            (stats.market_share / (*min_share + 1e-6)).clamp(0.0, 1.0),
+                        )
+                    } else {
+                        (format!("{desc} ({segment} not on the market yet)"), 0.0)
+                    }
+                }
                 runtime::GoalKind::LaunchNode { node, deadline: _ } => {
                     let pipe = world.resource::<runtime::Pipeline>();
                     let done = pipe.0.released.iter().any(|p| p.tech_node.0 == *node);
@@ -755,6 +2182,26 @@ fn build_campaign_dto(st: &SimState, sc: &CampaignScenario) -> DtoCampaign {
                     event_id,
                     deadline: _,
                 } => (format!("Survive {}", event_id), 0.0),
+                runtime::GoalKind::Condition {
+                    condition,
+                    deadline: _,
+                } => (
+                    condition.source.clone(),
+                    if matches!(state.goal_status.get(i), Some(runtime::GoalStatus::Done)) {
+                        1.0
+                    } else {
+                        0.0
+                    },
+                ),
+                runtime::GoalKind::OutlastRivals { count, deadline: _ } => {
+                    let exits = world
+                        .get_resource::<runtime::RivalBooks>()
+                        .map_or(0, |r| r.exits());
+                    (
+                        g.describe(),
+                        (exits as f32 / (*count).max(1) as f32).clamp(0.0, 1.0),
+                    )
+                }
             };
             let st = state
                 .goal_status
@@ -775,15 +2222,16 @@ fn build_campaign_dto(st: &SimState, sc: &CampaignScenario) -> DtoCampaign {
             runtime::CampaignOutcome::Failed => "Failed",
         }
         .to_string();
-        return DtoCampaign {
+        return Some(DtoCampaign {
             status,
             goals,
-            start: sc.start_date.clone(),
-            end: sc.end_date.clone(),
+            start: cfg.start.to_string(),
+            end: cfg.end.to_string(),
             difficulty: cfg.difficulty.clone(),
-        };
+        });
     }
     // Fallback to simple computation from YAML
+    let sc = sc?;
     for g in &sc.goals {
         match g {
             YamlGoal::ReachShare {
@@ -834,31 +2282,51 @@ fn build_campaign_dto(st: &SimState, sc: &CampaignScenario) -> DtoCampaign {
                     done: false,
                 });
             }
+            YamlGoal::Condition { expr, deadline } => {
+                goals.push(DtoGoal {
+                    kind: "condition".into(),
+                    desc: expr.clone(),
+                    progress: 0.0,
+                    deadline: deadline.clone().unwrap_or_default(),
+                    done: false,
+                });
+            }
+            YamlGoal::OutlastRivals { count, deadline } => {
+                goals.push(DtoGoal {
+                    kind: "outlast_rivals".into(),
+                    desc: format!("Outlast {count} rivals"),
+                    progress: 0.0,
+                    deadline: deadline.clone(),
+                    done: false,
+                });
+            }
         }
     }
-    DtoCampaign {
+    Some(DtoCampaign {
         status: "InProgress".into(),
         goals,
         start: sc.start_date.clone(),
         end: sc.end_date.clone(),
         difficulty: None,
-    }
+    })
 }
 
 #[tauri::command]
-fn sim_state() -> Result<SimStateDto, String> {
+fn sim_state(session_id: Option<String>) -> Result<SimStateDto, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
     // If not initialized (e.g., packaged Windows without assets path), init from embedded defaults
-    if SIM_STATE.read().unwrap().is_none() {
-        let _ = init_default_from_embedded();
+    if sess.state.read().unwrap().is_none() {
+        let _ = init_default_from_embedded(&sess);
     }
-    let guard = SIM_STATE.read().unwrap();
+    let guard = sess.state.read().unwrap();
     let st = guard.as_ref().ok_or_else(|| "sim not initialized".to_string())?;
     Ok(build_sim_state_dto(st))
 }
 
 #[tauri::command]
-fn sim_lists() -> Result<SimListsDto, String> {
-    let guard = SIM_STATE.read().unwrap();
+fn sim_lists(session_id: Option<String>) -> Result<SimListsDto, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
     let st = guard
         .as_ref()
         .ok_or_else(|| "sim not initialized".to_string())?;
@@ -868,18 +2336,53 @@ fn sim_lists() -> Result<SimListsDto, String> {
         .iter()
         .map(|n| n.id.0.clone())
         .collect::<Vec<_>>();
-    let foundries = st
+    let mut foundries = st
         .world
-        .resource::<runtime::CapacityBook>()
-        .contracts
+        .resource::<runtime::FoundryReliability>()
+        .profiles
         .iter()
-        .map(|c| c.foundry_id.clone())
+        .map(|p| p.id.clone())
         .collect::<Vec<_>>();
+    for c in &st.world.resource::<runtime::CapacityBook>().contracts {
+        if !foundries.contains(&c.foundry_id) {
+            foundries.push(c.foundry_id.clone());
+        }
+    }
     let segments = st.dom.segments.iter().map(|s| s.name.clone()).collect();
+    let markets = st.world.get_resource::<runtime::MarketConfigRes>();
+    let design_segments = markets
+        .map(|m| {
+            m.segments
+                .iter()
+                .map(|s| DesignSegmentDto {
+                    id: s.id.clone(),
+                    name: s.name.clone(),
+                    tdp_min_w: s.tdp_w.map(|t| t.0),
+                    tdp_max_w: s.tdp_w.map(|t| t.1),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let product_kinds = markets
+        .map(|m| {
+            m.product_kinds
+                .iter()
+                .map(|k| ProductKindDto {
+                    kind: k.kind.clone(),
+                    unit_cost_pct: k.unit_cost_frac * 100.0,
+                    perf_pct: k.perf_frac * 100.0,
+                    appeal: k.appeal,
+                    segments: k.segments.clone(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
     Ok(SimListsDto {
         tech_nodes,
         foundries,
         segments,
+        design_segments,
+        product_kinds,
     })
 }
 
@@ -898,35 +2401,73 @@ struct BalanceInfoDto {
     active_mods: Vec<ActiveModDto>,
 }
 
+/// Base value, modifiers in effect (in the order added, with source and magnitude) and the
+/// result for one quantity, e.g. `{ "target": "wafer_cost" }` with `key` a node id.
+#[tauri::command]
+fn sim_value_provenance(
+    target: runtime::EffectTarget,
+    key: Option<String>,
+    session_id: Option<String>,
+) -> Result<runtime::ValueProvenance, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    runtime::value_provenance(&st.world, &target, key.as_deref())
+}
+
+#[tauri::command]
+fn sim_finance_consolidation(
+    session_id: Option<String>,
+) -> Result<runtime::FinanceConsolidation, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    Ok(runtime::consolidate_finances(&st.world))
+}
+
 #[tauri::command]
-fn sim_balance_info() -> Result<BalanceInfoDto, String> {
-    let guard = SIM_STATE.read().unwrap();
+fn sim_balance_info(session_id: Option<String>) -> Result<BalanceInfoDto, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
     let st = guard
         .as_ref()
         .ok_or_else(|| "sim not initialized".to_string())?;
     let dto = build_sim_state_dto(st);
-    // Build active mods from runtime resources: tech via ModEngine, market via MarketModEffects
+    // Active mods from the effects engine: one row per effect and target kind
     let world = &st.world;
     let mut mods_list: Vec<ActiveModDto> = Vec::new();
-    if let Some(me) = world.get_non_send_resource::<runtime::ModEngineRes>() {
-        for (id, start, end) in me.engine.active_effects_summary() {
-            mods_list.push(ActiveModDto {
-                id,
-                kind: "tech".into(),
-                target: "tech_tree".into(),
-                start: start.to_string(),
-                end: end.to_string(),
-            });
-        }
-    }
-    if let Some(mm) = world.get_resource::<runtime::MarketModEffects>() {
-        for e in &mm.0 {
+    if let Some(fx) = world.get_resource::<runtime::Effects>() {
+        for m in fx
+            .modifiers
+            .iter()
+            .filter(|m| m.source != runtime::EffectSource::Macro)
+        {
+            let (kind, target) = match &m.effect.target {
+                runtime::EffectTarget::WaferCost | runtime::EffectTarget::Yield => {
+                    ("tech", "tech_tree".to_string())
+                }
+                runtime::EffectTarget::SegmentDemand { segment }
+                | runtime::EffectTarget::SegmentElasticity { segment } => {
+                    ("market", segment.clone())
+                }
+                _ => continue,
+            };
+            if mods_list
+                .iter()
+                .any(|d| d.id == m.id && d.kind == kind && d.target == target)
+            {
+                continue;
+            }
             mods_list.push(ActiveModDto {
-                id: e.id.clone(),
-                kind: "market".into(),
-                target: e.segment_id.clone(),
-                start: e.start.to_string(),
-                end: e.end.to_string(),
+                id: m.id.clone(),
+                kind: kind.into(),
+                target,
+                start: m.start.to_string(),
+                end: m.end.map(|e| e.to_string()).unwrap_or_default(),
             });
         }
     }
@@ -937,172 +2478,57 @@ fn sim_balance_info() -> Result<BalanceInfoDto, String> {
 }
 
 #[tauri::command]
-fn sim_campaign_reset(which: Option<String>) -> Result<SimStateDto, String> {
-    let id = which.unwrap_or_else(|| "1990s".to_string());
-    tracing::info!(target: "ipc", which = %id, "sim_campaign_reset");
-    // Resolve embedded scenario YAML
-    let text = match id.as_str() {
-        "1990s" => embedded::get_yaml("campaign_1990s").to_string(),
-        "tutorial_24m" => embedded::get_yaml("tutorial_24m").to_string(),
-        other => {
-            // Back-compat: allow raw names that contain campaign/tutorial ids
-            if other.contains("campaign_1990s") {
-                embedded::get_yaml("campaign_1990s").to_string()
-            } else if other.contains("tutorial_24m") {
-                embedded::get_yaml("tutorial_24m").to_string()
-            } else {
-                return Err("unknown scenario".into());
-            }
-        }
-    };
-    // Validate scenario YAML
-    validate_yaml::<CampaignScenario>(&text, "campaign")
-        .map_err(|e| format!("campaign.yaml invalid: {e}"))?;
-    let sc: CampaignScenario = from_yaml_with_coerce(&text)?;
-    // Build new dom
-    let start =
-        chrono::NaiveDate::parse_from_str(&sc.start_date, "%Y-%m-%d").map_err(|e| e.to_string())?;
-    let end =
-        chrono::NaiveDate::parse_from_str(&sc.end_date, "%Y-%m-%d").map_err(|e| e.to_string())?;
-    // Validate and load tech + markets assets from embedded
+fn sim_campaign_reset(
+    which: Option<String>,
+    session_id: Option<String>,
+) -> Result<SimStateDto, String> {
+    let args = serde_json::json!({ "which": &which });
+    audited(session_id.clone(), "sim_campaign_reset", args, || {
+        campaign_reset(which, session_id)
+    })
+}
+
+/// The optional `tutorial` section of a scenario file.
+fn tutorial_cfg_from_yaml(text: &str) -> Option<TutorialCfg> {
+    serde_yaml::from_str::<serde_yaml::Value>(text)
+        .ok()?
+        .get("tutorial")
+        .and_then(|t| serde_yaml::from_value::<TutorialCfg>(t.clone()).ok())
+}
+
+/// Tech, markets, events and difficulty levels embedded in the app, validated.
+fn game_content() -> Result<runtime::GameContent, String> {
     let tech_text = embedded::get_yaml("tech_era_1990s");
     validate_yaml::<TechRoot>(&tech_text, "tech_era")
         .map_err(|e| format!("tech_era_1990s.yaml invalid: {e}"))?;
-    let tech_nodes = load_tech_nodes_from_yaml(tech_text);
     let markets_text = embedded::get_yaml("markets_1990s");
     validate_yaml::<MarketsRoot>(&markets_text, "markets")
         .map_err(|e| format!("markets_1990s.yaml invalid: {e}"))?;
-    let markets = runtime::MarketConfigRes::from_yaml_str(markets_text).unwrap_or_default();
-    let segments: Vec<core::MarketSegment> = markets
-        .segments
-        .iter()
-        .map(|s| core::MarketSegment {
-            name: s.name.clone(),
-            base_demand_units: s.base_demand_units_1990,
-            price_elasticity: s.elasticity,
-        })
-        .collect();
-    let dom = core::World {
-        macro_state: core::MacroState {
-            date: start,
-            inflation_annual: 0.02,
-            interest_rate: 0.05,
-            fx_usd_index: 100.0,
-        },
-        tech_tree: tech_nodes,
-        companies: vec![core::Company {
-            name: "Player".into(),
-            cash_usd: persistence::cents_i64_to_decimal(sc.player_start_cash_cents),
-            debt_usd: rust_decimal::Decimal::ZERO,
-            ip_portfolio: vec![],
-        }],
-        segments,
-    };
-    let mut world = runtime::init_world(
-        dom.clone(),
-        core::SimConfig {
-            tick_days: 30,
-            rng_seed: 42,
-        },
-    );
-    world.insert_resource(markets);
-    // Load campaign events YAML from embedded by convention
-    let ev_cfg = if sc.events_yaml.contains("campaign_1990s") {
-        market_events_from_yaml_str(embedded::get_yaml("events_1990s"))
-    } else {
-        // Fallback to embedded 1990s events
-        market_events_from_yaml_str(embedded::get_yaml("events_1990s"))
-    };
-    world.insert_resource(ev_cfg);
-    // Inject campaign scenario into runtime for goal tracking
-    let mut cfg = runtime::CampaignScenarioRes {
-        start,
-        end,
-        difficulty: None,
-        goals: vec![],
-        fails: vec![],
-    };
-    for g in &sc.goals {
-        match g {
-            YamlGoal::ReachShare {
-                segment,
-                min_share,
-                deadline,
-            } => {
-                let d = chrono::NaiveDate::parse_from_str(deadline, "%Y-%m-%d")
-                    .map_err(|e| e.to_string())?;
-                cfg.goals.push(runtime::GoalKind::ReachShare {
-                    segment: segment.clone(),
-                    min_share: min_share.clone(),
-                    deadline: d,
-                });
-            }
-            YamlGoal::LaunchNode { node, deadline } => {
-                let d = chrono::NaiveDate::parse_from_str(deadline, "%Y-%m-%d")
-                    .map_err(|e| e.to_string())?;
-                cfg.goals.push(runtime::GoalKind::LaunchNode {
-                    node: node.clone(),
-                    deadline: d,
-                });
-            }
-            YamlGoal::ProfitTarget {
-                profit_cents,
-                deadline,
-            } => {
-                let d = chrono::NaiveDate::parse_from_str(deadline, "%Y-%m-%d")
-                    .map_err(|e| e.to_string())?;
-                cfg.goals.push(runtime::GoalKind::ProfitTarget {
-                    profit_cents: profit_cents.clone(),
-                    deadline: d,
-                });
-            }
-            YamlGoal::SurviveEvent { event_id, deadline } => {
-                let d = chrono::NaiveDate::parse_from_str(deadline, "%Y-%m-%d")
-                    .map_err(|e| e.to_string())?;
-                cfg.goals.push(runtime::GoalKind::SurviveEvent {
-                    event_id: event_id.clone(),
-                    deadline: d,
-                });
-            }
-        }
-    }
-    for f in &sc.fail_conditions {
-        match f {
-            YamlFail::CashBelow { threshold_cents } => {
-                cfg.fails.push(runtime::FailCondKind::CashBelow {
-                    threshold_cents: *threshold_cents,
-                })
-            }
-            YamlFail::ShareBelow {
-                segment,
-                min_share,
-                deadline,
-            } => {
-                let d = chrono::NaiveDate::parse_from_str(deadline, "%Y-%m-%d")
-                    .map_err(|e| e.to_string())?;
-                cfg.fails.push(runtime::FailCondKind::ShareBelow {
-                    segment: segment.clone(),
-                    min_share: min_share.clone(),
-                    deadline: d,
-                });
-            }
-        }
-    }
-    world.insert_resource(cfg);
-    world.insert_resource(runtime::CampaignStateRes::default());
-    // Optional tutorial section
-    let tutorial_cfg: Option<TutorialCfg> = match serde_yaml::from_str::<serde_yaml::Value>(&text) {
-        Ok(v) => v
-            .get("tutorial")
-            .and_then(|t| serde_yaml::from_value::<TutorialCfg>(t.clone()).ok()),
-        Err(_) => None,
-    };
-    if let Some(tcfg) = &tutorial_cfg {
-        runtime::init_tutorial(&mut world, tcfg.cash_threshold_cents_month24);
-    }
+    Ok(runtime::GameContent {
+        tech: runtime::cached_config(tech_text, |t| Ok(load_tech_nodes_from_yaml(t)))?,
+        markets: runtime::cached_config(markets_text, runtime::MarketConfigRes::from_yaml_str)
+            .unwrap_or_default(),
+        events: market_events_from_yaml_str(embedded::get_yaml("events_1990s")),
+        difficulty: runtime::cached_config(
+            embedded::get_yaml("difficulty"),
+            runtime::DifficultyPresets::from_yaml_str,
+        )
+        .map_err(|e| format!("difficulty.yaml invalid: {e}"))?,
+    })
+}
+
+fn campaign_reset(
+    which: Option<String>,
+    session_id: Option<String>,
+) -> Result<SimStateDto, String> {
+    let id = which.unwrap_or_else(|| "1990s".to_string());
+    tracing::info!(target: "ipc", which = %id, "sim_campaign_reset");
+    let sess = SESSIONS.open(session_id.as_deref());
+    let (world, sc, tutorial_cfg) = campaign_world(&id)?;
+    let dom = world.resource::<runtime::DomainWorld>().0.clone();
     // Replace global state
     {
-        let mut guard = SIM_STATE.write().unwrap();
+        let mut guard = sess.state.write().unwrap();
         *guard = Some(SimState {
             world,
             dom,
@@ -1112,21 +2538,146 @@ fn sim_campaign_reset(which: Option<String>) -> Result<SimStateDto, String> {
             autosave: true,
         });
     }
+    sess.spectator.write().unwrap().take();
+    close_journal(&sess);
     // Return the new state
-    let guard = SIM_STATE.read().unwrap();
+    let guard = sess.state.read().unwrap();
     let st = guard.as_ref().unwrap();
     let dto = build_sim_state_dto(st);
     tracing::info!(target: "ipc", date = %dto.date, "sim_campaign_reset: ok");
     Ok(dto)
 }
 
+/// Start of the embedded campaign `id`: its world, scenario and tutorial section.
+fn campaign_world(
+    id: &str,
+) -> Result<(runtime::World, CampaignScenario, Option<TutorialCfg>), String> {
+    // Resolve embedded scenario YAML
+    let text = match id {
+        "1990s" => embedded::get_yaml("campaign_1990s").to_string(),
+        "tutorial_24m" => embedded::get_yaml("tutorial_24m").to_string(),
+        other => {
+            // Back-compat: allow raw names that contain campaign/tutorial ids
+            if other.contains("campaign_1990s") {
+                embedded::get_yaml("campaign_1990s").to_string()
+            } else if other.contains("tutorial_24m") {
+                embedded::get_yaml("tutorial_24m").to_string()
+            } else {
+                return Err("unknown scenario".into());
+            }
+        }
+    };
+    // Validate scenario YAML
+    validate_yaml::<CampaignScenario>(&text, "campaign")
+        .map_err(|e| format!("campaign.yaml invalid: {e}"))?;
+    let mut sc: CampaignScenario = from_yaml_with_coerce(&text)?;
+    sc.id = id.to_string();
+    let (mut world, _) = game_content()?
+        .campaign_world(&text, 42)
+        .map_err(|e| format!("campaign.yaml invalid: {e}"))?;
+    // Interactive sessions pause on decision events until the player chooses
+    world.insert_resource(runtime::DecisionMode::Pause);
+    let tutorial_cfg = tutorial_cfg_from_yaml(&text);
+    Ok((world, sc, tutorial_cfg))
+}
+
+/// Drop the parsed asset cache so the next reset re-reads every bundle; returns the cache's
+/// counters from before the drop.
+#[tauri::command]
+fn sim_config_cache_invalidate() -> runtime::ConfigCacheStats {
+    let stats = runtime::config_cache_stats();
+    runtime::invalidate_config_cache();
+    tracing::info!(target: "ipc", entries = stats.entries, hits = stats.hits, "sim_config_cache_invalidate");
+    stats
+}
+
+/// Swap in a new market or AI config (YAML text) from the next month, for modding and live
+/// balancing; refused if it does not parse or drops a segment on sale.
+#[tauri::command]
+fn sim_config_swap(
+    kind: runtime::ConfigKind,
+    yaml: String,
+    session_id: Option<String>,
+) -> Result<SimStateDto, String> {
+    audited(
+        session_id.clone(),
+        "sim_config_swap",
+        serde_json::json!({ "kind": kind, "yaml": yaml }),
+        || {
+            world_op(
+                session_id,
+                runtime::PlayerCommand::ConfigSwap { kind, yaml },
+            )
+        },
+    )
+}
+
+/// Pending and applied config swaps, oldest first.
+#[tauri::command]
+fn sim_config_versions(session_id: Option<String>) -> Result<runtime::ConfigSwaps, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    Ok(st
+        .world
+        .get_resource::<runtime::ConfigSwaps>()
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// Start a sandbox game from the new-game screen's starting conditions.
+#[tauri::command]
+fn sim_sandbox_new(
+    setup: runtime::SandboxSetup,
+    session_id: Option<String>,
+) -> Result<SimStateDto, String> {
+    let args = serde_json::to_value(&setup).unwrap_or_default();
+    audited(session_id.clone(), "sim_sandbox_new", args, || {
+        let sess = SESSIONS.open(session_id.as_deref());
+        let (mut world, _months) = setup.build(&game_content()?)?;
+        world.insert_resource(runtime::DecisionMode::Pause);
+        let dom = world.resource::<runtime::DomainWorld>().0.clone();
+        *sess.state.write().unwrap() = Some(SimState {
+            world,
+            dom,
+            busy: false,
+            scenario: None,
+            tutorial: None,
+            autosave: true,
+        });
+        sess.spectator.write().unwrap().take();
+        close_journal(&sess);
+        let guard = sess.state.read().unwrap();
+        let dto = build_sim_state_dto(guard.as_ref().unwrap());
+        tracing::info!(target: "ipc", date = %dto.date, "sim_sandbox_new: ok");
+        Ok(dto)
+    })
+}
+
 #[tauri::command]
-fn sim_override(app: tauri::AppHandle, ovr: OverrideReq) -> Result<OverrideResp, String> {
+fn sim_override(
+    app: tauri::AppHandle,
+    ovr: OverrideReq,
+    session_id: Option<String>,
+) -> Result<OverrideResp, String> {
+    let args = serde_json::to_value(&ovr).unwrap_or_default();
+    audited(session_id.clone(), "sim_override", args, || {
+        apply_override(app, ovr, session_id)
+    })
+}
+
+fn apply_override(
+    app: tauri::AppHandle,
+    ovr: OverrideReq,
+    session_id: Option<String>,
+) -> Result<OverrideResp, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
     let (tx, rx) = std::sync::mpsc::channel();
     let _ = app.run_on_main_thread(move || {
         let mut resp = OverrideResp::default();
-        let state = SIM_STATE.clone();
-        let mut guard = state.write().unwrap();
+        let mut guard = sess.state.write().unwrap();
         let st = match guard.as_mut() {
             Some(s) => s,
             None => {
@@ -1138,39 +2689,964 @@ fn sim_override(app: tauri::AppHandle, ovr: OverrideReq) -> Result<OverrideResp,
         if let Some(df) = ovr.price_delta_frac {
             let asp = runtime::apply_price_delta(world, df);
             resp.asp_cents = Some(persistence::decimal_to_cents_i64(asp).unwrap_or(0));
+            journal(&sess, |j| {
+                j.command(world, &runtime::PlayerCommand::PriceDelta { frac: df })
+            });
         }
         if let Some(d) = ovr.rd_delta_cents {
             let b = runtime::apply_rd_delta(world, d);
             resp.rd_budget_cents = Some(b);
+            journal(&sess, |j| {
+                j.command(world, &runtime::PlayerCommand::RdDelta { cents: d })
+            });
         }
         if let Some(cap) = ovr.capacity_request {
-            let s = runtime::apply_capacity_request(
+            let s = runtime::apply_capacity_request_with(
                 world,
                 cap.wafers_per_month,
                 cap.months,
                 cap.billing_cents_per_wafer,
                 cap.take_or_pay_frac,
+                cap.ramp_wafers.clone(),
+                cap.min_order_wafers,
+                cap.rollover,
             );
             resp.capacity_summary = Some(s);
+            let command = runtime::PlayerCommand::CapacityRequest {
+                wafers_per_month: cap.wafers_per_month,
+                months: cap.months,
+                billing_cents_per_wafer: cap.billing_cents_per_wafer,
+                take_or_pay_frac: cap.take_or_pay_frac,
+                ramp_wafers: cap.ramp_wafers,
+                min_order_wafers: cap.min_order_wafers,
+                rollover: cap.rollover,
+            };
+            journal(&sess, |j| j.command(world, &command));
         }
         if let Some(t) = ovr.tapeout {
-            let ready = runtime::apply_tapeout_request(
-                world,
-                t.perf_index,
-                t.die_area_mm2,
-                t.tech_node,
-                t.expedite.unwrap_or(false),
-            );
-            resp.tapeout_ready = Some(ready.to_string());
+            let base = runtime::TapeoutDesign::new(t.perf_index, t.die_area_mm2, t.tech_node);
+            let design = runtime::TapeoutDesign {
+                kind: t.kind.unwrap_or(base.kind.clone()),
+                microarch: t.microarch.unwrap_or(base.microarch.clone()),
+                tdp_w: t.tdp_w.unwrap_or(base.tdp_w),
+                target_segments: t.target_segments.unwrap_or_default(),
+                clock_frac: t.clock_frac.unwrap_or(base.clock_frac),
+                expedite: t.expedite.unwrap_or(false),
+                expedite_months: t.expedite_months,
+                family: t.family.filter(|f| !f.trim().is_empty()),
+                ..base
+            };
+            match runtime::apply_tapeout_design(world, &design) {
+                Ok(ready) => {
+                    resp.tapeout_ready = Some(ready.to_string());
+                    journal(&sess, |j| {
+                        j.command(world, &runtime::PlayerCommand::Tapeout { design })
+                    });
+                }
+                Err(e) => {
+                    let _ = tx.send(Err(e));
+                    return;
+                }
+            }
+        }
+        let _ = tx.send(Ok(resp));
+    });
+    rx.recv().map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+fn sim_decide(
+    event_id: String,
+    choice: String,
+    session_id: Option<String>,
+) -> Result<SimStateDto, String> {
+    let args = serde_json::json!({ "event_id": &event_id, "choice": &choice });
+    audited(session_id.clone(), "sim_decide", args, || {
+        decide(event_id, choice, session_id)
+    })
+}
+
+fn decide(
+    event_id: String,
+    choice: String,
+    session_id: Option<String>,
+) -> Result<SimStateDto, String> {
+    tracing::info!(target: "ipc", %event_id, %choice, "sim_decide");
+    world_op(
+        session_id,
+        runtime::PlayerCommand::Decide {
+            event_id,
+            choice_id: choice,
+        },
+    )
+}
+
+#[tauri::command]
+fn sim_accept_loan(session_id: Option<String>) -> Result<SimStateDto, String> {
+    audited(
+        session_id.clone(),
+        "sim_accept_loan",
+        serde_json::json!({}),
+        || accept_loan(session_id),
+    )
+}
+
+fn accept_loan(session_id: Option<String>) -> Result<SimStateDto, String> {
+    tracing::info!(target: "ipc", "sim_accept_loan");
+    world_op(session_id, runtime::PlayerCommand::AcceptLoan)
+}
+
+/// Draw on the credit line at the current rating's rate.
+#[tauri::command]
+fn sim_credit_draw(cents: i64, session_id: Option<String>) -> Result<SimStateDto, String> {
+    audited(
+        session_id.clone(),
+        "sim_credit_draw",
+        serde_json::json!({ "cents": cents }),
+        || world_op(session_id, runtime::PlayerCommand::DrawCredit { cents }),
+    )
+}
+
+/// Repay credit-line principal, oldest draws first.
+#[tauri::command]
+fn sim_credit_repay(cents: i64, session_id: Option<String>) -> Result<SimStateDto, String> {
+    audited(
+        session_id.clone(),
+        "sim_credit_repay",
+        serde_json::json!({ "cents": cents }),
+        || world_op(session_id, runtime::PlayerCommand::RepayCredit { cents }),
+    )
+}
+
+/// Charge a price other than the global ASP in a region (`price_cents` = null clears it).
+#[tauri::command]
+fn sim_regional_price(
+    region: String,
+    price_cents: Option<i64>,
+    session_id: Option<String>,
+) -> Result<SimStateDto, String> {
+    audited(
+        session_id.clone(),
+        "sim_regional_price",
+        serde_json::json!({ "region": region, "price_cents": price_cents }),
+        || {
+            world_op(
+                session_id,
+                runtime::PlayerCommand::RegionalPrice {
+                    region,
+                    price_cents,
+                },
+            )
+        },
+    )
+}
+
+/// Split wafers between product lines by share (line → share); an empty plan returns to
+/// automatic allocation.
+#[tauri::command]
+fn sim_wafer_allocation(
+    plan: std::collections::BTreeMap<String, f32>,
+    session_id: Option<String>,
+) -> Result<SimStateDto, String> {
+    audited(
+        session_id.clone(),
+        "sim_wafer_allocation",
+        serde_json::json!({ "plan": plan }),
+        || world_op(session_id, runtime::PlayerCommand::WaferAllocation { plan }),
+    )
+}
+
+/// Sign the volume deal an OEM offers once the relationship is strong enough.
+#[tauri::command]
+fn sim_oem_deal(oem_id: String, session_id: Option<String>) -> Result<SimStateDto, String> {
+    audited(
+        session_id.clone(),
+        "sim_oem_deal",
+        serde_json::json!({ "oem_id": oem_id }),
+        || world_op(session_id, runtime::PlayerCommand::OemDeal { oem_id }),
+    )
+}
+
+/// Set the price concession on an OEM's deal units and whether it is the exclusive partner.
+#[tauri::command]
+fn sim_oem_terms(
+    oem_id: String,
+    concession_frac: f32,
+    exclusive: bool,
+    session_id: Option<String>,
+) -> Result<SimStateDto, String> {
+    audited(
+        session_id.clone(),
+        "sim_oem_terms",
+        serde_json::json!({
+            "oem_id": oem_id,
+            "concession_frac": concession_frac,
+            "exclusive": exclusive,
+        }),
+        || {
+            world_op(
+                session_id,
+                runtime::PlayerCommand::OemTerms {
+                    oem_id,
+                    concession_frac,
+                    exclusive,
+                },
+            )
+        },
+    )
+}
+
+/// Invest cash in an OEM relationship to raise its score.
+#[tauri::command]
+fn sim_oem_invest(
+    oem_id: String,
+    cents: i64,
+    session_id: Option<String>,
+) -> Result<SimStateDto, String> {
+    audited(
+        session_id.clone(),
+        "sim_oem_invest",
+        serde_json::json!({ "oem_id": oem_id, "cents": cents }),
+        || {
+            world_op(
+                session_id,
+                runtime::PlayerCommand::OemInvest { oem_id, cents },
+            )
+        },
+    )
+}
+
+/// Publicly promise a product on `node` with at least `min_perf_index` by `due` (YYYY-MM-DD).
+#[tauri::command]
+fn sim_roadmap_announce(
+    node: String,
+    min_perf_index: f32,
+    due: String,
+    session_id: Option<String>,
+) -> Result<SimStateDto, String> {
+    audited(
+        session_id.clone(),
+        "sim_roadmap_announce",
+        serde_json::json!({ "node": node, "min_perf_index": min_perf_index, "due": due }),
+        || {
+            let due =
+                chrono::NaiveDate::parse_from_str(&due, "%Y-%m-%d").map_err(|e| e.to_string())?;
+            world_op(
+                session_id,
+                runtime::PlayerCommand::RoadmapAnnounce {
+                    node,
+                    min_perf_index,
+                    due,
+                },
+            )
+        },
+    )
+}
+
+/// Set the monthly process and design R&D budgets (cents).
+#[tauri::command]
+fn sim_rnd_budgets(
+    process_cents: i64,
+    design_cents: i64,
+    session_id: Option<String>,
+) -> Result<SimStateDto, String> {
+    audited(
+        session_id.clone(),
+        "sim_rnd_budgets",
+        serde_json::json!({ "process_cents": process_cents, "design_cents": design_cents }),
+        || {
+            world_op(
+                session_id,
+                runtime::PlayerCommand::RndBudgets {
+                    process_cents,
+                    design_cents,
+                },
+            )
+        },
+    )
+}
+
+/// Set the monthly quality-control budget (cents).
+#[tauri::command]
+fn sim_qc_budget(cents: i64, session_id: Option<String>) -> Result<SimStateDto, String> {
+    audited(
+        session_id.clone(),
+        "sim_qc_budget",
+        serde_json::json!({ "cents": cents }),
+        || world_op(session_id, runtime::PlayerCommand::QcBudget { cents }),
+    )
+}
+
+/// Developer console: run a `runtime::debug_command` (`help` lists them) and return its
+/// output. Only builds with the `debug-console` feature have the command.
+#[cfg(feature = "debug-console")]
+#[tauri::command]
+fn sim_debug(cmd: String, session_id: Option<String>) -> Result<String, String> {
+    audited(
+        session_id.clone(),
+        "sim_debug",
+        serde_json::json!({ "cmd": cmd }),
+        || {
+            let sess = SESSIONS.get(session_id.as_deref())?;
+            if sess.auto.lock().unwrap().is_some() {
+                return Err("pause auto-advance first".into());
+            }
+            let mut guard = sess.state.write().unwrap();
+            let st = guard
+                .as_mut()
+                .ok_or_else(|| "sim not initialized".to_string())?;
+            if st.busy {
+                return Err("busy".to_string());
+            }
+            let out = runtime::debug_command(&mut st.world, &cmd)?;
+            st.dom = st.world.resource::<runtime::DomainWorld>().0.clone();
+            refresh_spectator(&sess, st);
+            Ok(out)
+        },
+    )
+}
+
+/// Replace the player's standing pricing rules.
+#[tauri::command]
+fn sim_pricing_rules_set(
+    rules: Vec<runtime::PricingRule>,
+    session_id: Option<String>,
+) -> Result<SimStateDto, String> {
+    let args = serde_json::json!({ "rules": rules });
+    audited(session_id.clone(), "sim_pricing_rules_set", args, || {
+        world_op(session_id, runtime::PlayerCommand::PricingRules { rules })
+    })
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct DtoPricingRules {
+    rules: Vec<runtime::PricingRule>,
+    firings: Vec<runtime::PricingRuleFiring>,
+}
+
+/// Standing pricing rules and which of them fired, oldest first.
+#[tauri::command]
+fn sim_pricing_rules(session_id: Option<String>) -> Result<DtoPricingRules, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    Ok(DtoPricingRules {
+        rules: st
+            .world
+            .get_resource::<runtime::PricingRules>()
+            .map(|r| r.rules.clone())
+            .unwrap_or_default(),
+        firings: st
+            .world
+            .get_resource::<runtime::PricingRuleLog>()
+            .map(|l| l.firings.clone())
+            .unwrap_or_default(),
+    })
+}
+
+/// File a patent on a microarchitecture feature.
+#[tauri::command]
+fn sim_patent_file(feature: String, session_id: Option<String>) -> Result<SimStateDto, String> {
+    audited(
+        session_id.clone(),
+        "sim_patent_file",
+        serde_json::json!({ "feature": feature }),
+        || world_op(session_id, runtime::PlayerCommand::FilePatent { feature }),
+    )
+}
+
+/// Buy months of market research on a segment, paid up front.
+#[tauri::command]
+fn sim_market_research_buy(
+    segment: String,
+    months: u32,
+    session_id: Option<String>,
+) -> Result<SimStateDto, String> {
+    audited(
+        session_id.clone(),
+        "sim_market_research_buy",
+        serde_json::json!({ "segment": segment, "months": months }),
+        || {
+            world_op(
+                session_id,
+                runtime::PlayerCommand::MarketResearch { segment, months },
+            )
+        },
+    )
+}
+
+/// Sign second-hand capacity on a trailing-edge node at the market price.
+#[tauri::command]
+fn sim_bargain_contract(
+    node: String,
+    wafers_per_month: u32,
+    months: u32,
+    session_id: Option<String>,
+) -> Result<SimStateDto, String> {
+    audited(
+        session_id.clone(),
+        "sim_bargain_contract",
+        serde_json::json!({ "node": node, "wafers_per_month": wafers_per_month, "months": months }),
+        || {
+            world_op(
+                session_id,
+                runtime::PlayerCommand::BargainContract {
+                    node,
+                    wafers_per_month,
+                    months,
+                },
+            )
+        },
+    )
+}
+
+/// Bid for the open console generation RFQ at a share of the ASP.
+#[tauri::command]
+fn sim_console_bid(price_frac: f64, session_id: Option<String>) -> Result<SimStateDto, String> {
+    audited(
+        session_id.clone(),
+        "sim_console_bid",
+        serde_json::json!({ "price_frac": price_frac }),
+        || {
+            world_op(
+                session_id,
+                runtime::PlayerCommand::ConsoleBid { price_frac },
+            )
+        },
+    )
+}
+
+/// Quote the most a rival would pay (total cents) to license one of our nodes or features.
+#[tauri::command]
+fn sim_license_quote(
+    ip: runtime::LicensedIp,
+    licensee: String,
+    session_id: Option<String>,
+) -> Result<i64, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    runtime::license_quote(&st.world, &ip, &licensee)
+}
+
+/// License a node or microarchitecture feature to a rival for a per-unit royalty.
+#[tauri::command]
+fn sim_license_offer(
+    terms: runtime::LicenseTerms,
+    session_id: Option<String>,
+) -> Result<SimStateDto, String> {
+    audited(
+        session_id.clone(),
+        "sim_license_offer",
+        serde_json::json!({ "terms": terms }),
+        || world_op(session_id, runtime::PlayerCommand::LicenseOut { terms }),
+    )
+}
+
+/// Run a player command against the session world, journal it and return the refreshed
+/// state. While auto-advance runs, the command is queued for its thread instead (failures
+/// arrive as `sim-auto-tick` events) and the state returned is the one before auto-advance
+/// started.
+fn world_op(
+    session_id: Option<String>,
+    command: runtime::PlayerCommand,
+) -> Result<SimStateDto, String> {
+    world_op_with(session_id, command, build_sim_state_dto)
+}
+
+/// [`world_op`], answering with `view` of the session instead of the full state.
+fn world_op_with<T>(
+    session_id: Option<String>,
+    command: runtime::PlayerCommand,
+    view: impl FnOnce(&SimState) -> T,
+) -> Result<T, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    if let Some(driver) = sess.auto.lock().unwrap().as_ref() {
+        driver.inject(move |w| command.apply(w));
+        let guard = sess.state.read().unwrap();
+        let st = guard
+            .as_ref()
+            .ok_or_else(|| "sim not initialized".to_string())?;
+        return Ok(view(st));
+    }
+    let mut guard = sess.state.write().unwrap();
+    let st = guard
+        .as_mut()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    if st.busy {
+        return Err("busy".to_string());
+    }
+    command.apply(&mut st.world)?;
+    journal(&sess, |j| j.command(&st.world, &command));
+    st.dom = st.world.resource::<runtime::DomainWorld>().0.clone();
+    Ok(view(st))
+}
+
+/// Recommended wafer starts with shortage/overbuild flags for the next `horizon_months`.
+#[tauri::command]
+fn sim_production_plan(
+    horizon_months: Option<u32>,
+    session_id: Option<String>,
+) -> Result<runtime::ProductionPlan, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    let horizon = horizon_months.unwrap_or(12).clamp(1, 60);
+    let input = runtime::ProductionPlanInput::from_world(&st.world, horizon);
+    Ok(runtime::plan_production(&input))
+}
+
+/// In-flight work for a Gantt view: tapeouts, patents, variants, cases, contracts, research
+/// and roadmap promises, built from the live subsystems on each call.
+#[tauri::command]
+fn sim_schedule(session_id: Option<String>) -> Result<runtime::WorkSchedule, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    Ok(runtime::work_schedule(&st.world))
+}
+
+/// Expedite levels for a tapeout on `tech_node` with their cost and availability, quoted
+/// before anything is committed.
+#[tauri::command]
+fn sim_expedite_quote(
+    tech_node: String,
+    session_id: Option<String>,
+) -> Result<Vec<runtime::ExpediteOption>, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    Ok(runtime::expedite_quote(&st.world, &tech_node))
+}
+
+/// Suggested foundry contracts that cover forecast demand at `service_level` for the least
+/// expected cost; advisory only, nothing is signed.
+#[tauri::command]
+fn sim_contract_advice(
+    horizon_months: Option<u32>,
+    service_level: Option<f32>,
+    session_id: Option<String>,
+) -> Result<runtime::PortfolioPlan, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    let horizon = horizon_months.unwrap_or(12).clamp(1, 60);
+    runtime::contract_portfolio_advice(&st.world, horizon, service_level.unwrap_or(0.95))
+}
+
+/// KPI sensitivities to ASP, capacity and R&D from short dry-runs on a clone of the world.
+#[tauri::command]
+fn sim_sensitivity(
+    months: Option<u32>,
+    session_id: Option<String>,
+) -> Result<runtime::SensitivityReport, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    let months = months.unwrap_or(runtime::SENSITIVITY_MONTHS).clamp(1, 24);
+    Ok(runtime::sensitivity_analysis(&st.world, months))
+}
+
+/// Advisor suggestion: the ASP, capacity or R&D move that reaches a share or cash target by a
+/// date, found by bisecting dry-runs on a clone of the world.
+#[tauri::command]
+fn sim_goal_seek(
+    seek: runtime::GoalSeek,
+    session_id: Option<String>,
+) -> Result<runtime::GoalSeekResult, String> {
+    tracing::info!(target: "ipc", ?seek, "sim_goal_seek");
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    runtime::goal_seek(&st.world, &seek)
+}
+
+fn promo_dtos(world: &runtime::World) -> Vec<DtoPromo> {
+    let date = world.resource::<runtime::DomainWorld>().0.macro_state.date;
+    world
+        .resource::<runtime::PromoCalendar>()
+        .promos
+        .iter()
+        .map(|p| DtoPromo {
+            id: p.id,
+            segment_id: p.segment_id.clone(),
+            discount_frac: p.discount_frac,
+            reach_frac: p.reach_frac,
+            start: p.start.to_string(),
+            end: p.end().to_string(),
+            active: p.is_active(date),
+        })
+        .collect()
+}
+
+/// Scheduled, running and finished promos.
+#[tauri::command]
+fn sim_promo_list(session_id: Option<String>) -> Result<Vec<DtoPromo>, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    Ok(promo_dtos(&st.world))
+}
+
+/// Schedule a time-boxed discount on a segment; starts this month unless `start` is given.
+#[tauri::command]
+fn sim_promo_schedule(
+    segment_id: String,
+    discount_frac: f32,
+    months: u32,
+    reach_frac: Option<f32>,
+    start: Option<String>,
+    session_id: Option<String>,
+) -> Result<Vec<DtoPromo>, String> {
+    let args = serde_json::json!({
+        "segment_id": &segment_id,
+        "discount_frac": discount_frac,
+        "months": months,
+        "reach_frac": reach_frac,
+        "start": &start,
+    });
+    audited(session_id.clone(), "sim_promo_schedule", args, || {
+        tracing::info!(target: "ipc", %segment_id, discount_frac, months, "sim_promo_schedule");
+        let start = start
+            .map(|s| chrono::NaiveDate::parse_from_str(&s, "%Y-%m-%d"))
+            .transpose()
+            .map_err(|e| e.to_string())?;
+        let command = runtime::PlayerCommand::SchedulePromo {
+            segment_id,
+            discount_frac,
+            reach_frac: reach_frac.unwrap_or(1.0),
+            start,
+            months,
+        };
+        world_op_with(session_id, command, |st| promo_dtos(&st.world))
+    })
+}
+
+/// Cancel a promo; one already running ends before the current month.
+#[tauri::command]
+fn sim_promo_cancel(id: u32, session_id: Option<String>) -> Result<Vec<DtoPromo>, String> {
+    audited(
+        session_id.clone(),
+        "sim_promo_cancel",
+        serde_json::json!({ "id": id }),
+        || {
+            let command = runtime::PlayerCommand::CancelPromo { id };
+            world_op_with(session_id, command, |st| promo_dtos(&st.world))
+        },
+    )
+}
+
+fn budget_dto(world: &runtime::World) -> DtoBudget {
+    let date = world.resource::<runtime::DomainWorld>().0.macro_state.date;
+    let b = world.resource::<runtime::BudgetState>();
+    DtoBudget {
+        mode: b.mode,
+        board_confidence: b.board_confidence,
+        quarter_to_date: b.quarter_to_date(date),
+        history: b.history.clone(),
+    }
+}
+
+/// Quarterly budgets with quarter-to-date spend, past variances and board confidence.
+#[tauri::command]
+fn sim_budget(session_id: Option<String>) -> Result<DtoBudget, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    Ok(budget_dto(&st.world))
+}
+
+/// Set or clear (`cents` = null) a category's quarterly budget and/or switch between warning
+/// on and enforcing overspend.
+#[tauri::command]
+fn sim_budget_set(
+    category: Option<runtime::BudgetCategory>,
+    cents: Option<i64>,
+    mode: Option<runtime::BudgetMode>,
+    session_id: Option<String>,
+) -> Result<DtoBudget, String> {
+    let args = serde_json::json!({ "category": category, "cents": cents, "mode": mode });
+    audited(session_id.clone(), "sim_budget_set", args, || {
+        tracing::info!(target: "ipc", ?category, ?cents, "sim_budget_set");
+        if let Some(category) = category {
+            let command = runtime::PlayerCommand::Budget { category, cents };
+            world_op_with(session_id.clone(), command, |_| ())?;
+        }
+        if let Some(mode) = mode {
+            let command = runtime::PlayerCommand::BudgetMode { mode };
+            world_op_with(session_id.clone(), command, |_| ())?;
+        }
+        let sess = SESSIONS.get(session_id.as_deref())?;
+        let guard = sess.state.read().unwrap();
+        let st = guard
+            .as_ref()
+            .ok_or_else(|| "sim not initialized".to_string())?;
+        Ok(budget_dto(&st.world))
+    })
+}
+
+fn insurance_dto(world: &runtime::World) -> DtoInsurance {
+    let catalog = world.resource::<runtime::InsuranceCatalog>();
+    let st = world.resource::<runtime::InsuranceState>();
+    DtoInsurance {
+        products: catalog.products.clone(),
+        policies: st.policies.clone(),
+        premiums_paid_cents: st.premiums_paid_cents,
+        claims: st.claims.clone(),
+    }
+}
+
+/// Insurance products on offer, held policies and the claims they paid.
+#[tauri::command]
+fn sim_insurance(session_id: Option<String>) -> Result<DtoInsurance, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    Ok(insurance_dto(&st.world))
+}
+
+/// Take out a policy on an insurance product.
+#[tauri::command]
+fn sim_insurance_buy(
+    product_id: String,
+    session_id: Option<String>,
+) -> Result<DtoInsurance, String> {
+    audited(
+        session_id.clone(),
+        "sim_insurance_buy",
+        serde_json::json!({ "product_id": product_id }),
+        || {
+            let command = runtime::PlayerCommand::BuyInsurance { product_id };
+            world_op_with(session_id, command, |st| insurance_dto(&st.world))
+        },
+    )
+}
+
+/// Cancel a held policy.
+#[tauri::command]
+fn sim_insurance_cancel(
+    product_id: String,
+    session_id: Option<String>,
+) -> Result<DtoInsurance, String> {
+    audited(
+        session_id.clone(),
+        "sim_insurance_cancel",
+        serde_json::json!({ "product_id": product_id }),
+        || {
+            let command = runtime::PlayerCommand::CancelInsurance { product_id };
+            world_op_with(session_id, command, |st| insurance_dto(&st.world))
+        },
+    )
+}
+
+/// Export restrictions in force, the player's response to each, compliant variants and fines.
+#[tauri::command]
+fn sim_export_controls(session_id: Option<String>) -> Result<runtime::ExportControls, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    Ok(st.world.resource::<runtime::ExportControls>().clone())
+}
+
+/// Comply with (lose the demand) or defy (pay fines on) an export restriction.
+#[tauri::command]
+fn sim_export_policy(
+    restriction_id: String,
+    policy: runtime::ExportPolicy,
+    session_id: Option<String>,
+) -> Result<runtime::ExportControls, String> {
+    audited(
+        session_id.clone(),
+        "sim_export_policy",
+        serde_json::json!({ "restriction_id": restriction_id, "policy": policy }),
+        || {
+            let command = runtime::PlayerCommand::ExportPolicy {
+                restriction_id,
+                policy,
+            };
+            world_op_with(session_id, command, export_controls)
+        },
+    )
+}
+
+/// Pay for a compliant variant of the current product that ships into restricted regions.
+#[tauri::command]
+fn sim_export_variant(
+    restriction_id: String,
+    session_id: Option<String>,
+) -> Result<runtime::ExportControls, String> {
+    audited(
+        session_id.clone(),
+        "sim_export_variant",
+        serde_json::json!({ "restriction_id": restriction_id }),
+        || {
+            let command = runtime::PlayerCommand::ExportVariant { restriction_id };
+            world_op_with(session_id, command, export_controls)
+        },
+    )
+}
+
+fn export_controls(st: &SimState) -> runtime::ExportControls {
+    st.world.resource::<runtime::ExportControls>().clone()
+}
+
+/// Audit trail stored with a save (for support and desync diagnosis).
+#[tauri::command]
+async fn sim_audit_log(
+    app: tauri::AppHandle,
+    save_id: i64,
+) -> Result<Vec<persistence::AuditRow>, String> {
+    let url = saves_db_url(&app)?;
+    let pool = persistence::init_db(&url)
+        .await
+        .map_err(|e| e.to_string())?;
+    persistence::list_audit_rows(&pool, save_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct SaveIntegrityDto {
+    save_id: i64,
+    issues: Vec<persistence::SaveIssue>,
+    /// Repair actions taken; empty unless `repair` was requested.
+    actions: Vec<String>,
+}
+
+/// Verify a save's integrity, optionally repairing it (drop orphaned rows and corrupt
+/// snapshots, fall back to the newest intact snapshot).
+#[tauri::command]
+async fn sim_verify_save(
+    app: tauri::AppHandle,
+    save_id: i64,
+    repair: Option<bool>,
+) -> Result<SaveIntegrityDto, String> {
+    tracing::info!(target: "ipc", save_id, ?repair, "sim_verify_save");
+    let url = saves_db_url(&app)?;
+    let pool = persistence::init_db(&url)
+        .await
+        .map_err(|e| e.to_string())?;
+    if repair.unwrap_or(false) {
+        let r = persistence::repair_save(&pool, save_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        return Ok(SaveIntegrityDto {
+            save_id,
+            issues: r.after.issues,
+            actions: r.actions,
+        });
+    }
+    let v = persistence::verify_save(&pool, save_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(SaveIntegrityDto {
+        save_id,
+        issues: v.issues,
+        actions: Vec::new(),
+    })
+}
+
+/// Compare two saved playthroughs: aligned KPI series, the month their cash drifted apart
+/// and their final KPIs (`save_b` minus `save_a`).
+#[tauri::command]
+async fn sim_compare_saves(
+    app: tauri::AppHandle,
+    save_a: i64,
+    save_b: i64,
+) -> Result<runtime::RunComparison, String> {
+    tracing::info!(target: "ipc", save_a, save_b, "sim_compare_saves");
+    let url = saves_db_url(&app)?;
+    let pool = persistence::init_db(&url)
+        .await
+        .map_err(|e| e.to_string())?;
+    let a = persistence::read_save(&pool, save_a)
+        .await
+        .map_err(|e| e.to_string())?;
+    let b = persistence::read_save(&pool, save_b)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(runtime::compare_worlds(
+        &runtime::world_from_save(&a)?,
+        &runtime::world_from_save(&b)?,
+    ))
+}
+
+/// Ids of all open game sessions.
+#[tauri::command]
+fn sim_session_list() -> Result<Vec<String>, String> {
+    Ok(SESSIONS.ids())
+}
+
+/// Copy a session's current game into a new session (e.g. to compare two strategies from
+/// the same starting point). Fails if the target id is already in use.
+#[tauri::command]
+fn sim_session_clone(from: Option<String>, to: String) -> Result<SimStateDto, String> {
+    tracing::info!(target: "ipc", from = ?from, %to, "sim_session_clone");
+    let copy = {
+        let src = SESSIONS.get(from.as_deref())?;
+        let g = src.state.read().unwrap();
+        let st = g
+            .as_ref()
+            .ok_or_else(|| "sim not initialized".to_string())?;
+        SimState {
+            world: runtime::clone_world_state(&st.world),
+            dom: st.dom.clone(),
+            busy: false,
+            scenario: st.scenario.clone(),
+            tutorial: st.tutorial.clone(),
+            autosave: false,
         }
-        let _ = tx.send(Ok(resp));
-    });
-    rx.recv().map_err(|e| e.to_string())?
+    };
+    let dto = build_sim_state_dto(&copy);
+    // Checked and claimed under one lock, so two clones to the same id cannot both land
+    SESSIONS.insert_new(
+        &to,
+        Session {
+            state: RwLock::new(Some(copy)),
+            ..Default::default()
+        },
+    )?;
+    Ok(dto)
+}
+
+/// Drop a session, its game state and its crash journal.
+#[tauri::command]
+fn sim_session_close(session_id: String) -> Result<bool, String> {
+    tracing::info!(target: "ipc", session = %session_id, "sim_session_close");
+    let Some(sess) = SESSIONS.remove(&session_id) else {
+        return Ok(false);
+    };
+    close_journal(&sess);
+    Ok(true)
 }
 
 fn main() {
     // Initialize default world from embedded to avoid filesystem dependencies.
-    let _ = init_default_from_embedded();
+    let _ = init_default_from_embedded(&SESSIONS.open(None));
 
     tauri::Builder::<tauri::Wry>::default()
         .setup(|_app| {
@@ -1194,17 +3670,84 @@ fn main() {
         .invoke_handler(tauri::generate_handler![
             sim_tick,
             sim_tick_quarter,
+            sim_run,
+            sim_run_cancel,
+            sim_auto_start,
+            sim_auto_stop,
+            sim_lockstep_frame,
+            sim_lockstep_step,
+            spectator_state,
+            spectator_kpi,
             sim_plan_quarter,
+            sim_plan_trace,
             sim_override,
+            sim_decide,
+            sim_accept_loan,
+            sim_credit_draw,
+            sim_credit_repay,
+            sim_regional_price,
+            sim_wafer_allocation,
+            sim_oem_deal,
+            sim_oem_terms,
+            sim_oem_invest,
+            sim_roadmap_announce,
+            sim_rnd_budgets,
+            sim_qc_budget,
+            #[cfg(feature = "debug-console")]
+            sim_debug,
+            sim_patent_file,
+            sim_market_research_buy,
+            sim_bargain_contract,
+            sim_console_bid,
+            sim_license_quote,
+            sim_license_offer,
+            sim_insurance,
+            sim_insurance_buy,
+            sim_insurance_cancel,
+            sim_export_controls,
+            sim_export_policy,
+            sim_export_variant,
+            sim_promo_list,
+            sim_promo_schedule,
+            sim_promo_cancel,
+            sim_budget,
+            sim_budget_set,
+            sim_production_plan,
+            sim_contract_advice,
+            sim_expedite_quote,
+            sim_schedule,
+            sim_sensitivity,
+            sim_goal_seek,
+            sim_session_list,
+            sim_session_clone,
+            sim_session_close,
             sim_state,
             sim_lists,
             sim_campaign_reset,
+            sim_sandbox_new,
+            sim_config_cache_invalidate,
+            sim_config_swap,
+            sim_config_versions,
+            sim_journal_status,
+            sim_journal_recover,
+            sim_journal_discard,
             sim_balance_info,
+            sim_value_provenance,
+            sim_finance_consolidation,
             sim_campaign_set_difficulty,
             sim_tutorial_state,
+            sim_hints,
+            sim_warnings,
+            sim_history,
+            sim_state_at,
+            sim_pricing_rules,
+            sim_pricing_rules_set,
             sim_save,
             sim_list_saves,
             sim_load,
+            sim_audit_log,
+            sim_verify_save,
+            sim_compare_saves,
             sim_set_autosave,
             sim_export_campaign,
             sim_build_info,
@@ -1238,17 +3781,21 @@ fn sim_help_markdown() -> Result<String, String> {
 }
 
 #[tauri::command]
-fn sim_campaign_set_difficulty(level: String) -> Result<(), String> {
+fn sim_campaign_set_difficulty(level: String, session_id: Option<String>) -> Result<(), String> {
+    let args = serde_json::json!({ "level": &level });
+    audited(
+        session_id.clone(),
+        "sim_campaign_set_difficulty",
+        args,
+        || set_difficulty(level, session_id),
+    )
+}
+
+fn set_difficulty(level: String, session_id: Option<String>) -> Result<(), String> {
     tracing::info!(target: "ipc", level = %level, "sim_campaign_set_difficulty");
-    let mut g = SIM_STATE.write().unwrap();
-    let st = g
-        .as_mut()
-        .ok_or_else(|| "sim not initialized".to_string())?;
-    if let Some(mut cfg) = st.world.get_resource_mut::<runtime::CampaignScenarioRes>() {
-        cfg.difficulty = Some(level.clone());
-    }
-    // Load presets
+    // Presets are checked against their schema before any is applied
     #[derive(serde::Deserialize, JsonSchema)]
+    #[allow(dead_code)]
     struct Level {
         cash_multiplier: f32,
         min_margin_frac: f32,
@@ -1256,6 +3803,10 @@ fn sim_campaign_set_difficulty(level: String) -> Result<(), String> {
         take_or_pay_frac: f32,
         annual_growth_pct_multiplier: f32,
         event_severity_multiplier: f32,
+        #[serde(default)]
+        distress_grace_months: Option<u32>,
+        #[serde(default)]
+        emergency_loan_cents: Option<i64>,
     }
     #[derive(serde::Deserialize, JsonSchema)]
     struct Root {
@@ -1265,55 +3816,15 @@ fn sim_campaign_set_difficulty(level: String) -> Result<(), String> {
     // Validate difficulty before applying
     validate_yaml::<Root>(&text, "difficulty")
         .map_err(|e| format!("difficulty.yaml invalid: {e}"))?;
-    let root: Root = serde_yaml::from_str(&text).map_err(|e| e.to_string())?;
-    let Some(preset) = root.levels.get(&level) else {
+    let presets = runtime::cached_config(&text, runtime::DifficultyPresets::from_yaml_str)
+        .map_err(|e| format!("difficulty.yaml invalid: {e}"))?;
+    let Some(preset) = presets.levels.get(&level) else {
         return Err("unknown difficulty".into());
     };
-    // Apply to AI config
-    {
-        let mut ai = st.world.resource_mut::<runtime::AiConfig>();
-        ai.0.tactics.min_margin_frac = preset.min_margin_frac;
-        ai.0.tactics.price_epsilon_frac = preset.price_epsilon_frac;
-    }
-    // Apply to difficulty params
-    {
-        let mut dp = st.world.resource_mut::<runtime::DifficultyParams>();
-        dp.default_take_or_pay_frac = preset.take_or_pay_frac.clamp(0.0, 1.0);
-    }
-    // Scale markets growth
-    {
-        let mut markets = st.world.resource_mut::<runtime::MarketConfigRes>();
-        for s in &mut markets.segments {
-            s.annual_growth_pct *= preset.annual_growth_pct_multiplier;
-        }
-    }
-    // Scale events severity for market effects in-place
-    {
-        if let Some(mut ev) = st.world.get_resource_mut::<runtime::MarketEventConfigRes>() {
-            let mult = preset.event_severity_multiplier as f64;
-            for v in &mut ev.events {
-                if let Some(me) = v.get_mut("market_effect") {
-                    if let Some(b) = me.get_mut("base_demand_pct") {
-                        if let Some(x) = b.as_f64() {
-                            *b = serde_yaml::Value::from(x * mult);
-                        }
-                    }
-                    if let Some(e) = me.get_mut("elasticity_delta") {
-                        if let Some(x) = e.as_f64() {
-                            *e = serde_yaml::Value::from(x * mult);
-                        }
-                    }
-                }
-            }
-        }
-    }
-    // Adjust player cash multiplicatively
-    if let Some(c) = st.dom.companies.get_mut(0) {
-        let cash = c.cash_usd;
-        let m = rust_decimal::Decimal::from_f32_retain(preset.cash_multiplier as f32)
-            .unwrap_or(rust_decimal::Decimal::ONE);
-        c.cash_usd = cash * m;
-    }
+    // Replaces the previous level's effects instead of stacking on them
+    let effects = preset.effects();
+    let command = runtime::PlayerCommand::Difficulty { level, effects };
+    world_op_with(session_id, command, |_| ())?;
     tracing::info!(target: "ipc", "sim_campaign_set_difficulty: ok");
     Ok(())
 }
@@ -1364,75 +3875,27 @@ struct SaveInfo {
     progress: u32,
 }
 
-async fn save_now(db_url: String, name: String, dom: core::World, world: runtime::World) -> Result<i64, String> {
+async fn save_now(
+    db_url: String,
+    name: String,
+    world: runtime::World,
+    audit: Vec<persistence::AuditRow>,
+) -> Result<i64, String> {
+    let data = runtime::save_data(&world, &name, audit)?;
+    write_save_data(db_url, data).await
+}
+
+async fn write_save_data(db_url: String, data: persistence::SaveData) -> Result<i64, String> {
     use persistence as p;
     let pool = p::init_db(&db_url)
         .await
         .map_err(|e| e.to_string())?;
-    // Autosave flow: mark in_progress first
-    let is_auto = name.starts_with("auto-");
-    let sid = if is_auto {
-        p::create_save_with_status(&pool, &name, None, "in_progress")
-            .await
-            .map_err(|e| e.to_string())?
-    } else {
-        p::create_save(&pool, &name, None)
-            .await
-            .map_err(|e| e.to_string())?
-    };
-    // Snapshot domain world
-    let month_index = world.resource::<runtime::Stats>().months_run as i64;
-    let bytes = p::serialize_world_bincode(&dom).map_err(|e| e.to_string())?;
-    let _snap_id = p::insert_snapshot(&pool, sid, month_index, "bincode", &bytes)
+    // One transaction: a crash or error midway leaves no partial save behind
+    let sid = p::write_save(&pool, &data)
         .await
         .map_err(|e| e.to_string())?;
-    // Persist contracts
-    let book = world.resource::<runtime::CapacityBook>();
-    for c in &book.contracts {
-        let row = p::ContractRow {
-            foundry_id: c.foundry_id.clone(),
-            wafers_per_month: c.wafers_per_month as i64,
-            price_per_wafer_cents: c.price_per_wafer_cents,
-            take_or_pay_frac: c.take_or_pay_frac,
-            billing_cents_per_wafer: c.billing_cents_per_wafer,
-            billing_model: c.billing_model.into(),
-            lead_time_months: c.lead_time_months as i64,
-            start: c.start.to_string(),
-            end: c.end.to_string(),
-        };
-        let _ = p::insert_contract(&pool, sid, &row)
-            .await
-            .map_err(|e| e.to_string())?;
-    }
-    // Persist tapeout queue and released
-    let pipe = world.resource::<runtime::Pipeline>();
-    for t in &pipe.0.queue {
-        let row = p::TapeoutRow {
-            product_json: serde_json::to_string(&t.product).map_err(|e| e.to_string())?,
-            tech_node: t.tech_node.0.clone(),
-            start: t.start.to_string(),
-            ready: t.ready.to_string(),
-            expedite: if t.expedite { 1 } else { 0 },
-            expedite_cost_cents: t.expedite_cost_cents,
-        };
-        let _ = p::insert_tapeout_request(&pool, sid, &row)
-            .await
-            .map_err(|e| e.to_string())?;
-    }
-    for r in &pipe.0.released {
-        let row = p::ReleasedRow {
-            product_json: serde_json::to_string(r).map_err(|e| e.to_string())?,
-            released_at: dom.macro_state.date.to_string(),
-        };
-        let _ = p::insert_released_product(&pool, sid, &row)
-            .await
-            .map_err(|e| e.to_string())?;
-    }
-    // Mark done for autosave and rotate to last N=6
-    if is_auto {
-        let _ = p::update_save_status(&pool, sid, "done")
-            .await
-            .map_err(|e| e.to_string())?;
+    // Rotate autosaves to the last N=6
+    if data.name.starts_with("auto-") {
         const N: usize = 6;
         if let Ok(list) = p::list_saves_by_prefix(&pool, "auto-").await {
             if list.len() > N {
@@ -1447,10 +3910,15 @@ async fn save_now(db_url: String, name: String, dom: core::World, world: runtime
 }
 
 #[tauri::command]
-async fn sim_save(app: tauri::AppHandle, name: Option<String>) -> Result<i64, String> {
+async fn sim_save(
+    app: tauri::AppHandle,
+    name: Option<String>,
+    session_id: Option<String>,
+) -> Result<i64, String> {
     tracing::info!(target: "ipc", name = ?name, "sim_save");
-    let (dom, world, nm) = {
-        let g = SIM_STATE.read().unwrap();
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let (world, nm) = {
+        let g = sess.state.read().unwrap();
         let st = g
             .as_ref()
             .ok_or_else(|| "sim not initialized".to_string())?;
@@ -1463,10 +3931,11 @@ async fn sim_save(app: tauri::AppHandle, name: Option<String>) -> Result<i64, St
         let nm = name.clone().unwrap_or_else(|| {
             format!("manual-{}{:02}{:02}", date.year(), date.month(), date.day())
         });
-        (st.dom.clone(), runtime::clone_world_state(&st.world), nm)
+        (runtime::clone_world_state(&st.world), nm)
     };
+    let audit = sess.audit.lock().unwrap().to_vec();
     let url = saves_db_url(&app)?;
-    let id = save_now(url, nm.clone(), dom, world).await?;
+    let id = save_now(url, nm.clone(), world, audit).await?;
     tracing::info!(target: "ipc", id, "sim_save: ok");
     Ok(id)
 }
@@ -1508,95 +3977,274 @@ async fn sim_list_saves(app: tauri::AppHandle) -> Result<Vec<SaveInfo>, String>
 }
 
 #[tauri::command]
-async fn sim_load(app: tauri::AppHandle, save_id: i64) -> Result<SimStateDto, String> {
+async fn sim_load(
+    app: tauri::AppHandle,
+    save_id: i64,
+    session_id: Option<String>,
+) -> Result<SimStateDto, String> {
     tracing::info!(target: "ipc", save_id, "sim_load");
+    let started = std::time::Instant::now();
+    let sess = SESSIONS.open(session_id.as_deref());
+    let res = load_save(&app, save_id, &sess).await;
+    audit_ipc(
+        &sess,
+        session_id.as_deref(),
+        "sim_load",
+        serde_json::json!({ "save_id": save_id }),
+        started,
+        &res,
+    );
+    res
+}
+
+/// Replace the session's game (and audit trail) with the contents of a save.
+async fn load_save(
+    app: &tauri::AppHandle,
+    save_id: i64,
+    sess: &Session,
+) -> Result<SimStateDto, String> {
     use persistence as p;
-    let url = saves_db_url(&app)?;
+    let url = saves_db_url(app)?;
     let pool = p::init_db(&url)
         .await
         .map_err(|e| e.to_string())?;
-    let (_snap_id, _m, data, _fmt) = p::latest_snapshot(&pool, save_id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| "no snapshot".to_string())?;
-    let dom = p::deserialize_world_bincode(&data).map_err(|e| e.to_string())?;
-    let mut world = runtime::init_world(
-        dom.clone(),
-        core::SimConfig {
-            tick_days: 30,
-            rng_seed: 42,
-        },
-    );
-    // Rehydrate contracts
-    let contracts = p::list_contracts(&pool, save_id)
+    // A save damaged by a crash mid-write is repaired (best effort) rather than refused
+    let check = p::verify_save(&pool, save_id)
         .await
         .map_err(|e| e.to_string())?;
-    if !contracts.is_empty() {
-        let mut book = world.resource_mut::<runtime::CapacityBook>();
-        for c in contracts {
-            let start = chrono::NaiveDate::parse_from_str(&c.start, "%Y-%m-%d")
-                .map_err(|e| e.to_string())?;
-            let end =
-                chrono::NaiveDate::parse_from_str(&c.end, "%Y-%m-%d").map_err(|e| e.to_string())?;
-            book.contracts.push(runtime::FoundryContract {
-                foundry_id: c.foundry_id,
-                wafers_per_month: c.wafers_per_month as u32,
-                price_per_wafer_cents: c.price_per_wafer_cents,
-                take_or_pay_frac: c.take_or_pay_frac,
-                billing_cents_per_wafer: c.billing_cents_per_wafer,
-                billing_model: Box::leak(c.billing_model.into_boxed_str()),
-                lead_time_months: c.lead_time_months as u8,
-                start,
-                end,
-            });
-        }
+    if !check.is_ok() {
+        let repair = p::repair_save(&pool, save_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        tracing::warn!(target: "ipc", save_id, issues = ?check.issues, actions = ?repair.actions, "sim_load: save repaired");
     }
-    // Rehydrate released and queue
-    let released = p::list_released_products(&pool, save_id)
-        .await
-        .map_err(|e| e.to_string())?;
-    runtime::rehydrate_released_products(&mut world, &released);
-    let queue = p::list_tapeout_requests(&pool, save_id)
+    let data = p::read_save(&pool, save_id)
         .await
         .map_err(|e| e.to_string())?;
-    if !queue.is_empty() {
-        let mut pipe = world.resource_mut::<runtime::Pipeline>();
-        for t in queue {
-            let product: core::ProductSpec =
-                serde_json::from_str(&t.product_json).map_err(|e| e.to_string())?;
-            let start = chrono::NaiveDate::parse_from_str(&t.start, "%Y-%m-%d")
-                .map_err(|e| e.to_string())?;
-            let ready = chrono::NaiveDate::parse_from_str(&t.ready, "%Y-%m-%d")
-                .map_err(|e| e.to_string())?;
-            pipe.0.queue.push(core::TapeoutRequest {
-                product,
-                tech_node: core::TechNodeId(t.tech_node),
-                start,
-                ready,
-                expedite: t.expedite != 0,
-                expedite_cost_cents: t.expedite_cost_cents,
-            });
-        }
-    }
+    let mut world = runtime::world_from_save(&data)?;
+    world.insert_resource(runtime::DecisionMode::Pause);
+    let dom = world.resource::<runtime::DomainWorld>().0.clone();
+    // Tutorial step texts come from the scenario file, not the save
+    let tutorial = world
+        .resource::<runtime::TutorialState>()
+        .enabled
+        .then(|| tutorial_cfg_from_yaml(embedded::get_yaml("tutorial_24m")))
+        .flatten();
     // Replace state
     {
-        let mut guard = SIM_STATE.write().unwrap();
+        let mut guard = sess.state.write().unwrap();
         *guard = Some(SimState {
             world,
             dom,
             busy: false,
             scenario: None,
-            tutorial: None,
+            tutorial,
             autosave: true,
         });
     }
-    let g = SIM_STATE.read().unwrap();
+    sess.spectator.write().unwrap().take();
+    close_journal(&sess);
+    sess.audit.lock().unwrap().restore(data.audit);
+    let g = sess.state.read().unwrap();
     let st = g.as_ref().unwrap();
     let dto = build_sim_state_dto(st);
     tracing::info!(target: "ipc", date = %dto.date, "sim_load: ok");
     Ok(dto)
 }
 
+/// Crash journal file of a session, next to the saves database.
+fn journal_path(
+    app: &tauri::AppHandle,
+    session_id: Option<&str>,
+) -> Result<std::path::PathBuf, String> {
+    use tauri::path::BaseDirectory;
+    let id: String = session_id
+        .unwrap_or(DEFAULT_SESSION)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let p = app
+        .path()
+        .resolve(
+            format!("chip-tycoon/saves/journal-{id}.wal"),
+            BaseDirectory::AppData,
+        )
+        .map_err(|e| e.to_string())?;
+    if let Some(parent) = p.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    Ok(p)
+}
+
+/// Append to the session's crash journal, if one is open. A failed write closes the journal
+/// rather than failing the action; recovery then ends at the last line written.
+fn journal(sess: &Session, write: impl FnOnce(&mut runtime::Journal) -> Result<(), String>) {
+    let mut open = sess.journal.lock().unwrap();
+    if let Some(j) = open.as_mut() {
+        if let Err(e) = write(j) {
+            tracing::error!(target: "ipc", error = %e, "journal write failed; journal closed");
+            *open = None;
+        }
+    }
+}
+
+/// Move the session's journal on top of autosave `save`, now stored under `save_id`.
+/// `mark` identifies the journal and its length when the save was taken; a journal replaced
+/// since, by a reset or a load, is left alone.
+fn rebase_journal(
+    sess: &Session,
+    path: Option<std::path::PathBuf>,
+    save: &persistence::SaveData,
+    save_id: i64,
+    mark: Option<(String, usize)>,
+) {
+    let mut open = sess.journal.lock().unwrap();
+    let res = match (mark, path) {
+        (Some((base, lines)), _) => match open.as_mut() {
+            Some(j) if j.base_hash() == base => j.rebase(save, save_id, lines),
+            _ => Ok(()),
+        },
+        (None, Some(path)) if open.is_none() => {
+            runtime::Journal::start(path, save, save_id).map(|j| *open = Some(j))
+        }
+        (None, _) => Ok(()),
+    };
+    if let Err(e) = res {
+        tracing::error!(target: "ipc", error = %e, "autosave: journal not moved; journal closed");
+        *open = None;
+    }
+}
+
+/// Stop journaling a game that is being replaced and remove its journal.
+fn close_journal(sess: &Session) {
+    if let Some(j) = sess.journal.lock().unwrap().take() {
+        let _ = std::fs::remove_file(j.path());
+    }
+}
+
+/// What recovering the session's crash journal would replay; `None` when there is nothing
+/// past its autosave, or the journal belongs to the game in play.
+#[tauri::command]
+fn sim_journal_status(
+    app: tauri::AppHandle,
+    session_id: Option<String>,
+) -> Result<Option<runtime::JournalRecovery>, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    if sess.journal.lock().unwrap().is_some() {
+        return Ok(None);
+    }
+    let path = journal_path(&app, session_id.as_deref())?;
+    Ok(runtime::journal_summary(&runtime::read_journal(&path)?))
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct JournalRecovered {
+    state: SimStateDto,
+    recovery: runtime::JournalRecovery,
+}
+
+/// Load the journal's autosave and replay the journal on top of it, then keep journaling.
+/// A replay whose state differs from the lost session's is refused, leaving the autosave
+/// loaded.
+#[tauri::command]
+async fn sim_journal_recover(
+    app: tauri::AppHandle,
+    session_id: Option<String>,
+) -> Result<JournalRecovered, String> {
+    tracing::info!(target: "ipc", session = ?session_id, "sim_journal_recover");
+    let started = std::time::Instant::now();
+    let sess = SESSIONS.open(session_id.as_deref());
+    let res = recover_journal(&app, session_id.as_deref(), &sess).await;
+    audit_ipc(
+        &sess,
+        session_id.as_deref(),
+        "sim_journal_recover",
+        serde_json::json!({}),
+        started,
+        &res,
+    );
+    res
+}
+
+async fn recover_journal(
+    app: &tauri::AppHandle,
+    session_id: Option<&str>,
+    sess: &Arc<Session>,
+) -> Result<JournalRecovered, String> {
+    use persistence as p;
+    let path = journal_path(app, session_id)?;
+    let entries = runtime::read_journal(&path)?;
+    let summary =
+        runtime::journal_summary(&entries).ok_or_else(|| "nothing to recover".to_string())?;
+    let pool = p::init_db(&saves_db_url(app)?)
+        .await
+        .map_err(|e| e.to_string())?;
+    let found = p::list_saves_by_prefix(&pool, &summary.save)
+        .await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .any(|s| s.id == summary.save_id);
+    if !found {
+        return Err(format!("autosave {} not found", summary.save));
+    }
+    load_save(app, summary.save_id, sess).await?;
+    // The replay runs on its own copy of the save, so a failed one leaves the session on it
+    let data = p::read_save(&pool, summary.save_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    // Months run on the main thread, as ticks do
+    let (tx, rx) = std::sync::mpsc::channel();
+    let s = sess.clone();
+    let _ = app.run_on_main_thread(move || {
+        let _q = s.tick_queue.lock().unwrap();
+        let mut guard = s.state.write().unwrap();
+        let res = (|| {
+            let st = guard
+                .as_mut()
+                .ok_or_else(|| "sim not initialized".to_string())?;
+            let mut world = runtime::world_from_save(&data)?;
+            world.insert_resource(runtime::DecisionMode::Pause);
+            let recovery = runtime::recover_from_journal(&mut world, &entries)?;
+            st.world = world;
+            st.dom = st.world.resource::<runtime::DomainWorld>().0.clone();
+            refresh_spectator(&s, st);
+            *s.journal.lock().unwrap() = Some(runtime::Journal::resume(path, &entries)?);
+            Ok(JournalRecovered {
+                state: build_sim_state_dto(st),
+                recovery,
+            })
+        })();
+        let _ = tx.send(res);
+    });
+    let out = rx.recv().map_err(|e| e.to_string()).and_then(|r| r)?;
+    tracing::info!(target: "ipc", months = out.recovery.months, "sim_journal_recover: ok");
+    Ok(out)
+}
+
+/// Drop the session's crash journal without recovering it.
+#[tauri::command]
+fn sim_journal_discard(app: tauri::AppHandle, session_id: Option<String>) -> Result<(), String> {
+    audited(
+        session_id.clone(),
+        "sim_journal_discard",
+        serde_json::json!({}),
+        || {
+            let sess = SESSIONS.get(session_id.as_deref())?;
+            close_journal(&sess);
+            match std::fs::remove_file(journal_path(&app, session_id.as_deref())?) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+                _ => Ok(()),
+            }
+        },
+    )
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct AutosavePolicy {
     enabled: bool,
@@ -1604,8 +4252,18 @@ struct AutosavePolicy {
 }
 
 #[tauri::command]
-fn sim_set_autosave(on: bool) -> Result<AutosavePolicy, String> {
-    let mut g = SIM_STATE.write().unwrap();
+fn sim_set_autosave(on: bool, session_id: Option<String>) -> Result<AutosavePolicy, String> {
+    audited(
+        session_id.clone(),
+        "sim_set_autosave",
+        serde_json::json!({ "on": on }),
+        || set_autosave(on, session_id),
+    )
+}
+
+fn set_autosave(on: bool, session_id: Option<String>) -> Result<AutosavePolicy, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let mut g = sess.state.write().unwrap();
     let st = g
         .as_mut()
         .ok_or_else(|| "sim not initialized".to_string())?;
@@ -1616,11 +4274,64 @@ fn sim_set_autosave(on: bool) -> Result<AutosavePolicy, String> {
     })
 }
 
+/// Campaign report for the session, compared against the AI autopilot on a fresh start of
+/// the same campaign and difficulty over the months played so far. The autopilot runs with
+/// the session unlocked.
+fn campaign_report(sess: &Session) -> Result<runtime::CampaignReport, String> {
+    let (spec, campaign) = {
+        let g = sess.state.read().unwrap();
+        let st = g
+            .as_ref()
+            .ok_or_else(|| "sim not initialized".to_string())?;
+        let campaign = st.scenario.as_ref().map(|sc| sc.id.clone());
+        (runtime::BaselineSpec::of(&st.world), campaign)
+    };
+    let baseline = campaign.filter(|_| spec.months > 0).and_then(|id| {
+        campaign_world(&id)
+            .map(|(world, _, _)| spec.run(world))
+            .map_err(|e| tracing::warn!(target: "ipc", error = %e, "report baseline"))
+            .ok()
+    });
+    let g = sess.state.read().unwrap();
+    let st = g
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    Ok(runtime::campaign_report(&st.world, baseline.as_ref()))
+}
+
 #[tauri::command]
-fn sim_export_campaign(path: String, format: Option<String>) -> Result<(), String> {
+async fn sim_export_campaign(
+    path: String,
+    format: Option<String>,
+    session_id: Option<String>,
+) -> Result<(), String> {
     tracing::info!(target: "ipc", path = %path, format = ?format, "sim_export_campaign");
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    // Report formats describe the campaign so far; no dry run needed
+    let report_fmt = match format.as_deref() {
+        Some("html") => Some(true),
+        Some("md" | "markdown") => Some(false),
+        Some(_) => None,
+        None if path.ends_with(".html") => Some(true),
+        None if path.ends_with(".md") => Some(false),
+        None => None,
+    };
+    if let Some(html) = report_fmt {
+        let report = tauri::async_runtime::spawn_blocking(move || campaign_report(&sess))
+            .await
+            .map_err(|e| e.to_string())??;
+        let text = if html {
+            report.to_html()
+        } else {
+            report.to_markdown()
+        };
+        if let Some(parent) = std::path::Path::new(&path).parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        return std::fs::write(&path, text).map_err(|e| e.to_string());
+    }
     // Always perform a dry-run export: clone the current world and simulate on the clone.
-    let g = SIM_STATE.read().unwrap();
+    let g = sess.state.read().unwrap();
     let st = g
         .as_ref()
         .ok_or_else(|| "sim not initialized".to_string())?;
@@ -1656,10 +4367,18 @@ fn sim_export_campaign(path: String, format: Option<String>) -> Result<(), Strin
         share: f32,
         output_units: u64,
         inventory_units: u64,
+        price_delta_frac: f32,
+        capacity_requested_wafers: u64,
+        tapeouts_scheduled: u32,
+        rd_budget_cents: i64,
+        promo_units: u64,
+        promo_asp_cents: i64,
+        cannibalized_units: u64,
     }
     let mut rows: Vec<Row> = Vec::with_capacity(months as usize);
     for _ in 0..months {
-        let (_s, _t) = runtime::run_months_in_place(&mut dry, 1);
+        let (_s, t) = runtime::run_months_in_place(&mut dry, 1);
+        let month = t.into_iter().next().unwrap_or_default();
         let dom = dry.resource::<runtime::DomainWorld>();
         let stats = dry.resource::<runtime::Stats>();
         let pricing = dry.resource::<runtime::Pricing>();
@@ -1676,14 +4395,32 @@ fn sim_export_campaign(path: String, format: Option<String>) -> Result<(), Strin
             share: stats.market_share,
             output_units: stats.output_units,
             inventory_units: stats.inventory_units,
+            price_delta_frac: month.price_delta_frac,
+            capacity_requested_wafers: month.capacity_requested_wafers,
+            tapeouts_scheduled: month.tapeouts_scheduled,
+            rd_budget_cents: month.rd_budget_cents,
+            promo_units: month.promo_units,
+            promo_asp_cents: persistence::decimal_to_cents_i64(month.promo_asp_usd).unwrap_or(0),
+            cannibalized_units: month.cannibalized_units,
         });
     }
+    // Event log sidecar in the telemetry's format, joinable on month_index
+    let write_events = |ext: &str| {
+        let events = dry
+            .get_resource::<runtime::EventLog>()
+            .map(|l| l.rows())
+            .unwrap_or_default();
+        let events_path =
+            persistence::event_log_path(std::path::Path::new(&path).with_extension(ext));
+        persistence::write_event_log(events_path, &events).map_err(|e| e.to_string())
+    };
     if path.ends_with(".json") || format.as_deref() == Some("json") {
         if let Some(parent) = std::path::Path::new(&path).parent() {
             let _ = std::fs::create_dir_all(parent);
         }
         let s = serde_json::to_string_pretty(&rows).map_err(|e| e.to_string())?;
         std::fs::write(&path, s).map_err(|e| e.to_string())?;
+        write_events("json")?;
         return Ok(());
     } else if path.ends_with(".parquet") || format.as_deref() == Some("parquet") {
         let mut trows: Vec<persistence::TelemetryRow> = Vec::with_capacity(rows.len());
@@ -1696,17 +4433,33 @@ fn sim_export_campaign(path: String, format: Option<String>) -> Result<(), Strin
                 unit_cost_cents: r.unit_cost_cents,
                 margin_cents: r.profit_cents,
                 revenue_cents: r.revenue_cents,
+                price_delta_frac: r.price_delta_frac as f64,
+                capacity_requested_wafers: r.capacity_requested_wafers,
+                tapeouts_scheduled: r.tapeouts_scheduled,
+                rd_budget_cents: r.rd_budget_cents,
+                promo_units: r.promo_units,
+                promo_asp_cents: r.promo_asp_cents,
+                cannibalized_units: r.cannibalized_units,
             });
         }
         persistence::write_telemetry_parquet(&path, &trows).map_err(|e| e.to_string())?;
+        write_events("parquet")?;
         return Ok(());
+    } else if format.as_deref() == Some("csv") || path.ends_with(".csv") {
+        // Event log on its own
+        let events = dry
+            .get_resource::<runtime::EventLog>()
+            .map(|l| l.rows())
+            .unwrap_or_default();
+        return persistence::write_event_log_csv(&path, &events).map_err(|e| e.to_string());
     }
     Err("unknown format".into())
 }
 
 #[tauri::command]
-fn sim_tutorial_state() -> Result<DtoTutorial, String> {
-    let g = SIM_STATE.read().unwrap();
+fn sim_tutorial_state(session_id: Option<String>) -> Result<DtoTutorial, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let g = sess.state.read().unwrap();
     let st = g
         .as_ref()
         .ok_or_else(|| "sim not initialized".to_string())?;
@@ -1739,18 +4492,147 @@ fn sim_tutorial_state() -> Result<DtoTutorial, String> {
     })
 }
 
+#[derive(Serialize, Debug, Clone)]
+struct DtoHints {
+    config: runtime::HintConfig,
+    hints: Vec<runtime::Hint>,
+}
+
+/// Mistake hints shown so far, oldest first; pass `config` to tune or disable the engine.
+#[tauri::command]
+fn sim_hints(
+    config: Option<runtime::HintConfig>,
+    session_id: Option<String>,
+) -> Result<DtoHints, String> {
+    let read = |world: &runtime::World| DtoHints {
+        config: world
+            .get_resource::<runtime::HintConfig>()
+            .cloned()
+            .unwrap_or_default(),
+        hints: world
+            .get_resource::<runtime::HintLog>()
+            .map(|l| l.hints.clone())
+            .unwrap_or_default(),
+    };
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let Some(config) = config else {
+        let guard = sess.state.read().unwrap();
+        let st = guard
+            .as_ref()
+            .ok_or_else(|| "sim not initialized".to_string())?;
+        return Ok(read(&st.world));
+    };
+    let args = serde_json::json!({ "config": config });
+    audited(session_id.clone(), "sim_hints", args, || {
+        tracing::info!(target: "ipc", ?config, "sim_hints");
+        let mut guard = sess.state.write().unwrap();
+        let st = guard
+            .as_mut()
+            .ok_or_else(|| "sim not initialized".to_string())?;
+        if st.busy {
+            return Err("busy".to_string());
+        }
+        st.world.insert_resource(config);
+        Ok(read(&st.world))
+    })
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct DtoWarnings {
+    warnings: Vec<runtime::ActionWarning>,
+    raised: u64,
+}
+
+/// Warnings player actions raised, oldest first.
+#[tauri::command]
+fn sim_warnings(session_id: Option<String>) -> Result<DtoWarnings, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    let log = st
+        .world
+        .get_resource::<runtime::ActionWarnings>()
+        .cloned()
+        .unwrap_or_default();
+    Ok(DtoWarnings {
+        warnings: log.warnings,
+        raised: log.raised,
+    })
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct DtoHistory {
+    recording: bool,
+    /// Earliest month `sim_state_at` can show, and the current one.
+    first_month: Option<u32>,
+    current_month: u32,
+}
+
+/// History kept for scrubbing back in time; pass `record` to start or stop recording.
+#[tauri::command]
+fn sim_history(record: Option<bool>, session_id: Option<String>) -> Result<DtoHistory, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let mut guard = sess.state.write().unwrap();
+    let st = guard
+        .as_mut()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    match record {
+        Some(true) => runtime::enable_time_travel(&mut st.world, runtime::HistoryPolicy::default()),
+        Some(false) => {
+            runtime::disable_time_travel(&mut st.world);
+        }
+        None => {}
+    }
+    let tape = st.world.get_resource::<runtime::StateTape>();
+    Ok(DtoHistory {
+        recording: tape.is_some(),
+        first_month: tape.and_then(|t| t.first_month()),
+        current_month: st.world.resource::<runtime::Stats>().months_run,
+    })
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct DtoStateAt {
+    month: u32,
+    replayed_months: u32,
+    state: SimStateDto,
+}
+
+/// The game as it stood at the start of `month`, read-only; the present is untouched.
+#[tauri::command]
+fn sim_state_at(month: u32, session_id: Option<String>) -> Result<DtoStateAt, String> {
+    let sess = SESSIONS.get(session_id.as_deref())?;
+    let guard = sess.state.read().unwrap();
+    let st = guard
+        .as_ref()
+        .ok_or_else(|| "sim not initialized".to_string())?;
+    let view = runtime::state_at(&st.world, month)?;
+    let replayed_months = view.replayed_months();
+    let world = view.into_world();
+    let past = SimState {
+        dom: world.resource::<runtime::DomainWorld>().0.clone(),
+        world,
+        busy: false,
+        scenario: st.scenario.clone(),
+        tutorial: st.tutorial.clone(),
+        autosave: false,
+    };
+    Ok(DtoStateAt {
+        month,
+        replayed_months,
+        state: build_sim_state_dto(&past),
+    })
+}
+
 // ------- Helpers: events from YAML, default init, saves path
 
 fn market_events_from_yaml_str(text: &str) -> runtime::MarketEventConfigRes {
-    #[derive(serde::Deserialize)]
-    struct Root {
-        events: Vec<serde_yaml::Value>,
-    }
-    let root: Root = serde_yaml::from_str(text).unwrap_or(Root { events: vec![] });
-    runtime::MarketEventConfigRes { events: root.events }
+    runtime::cached_config(text, runtime::MarketEventConfigRes::from_yaml_str).unwrap_or_default()
 }
 
-fn init_default_from_embedded() -> Result<(), String> {
+fn init_default_from_embedded(sess: &Session) -> Result<(), String> {
     let date0 = chrono::NaiveDate::from_ymd_opt(1990, 1, 1).unwrap();
     let tech_nodes = load_tech_nodes_from_yaml(embedded::get_yaml("tech_era_1990s"));
     let markets = runtime::MarketConfigRes::from_yaml_str(embedded::get_yaml("markets_1990s"))
@@ -1789,8 +4671,10 @@ fn init_default_from_embedded() -> Result<(), String> {
         },
     );
     ecs.insert_resource(markets);
+    runtime::insert_catalogs(&mut ecs)?;
     ecs.insert_resource(market_events_from_yaml_str(embedded::get_yaml("events_1990s")));
-    *SIM_STATE.write().unwrap() = Some(SimState {
+    ecs.insert_resource(runtime::DecisionMode::Pause);
+    *sess.state.write().unwrap() = Some(SimState {
         world: ecs,
         dom,
         busy: false,
@@ -1798,6 +4682,8 @@ fn init_default_from_embedded() -> Result<(), String> {
         tutorial: None,
         autosave: true,
     });
+    sess.spectator.write().unwrap().take();
+    close_journal(&sess);
     Ok(())
 }
 
@@ -1824,6 +4710,8 @@ mod tests {
 
     #[test]
     fn sequential_ticks_increase_month_index() {
+        let sid = Some("sequential_ticks_increase_month_index".to_string());
+        let sess = SESSIONS.open(sid.as_deref());
         // Initialize state
         let dom = core::World {
             macro_state: core::MacroState {
@@ -1852,7 +4740,7 @@ mod tests {
                 rng_seed: 42,
             },
         );
-        *SIM_STATE.write().unwrap() = Some(SimState {
+        *sess.state.write().unwrap() = Some(SimState {
             world: ecs,
             dom,
             busy: false,
@@ -1862,13 +4750,15 @@ mod tests {
         });
         // Run two ticks sequentially
         let rt = tauri::async_runtime::TokioRuntime::new().expect("rt");
-        let s1 = rt.block_on(sim_tick(1)).expect("tick1");
-        let s2 = rt.block_on(sim_tick(1)).expect("tick2");
+        let s1 = rt.block_on(sim_tick(1, sid.clone())).expect("tick1");
+        let s2 = rt.block_on(sim_tick(1, sid.clone())).expect("tick2");
         assert!(s2.months_run > s1.months_run);
     }
 
     #[test]
     fn second_tick_returns_busy_status() {
+        let sid = Some("second_tick_returns_busy_status".to_string());
+        let sess = SESSIONS.open(sid.as_deref());
         // Initialize state
         let dom = core::World {
             macro_state: core::MacroState {
@@ -1897,7 +4787,7 @@ mod tests {
                 rng_seed: 42,
             },
         );
-        *SIM_STATE.write().unwrap() = Some(SimState {
+        *sess.state.write().unwrap() = Some(SimState {
             world: ecs,
             dom,
             busy: true,
@@ -1907,19 +4797,21 @@ mod tests {
         });
         // Try tick while busy
         let rt = tauri::async_runtime::TokioRuntime::new().expect("rt");
-        let res = rt.block_on(sim_tick(1));
+        let res = rt.block_on(sim_tick(1, sid.clone()));
         assert!(res.is_err());
         assert_eq!(res.unwrap_err(), "busy");
         // Clear and tick OK
         {
-            let mut g = SIM_STATE.write().unwrap();
+            let mut g = sess.state.write().unwrap();
             g.as_mut().unwrap().busy = false;
         }
-        let _ = rt.block_on(sim_tick(1)).expect("tick ok");
+        let _ = rt.block_on(sim_tick(1, sid.clone())).expect("tick ok");
     }
 
     #[test]
     fn overrides_apply_and_affect_state() {
+        let sid = Some("overrides_apply_and_affect_state".to_string());
+        let sess = SESSIONS.open(sid.as_deref());
         // Init state with a tech node for tapeout
         let dom = core::World {
             macro_state: core::MacroState {
@@ -1958,7 +4850,7 @@ mod tests {
                 rng_seed: 42,
             },
         );
-        *SIM_STATE.write().unwrap() = Some(SimState {
+        *sess.state.write().unwrap() = Some(SimState {
             world: ecs,
             dom,
             busy: false,
@@ -1968,45 +4860,57 @@ mod tests {
         });
 
         // Apply price +5%
-        let r = sim_override(OverrideReq {
-            price_delta_frac: Some(0.05),
-            rd_delta_cents: None,
-            capacity_request: None,
-            tapeout: None,
-        })
+        let r = sim_override(
+            OverrideReq {
+                price_delta_frac: Some(0.05),
+                rd_delta_cents: None,
+                capacity_request: None,
+                tapeout: None,
+            },
+            sid.clone(),
+        )
         .expect("override");
         assert!(r.asp_cents.unwrap_or(0) > 0);
 
         // Apply R&D budget increase
-        let _ = sim_override(OverrideReq {
-            price_delta_frac: None,
-            rd_delta_cents: Some(10_000),
-            capacity_request: None,
-            tapeout: None,
-        })
+        let _ = sim_override(
+            OverrideReq {
+                price_delta_frac: None,
+                rd_delta_cents: Some(10_000),
+                capacity_request: None,
+                tapeout: None,
+            },
+            sid.clone(),
+        )
         .expect("rd");
         {
-            let g = SIM_STATE.read().unwrap();
+            let g = sess.state.read().unwrap();
             let world = &g.as_ref().unwrap().world;
             let b = world.resource::<runtime::RnDBudgetCents>().0;
             assert_eq!(b, 10_000);
         }
 
         // Capacity request
-        let _ = sim_override(OverrideReq {
-            price_delta_frac: None,
-            rd_delta_cents: None,
-            capacity_request: Some(CapacityReq {
-                wafers_per_month: 1000,
-                months: 12,
-                billing_cents_per_wafer: Some(5000),
-                take_or_pay_frac: Some(1.0),
-            }),
-            tapeout: None,
-        })
+        let _ = sim_override(
+            OverrideReq {
+                price_delta_frac: None,
+                rd_delta_cents: None,
+                capacity_request: Some(CapacityReq {
+                    wafers_per_month: 1000,
+                    months: 12,
+                    billing_cents_per_wafer: Some(5000),
+                    take_or_pay_frac: Some(1.0),
+                    ramp_wafers: None,
+                    min_order_wafers: None,
+                    rollover: false,
+                }),
+                tapeout: None,
+            },
+            sid.clone(),
+        )
         .expect("cap");
         {
-            let g = SIM_STATE.read().unwrap();
+            let g = sess.state.read().unwrap();
             let world = &g.as_ref().unwrap().world;
             assert!(!world
                 .resource::<runtime::CapacityBook>()
@@ -2015,22 +4919,26 @@ mod tests {
         }
 
         // Tapeout expedited, then tick to ready and expect release
-        let resp = sim_override(OverrideReq {
-            price_delta_frac: None,
-            rd_delta_cents: None,
-            capacity_request: None,
-            tapeout: Some(TapeoutReq {
-                perf_index: 0.8,
-                die_area_mm2: 100.0,
-                tech_node: "N90".into(),
-                expedite: Some(true),
-            }),
-        })
+        let resp = sim_override(
+            OverrideReq {
+                price_delta_frac: None,
+                rd_delta_cents: None,
+                capacity_request: None,
+                tapeout: Some(TapeoutReq {
+                    perf_index: 0.8,
+                    die_area_mm2: 100.0,
+                    tech_node: "N90".into(),
+                    expedite: Some(true),
+                }),
+            },
+            sid.clone(),
+        )
         .expect("tapeout");
         let ready =
             chrono::NaiveDate::parse_from_str(&resp.tapeout_ready.unwrap(), "%Y-%m-%d").unwrap();
         // Compute months to ready from current date
-        let start = SIM_STATE
+        let start = sess
+            .state
             .read()
             .unwrap()
             .as_ref()
@@ -2045,25 +4953,34 @@ mod tests {
             months += 1;
         }
         let rt = tauri::async_runtime::TokioRuntime::new().expect("rt");
-        let _ = rt.block_on(sim_tick(months)).expect("tick to ready");
-        let g = SIM_STATE.read().unwrap();
+        let _ = rt
+            .block_on(sim_tick(months, sid.clone()))
+            .expect("tick to ready");
+        let g = sess.state.read().unwrap();
         let world = &g.as_ref().unwrap().world;
         assert!(!world.resource::<runtime::Pipeline>().0.released.is_empty());
     }
 
     #[test]
     fn export_campaign_is_dry_run_and_file_valid() {
+        let sid = Some("export_campaign_is_dry_run_and_file_valid".to_string());
         // Reset campaign to a known state
-        let _ = sim_campaign_reset(Some("1990s".into())).expect("reset");
+        let _ = sim_campaign_reset(Some("1990s".into()), sid.clone()).expect("reset");
         // Capture KPI hash before
-        let s1 = sim_state().expect("state before");
+        let s1 = sim_state(sid.clone()).expect("state before");
         let kpi_before = serde_json::to_string(&s1.kpi).expect("ser kpi");
         // Export to JSON in telemetry dir
         let path = "telemetry/test_export_campaign.json".to_string();
         let _ = std::fs::remove_file(&path);
-        sim_export_campaign(path.clone(), Some("json".into())).expect("export");
+        let rt = tauri::async_runtime::TokioRuntime::new().expect("rt");
+        rt.block_on(sim_export_campaign(
+            path.clone(),
+            Some("json".into()),
+            sid.clone(),
+        ))
+        .expect("export");
         // State unchanged
-        let s2 = sim_state().expect("state after");
+        let s2 = sim_state(sid.clone()).expect("state after");
         let kpi_after = serde_json::to_string(&s2.kpi).expect("ser kpi2");
         assert_eq!(kpi_after, kpi_before, "KPI changed after dry-run export");
         assert_eq!(
@@ -2090,8 +5007,33 @@ mod tests {
         assert!(!rows.is_empty(), "no rows exported");
     }
 
+    #[test]
+    fn export_campaign_report_html_and_markdown() {
+        let sid = Some("export_campaign_report_html_and_markdown".to_string());
+        let _ = sim_campaign_reset(Some("1990s".into()), sid.clone()).expect("reset");
+        let rt = tauri::async_runtime::TokioRuntime::new().expect("rt");
+        let _ = rt.block_on(sim_tick(3, sid.clone())).expect("tick");
+        let html = "telemetry/test_campaign_report.html".to_string();
+        rt.block_on(sim_export_campaign(html.clone(), None, sid.clone()))
+            .expect("html report");
+        let text = std::fs::read_to_string(&html).expect("read html");
+        assert!(text.starts_with("<!DOCTYPE html>"));
+        assert!(text.contains("baseline (month"), "no baseline comparison");
+        let md = "telemetry/test_campaign_report.md".to_string();
+        rt.block_on(sim_export_campaign(
+            md.clone(),
+            Some("markdown".into()),
+            sid,
+        ))
+        .expect("md report");
+        let text = std::fs::read_to_string(&md).expect("read md");
+        assert!(text.starts_with("# Campaign report"));
+    }
+
     #[test]
     fn state_dto_roundtrip_and_updates() {
+        let sid = Some("state_dto_roundtrip_and_updates".to_string());
+        let sess = SESSIONS.open(sid.as_deref());
         let dom = core::World {
             macro_state: core::MacroState {
                 date: chrono::NaiveDate::from_ymd_opt(1990, 1, 1).unwrap(),
@@ -2129,7 +5071,7 @@ mod tests {
                 rng_seed: 42,
             },
         );
-        *SIM_STATE.write().unwrap() = Some(SimState {
+        *sess.state.write().unwrap() = Some(SimState {
             world: ecs,
             dom,
             busy: false,
@@ -2138,39 +5080,43 @@ mod tests {
             autosave: true,
         });
         // Initial state
-        let s1 = sim_state().expect("state");
+        let s1 = sim_state(sid.clone()).expect("state");
         let js = serde_json::to_string(&s1).expect("ser");
         let back: SimStateDto = serde_json::from_str(&js).expect("de");
         assert_eq!(back.month_index, 0);
         // Tick and state must update
         let rt = tauri::async_runtime::TokioRuntime::new().expect("rt");
-        let _ = rt.block_on(sim_tick(1)).expect("tick");
-        let s2 = sim_state().expect("state2");
+        let _ = rt.block_on(sim_tick(1, sid.clone())).expect("tick");
+        let s2 = sim_state(sid.clone()).expect("state2");
         assert!(s2.month_index > s1.month_index);
         // Price override updates pricing in dto
-        let _ = sim_override(OverrideReq {
-            price_delta_frac: Some(0.05),
-            rd_delta_cents: None,
-            capacity_request: None,
-            tapeout: None,
-        })
+        let _ = sim_override(
+            OverrideReq {
+                price_delta_frac: Some(0.05),
+                rd_delta_cents: None,
+                capacity_request: None,
+                tapeout: None,
+            },
+            sid.clone(),
+        )
         .unwrap();
-        let s3 = sim_state().unwrap();
+        let s3 = sim_state(sid.clone()).unwrap();
         assert!(s3.pricing.asp_cents >= s2.pricing.asp_cents);
     }
 
     #[test]
     fn autosave_transaction_and_rotation() {
+        let sid = Some("autosave_transaction_and_rotation".to_string());
         // Clean DB to start fresh
         let _ = std::fs::remove_file("./saves/main.db");
         let _ = std::fs::remove_dir_all("./saves");
         // Reset campaign and ensure autosave ON
-        let _ = sim_campaign_reset(Some("1990s".into())).expect("reset");
-        let _ = sim_set_autosave(true).expect("enable autosave");
+        let _ = sim_campaign_reset(Some("1990s".into()), sid.clone()).expect("reset");
+        let _ = sim_set_autosave(true, sid.clone()).expect("enable autosave");
         // Run two quarters and wait for autosaves
         let rt = tauri::async_runtime::TokioRuntime::new().expect("rt");
-        let _ = rt.block_on(sim_tick_quarter()).expect("q1");
-        let _ = rt.block_on(sim_tick_quarter()).expect("q2");
+        let _ = rt.block_on(sim_tick_quarter(sid.clone())).expect("q1");
+        let _ = rt.block_on(sim_tick_quarter(sid.clone())).expect("q2");
         // Poll for autosaves to appear
         let mut tries = 0;
         loop {
@@ -2192,7 +5138,7 @@ mod tests {
         }
         // Now run 6 more quarters and check rotation keeps only last 6
         for _ in 0..6 {
-            let _ = rt.block_on(sim_tick_quarter()).expect("quarter");
+            let _ = rt.block_on(sim_tick_quarter(sid.clone())).expect("quarter");
         }
         // Wait for rotation to settle
         let mut tries = 0;
@@ -2217,11 +5163,13 @@ mod tests {
 
     #[test]
     fn difficulty_presets_apply() {
+        let sid = Some("difficulty_presets_apply".to_string());
+        let sess = SESSIONS.open(sid.as_deref());
         // Reset campaign to ensure markets/events loaded
-        let _ = sim_campaign_reset(Some("1990s".into())).expect("reset");
+        let _ = sim_campaign_reset(Some("1990s".into()), sid.clone()).expect("reset");
         // Capture baseline values
         let (base_min_margin, base_growth, base_event): (f32, f32, f64) = {
-            let g = SIM_STATE.read().unwrap();
+            let g = sess.state.read().unwrap();
             let st = g.as_ref().unwrap();
             let ai = st.world.resource::<runtime::AiConfig>().0.clone();
             let markets = st.world.resource::<runtime::MarketConfigRes>().clone();
@@ -2246,9 +5194,9 @@ mod tests {
             (ai.tactics.min_margin_frac, growth, ev_mag)
         };
         // Apply hard difficulty
-        sim_campaign_set_difficulty("hard".into()).expect("apply hard");
+        sim_campaign_set_difficulty("hard".into(), sid.clone()).expect("apply hard");
         // Check updated values
-        let g = SIM_STATE.read().unwrap();
+        let g = sess.state.read().unwrap();
         let st = g.as_ref().unwrap();
         let ai2 = st.world.resource::<runtime::AiConfig>().0.clone();
         assert!(ai2.tactics.min_margin_frac >= 0.10 - 1e-6);
@@ -2276,6 +5224,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn audit_log_rate_limits_per_command() {
+        let row = |command: &str, at_ms: i64| persistence::AuditRow {
+            session_id: "default".into(),
+            command: command.into(),
+            args_json: "{}".into(),
+            duration_ms: 0,
+            ok: true,
+            error: None,
+            suppressed: 0,
+            at_ms,
+        };
+        let mut log = AuditLog::default();
+        for i in 0..(AUDIT_MAX_PER_SEC + 5) {
+            log.record(row("sim_tick", i as i64));
+        }
+        // Other commands have their own budget
+        assert!(log.record(row("sim_decide", 10)));
+        assert_eq!(log.rows.len(), AUDIT_MAX_PER_SEC as usize + 1);
+        // Next window records again and reports what was dropped
+        assert!(log.record(row("sim_tick", 1_500)));
+        assert_eq!(log.rows.back().unwrap().suppressed, 5);
+        // Past the buffer cap the oldest rows go first
+        let mut log = AuditLog::default();
+        for i in 0..=AUDIT_BUFFER_CAP as i64 {
+            log.record(row("sim_tick", i * 1_000));
+        }
+        assert_eq!(log.rows.len(), AUDIT_BUFFER_CAP);
+        assert_eq!(log.to_vec()[0].at_ms, 1_000);
+    }
+
+    #[test]
+    fn spectator_reads_are_rate_limited_per_second() {
+        let mut window = (0, 0);
+        for i in 0..SPECTATOR_MAX_PER_SEC {
+            assert!(spectator_admit(&mut window, i as i64).is_ok());
+        }
+        assert!(spectator_admit(&mut window, 999).is_err());
+        // A new second starts a fresh budget
+        assert!(spectator_admit(&mut window, 1_000).is_ok());
+        assert_eq!(window, (1, 1));
+    }
+
     #[test]
     fn yaml_schema_validation_works() {
         // Valid markets
@@ -2324,6 +5315,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sessions_are_only_created_by_starting_a_game() {
+        let sid = "sessions_are_only_created_by_starting_a_game".to_string();
+        let copy = format!("{sid}-copy");
+        let err = sim_state(Some(sid.clone())).err().unwrap();
+        assert!(err.contains("unknown session"), "{err}");
+        assert!(!SESSIONS.ids().contains(&sid));
+        init_default_from_embedded(&SESSIONS.open(Some(sid.as_str()))).unwrap();
+        assert!(sim_state(Some(sid.clone())).is_ok());
+        // A clone claims its id once; closing it frees the id again
+        assert!(sim_session_clone(Some(sid.clone()), copy.clone()).is_ok());
+        let err = sim_session_clone(Some(sid.clone()), copy.clone())
+            .err()
+            .unwrap();
+        assert!(err.contains("already exists"), "{err}");
+        assert_eq!(sim_session_close(copy.clone()), Ok(true));
+        assert_eq!(sim_session_close(copy.clone()), Ok(false));
+        assert!(sim_state(Some(copy)).is_err());
+    }
+
     #[test]
     fn yaml_schema_strings_with_underscores_are_coerced() {
         // Campaign with quoted underscore-separated ints should pass
@@ -21,6 +21,8 @@ describe("App", () => {
     await vi.waitFor(() => {
       expect(getInvokeMock()).toHaveBeenCalledWith("sim_state", undefined);
     })
+    // The snapshot's state checksum is shown for desync checks
+    expect((await screen.findByTestId("state-hash")).textContent).toBe("#0000000000000001");
   });
 
   it("simulate quarter advances by three months", async () => {
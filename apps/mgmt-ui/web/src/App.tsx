@@ -1,12 +1,12 @@
 import React, { useEffect, useState } from "react";
 import { useAppStore } from "./store";
-import { simTick, simPlanQuarter, simOverride, getSimLists, getSimState, simCampaignReset, simBalanceInfo, simCampaignSetDifficulty, simTutorialState, TutorialDto, simBuildInfo, BuildInfo, simTickQuarter, simListSaves, simSetAutosave, simSave, simLoad, simExportCampaign, simHelpMarkdown } from "./api";
+import { simTick, simPlanQuarter, simOverride, getSimLists, getSimState, simCampaignReset, simSandboxNew, simBalanceInfo, simCampaignSetDifficulty, simTutorialState, TutorialDto, simHints, HintsDto, simWarnings, WarningsDto, simHistory, HistoryDto, simPricingRules, simPricingRulesSet, PricingRulesDto, PricingRuleDto, simStateAt, StateAtDto, simBuildInfo, BuildInfo, simTickQuarter, simListSaves, simSetAutosave, simSave, simLoad, simVerifySave, SaveIntegrityDto, simCompareSaves, RunComparisonDto, simExportCampaign, simHelpMarkdown, simDecide, DecisionDto, simAcceptLoan, simCreditDraw, simCreditRepay, SimStateDto, simProductionPlan, ProductionPlanDto, simContractAdvice, ContractAdviceDto, ForecastPointDto, simBudget, simBudgetSet, BudgetDto, BudgetCategory, simInsurance, simInsuranceBuy, simInsuranceCancel, InsuranceDto, simExportControls, simExportPolicy, simExportVariant, ExportControlsDto, simRegionalPrice, simOemDeal, simOemTerms, simOemInvest, simRoadmapAnnounce, simRndBudgets, simQcBudget, simDebug, simPatentFile, simMarketResearchBuy, CompetitorHintDto, simBargainContract, simConsoleBid, simSensitivity, SensitivityReportDto, simGoalSeek, GoalSeekDto, GoalSeekResultDto, ProductKind, OverrideReq, simExpediteQuote, ExpediteOptionDto, simSchedule, WorkScheduleDto, ScheduleKind, simConfigSwap, simConfigVersions, ConfigSwapsDto, ConfigKind, simJournalStatus, simJournalRecover, simJournalDiscard, JournalRecoveryDto } from "./api";
 import { t, getLang, setLang } from "./i18n";
 import { QueryClient, QueryClientProvider, useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
 import { LineChart as RLineChart, Line, XAxis, YAxis, Tooltip, Legend, ResponsiveContainer } from "recharts";
 
 export function App({ client }: { client?: QueryClient }) {
-  const { snapshot, setSnapshot, loading, setLoading, stateDto, setStateDto, lists, setLists, isBusy, setBusy, setError } = useAppStore();
+  const { snapshot, setSnapshot, loading, setLoading, stateDto, setStateDto, lists, setLists, isBusy, setBusy, setError, setTurnSummary } = useAppStore();
   const [nav, setNav] = useState<"dashboard" | "tutorial" | "campaign" | "markets" | "rd" | "capacity" | "ai">(
     "dashboard"
   );
@@ -32,6 +32,50 @@ function HelpModal({ text, onClose }: { text: string; onClose: ()=>void }) {
   )
 }
 
+function DecisionModal({ decision, onDecide }: { decision: DecisionDto; onDecide: (choice: string)=>void }) {
+  return (
+    <div style={{ position: 'fixed', inset: 0, background: 'rgba(0,0,0,0.3)' }}>
+      <div data-testid="decision-modal" style={{ position: 'absolute', top: '25%', left: '25%', right: '25%', background: '#fff', padding: 16, borderRadius: 8 }}>
+        <h3>{decision.name}</h3>
+        <p>{decision.prompt}</p>
+        {decision.choices.map((c) => (
+          <div key={c.id} style={{ margin: '6px 0' }}>
+            <button data-testid={`decide-${c.id}`} onClick={() => onDecide(c.id)}>{c.label}</button>
+          </div>
+        ))}
+      </div>
+    </div>
+  )
+}
+
+function JournalRecoveryModal() {
+  const { setStateDto, showToast } = useAppStore();
+  const qc = useQueryClient();
+  const [offer, setOffer] = useState<JournalRecoveryDto | null>(null);
+  useEffect(() => { (async () => { try { setOffer(await simJournalStatus()); } catch {} })(); }, []);
+  if (!offer) return null;
+  const recover = async () => {
+    try {
+      const { state, recovery } = await simJournalRecover();
+      setStateDto(state);
+      await qc.invalidateQueries({ queryKey: ["sim_state"] });
+      showToast(`Recovered ${recovery.months} months`);
+      setOffer(null);
+    } catch (e) { alert(String(e)); }
+  };
+  const discard = async () => { try { await simJournalDiscard(); setOffer(null); } catch (e) { alert(String(e)); } };
+  return (
+    <div style={{ position: 'fixed', inset: 0, background: 'rgba(0,0,0,0.3)' }}>
+      <div data-testid="journal-recovery" style={{ position: 'absolute', top: '25%', left: '25%', right: '25%', background: '#fff', padding: 16, borderRadius: 8 }}>
+        <h3>Recover unsaved progress?</h3>
+        <p>The last session ended {offer.months} months and {offer.commands} actions after autosave {offer.save}. Replay them on top of it?</p>
+        <button data-testid="btn-journal-recover" onClick={recover}>Recover</button>{' '}
+        <button data-testid="btn-journal-discard" onClick={discard}>Discard</button>
+      </div>
+    </div>
+  )
+}
+
 function InnerApp({ nav, setNav }: { nav: any; setNav: (v: any) => void }) {
   const { snapshot, setSnapshot, loading, setLoading, stateDto, setStateDto, lists, setLists, isBusy, setBusy, setError, toast, showToast } = useAppStore();
   const qc = useQueryClient();
@@ -76,7 +120,9 @@ function InnerApp({ nav, setNav }: { nav: any; setNav: (v: any) => void }) {
   const quarterMut = useMutation({
     mutationFn: async () => simTickQuarter(),
     onMutate: () => setBusy(true),
-    onSuccess: async () => {
+    onSuccess: async (tick) => {
+      setSnapshot(tick);
+      setTurnSummary(tick.summary);
       await refetchState();
       showToast("Simulated quarter");
     },
@@ -103,6 +149,16 @@ function InnerApp({ nav, setNav }: { nav: any; setNav: (v: any) => void }) {
     onError: (e: any) => setError(e?.toString?.() ?? String(e)),
     onSettled: () => setBusy(false),
   });
+  const pendingDecision = stateDto?.pending_decisions?.[0];
+  const decideMut = useMutation({
+    mutationFn: async (choice: string) => simDecide(pendingDecision!.event_id, choice),
+    onSuccess: (dto) => {
+      setStateDto(dto);
+      showToast("Decision made");
+    },
+    onError: (e: any) => setError(e?.toString?.() ?? String(e)),
+  });
+  const paused = !!pendingDecision;
   return (
     <div style={{ display: "flex", height: "100vh", fontFamily: "sans-serif" }}>
       <div style={{ width: 220, borderRight: "1px solid #ddd", padding: 12 }}>
@@ -144,9 +200,9 @@ function InnerApp({ nav, setNav }: { nav: any; setNav: (v: any) => void }) {
           ))}
         </div>
         <div style={{ marginTop: 16 }}>
-          <button data-testid="btn-tick" disabled={loading || isBusy} onClick={() => tickMut.mutate()}>{t("btn_tick_month")}</button>
-          <button data-testid="btn-quarter" style={{ marginLeft: 8 }} disabled={loading || isBusy} onClick={() => quarterMut.mutate()}>{t("btn_sim_quarter")}</button>
-          <button data-testid="btn-year" style={{ marginLeft: 8 }} disabled={loading || isBusy} onClick={() => yearMut.mutate()}>{t("btn_sim_year")}</button>
+          <button data-testid="btn-tick" disabled={loading || isBusy || paused} onClick={() => tickMut.mutate()}>{t("btn_tick_month")}</button>
+          <button data-testid="btn-quarter" style={{ marginLeft: 8 }} disabled={loading || isBusy || paused} onClick={() => quarterMut.mutate()}>{t("btn_sim_quarter")}</button>
+          <button data-testid="btn-year" style={{ marginLeft: 8 }} disabled={loading || isBusy || paused} onClick={() => yearMut.mutate()}>{t("btn_sim_year")}</button>
           <button data-testid="btn-open-save" style={{ marginLeft: 8 }} onClick={() => setShowSave(true)}>{t("btn_save_load")}</button>
         </div>
       </div>
@@ -162,6 +218,11 @@ function InnerApp({ nav, setNav }: { nav: any; setNav: (v: any) => void }) {
         )}
         <div style={{ marginBottom: 8, color: "#666" }}>
           {stateDto ? `Date ${stateDto.date} · Month #${stateDto.month_index}` : `Month #${snapshot?.months_run ?? 0}`}
+          {snapshot?.state_hash && (
+            <span data-testid="state-hash" title="State checksum: equal runs share it, a diverging run changes it" style={{ marginLeft: 8, fontFamily: "monospace" }}>
+              #{snapshot.state_hash}
+            </span>
+          )}
         </div>
         {nav === "dashboard" && <Dashboard tut={tut} onGoto={(p)=>setNav(p as any)} />}
         {nav === "tutorial" && <TutorialPage tut={tut} onGoto={(p)=>setNav(p as any)} />}
@@ -173,6 +234,8 @@ function InnerApp({ nav, setNav }: { nav: any; setNav: (v: any) => void }) {
       </div>
       {showSave && <SaveLoadModal onClose={()=>setShowSave(false)} />}
       {showHelp && <HelpModal text={helpText} onClose={()=>setShowHelp(false)} />}
+      {pendingDecision && <DecisionModal decision={pendingDecision} onDecide={(c)=>decideMut.mutate(c)} />}
+      <JournalRecoveryModal />
     </div>
   );
 }
@@ -189,8 +252,35 @@ function Dashboard({ tut, onGoto }: { tut: TutorialDto | null; onGoto: (p: strin
   return (
     <div>
       <h2>Dashboard</h2>
+      <DistressHUD />
+      <TurnSummaryPanel />
+      <BudgetPanel />
+      <CreditPanel />
+      <WarrantyPanel />
+      <InsurancePanel />
+      <GrantsPanel />
+      <ExportControlsPanel />
+      <RegionsPanel />
+      <ClearingPanel />
+      <OemPanel />
+      <ConsolePanel />
+      <RoadmapPanel />
+      <LaunchReviewsPanel />
+      <SchedulePanel />
+      <RnDPanel />
+      <QualityPanel />
+      <ProductsPanel />
+      <PricingRulesPanel />
+      <PatentsPanel />
+      <MarketResearchPanel />
+      <SensitivityPanel />
+      <AdvisorPanel />
       <MissionHUD />
       <TutorialHUD tut={tut} onGoto={onGoto} />
+      <HintsPanel />
+      <WarningsPanel />
+      <TimeTravelPanel />
+      <DebugConsolePanel />
       <div style={{ display: "grid", gridTemplateColumns: "repeat(3, 1fr)", gap: 12 }}>
         <Kpi label="Cash" value={cents(kpi.cash_cents)} />
         <Kpi label="Revenue" value={cents(kpi.revenue_cents)} />
@@ -203,11 +293,770 @@ function Dashboard({ tut, onGoto }: { tut: TutorialDto | null; onGoto: (p: strin
         <Kpi label="R&D" value={`${(kpi.rd_pct * 100).toFixed(1)}%`} />
         <Kpi label="Output" value={`${kpi.output_units}`} />
         <Kpi label="Inventory" value={`${kpi.inventory_units}`} />
+        {kpi.utilization != null && <Kpi label="Utilization" value={`${(kpi.utilization * 100).toFixed(0)}%`} />}
       </div>
         <div style={{ marginTop: 16 }}>
           <h3>Revenue vs Profit (last 24 months)</h3>
           <RechartsLine data={data.slice(-24)} />
         </div>
+        {stateDto.forecast && (
+          <div style={{ marginTop: 16 }}>
+            <h3>Cash forecast (next 12 months)</h3>
+            <ForecastChart
+              history={history.slice(-12).map((h) => ({ m: h.months_run, value: h.cash_cents }))}
+              points={stateDto.forecast.cash_cents}
+            />
+          </div>
+        )}
+    </div>
+  );
+}
+
+function DistressHUD() {
+  const { stateDto, setStateDto, showToast } = useAppStore();
+  const d = stateDto?.distress;
+  if (!d?.active && !d?.failed && d?.warning) {
+    return (
+      <div data-testid="solvency-warning" style={{ padding: 8, border: "1px solid #fcd34d", margin: "8px 0", borderRadius: 6, background: "#fffbeb" }}>
+        <strong>Cash warning</strong>
+        <span> — at the current trend cash may fall below the distress threshold in {d.warning.months_ahead} months (projected {cents(d.warning.projected_cash_cents)}, low case {cents(d.warning.lower_cash_cents)}).</span>
+      </div>
+    );
+  }
+  if (!d?.active && !d?.failed) return null;
+  return (
+    <div data-testid="distress-hud" style={{ padding: 8, border: "1px solid #fca5a5", margin: "8px 0", borderRadius: 6, background: "#fef2f2" }}>
+      <strong>{d.failed ? "Bankrupt" : "Financial distress"}</strong>
+      {!d.failed && <span> — {d.months_left} months to recover. Capacity, expedite and R&D increases are frozen; inventory is being liquidated.</span>}
+      {d.loan_offer_cents ? (
+        <button style={{ marginLeft: 8 }} onClick={async () => { try { setStateDto(await simAcceptLoan()); showToast("Loan accepted"); } catch (e: any) { showToast("Loan failed: " + (e?.message ?? e)); } }}>
+          Accept emergency loan {cents(d.loan_offer_cents)}
+        </button>
+      ) : null}
+    </div>
+  );
+}
+
+function TurnSummaryPanel() {
+  const { turnSummary: s, setTurnSummary } = useAppStore();
+  if (!s) return null;
+  const pct = (a: number, b: number) => (a ? `${(((b - a) / a) * 100).toFixed(1)}%` : "n/a");
+  return (
+    <div data-testid="turn-summary" style={{ padding: 8, border: "1px solid #c7d2fe", margin: "8px 0", borderRadius: 6, background: "#eef2ff" }}>
+      <strong>Quarter {s.from} → {s.to}</strong>
+      <button style={{ marginLeft: 8 }} onClick={() => setTurnSummary(undefined)}>Dismiss</button>
+      <ul style={{ margin: "4px 0" }}>
+        {s.demand.map((d) => (
+          <li key={d.segment}>{d.segment}: demand {pct(d.demand_from, d.demand_to)}, price {cents(d.ref_price_from_cents)} → {cents(d.ref_price_to_cents)}</li>
+        ))}
+        <li>Competitors' share {(s.competitors.share_from * 100).toFixed(1)}% → {(s.competitors.share_to * 100).toFixed(1)}%</li>
+        {s.contracts_started.map((c) => <li key={`cs${c.id}`}>Contract started: {c.wafers_per_month} wpm at {c.foundry_id} ({c.date})</li>)}
+        {s.contracts_ended.map((c) => <li key={`ce${c.id}`}>Contract ended: {c.wafers_per_month} wpm at {c.foundry_id} ({c.date})</li>)}
+        {s.products_released.map((p, i) => <li key={`p${i}`}>Released product on {p}</li>)}
+        {s.events.map((e, i) => <li key={`e${i}`}>{e.date}: {e.text}</li>)}
+      </ul>
+      <div>
+        Cash {cents(s.cash.start_cents)}
+        {s.cash.items.map(([cat, v]) => <span key={cat}> {v < 0 ? "−" : "+"} {cat.replace("_", " ")} {cents(Math.abs(v))}</span>)}
+        {" "}= {cents(s.cash.end_cents)}
+      </div>
+    </div>
+  );
+}
+
+function CreditPanel() {
+  const { stateDto, setStateDto } = useAppStore();
+  const [amount, setAmount] = useState(0);
+  const f = stateDto?.finance;
+  if (!f) return null;
+  const act = async (op: (cents: number) => Promise<SimStateDto>) => {
+    try { setStateDto(await op(Math.round(amount * 100))); } catch (e) { alert(String(e)); }
+  };
+  return (
+    <div data-testid="credit-panel" style={{ margin: "8px 0" }}>
+      <h3>Credit</h3>
+      <div>
+        Rating <strong>{f.rating}</strong> · new debt at {(f.rate_on_new_debt * 100).toFixed(2)}% (+{f.spread_bps} bps) · debt {cents(f.debt_cents)}
+      </div>
+      <div>
+        Credit line {cents(f.credit_line_cents)}, drawn {cents(f.drawn_cents)}, available {cents(f.available_cents)} · leverage {f.leverage.toFixed(2)}, coverage {f.interest_coverage == null ? "n/a" : f.interest_coverage.toFixed(1)}, volatility {f.volatility.toFixed(2)}
+      </div>
+      <div data-testid="credit-interest">
+        Interest last month: earned {cents(f.interest_income_cents)} on cash at {(f.deposit_rate * 100).toFixed(2)}%, paid {cents(f.interest_expense_cents)} (other debt at {(f.debt_rate * 100).toFixed(2)}%)
+      </div>
+      <input data-testid="credit-amount" type="number" value={amount} onChange={(e) => setAmount(Number(e.target.value))} />
+      <button data-testid="btn-credit-draw" onClick={() => act(simCreditDraw)}>Draw</button>
+      <button data-testid="btn-credit-repay" onClick={() => act(simCreditRepay)}>Repay</button>
+      {stateDto.news.length > 0 && (
+        <ul style={{ margin: "4px 0" }}>
+          {stateDto.news.slice(-3).map((n, i) => <li key={n.id ?? i}>{n.date}: {n.headline}</li>)}
+        </ul>
+      )}
+    </div>
+  );
+}
+
+function WarrantyPanel() {
+  const { stateDto } = useAppStore();
+  const w = stateDto?.finance?.warranty;
+  if (!w || (w.accrued_cents === 0 && w.claims_cents === 0)) return null;
+  return (
+    <div data-testid="warranty-panel" style={{ margin: "8px 0" }}>
+      <h3>Warranty reserve</h3>
+      <div>
+        Reserve {cents(w.balance_cents)} against {cents(w.outstanding_cents)} of expected claims{w.adequacy != null && <> ({(w.adequacy * 100).toFixed(0)}% covered)</>}
+      </div>
+      <div>
+        Last month: accrued {cents(w.last_accrual_cents)} at {(w.accrual_frac * 100).toFixed(2)}% of revenue, paid {cents(w.last_claims_cents)} in claims · to date {cents(w.claims_cents)} claimed, {cents(w.shortfall_cents)} beyond the reserve
+      </div>
+    </div>
+  );
+}
+
+function GrantsPanel() {
+  const { stateDto } = useAppStore();
+  const grants = stateDto?.grants ?? [];
+  if (!grants.length) return null;
+  return (
+    <div data-testid="grants-panel" style={{ margin: "8px 0" }}>
+      <h3>Grants</h3>
+      <ul style={{ margin: "4px 0" }}>
+        {grants.map((g) => {
+          const ob = g.terms.obligation;
+          const needs = [
+            ob.min_wafers_per_month > 0 ? `${ob.min_wafers_per_month} wafers/mo${ob.foundries.length ? ` at ${ob.foundries.join("/")}` : ""}` : null,
+            ob.node ? `a ${ob.node} product` : null,
+          ].filter(Boolean).join(" and ");
+          return (
+            <li key={g.event_id}>
+              {g.name}: received {cents(g.received_cents)} · needs {needs || "nothing"} by {g.deadline} · {g.status.replace("_", " ")}
+              {g.status === "clawed_back" && <> ({cents(g.clawback_cents)} repaid)</>}
+            </li>
+          );
+        })}
+      </ul>
+    </div>
+  );
+}
+
+function RegionsPanel() {
+  const { stateDto, setStateDto } = useAppStore();
+  const regions = stateDto?.regions ?? [];
+  if (!regions.length) return null;
+  const setPrice = async (region: string, usd: string) => {
+    try { setStateDto(await simRegionalPrice(region, usd === "" ? null : Math.round(Number(usd) * 100))); } catch (e) { alert(String(e)); }
+  };
+  return (
+    <div data-testid="regions-panel" style={{ margin: "8px 0" }}>
+      <h3>Regions</h3>
+      <table style={{ width: "100%", margin: "8px 0" }}>
+        <thead><tr><th align="left">Region</th><th>Shipped</th><th>Share</th><th>Price</th><th>Freight + tariffs</th><th>Override ($)</th></tr></thead>
+        <tbody>
+          {regions.map((r) => (
+            <tr key={r.id}>
+              <td>{r.name}</td><td align="right">{r.sold_units}</td><td align="right">{(r.share * 100).toFixed(1)}%</td><td align="right">{cents(r.price_cents)}</td><td align="right">{cents(r.logistics_cents)}</td>
+              <td><input data-testid={`region-price-${r.id}`} type="number" defaultValue={r.price_override_cents == null ? "" : r.price_override_cents / 100} onBlur={(e) => setPrice(r.id, e.target.value)} /></td>
+            </tr>
+          ))}
+        </tbody>
+      </table>
+    </div>
+  );
+}
+
+function ClearingPanel() {
+  const { stateDto } = useAppStore();
+  const clearing = stateDto?.clearing ?? [];
+  if (!clearing.length) return null;
+  return (
+    <div data-testid="clearing-panel" style={{ margin: "8px 0" }}>
+      <h3>Market clearing</h3>
+      <table style={{ width: "100%", margin: "8px 0" }}>
+        <thead><tr><th align="left">Segment</th><th>Demand</th>{clearing[0].sales.map((c) => <th key={c.company}>{c.company}</th>)}<th>Unmet</th></tr></thead>
+        <tbody>
+          {clearing.map((s) => (
+            <tr key={s.id}>
+              <td>{s.id}</td><td align="right">{s.demand_units}</td>
+              {s.sales.map((c) => <td key={c.company} align="right" title={`offered ${c.offered_units}, attractiveness ${c.attractiveness.toFixed(2)}`}>{c.sold_units}</td>)}
+              <td align="right">{s.unmet_units}</td>
+            </tr>
+          ))}
+        </tbody>
+      </table>
+    </div>
+  );
+}
+
+function OemPanel() {
+  const { stateDto, setStateDto } = useAppStore();
+  const oems = stateDto?.oems ?? [];
+  if (!oems.length) return null;
+  const act = async (p: Promise<SimStateDto>) => {
+    try { setStateDto(await p); } catch (e) { alert(String(e)); }
+  };
+  return (
+    <div data-testid="oem-panel" style={{ margin: "8px 0" }}>
+      <h3>OEM customers</h3>
+      <table style={{ width: "100%", margin: "8px 0" }}>
+        <thead><tr><th align="left">OEM</th><th>Score</th><th>Volume deal</th><th>Design wins</th><th>Concession (%)</th><th>Exclusive</th><th></th></tr></thead>
+        <tbody>
+          {oems.map((o) => {
+            const deal = o.terms?.volume_deal;
+            return (
+              <tr key={o.id}>
+                <td>{o.name}</td>
+                <td align="right">{o.score.toFixed(1)}</td>
+                <td>
+                  {o.deal
+                    ? <>{o.deal.units_per_month}/mo at {cents(o.deal.price_cents)} until {o.deal.end}{o.last_fill != null && <> · {(o.last_fill * 100).toFixed(0)}% shipped</>}</>
+                    : deal && (o.score >= deal.min_score
+                      ? <button data-testid={`btn-oem-deal-${o.id}`} onClick={() => act(simOemDeal(o.id))}>Sign {deal.units_per_month}/mo for {deal.months} mo</button>
+                      : <>unlocks at {deal.min_score}</>)}
+                </td>
+                <td align="right">{o.design_wins}{o.terms && <> (at {o.terms.design_win.min_score})</>}</td>
+                <td><input data-testid={`oem-concession-${o.id}`} type="number" defaultValue={o.concession_frac * 100} onBlur={(e) => act(simOemTerms(o.id, Number(e.target.value) / 100, o.exclusive))} /></td>
+                <td><input data-testid={`oem-exclusive-${o.id}`} type="checkbox" checked={o.exclusive} onChange={(e) => act(simOemTerms(o.id, o.concession_frac, e.target.checked))} /></td>
+                <td><button data-testid={`btn-oem-invest-${o.id}`} onClick={() => act(simOemInvest(o.id, 5_000_000))}>Invest $50k</button></td>
+              </tr>
+            );
+          })}
+        </tbody>
+      </table>
+    </div>
+  );
+}
+
+function ConsolePanel() {
+  const { stateDto, setStateDto } = useAppStore();
+  const [bid, setBid] = useState("90");
+  const c = stateDto?.consoles;
+  if (!c?.cycle) return null;
+  const submit = async () => {
+    try { setStateDto(await simConsoleBid(Number(bid) / 100)); } catch (e) { alert(String(e)); }
+  };
+  const locked = c.lockout_until && stateDto && c.lockout_until > stateDto.date;
+  return (
+    <div data-testid="console-panel" style={{ margin: "8px 0" }}>
+      <h3>Console generations</h3>
+      {c.rfq ? (
+        <div>
+          Generation {c.rfq.generation} RFQ closes {c.rfq.closes}. Bid score {c.score.toFixed(1)} / {c.needed.toFixed(0)} needed
+          {c.rfq.bid_price_frac != null && <> · bid at {(c.rfq.bid_price_frac * 100).toFixed(0)}% of ASP</>}
+          <div>
+            <input data-testid="console-bid" type="number" min={c.cycle.min_price_frac * 100} max={100} value={bid} onChange={(e) => setBid(e.target.value)} />% of ASP
+            <button data-testid="btn-console-bid" onClick={submit}>Bid</button>
+          </div>
+        </div>
+      ) : (
+        <div>Next RFQ opens {c.next_rfq}</div>
+      )}
+      {c.annuity && <div>Annuity: {c.annuity.units_per_month}/mo at {cents(c.annuity.price_cents)} until {c.annuity.end}</div>}
+      {locked && <div style={{ color: "#b00" }}>A rival platform holds {(c.cycle.lockout_frac * 100).toFixed(0)}% of {c.cycle.segment} demand until {c.lockout_until}</div>}
+      {c.awards.length > 0 && (
+        <small>{c.awards.map((a) => `Gen ${a.generation}: ${a.won ? "won" : "lost"}`).join(" · ")}</small>
+      )}
+    </div>
+  );
+}
+
+function SensitivityPanel() {
+  const { stateDto } = useAppStore();
+  const [report, setReport] = useState<SensitivityReportDto | null>(null);
+  useEffect(() => { setReport(null); }, [stateDto?.date]);
+  if (!stateDto) return null;
+  const run = async () => {
+    try { setReport(await simSensitivity(6)); } catch (e) { alert(String(e)); }
+  };
+  const label = { asp: "ASP", capacity: "Capacity", rnd: "R&D" } as const;
+  const base = report?.base.cash_cents ?? 0;
+  const widest = Math.max(1, ...(report?.levers ?? []).flatMap((l) => [Math.abs(l.down.cash_cents - base), Math.abs(l.up.cash_cents - base)]));
+  const bar = (delta: number) => (
+    <div style={{ width: `${(Math.abs(delta) / widest) * 100}%`, height: 12, background: delta >= 0 ? "#4caf50" : "#e57373" }} title={cents(delta)} />
+  );
+  return (
+    <div data-testid="sensitivity-panel" style={{ margin: "8px 0" }}>
+      <h3>What matters most</h3>
+      <button data-testid="btn-sensitivity" onClick={run}>Analyze levers</button>
+      {report && (
+        <div>
+          <small>Cash after {report.months} months vs {cents(base)} if nothing changes; lower step on the left, higher on the right.</small>
+          {report.levers.map((l) => (
+            <div key={l.lever} data-testid={`sensitivity-${l.lever}`} style={{ display: "flex", alignItems: "center", gap: 8 }}>
+              <span style={{ width: 110 }}>{label[l.lever]} ±{(l.step_frac * 100).toFixed(0)}%</span>
+              <div style={{ flex: 1, display: "flex", justifyContent: "flex-end" }}>{bar(l.down.cash_cents - base)}</div>
+              <div style={{ flex: 1, display: "flex" }}>{bar(l.up.cash_cents - base)}</div>
+              <span style={{ width: 100 }}>{cents(l.cash_swing_cents)}</span>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
+
+function AdvisorPanel() {
+  const { stateDto } = useAppStore();
+  const [seek, setSeek] = useState<GoalSeekDto>({ lever: "asp", metric: "share", target: 0.3, by: "" });
+  const [result, setResult] = useState<GoalSeekResultDto | null>(null);
+  useEffect(() => { setResult(null); }, [stateDto?.date]);
+  if (!stateDto) return null;
+  const by = seek.by || `${Number(stateDto.date.slice(0, 4)) + 1}${stateDto.date.slice(4)}`;
+  const run = async () => {
+    try { setResult(await simGoalSeek({ ...seek, by })); } catch (e) { alert(String(e)); }
+  };
+  const label = { asp: "ASP", capacity: "capacity", rnd: "R&D budget" } as const;
+  return (
+    <div data-testid="advisor-panel" style={{ margin: "8px 0" }}>
+      <h3>Advisor</h3>
+      Reach <select value={seek.metric} onChange={(e) => setSeek({ ...seek, metric: e.target.value as GoalSeekDto["metric"] })}>
+        <option value="share">share %</option>
+        <option value="cash">cash $</option>
+      </select>
+      {" "}<input data-testid="advisor-target" type="number" style={{ width: 90 }} value={seek.metric === "share" ? seek.target * 100 : seek.target / 100} onChange={(e) => setSeek({ ...seek, target: seek.metric === "share" ? Number(e.target.value) / 100 : Math.round(Number(e.target.value) * 100) })} />
+      {" "}by <input type="date" value={by} onChange={(e) => setSeek({ ...seek, by: e.target.value })} />
+      {" "}moving <select value={seek.lever} onChange={(e) => setSeek({ ...seek, lever: e.target.value as GoalSeekDto["lever"] })}>
+        <option value="asp">ASP</option>
+        <option value="capacity">Capacity</option>
+        <option value="rnd">R&amp;D</option>
+      </select>
+      <button data-testid="btn-advisor" style={{ marginLeft: 8 }} onClick={run}>Advisor suggestion</button>
+      {result && (
+        <div data-testid="advisor-result">
+          {result.achieved
+            ? `Change ${label[result.lever]} by ${(result.frac * 100).toFixed(1)}%`
+            : `Out of reach: ${label[result.lever]} ${(result.frac * 100).toFixed(0)}% gets closest`}
+          {" "}— {(result.confidence * 100).toFixed(0)}% confidence over {result.months} months
+        </div>
+      )}
+    </div>
+  );
+}
+
+function RoadmapPanel() {
+  const { stateDto, setStateDto } = useAppStore();
+  const [node, setNode] = useState("");
+  const [perf, setPerf] = useState("0.7");
+  const [due, setDue] = useState("");
+  if (!stateDto) return null;
+  const announce = async () => {
+    try { setStateDto(await simRoadmapAnnounce(node, Number(perf), due)); } catch (e) { alert(String(e)); }
+  };
+  return (
+    <div data-testid="roadmap-panel" style={{ margin: "8px 0" }}>
+      <h3>Roadmap · reputation {stateDto.reputation.toFixed(0)}</h3>
+      <ul style={{ margin: "4px 0" }}>
+        {stateDto.roadmap.map((a) => (
+          <li key={a.id}>
+            {a.node} at perf ≥ {a.min_perf_index} by {a.due}: {a.status === "hit" ? "delivered on time" : a.status === "slipped" ? (a.delivered ? `slipped, shipped ${a.delivered}` : "slipped") : "pending"}
+          </li>
+        ))}
+      </ul>
+      <input data-testid="roadmap-node" placeholder="node" value={node} onChange={(e) => setNode(e.target.value)} />
+      <input data-testid="roadmap-perf" type="number" step="0.05" value={perf} onChange={(e) => setPerf(e.target.value)} />
+      <input data-testid="roadmap-due" type="date" value={due} onChange={(e) => setDue(e.target.value)} />
+      <button data-testid="btn-roadmap-announce" disabled={!node || !due} onClick={announce}>Announce</button>
+    </div>
+  );
+}
+
+function LaunchReviewsPanel() {
+  const { stateDto } = useAppStore();
+  const reviews = stateDto?.reviews ?? [];
+  if (reviews.length === 0) return null;
+  return (
+    <div data-testid="reviews-panel" style={{ margin: "8px 0" }}>
+      <h3>Launch reviews</h3>
+      <ul style={{ margin: "4px 0" }}>
+        {reviews.map((r, i) => (
+          <li key={i} title={`perf ${r.inputs.perf_rel.toFixed(2)}× contemporaries, price ${r.inputs.price.toFixed(2)}, quality ${r.inputs.quality.toFixed(2)}`}>
+            {r.date} {r.product}: {r.score.toFixed(0)}/100, appeal {r.appeal_swing >= 0 ? "+" : ""}{(r.appeal_swing * 100).toFixed(1)} pts
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+}
+
+const SCHEDULE_COLORS: Record<ScheduleKind, string> = {
+  tapeout: "#4a7bd0", patent: "#8a5cc2", variant: "#c27c2c", legal_case: "#c0392b", contract: "#5a9e5a", research: "#2aa198", roadmap: "#888",
+};
+
+function SchedulePanel() {
+  const { stateDto } = useAppStore();
+  const [sched, setSched] = useState<WorkScheduleDto | null>(null);
+  useEffect(() => { (async () => { try { setSched(await simSchedule()); } catch {} })(); }, [stateDto?.date]);
+  if (!sched || sched.items.length === 0) return null;
+  const month = (d: string) => { const [y, m] = d.split("-").map(Number); return y * 12 + m - 1; };
+  const from = Math.min(month(sched.date), ...sched.items.map((i) => month(i.start)));
+  const to = Math.max(...sched.items.map((i) => month(i.end))) + 1;
+  const pct = (m: number) => `${((m - from) / (to - from)) * 100}%`;
+  return (
+    <div data-testid="schedule-panel" style={{ margin: "8px 0" }}>
+      <h3>Schedule</h3>
+      <div style={{ position: "relative" }}>
+        <div style={{ position: "absolute", left: `calc(30% + 70% * ${(month(sched.date) - from) / (to - from)})`, top: 0, bottom: 0, borderLeft: "1px dashed #c00" }} title={sched.date} />
+        {sched.items.map((i) => (
+          <div key={i.id} style={{ display: "flex", alignItems: "center", fontSize: 12, height: 18 }}>
+            <div style={{ width: "30%", overflow: "hidden", whiteSpace: "nowrap" }}>{i.label}{i.expedited ? " ⚡" : ""}</div>
+            <div style={{ width: "70%", position: "relative", height: 12 }}>
+              <div title={`${i.start} → ${i.end} (${(i.progress * 100).toFixed(0)}%)`} style={{ position: "absolute", left: pct(month(i.start)), width: `calc(${pct(month(i.end) + 1)} - ${pct(month(i.start))})`, height: "100%", background: SCHEDULE_COLORS[i.kind], opacity: 0.35 }} />
+              <div style={{ position: "absolute", left: pct(month(i.start)), width: `calc((${pct(month(i.end) + 1)} - ${pct(month(i.start))}) * ${i.progress})`, height: "100%", background: SCHEDULE_COLORS[i.kind] }} />
+            </div>
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
+
+function RnDPanel() {
+  const { stateDto, setStateDto } = useAppStore();
+  const [processK, setProcessK] = useState("");
+  const [designK, setDesignK] = useState("");
+  if (!stateDto) return null;
+  const r = stateDto.rnd;
+  const apply = async () => {
+    const p = processK === "" ? r.process_budget_cents : Math.round(Number(processK) * 100_000);
+    const d = designK === "" ? r.design_budget_cents : Math.round(Number(designK) * 100_000);
+    try { setStateDto(await simRndBudgets(p, d)); } catch (e) { alert(String(e)); }
+  };
+  return (
+    <div data-testid="rnd-panel" style={{ margin: "8px 0" }}>
+      <h3>R&D tracks{r.owns_fab ? " · own fab" : ""}</h3>
+      <div>Process {cents(r.process_budget_cents)}/mo · progress {(r.process * 100).toFixed(0)}% (yield ramp{r.owns_fab ? ", earlier nodes" : ""})</div>
+      <div>Design {cents(r.design_budget_cents)}/mo · progress {(r.design * 100).toFixed(0)}% (perf/IPC, shorter tapeouts)</div>
+      <input data-testid="rnd-process" type="number" placeholder="process $k/mo" value={processK} onChange={(e) => setProcessK(e.target.value)} />
+      <input data-testid="rnd-design" type="number" placeholder="design $k/mo" value={designK} onChange={(e) => setDesignK(e.target.value)} />
+      <button data-testid="btn-rnd-apply" onClick={apply}>Set budgets</button>
+    </div>
+  );
+}
+
+function QualityPanel() {
+  const { stateDto, setStateDto } = useAppStore();
+  const [budgetK, setBudgetK] = useState("");
+  if (!stateDto) return null;
+  const q = stateDto.quality;
+  const apply = async () => {
+    try { setStateDto(await simQcBudget(Math.round(Number(budgetK) * 100_000))); } catch (e) { alert(String(e)); }
+  };
+  return (
+    <div data-testid="quality-panel" style={{ margin: "8px 0" }}>
+      <h3>Quality control</h3>
+      <div>Budget {cents(q.budget_cents)}/mo · defects {(q.defect_frac * 100).toFixed(1)}% · field failures ×{q.field_failure_factor.toFixed(2)}</div>
+      <div style={{ fontSize: 12, color: "#64748b" }}>Spent {cents(q.spent_cents)} · {q.defects_avoided.toLocaleString()} defective dies avoided</div>
+      <input data-testid="qc-budget" type="number" placeholder="$k/mo" value={budgetK} onChange={(e) => setBudgetK(e.target.value)} />
+      <button data-testid="btn-qc-apply" onClick={apply}>Set budget</button>
+    </div>
+  );
+}
+
+// Hidden unless the backend was built with the debug-console feature
+function DebugConsolePanel() {
+  const { setStateDto } = useAppStore();
+  const [available, setAvailable] = useState(false);
+  const [cmd, setCmd] = useState("");
+  const [log, setLog] = useState<string[]>([]);
+  useEffect(() => { (async () => { try { setLog([await simDebug("help")]); setAvailable(true); } catch {} })(); }, []);
+  if (!available) return null;
+  const run = async () => {
+    let out: string;
+    try { out = await simDebug(cmd); } catch (e) { out = String(e); }
+    setLog((l) => [...l, `> ${cmd}`, out].slice(-40));
+    setCmd("");
+    try { setStateDto(await getSimState()); } catch {}
+  };
+  return (
+    <div data-testid="debug-console" style={{ margin: "8px 0" }}>
+      <h3>Debug console</h3>
+      <pre style={{ maxHeight: 200, overflow: "auto", fontSize: 12 }}>{log.join("\n")}</pre>
+      <input data-testid="debug-cmd" value={cmd} onChange={(e) => setCmd(e.target.value)} onKeyDown={(e) => { if (e.key === "Enter") run(); }} placeholder="help" />
+      <button data-testid="btn-debug-run" onClick={run}>Run</button>
+    </div>
+  );
+}
+
+function ProductsPanel() {
+  const { stateDto } = useAppStore();
+  const products = stateDto?.products ?? [];
+  if (products.length < 2) return null;
+  return (
+    <div data-testid="products-panel" style={{ margin: "8px 0" }}>
+      <h3>Products on sale</h3>
+      <table>
+        <thead><tr><th>Product</th><th>Line</th><th>Demand</th><th>Price</th><th>Units</th><th>Revenue</th></tr></thead>
+        <tbody>
+          {products.map((p) => (
+            <tr key={`${p.line}-${p.product}-${p.launched}`}>
+              <td>{p.product}{p.succeeded ? ` (succeeded ${p.succeeded})` : ""}</td>
+              <td>{p.line}</td>
+              <td>{(p.demand_share * 100).toFixed(0)}%</td>
+              <td>{(p.price_frac * 100).toFixed(0)}% of ASP</td>
+              <td>{p.last_units.toLocaleString()}</td>
+              <td>{cents(p.last_revenue_cents)}</td>
+            </tr>
+          ))}
+        </tbody>
+      </table>
+    </div>
+  );
+}
+
+function describeRule(r: PricingRuleDto) {
+  switch (r.type) {
+    case "min_margin": return `keep margin ≥ ${(r.frac * 100).toFixed(0)}%`;
+    case "undercut_ref": return `undercut ${r.segment} ref price by ${(r.frac * 100).toFixed(0)}%`;
+    case "raise_on_utilization": return `raise ${(r.step_frac * 100).toFixed(0)}% when > ${(r.above * 100).toFixed(0)}% of output sells`;
+    case "cut_on_inventory": return `cut ${(r.step_frac * 100).toFixed(0)}% when stock > ${r.months} months of sales`;
+  }
+}
+
+function PricingRulesPanel() {
+  const { stateDto, setStateDto } = useAppStore();
+  const [data, setData] = useState<PricingRulesDto | null>(null);
+  const [kind, setKind] = useState<PricingRuleDto["type"]>("min_margin");
+  const [value, setValue] = useState("20");
+  const [step, setStep] = useState("5");
+  const [segment, setSegment] = useState("");
+  const [priority, setPriority] = useState("0");
+  useEffect(() => { (async () => { try { setData(await simPricingRules()); } catch {} })(); }, [stateDto]);
+  if (!stateDto || !data) return null;
+  const save = async (rules: PricingRuleDto[]) => {
+    try { setStateDto(await simPricingRulesSet(rules)); } catch (e) { alert(String(e)); }
+  };
+  const add = () => {
+    const n = Number(value);
+    const s = Number(step) / 100;
+    const base = { id: `${kind}_${data.rules.length + 1}`, priority: Number(priority), enabled: true };
+    const rule: PricingRuleDto =
+      kind === "min_margin" ? { ...base, type: kind, frac: n / 100 }
+      : kind === "undercut_ref" ? { ...base, type: kind, segment: segment || stateDto.segments[0]?.name || "", frac: n / 100 }
+      : kind === "raise_on_utilization" ? { ...base, type: kind, above: n / 100, step_frac: s }
+      : { ...base, type: kind, months: n, step_frac: s };
+    save([...data.rules, rule]);
+  };
+  return (
+    <div data-testid="pricing-rules-panel" style={{ margin: "8px 0" }}>
+      <h3>Pricing rules</h3>
+      <ul style={{ margin: "4px 0" }}>
+        {data.rules.map((r, i) => (
+          <li key={r.id}>
+            <input type="checkbox" checked={r.enabled} onChange={(e) => save(data.rules.map((x, j) => (j === i ? { ...x, enabled: e.target.checked } : x)))} />
+            {" "}{r.id}: {describeRule(r)} (priority {r.priority})
+            <button style={{ marginLeft: 4 }} onClick={() => save(data.rules.filter((_, j) => j !== i))}>Remove</button>
+          </li>
+        ))}
+      </ul>
+      <select data-testid="rule-kind" value={kind} onChange={(e) => setKind(e.target.value as PricingRuleDto["type"])}>
+        <option value="min_margin">Min margin %</option>
+        <option value="undercut_ref">Undercut ref price %</option>
+        <option value="raise_on_utilization">Raise above utilization %</option>
+        <option value="cut_on_inventory">Cut above stock months</option>
+      </select>
+      {kind === "undercut_ref" && (
+        <select value={segment} onChange={(e) => setSegment(e.target.value)}>
+          {stateDto.segments.map((s) => <option key={s.name} value={s.name}>{s.name}</option>)}
+        </select>
+      )}
+      <input data-testid="rule-value" type="number" value={value} onChange={(e) => setValue(e.target.value)} style={{ width: 60 }} />
+      {(kind === "raise_on_utilization" || kind === "cut_on_inventory") && (
+        <input placeholder="step %" type="number" value={step} onChange={(e) => setStep(e.target.value)} style={{ width: 60 }} />
+      )}
+      <input placeholder="priority" type="number" value={priority} onChange={(e) => setPriority(e.target.value)} style={{ width: 60 }} />
+      <button data-testid="btn-rule-add" onClick={add}>Add rule</button>
+      {data.firings.slice(-5).reverse().map((f, i) => (
+        <div key={i} style={{ fontSize: 12, color: "#64748b" }}>
+          {f.date} {f.rule}: {f.outcome === "overridden" ? `overridden by ${f.by}` : f.outcome} {cents(f.from_cents)} → {cents(f.to_cents)}
+        </div>
+      ))}
+    </div>
+  );
+}
+
+const PATENT_FEATURES = ["deep_pipeline", "large_l1", "large_l2", "chiplet", "high_ipc"];
+
+function PatentsPanel() {
+  const { stateDto, setStateDto } = useAppStore();
+  const [feature, setFeature] = useState(PATENT_FEATURES[0]);
+  if (!stateDto) return null;
+  const p = stateDto.patents;
+  const rivalName = (id: string) => p.rivals.find((r) => r.id === id)?.name ?? id;
+  const file = async () => {
+    try { setStateDto(await simPatentFile(feature)); } catch (e) { alert(String(e)); }
+  };
+  return (
+    <div data-testid="patents-panel" style={{ margin: "8px 0" }}>
+      <h3>Patents · strength {p.strength.toFixed(0)}</h3>
+      <ul style={{ margin: "4px 0" }}>
+        {p.filings.map((f) => (
+          <li key={f.feature}>{f.feature}: {f.granted ? "granted" : `pending until ${f.grant}`}</li>
+        ))}
+        {p.rivals.map((r) => (
+          <li key={r.id}>{r.name} (strength {r.strength}) holds {r.patents.join(", ") || "nothing"}</li>
+        ))}
+      </ul>
+      {p.cases.length > 0 && (
+        <table>
+          <thead><tr><th>Case</th><th>Feature</th><th>Verdict</th><th>Net</th></tr></thead>
+          <tbody>
+            {p.cases.map((c) => (
+              <tr key={c.id}>
+                <td>{c.role === "defendant" ? `${rivalName(c.rival)} v. us` : `us v. ${rivalName(c.rival)}`}</td>
+                <td>{c.feature}</td>
+                <td>{c.outcome ?? `due ${c.verdict_due}`}{c.royalty_until ? ` · royalties to ${c.royalty_until}` : ""}</td>
+                <td>{cents(c.cash_cents)}</td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      )}
+      <select data-testid="patent-feature" value={feature} onChange={(e) => setFeature(e.target.value)}>
+        {PATENT_FEATURES.map((f) => <option key={f} value={f}>{f}</option>)}
+      </select>
+      <button data-testid="btn-patent-file" onClick={file}>File ({cents(p.filing_cost_cents)})</button>
+    </div>
+  );
+}
+
+function MarketResearchPanel() {
+  const { stateDto, setStateDto } = useAppStore();
+  const segments = useAppStore((s) => s.lists?.design_segments ?? []);
+  const [segment, setSegment] = useState("");
+  const [months, setMonths] = useState(6);
+  if (!stateDto) return null;
+  const r = stateDto.market_research;
+  const target = segment || segments[0]?.id || "";
+  const buy = async () => {
+    try { setStateDto(await simMarketResearchBuy(target, months)); } catch (e) { alert(String(e)); }
+  };
+  const hint = (h: CompetitorHintDto) =>
+    h.kind === "strength" ? `Rivals ${h.ahead > h.now ? "strengthen" : "weaken"} from ${h.now.toFixed(2)} to ${h.ahead.toFixed(2)} by ${h.date}`
+    : h.kind === "entrant_pressure" ? `High margins ${h.quarters}/${h.needed} quarters: an entrant may follow`
+    : `${h.company} is short of cash (${cents(h.cash_cents)})`;
+  const pct = (p?: number | null) => (p ? `${p > 0 ? "+" : ""}${p}%` : "");
+  return (
+    <div data-testid="market-research" style={{ margin: "8px 0" }}>
+      <h3>Market research</h3>
+      {r.reports.map((rep) => (
+        <div key={rep.segment} data-testid={`research-${rep.segment}`}>
+          <b>{rep.segment}</b> (to {rep.until}): demand {rep.base_demand_units}, ref price {cents(rep.ref_price_cents)}, elasticity {rep.elasticity.toFixed(2)}, trend {rep.trend_pct.toFixed(1)}%
+          <ul style={{ margin: "4px 0" }}>
+            {rep.upcoming_events.map((ev, i) => (
+              <li key={i}>From {ev.start} for {ev.months} months: demand {pct(ev.base_demand_pct)} price {pct(ev.ref_price_pct)}{ev.elasticity_delta ? ` elasticity -${(ev.elasticity_delta * 100).toFixed(0)}%` : ""}</li>
+            ))}
+            {rep.competitor_hints.map((h, i) => <li key={`h${i}`}>{hint(h)}</li>)}
+          </ul>
+        </div>
+      ))}
+      <select data-testid="research-segment" value={target} onChange={(e) => setSegment(e.target.value)}>
+        {segments.map((s) => <option key={s.id} value={s.id}>{s.name}</option>)}
+      </select>
+      <input type="number" min={1} max={r.max_months} value={months} onChange={(e) => setMonths(Number(e.target.value))} /> months
+      <button data-testid="btn-research-buy" onClick={buy}>Buy ({cents(r.cents_per_month * months)})</button>
+    </div>
+  );
+}
+
+function ExportControlsPanel() {
+  const { stateDto } = useAppStore();
+  const [ec, setEc] = useState<ExportControlsDto | null>(null);
+  useEffect(() => { (async () => { try { setEc(await simExportControls()); } catch {} })(); }, [stateDto?.date]);
+  if (!ec?.active.length) return null;
+  const act = async (p: Promise<ExportControlsDto>) => {
+    try { setEc(await p); } catch (e) { alert(String(e)); }
+  };
+  return (
+    <div data-testid="export-panel" style={{ margin: "8px 0" }}>
+      <h3>Export controls</h3>
+      <ul style={{ margin: "4px 0" }}>
+        {ec.active.map((r) => {
+          const policy = ec.policies[r.id] ?? "comply";
+          const variant = ec.variants.find((v) => v.restriction_id === r.id);
+          const fined = ec.fines.filter((f) => f.restriction_id === r.id).reduce((a, f) => a + f.cents, 0);
+          return (
+            <li key={r.id}>
+              {r.name}: {r.nodes.length ? r.nodes.join("/") : "all nodes"} barred from {r.regions.join(", ")} until {r.end}
+              {" "}· <select data-testid={`export-policy-${r.id}`} value={policy} onChange={(e) => act(simExportPolicy(r.id, e.target.value as "comply" | "defy"))}>
+                <option value="comply">Comply</option>
+                <option value="defy">Defy ({cents(r.fine_cents_per_unit)}/unit fine)</option>
+              </select>
+              {variant
+                ? <> · compliant {variant.node} variant ships {variant.ready}</>
+                : <button data-testid={`btn-export-variant-${r.id}`} onClick={() => act(simExportVariant(r.id))}>Compliant variant ({cents(r.variant_cost_cents)}, {r.variant_months} mo)</button>}
+              {fined > 0 && <> · fined {cents(fined)}</>}
+            </li>
+          );
+        })}
+      </ul>
+    </div>
+  );
+}
+
+function InsurancePanel() {
+  const { stateDto } = useAppStore();
+  const [ins, setIns] = useState<InsuranceDto | null>(null);
+  useEffect(() => { (async () => { try { setIns(await simInsurance()); } catch {} })(); }, [stateDto?.date]);
+  if (!ins) return null;
+  const act = async (op: (id: string) => Promise<InsuranceDto>, id: string) => {
+    try { setIns(await op(id)); } catch (e) { alert(String(e)); }
+  };
+  return (
+    <div data-testid="insurance-panel" style={{ margin: "8px 0" }}>
+      <h3>Insurance</h3>
+      <ul style={{ margin: "4px 0" }}>
+        {ins.products.map((p) => {
+          const held = ins.policies.some((x) => x.product_id === p.id);
+          return (
+            <li key={p.id}>
+              {p.name}: {cents(p.premium_cents_per_month)}/mo, pays {(p.cover_frac * 100).toFixed(0)}% over {cents(p.deductible_cents)} up to {cents(p.limit_cents)}{" "}
+              {held
+                ? <button data-testid={`btn-insurance-cancel-${p.id}`} onClick={() => act(simInsuranceCancel, p.id)}>Cancel</button>
+                : <button data-testid={`btn-insurance-buy-${p.id}`} onClick={() => act(simInsuranceBuy, p.id)}>Buy</button>}
+            </li>
+          );
+        })}
+      </ul>
+      <div>Premiums paid {cents(ins.premiums_paid_cents)} · claims paid {cents(ins.claims.reduce((a, c) => a + c.payout_cents, 0))}</div>
+    </div>
+  );
+}
+
+function BudgetPanel() {
+  const { stateDto } = useAppStore();
+  const [budget, setBudget] = useState<BudgetDto | null>(null);
+  const [category, setCategory] = useState<BudgetCategory>("rnd");
+  const [amount, setAmount] = useState(0);
+  useEffect(() => { (async () => { try { setBudget(await simBudget()); } catch {} })(); }, [stateDto?.date]);
+  if (!budget) return null;
+  const save = async (cents: number | null) => {
+    try { setBudget(await simBudgetSet(category, cents)); } catch (e) { alert(String(e)); }
+  };
+  return (
+    <div data-testid="budget-panel" style={{ margin: "8px 0" }}>
+      <h3>Quarterly budgets</h3>
+      <div>Board confidence: {(budget.board_confidence * 100).toFixed(0)}%</div>
+      <table style={{ width: "100%", margin: "8px 0" }}>
+        <thead><tr><th align="left">Category</th><th>Budget</th><th>Spent</th><th>Variance</th></tr></thead>
+        <tbody>
+          {budget.quarter_to_date.map((r) => (
+            <tr key={r.category} style={{ color: r.variance_cents > 0 ? "#b91c1c" : undefined }}>
+              <td>{r.category.replace("_", " ")}</td><td align="right">{cents(r.budget_cents)}</td><td align="right">{cents(r.actual_cents)}</td><td align="right">{cents(r.variance_cents)}</td>
+            </tr>
+          ))}
+        </tbody>
+      </table>
+      <select data-testid="budget-category" value={category} onChange={(e) => setCategory(e.target.value as BudgetCategory)}>
+        <option value="rnd">R&amp;D</option>
+        <option value="marketing">Marketing</option>
+        <option value="capacity">Capacity</option>
+        <option value="opex">Opex</option>
+      </select>
+      <input data-testid="budget-amount" type="number" value={amount} onChange={(e) => setAmount(Number(e.target.value))} />
+      <button data-testid="btn-budget-set" onClick={() => save(Math.round(amount * 100))}>Set</button>
+      <button onClick={() => save(null)}>Clear</button>
+      <label style={{ marginLeft: 8 }}>
+        <input data-testid="toggle-budget-enforce" type="checkbox" checked={budget.mode === "enforce"} onChange={async (e) => { try { setBudget(await simBudgetSet(null, null, e.target.checked ? "enforce" : "warn")); } catch {} }} /> Enforce
+      </label>
     </div>
   );
 }
@@ -242,6 +1091,77 @@ function TutorialHUD({ tut, onGoto }: { tut: TutorialDto | null; onGoto: (p: str
   );
 }
 
+function HintsPanel() {
+  const { stateDto } = useAppStore();
+  const [hints, setHints] = useState<HintsDto | null>(null);
+  useEffect(() => { (async () => { try { setHints(await simHints()); } catch {} })(); }, [stateDto?.date]);
+  if (!hints) return null;
+  const recent = hints.hints.slice(-3).reverse();
+  return (
+    <div data-testid="hints-panel" style={{ padding: 8, border: "1px solid #fde68a", margin: "8px 0", borderRadius: 6, background: "#fffbeb" }}>
+      <strong>Hints</strong>
+      <label style={{ marginLeft: 8, fontSize: 12 }}>
+        <input data-testid="toggle-hints" type="checkbox" checked={hints.config.enabled} onChange={async (e) => { try { setHints(await simHints({ ...hints.config, enabled: e.target.checked })); } catch {} }} /> Show
+      </label>
+      {hints.config.enabled && recent.map((h, i) => (
+        <div key={i} style={{ fontSize: 12, marginTop: 4 }}>{h.date}: {h.message}</div>
+      ))}
+    </div>
+  );
+}
+
+function WarningsPanel() {
+  const { stateDto } = useAppStore();
+  const [warnings, setWarnings] = useState<WarningsDto | null>(null);
+  useEffect(() => { (async () => { try { setWarnings(await simWarnings()); } catch {} })(); }, [stateDto]);
+  if (!warnings || warnings.warnings.length === 0) return null;
+  const recent = warnings.warnings.slice(-3).reverse();
+  const color = { info: "#64748b", adjusted: "#b45309", costly: "#b91c1c" };
+  return (
+    <div data-testid="warnings-panel" style={{ padding: 8, border: "1px solid #fecaca", margin: "8px 0", borderRadius: 6, background: "#fef2f2" }}>
+      <strong>Warnings</strong>
+      {recent.map((w, i) => (
+        <div key={i} style={{ fontSize: 12, marginTop: 4 }}>
+          <span style={{ color: color[w.severity] }}>[{w.severity}]</span> {w.date}: {w.message}
+          <div style={{ color: "#64748b" }}>{w.fix}</div>
+        </div>
+      ))}
+    </div>
+  );
+}
+
+function TimeTravelPanel() {
+  const { stateDto } = useAppStore();
+  const [history, setHistory] = useState<HistoryDto | null>(null);
+  const [month, setMonth] = useState<number | null>(null);
+  const [past, setPast] = useState<StateAtDto | null>(null);
+  useEffect(() => { (async () => { try { setHistory(await simHistory()); } catch {} })(); }, [stateDto?.date]);
+  if (!history) return null;
+  const first = history.first_month ?? history.current_month;
+  const scrub = async (m: number) => {
+    setMonth(m);
+    try { setPast(await simStateAt(m)); } catch { setPast(null); }
+  };
+  return (
+    <div data-testid="time-travel-panel" style={{ padding: 8, border: "1px solid #cbd5e1", margin: "8px 0", borderRadius: 6 }}>
+      <strong>History</strong>
+      <label style={{ marginLeft: 8, fontSize: 12 }}>
+        <input data-testid="toggle-history" type="checkbox" checked={history.recording} onChange={async (e) => { try { setHistory(await simHistory(e.target.checked)); setPast(null); } catch {} }} /> Record
+      </label>
+      {history.recording && history.current_month > first && (
+        <div style={{ marginTop: 4 }}>
+          <input data-testid="history-scrubber" type="range" min={first} max={history.current_month} value={month ?? history.current_month} onChange={(e) => scrub(Number(e.target.value))} />
+          {past && (
+            <div style={{ fontSize: 12 }}>
+              Month {past.month} ({past.state.date}){past.replayed_months > 0 ? `, replayed ${past.replayed_months} months` : ""}: cash {cents(past.state.kpi.cash_cents)}, revenue {cents(past.state.kpi.revenue_cents)}, profit {cents(past.state.kpi.profit_cents)}, share {(past.state.kpi.share * 100).toFixed(1)}%, ASP {cents(past.state.pricing.asp_cents)}
+            </div>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
+
 function TutorialPage({ tut, onGoto }: { tut: TutorialDto | null; onGoto: (p: string) => void }) {
   const qc = useQueryClient();
   const loadTutorial = async () => {
@@ -286,13 +1206,26 @@ function TutorialPage({ tut, onGoto }: { tut: TutorialDto | null; onGoto: (p: st
 function Campaign() {
   const { stateDto, showToast, setStateDto, isBusy } = useAppStore();
   const camp = (stateDto as any)?.campaign as any;
-  const [fmt, setFmt] = useState<"json"|"parquet">("json");
+  const [fmt, setFmt] = useState<"json"|"parquet"|"csv"|"html"|"markdown">("json");
   const [path, setPath] = useState("telemetry/campaign_export.json");
+  const [sandbox, setSandbox] = useState({ start_year: 1990, cash_musd: 5, competitors: 3, difficulty: "normal", fog: true });
   return (
     <div>
       <h2>{t("nav_campaign")}</h2>
       <div style={{ marginBottom: 8 }}>
         <button data-testid="btn-campaign-reset" disabled={isBusy} onClick={async () => { try { const dto = await simCampaignReset("1990s"); setStateDto(dto as any); showToast("Campaign reset"); } catch (e: any) { showToast("Reset failed: "+(e?.message ?? e)); } }}>Restart 1990s Campaign</button>
+        <span data-testid="sandbox-setup" style={{ marginLeft: 12 }}>
+          Sandbox: start <input type="number" value={sandbox.start_year} onChange={e => setSandbox({ ...sandbox, start_year: Number(e.target.value) })} style={{ width: 64 }} />
+          {" "}cash $M <input type="number" value={sandbox.cash_musd} onChange={e => setSandbox({ ...sandbox, cash_musd: Number(e.target.value) })} style={{ width: 56 }} />
+          {" "}rivals <input type="number" min={0} value={sandbox.competitors} onChange={e => setSandbox({ ...sandbox, competitors: Number(e.target.value) })} style={{ width: 40 }} />
+          {" "}<select value={sandbox.difficulty} onChange={e => setSandbox({ ...sandbox, difficulty: e.target.value })}>
+            <option value="easy">Easy</option>
+            <option value="normal">Normal</option>
+            <option value="hard">Hard</option>
+          </select>
+          {" "}<label><input type="checkbox" data-testid="sandbox-fog" checked={sandbox.fog} onChange={e => setSandbox({ ...sandbox, fog: e.target.checked })} /> market fog</label>
+          <button data-testid="btn-sandbox-new" disabled={isBusy} onClick={async () => { try { const dto = await simSandboxNew({ start_year: sandbox.start_year, player_cash_cents: Math.round(sandbox.cash_musd * 100_000_000), competitors: sandbox.competitors, difficulty: sandbox.difficulty, fog: sandbox.fog }); setStateDto(dto as any); showToast("Sandbox started"); } catch (e: any) { showToast("Sandbox failed: "+(e?.message ?? e)); } }} style={{ marginLeft: 6 }}>New sandbox game</button>
+        </span>
         <span style={{ marginLeft: 12 }}>
           {t("lbl_difficulty")}
           <select onChange={async (e) => { try { await simCampaignSetDifficulty(e.target.value); showToast("Difficulty set"); } catch (err: any) { showToast("Difficulty failed: "+(err?.message ?? err)); } }} defaultValue={camp?.difficulty ?? "normal"} style={{ marginLeft: 6 }}>
@@ -303,9 +1236,12 @@ function Campaign() {
         </span>
         <span style={{ marginLeft: 12 }}>
           <label>Export: </label>
-          <select value={fmt} onChange={(e)=>{ const f = e.target.value as any; setFmt(f); setPath(f === "json" ? "telemetry/campaign_export.json" : "telemetry/campaign_export.parquet"); }}>
+          <select value={fmt} onChange={(e)=>{ const f = e.target.value as any; setFmt(f); setPath(`telemetry/campaign_${f === "html" || f === "markdown" ? "report" : f === "csv" ? "events" : "export"}.${({ json: "json", parquet: "parquet", csv: "csv", html: "html", markdown: "md" } as any)[f]}`); }}>
             <option value="json">JSON</option>
             <option value="parquet">Parquet</option>
+            <option value="csv">Event log (CSV)</option>
+            <option value="html">Report (HTML)</option>
+            <option value="markdown">Report (Markdown)</option>
           </select>
           <button data-testid="btn-export" style={{ marginLeft: 6 }} onClick={async ()=>{ try { await simExportCampaign(path, fmt); showToast(`Exported to ${path}`); } catch(e: any) { showToast("Export failed: "+(e?.message ?? e)); } }}>{t("btn_export_report")}</button>
         </span>
@@ -324,6 +1260,7 @@ function Campaign() {
             </tbody>
           </table>
           <ActiveModsTable />
+          <ConfigSwapPanel />
         </div>
       ) : (
         <div>No campaign loaded.</div>
@@ -361,7 +1298,24 @@ function Markets({ onOverride }: { onOverride: (p: any) => void }) {
 function RD({ onOverride }: { onOverride: (p: any) => void }) {
   const [rd, setRd] = useState(0);
   const [expedite, setExpedite] = useState(false);
+  const [expediteMonths, setExpediteMonths] = useState(1);
+  const [expediteQuote, setExpediteQuote] = useState<ExpediteOptionDto[]>([]);
   const [tech, setTech] = useState("N90");
+  const [kind, setKind] = useState<ProductKind>("CPU");
+  const [tdp, setTdp] = useState(65);
+  const [clockPct, setClockPct] = useState(100);
+  const [family, setFamily] = useState("");
+  const [targets, setTargets] = useState<string[]>([]);
+  const segments = useAppStore((s) => s.lists?.design_segments ?? []);
+  const kindCfg = useAppStore((s) => s.lists?.product_kinds ?? []).find((k) => k.kind === kind);
+  const reaches = (id: string) => !kindCfg || kindCfg.segments.includes(id);
+  const misfit = segments.filter((s) => targets.includes(s.id) && ((s.tdp_min_w ?? 0) > tdp || tdp > (s.tdp_max_w ?? Infinity)));
+  const offKind = segments.filter((s) => targets.includes(s.id) && !reaches(s.id));
+  // Quote expedite levels before anything is committed
+  useEffect(() => {
+    if (!expedite) return;
+    simExpediteQuote(tech).then((q) => setExpediteQuote(q ?? [])).catch(() => setExpediteQuote([]));
+  }, [expedite, tech]);
   return (
     <div>
       <h2>R&D / Tapeout</h2>
@@ -376,7 +1330,37 @@ function RD({ onOverride }: { onOverride: (p: any) => void }) {
         <input value={tech} onChange={(e) => setTech(e.target.value)} />
         <label> Expedite </label>
         <input data-testid="toggle-expedite" type="checkbox" checked={expedite} onChange={(e) => setExpedite(e.target.checked)} />
-        <button data-testid="btn-tapeout-queue" disabled={useAppStore.getState().isBusy} onClick={() => onOverride({ tapeout: { perf_index: 0.8, die_area_mm2: 100, tech_node: tech, expedite } })}>Queue Tapeout</button>
+        {expedite && (
+          <select data-testid="select-expedite-months" value={expediteMonths} onChange={(e) => setExpediteMonths(Number(e.target.value))}>
+            {expediteQuote.map((o) => (
+              <option key={o.months} value={o.months} disabled={!o.available} title={o.reason ?? undefined}>
+                {o.months} mo sooner, {cents(o.cost_cents)}{o.available ? "" : " (locked)"}
+              </option>
+            ))}
+          </select>
+        )}
+        <label> Kind </label>
+        <select data-testid="select-product-kind" value={kind} onChange={(e) => setKind(e.target.value as ProductKind)}>
+          {["CPU", "GPU", "APU", "ASIC", "NPU"].map((k) => <option key={k} value={k}>{k}</option>)}
+        </select>
+        <label> TDP (W) </label>
+        <input data-testid="input-tdp" type="number" value={tdp} onChange={(e) => setTdp(Number(e.target.value))} style={{ width: 60 }} />
+        <label title="Faster bins raise performance, but fewer dies make them"> Clock (% of node) </label>
+        <input data-testid="input-clock" type="number" min={50} max={150} step={5} value={clockPct} onChange={(e) => setClockPct(Number(e.target.value))} style={{ width: 60 }} />
+        <label title="Successive tapeouts of a family replace the previous generation"> Family </label>
+        <input data-testid="input-family" value={family} placeholder="e.g. falcon" onChange={(e) => setFamily(e.target.value)} style={{ width: 90 }} />
+        <button data-testid="btn-tapeout-queue" disabled={useAppStore.getState().isBusy || misfit.length > 0 || offKind.length > 0} onClick={() => onOverride({ tapeout: { perf_index: 0.8, die_area_mm2: 100, tech_node: tech, expedite, expedite_months: expedite ? expediteMonths : undefined, kind, tdp_w: tdp, target_segments: targets, clock_frac: clockPct / 100, family: family.trim() || undefined } })}>Queue Tapeout</button>
+        {kindCfg && <div data-testid="product-kind-economics">{kind}: cost {kindCfg.unit_cost_pct}%, perf {kindCfg.perf_pct}% of a CPU; sells into {segments.filter((s) => reaches(s.id)).map((s) => s.name).join(", ")}</div>}
+        <div data-testid="tapeout-targets">
+          Target segments (none = whole market):
+          {segments.map((s) => (
+            <label key={s.id} style={{ marginLeft: 8 }} title={s.tdp_min_w != null ? `${s.tdp_min_w}–${s.tdp_max_w} W` : undefined}>
+              <input type="checkbox" data-testid={`target-${s.id}`} disabled={!reaches(s.id) && !targets.includes(s.id)} checked={targets.includes(s.id)} onChange={(e) => setTargets((t) => e.target.checked ? [...t, s.id] : t.filter((x) => x !== s.id))} /> {s.name}
+            </label>
+          ))}
+        </div>
+        {offKind.length > 0 && <div data-testid="tapeout-off-kind" style={{ color: "#b91c1c" }}>{kind} products cannot sell into {offKind.map((s) => s.name).join(", ")}</div>}
+        {misfit.length > 0 && <div data-testid="tapeout-misfit" style={{ color: "#b91c1c" }}>{tdp} W does not fit {misfit.map((s) => `${s.name} (${s.tdp_min_w}–${s.tdp_max_w} W)`).join(", ")}</div>}
       </div>
     </div>
   );
@@ -385,16 +1369,36 @@ function RD({ onOverride }: { onOverride: (p: any) => void }) {
 function Capacity({ onOverride }: { onOverride: (p: any) => void }) {
   const [wpm, setWpm] = useState(1000);
   const [months, setMonths] = useState(12);
+  // Blank ramp and minimum order take the going terms; "0" ramps flat
+  const [ramp, setRamp] = useState("");
+  const [moq, setMoq] = useState("");
+  const [rollover, setRollover] = useState(false);
+  const request = () => {
+    if (wpm <= 0 || months <= 0) { alert("Enter positive numbers"); return; }
+    const req: NonNullable<OverrideReq["capacity_request"]> = { wafers_per_month: wpm, months };
+    if (ramp.trim()) req.ramp_wafers = ramp.split(",").map((x) => Number(x.trim())).filter((x) => x > 0);
+    if (moq.trim()) req.min_order_wafers = Number(moq);
+    if (rollover) req.rollover = true;
+    onOverride({ capacity_request: req });
+  };
   return (
     <div>
       <h2>Capacity</h2>
       <ContractsTable />
+      <CapacityMarketTable />
+      <ProductionPlanTable />
+      <ContractAdvicePanel />
       <div>
         <label>Wafers/mo: </label>
         <input type="number" value={wpm} onChange={(e) => setWpm(Number(e.target.value))} />
         <label> Months: </label>
         <input type="number" value={months} onChange={(e) => setMonths(Number(e.target.value))} />
-        <button data-testid="btn-capacity-request" disabled={useAppStore.getState().isBusy} onClick={() => { if (wpm <= 0 || months <= 0) { alert("Enter positive numbers"); return; } onOverride({ capacity_request: { wafers_per_month: wpm, months } }); }}>Request</button>
+        <label> Ramp: </label>
+        <input data-testid="capacity-ramp" placeholder="going" value={ramp} onChange={(e) => setRamp(e.target.value)} />
+        <label> Min order: </label>
+        <input data-testid="capacity-moq" type="number" placeholder="going" value={moq} onChange={(e) => setMoq(e.target.value)} />
+        <label> <input data-testid="capacity-rollover" type="checkbox" checked={rollover} onChange={(e) => setRollover(e.target.checked)} /> Rollover </label>
+        <button data-testid="btn-capacity-request" disabled={useAppStore.getState().isBusy} onClick={request}>Request</button>
       </div>
     </div>
   );
@@ -474,14 +1478,22 @@ function SegmentsTable() {
       <tbody>
         {stateDto.segments.map((s) => {
           const pts = forecast(s);
+          // Estimates are marked "~" with their error until history or research makes them exact
+          const err = s.estimate_error_frac ?? 0;
+          const est = (v: string | number) => (err > 0 ? <span title={`estimate, ±${(err * 100).toFixed(0)}%`}>~{v}</span> : v);
           return (
             <tr key={s.name}>
-              <td>{s.name}</td>
-              <td align="right">{s.base_demand_units}</td>
-              <td align="right">{s.base_demand_t}</td>
+              <td>
+                {s.name}
+                {s.lifecycle_frac < 1 && (
+                  <small style={{ color: "#888" }}> ({(s.lifecycle_frac * 100).toFixed(0)}% of trend)</small>
+                )}
+              </td>
+              <td align="right">{est(s.base_demand_units)}</td>
+              <td align="right">{est(s.base_demand_t)}</td>
               <td align="right">{cents(s.ref_price_t_cents)}</td>
-              <td align="right">{s.elasticity.toFixed(2)}</td>
-              <td align="right">{(s.trend_pct ?? 0).toFixed(1)}%</td>
+              <td align="right">{est(s.elasticity.toFixed(2))}</td>
+              <td align="right">{est(`${(s.trend_pct ?? 0).toFixed(1)}%`)}</td>
               <td align="right">{s.sold_units}</td>
               <td><small>{pts.join(", ")}</small></td>
             </tr>
@@ -492,20 +1504,64 @@ function SegmentsTable() {
   );
 }
 
+function CapacityMarketTable() {
+  const { stateDto, setStateDto } = useAppStore();
+  const [wpm, setWpm] = useState(500);
+  const [months, setMonths] = useState(12);
+  if (!stateDto) return null;
+  const sign = async (node: string) => {
+    try { setStateDto(await simBargainContract(node, wpm, months)); } catch (e) { alert(String(e)); }
+  };
+  return (
+    <div data-testid="capacity-market" style={{ margin: "8px 0" }}>
+      <h3>Capacity market</h3>
+      <table style={{ width: "100%" }}>
+        <thead>
+          <tr><th align="left">Node</th><th>Since</th><th>Wafer price</th><th>Status</th><th></th></tr>
+        </thead>
+        <tbody>
+          {stateDto.capacity_market.map((q) => (
+            <tr key={q.node}>
+              <td>{q.node}</td>
+              <td>{q.year_available}</td>
+              <td align="right">{cents(q.price_cents_per_wafer)} ({Math.round(q.price_frac * 100)}%)</td>
+              <td>{q.retired ? "retired" : q.trailing_edge ? `trailing edge${q.retires ? `, retires ${q.retires}` : ""}` : "leading edge"}</td>
+              <td>{q.trailing_edge && <button data-testid={`btn-bargain-${q.node}`} onClick={() => sign(q.node)}>Sign bargain</button>}</td>
+            </tr>
+          ))}
+        </tbody>
+      </table>
+      <label>Wafers/mo: </label>
+      <input type="number" value={wpm} onChange={(e) => setWpm(Number(e.target.value))} />
+      <label> Months: </label>
+      <input type="number" value={months} onChange={(e) => setMonths(Number(e.target.value))} />
+    </div>
+  );
+}
+
 function ContractsTable() {
   const { stateDto } = useAppStore();
   if (!stateDto) return null;
   return (
-    <table style={{ width: "100%", margin: "8px 0" }}>
-      <thead>
-        <tr><th align="left">Foundry</th><th>Wafers/mo</th><th>Billing</th><th>ToP</th><th>Start</th><th>End</th></tr>
-      </thead>
-      <tbody>
-        {stateDto.contracts.map((c, i) => (
-          <tr key={i}><td>{c.foundry_id}</td><td align="right">{c.wafers_per_month}</td><td align="right">{c.billing_cents_per_wafer}c</td><td align="right">{Math.round(c.take_or_pay_frac * 100)}%</td><td>{c.start}</td><td>{c.end}</td></tr>
+    <>
+      <table style={{ width: "100%", margin: "8px 0" }}>
+        <thead>
+          <tr><th align="left">Foundry</th><th>Wafers/mo</th><th>Billing</th><th>ToP</th><th>Comp/miss</th><th>Ramp</th><th>MOQ</th><th>Banked</th><th>Node</th><th>Start</th><th>End</th></tr>
+        </thead>
+        <tbody>
+          {stateDto.contracts.map((c, i) => (
+            <tr key={c.id ?? i}><td>{c.foundry_id}</td><td align="right">{c.wafers_per_month}</td><td align="right">{c.billing_cents_per_wafer}c</td><td align="right">{Math.round(c.take_or_pay_frac * 100)}%</td><td align="right">{c.compensation_cents_per_wafer}c</td><td>{(c.ramp_wafers ?? []).join("→") || "flat"}</td><td align="right">{c.min_order_wafers ?? 0}</td><td align="right">{c.rollover ? c.banked_wafers ?? 0 : "-"}</td><td>{c.node ?? "any"}</td><td>{c.start}</td><td>{c.end}</td></tr>
+          ))}
+        </tbody>
+      </table>
+      <div data-testid="foundry-reliability" style={{ fontSize: 12, opacity: 0.8 }}>
+        {(stateDto.foundries ?? []).map((f) => (
+          <span key={f.id} style={{ marginRight: 12 }}>
+            {f.id}: {Math.round(f.score * 100)}% reliable ({f.misses}/{f.months_observed} missed){f.preferred ? " ★" : ""}
+          </span>
         ))}
-      </tbody>
-    </table>
+      </div>
+    </>
   );
 }
 
@@ -515,11 +1571,11 @@ function QueueTable() {
   return (
     <table style={{ width: "100%", margin: "8px 0" }}>
       <thead>
-        <tr><th align="left">Tech</th><th>Start</th><th>Ready</th><th>Expedite</th><th>Cost</th><th>Perf</th></tr>
+        <tr><th align="left">Product</th><th align="left">Tech</th><th>Start</th><th>Ready</th><th>Expedite</th><th>Cost</th><th>Perf</th><th>Clock</th></tr>
       </thead>
       <tbody>
         {stateDto.pipeline.queue.map((q, i) => (
-          <tr key={i}><td>{q.tech_node}</td><td>{q.start}</td><td>{q.ready}</td><td align="center">{q.expedite ? "Yes" : "No"}</td><td align="right">{cents(q.expedite_cost_cents)}</td><td align="right">{q.perf_index}</td></tr>
+          <tr key={q.id ?? i}><td>{q.name ?? "—"}</td><td>{q.tech_node}</td><td>{q.start}</td><td>{q.ready}</td><td align="center">{q.expedite ? "Yes" : "No"}</td><td align="right">{cents(q.expedite_cost_cents)}</td><td align="right">{q.perf_index}</td><td align="right">{q.clock_ghz != null ? `${q.clock_ghz.toFixed(2)} GHz` : "—"}</td></tr>
         ))}
       </tbody>
     </table>
@@ -558,6 +1614,89 @@ function RechartsLine({ data }: { data: { m: number; revenue: number; profit: nu
   );
 }
 
+function ForecastChart({ history, points }: { history: { m: number; value: number }[]; points: ForecastPointDto[] }) {
+  const fmt = (v: number) => {
+    const a = Math.abs(v);
+    if (a >= 1_000_000) return `$${(v/1_000_000).toFixed(1)}M`;
+    if (a >= 1_000) return `$${(v/1_000).toFixed(1)}k`;
+    return `$${v.toFixed(0)}`;
+  };
+  // Actuals and forecast share the month axis; the band is drawn as lower/upper lines
+  const data = [
+    ...history.map((h) => ({ m: h.m, actual: h.value / 100 })),
+    ...points.map((p) => ({ m: p.month_index, forecast: p.mean / 100, lower: p.lower / 100, upper: p.upper / 100 })),
+  ];
+  return (
+    <div style={{ width: "100%", height: 240 }}>
+      <ResponsiveContainer>
+        <RLineChart data={data}>
+          <XAxis dataKey="m" />
+          <YAxis tickFormatter={(v)=>fmt(v as any)} width={70} />
+          <Tooltip formatter={(value: any) => fmt(value)} />
+          <Legend />
+          <Line type="monotone" dataKey="actual" stroke="#3b82f6" dot={false} />
+          <Line type="monotone" dataKey="forecast" stroke="#6366f1" strokeDasharray="4 4" dot={false} />
+          <Line type="monotone" dataKey="lower" stroke="#a5b4fc" dot={false} />
+          <Line type="monotone" dataKey="upper" stroke="#a5b4fc" dot={false} />
+        </RLineChart>
+      </ResponsiveContainer>
+    </div>
+  );
+}
+
+function ProductionPlanTable() {
+  const { stateDto } = useAppStore();
+  const [plan, setPlan] = useState<ProductionPlanDto | null>(null);
+  useEffect(() => { (async () => { try { setPlan(await simProductionPlan(12)); } catch {} })(); }, [stateDto?.date]);
+  if (!plan?.months.length) return null;
+  const flag = (f: ProductionPlanDto["months"][number]["flag"]) =>
+    !f ? "" : f.kind === "shortage" ? `Short ${f.units}` : `Overbuild ${f.excess_units} (${f.idle_wafers} idle wafers)`;
+  return (
+    <div data-testid="production-plan">
+      <h3>Production plan</h3>
+      {plan.releasable_wafers != null && (
+        <div data-testid="releasable-wafers">Capacity underused for months: {plan.releasable_wafers} wafers/month can be released</div>
+      )}
+      <table style={{ width: "100%", margin: "8px 0" }}>
+        <thead><tr><th align="left">Month</th><th>Demand</th><th>Capacity</th><th>Wafer starts</th><th>End inventory</th><th>Flag</th></tr></thead>
+        <tbody>
+          {plan.months.map((m) => (
+            <tr key={m.date}><td>{m.date}</td><td align="right">{m.demand_units}</td><td align="right">{m.capacity_wafers}</td><td align="right">{m.recommended_wafer_starts}</td><td align="right">{m.projected_inventory}</td><td>{flag(m.flag)}</td></tr>
+          ))}
+        </tbody>
+      </table>
+    </div>
+  );
+}
+
+function ContractAdvicePanel() {
+  const [service, setService] = useState(0.95);
+  const [advice, setAdvice] = useState<ContractAdviceDto | null>(null);
+  const run = async () => {
+    try { setAdvice(await simContractAdvice(12, service)); } catch (e) { alert(String(e)); }
+  };
+  return (
+    <div data-testid="contract-advice">
+      <h3>Contract advice</h3>
+      <label>Service level <input type="number" min={0} max={1} step={0.05} value={service} onChange={(e) => setService(Number(e.target.value))} /></label>
+      <button onClick={run}>Suggest contracts</button>
+      {advice && (
+        <>
+          <div>{advice.met ? "Meets" : "Falls short of"} the target: {(advice.service_level * 100).toFixed(1)}% of demand served, expected cost ${(advice.expected_cost_cents / 100).toLocaleString()}</div>
+          <table style={{ width: "100%", margin: "8px 0" }}>
+            <thead><tr><th align="left">Foundry</th><th>Wafers/mo</th><th>Starts in</th><th>Months</th><th>$/wafer</th></tr></thead>
+            <tbody>
+              {advice.contracts.map((c, i) => (
+                <tr key={i}><td>{c.foundry_id}</td><td align="right">{c.wafers_per_month}</td><td align="right">{c.start_month}</td><td align="right">{c.months}</td><td align="right">{(c.cents_per_wafer / 100).toFixed(2)}</td></tr>
+              ))}
+            </tbody>
+          </table>
+        </>
+      )}
+    </div>
+  );
+}
+
 function ActiveModsTable() {
   const [mods, setMods] = React.useState<{ id: string; kind: string; target: string; start: string; end: string }[]>([]);
   useEffect(() => { (async () => { try { const info = await simBalanceInfo(); setMods(info.active_mods); } catch {} })(); }, []);
@@ -577,11 +1716,50 @@ function ActiveModsTable() {
   );
 }
 
+function ConfigSwapPanel() {
+  const { stateDto, setStateDto } = useAppStore();
+  const [kind, setKind] = useState<ConfigKind>("market");
+  const [yaml, setYaml] = useState("");
+  const [swaps, setSwaps] = useState<ConfigSwapsDto | null>(null);
+  const refresh = async () => { try { setSwaps(await simConfigVersions()); } catch {} };
+  useEffect(() => { refresh(); }, [stateDto?.date]);
+  const swap = async () => {
+    try { setStateDto(await simConfigSwap(kind, yaml)); setYaml(""); await refresh(); } catch (e) { alert(String(e)); }
+  };
+  const rows = [...(swaps?.applied ?? []), ...(swaps?.pending ?? [])];
+  return (
+    <div data-testid="config-swap-panel">
+      <h3>Config versions</h3>
+      <table style={{ width: "100%", margin: "8px 0" }}>
+        <thead><tr><th align="left">Config</th><th>Version</th><th>Hash</th><th>From</th></tr></thead>
+        <tbody>
+          {rows.map((v) => (
+            <tr key={`${v.kind}${v.version}`}><td>{v.kind === "ai" ? "AI" : "Market"}</td><td align="right">{v.version}</td><td><code>{v.hash.slice(0, 8)}</code></td><td>{v.date ?? "next month"}</td></tr>
+          ))}
+        </tbody>
+      </table>
+      <select data-testid="config-swap-kind" value={kind} onChange={(e) => setKind(e.target.value as ConfigKind)}>
+        <option value="market">Market</option>
+        <option value="ai">AI</option>
+      </select>
+      <textarea data-testid="config-swap-yaml" rows={4} style={{ width: "100%" }} placeholder="YAML" value={yaml} onChange={(e) => setYaml(e.target.value)} />
+      <button data-testid="btn-config-swap" disabled={!yaml.trim()} onClick={swap}>Apply next month</button>
+    </div>
+  );
+}
+
 function SaveLoadModal({ onClose }: { onClose: ()=>void }) {
   const [saves, setSaves] = useState<{ id: number; name: string; created_at: string; progress: number }[]>([]);
   const qc = useQueryClient();
   const [name, setName] = useState("");
   const [autosave, setAutosave] = useState(true);
+  const [integrity, setIntegrity] = useState<Record<number, SaveIntegrityDto>>({});
+  const check = async (id: number, repair: boolean) => { try { const r = await simVerifySave(id, repair); setIntegrity((m) => ({ ...m, [id]: r })); } catch (e) { console.error(e); } };
+  const [picked, setPicked] = useState<number[]>([]);
+  const [comparison, setComparison] = useState<RunComparisonDto | null>(null);
+  const pick = (id: number, on: boolean) => { setComparison(null); setPicked((p) => on ? [...p.filter((x) => x !== id), id].slice(-2) : p.filter((x) => x !== id)); };
+  const compare = async () => { try { setComparison(await simCompareSaves(picked[0], picked[1])); } catch (e) { console.error(e); } };
+  const saveName = (id: number) => saves.find((s) => s.id === id)?.name ?? `#${id}`;
   useEffect(() => { (async () => { try { const list = await simListSaves(); setSaves(list as any); } catch (e) { console.error(e); } })(); }, []);
   useEffect(() => { (async () => { try { await simSetAutosave(autosave); } catch (e) { console.error(e); } })(); }, [autosave]);
   return (
@@ -600,18 +1778,71 @@ function SaveLoadModal({ onClose }: { onClose: ()=>void }) {
           </label>
         </div>
         <table style={{ width: "100%" }}>
-          <thead><tr><th align="left">Name</th><th>Created</th><th>Progress</th><th></th></tr></thead>
+          <thead><tr><th align="left">Name</th><th>Created</th><th>Progress</th><th>Integrity</th><th>Compare</th><th></th></tr></thead>
           <tbody>
             {saves.map(s => (
               <tr key={s.id} data-testid="row-save" data-id={s.id}>
                 <td>{s.name}</td>
                 <td>{s.created_at}</td>
                 <td align="center">{s.progress}</td>
+                <td align="center" data-testid="save-integrity">
+                  {integrity[s.id] ? (
+                    integrity[s.id].issues.length === 0 ? (
+                      <span title={integrity[s.id].actions.join("\n")}>OK{integrity[s.id].actions.length > 0 ? " (repaired)" : ""}</span>
+                    ) : (
+                      <span title={integrity[s.id].issues.map((i) => i.kind).join(", ")}>
+                        {integrity[s.id].issues.length} issue(s) <button data-testid="btn-repair" onClick={() => check(s.id, true)}>Repair</button>
+                      </span>
+                    )
+                  ) : (
+                    <button data-testid="btn-verify" onClick={() => check(s.id, false)}>Verify</button>
+                  )}
+                </td>
+                <td align="center"><input data-testid="pick-compare" type="checkbox" checked={picked.includes(s.id)} onChange={(e) => pick(s.id, e.target.checked)} /></td>
                 <td align="right"><button data-testid="btn-load" onClick={async ()=>{ try { await simLoad(s.id); await qc.invalidateQueries({ queryKey: ["sim_state"] }); await qc.invalidateQueries({ queryKey: ["sim_tutorial"] }); onClose(); } catch(e){ console.error(e); } }}>Load</button></td>
             </tr>
             ))}
           </tbody>
         </table>
+        <div style={{ marginTop: 8 }}>
+          <button data-testid="btn-compare" disabled={picked.length !== 2} onClick={compare}>Compare selected</button>
+        </div>
+        {comparison && (
+          <div data-testid="save-comparison" style={{ marginTop: 8 }}>
+            <div>
+              {comparison.divergence
+                ? `Cash diverged at month ${comparison.divergence.month + 1}: ${saveName(picked[0])} $${comparison.divergence.cash_a.toFixed(0)} vs ${saveName(picked[1])} $${comparison.divergence.cash_b.toFixed(0)}`
+                : `No divergence over ${comparison.months.length} common months`}
+            </div>
+            <table style={{ width: "100%" }}>
+              <thead><tr><th align="left">KPI</th><th>{saveName(picked[0])}</th><th>{saveName(picked[1])}</th><th>Δ</th></tr></thead>
+              <tbody>
+                {comparison.finals.map((f) => (
+                  <tr key={f.kpi}><td>{f.kpi}</td><td align="right">{f.a.toFixed(2)}</td><td align="right">{f.b.toFixed(2)}</td><td align="right">{f.delta.toFixed(2)}</td></tr>
+                ))}
+              </tbody>
+            </table>
+            {(() => {
+              const cash = comparison.series.find((x) => x.kpi === "cash");
+              if (!cash) return null;
+              const data = comparison.months.map((m, i) => ({ m: m + 1, a: cash.a[i], b: cash.b[i] }));
+              return (
+                <div style={{ width: "100%", height: 200 }}>
+                  <ResponsiveContainer>
+                    <RLineChart data={data}>
+                      <XAxis dataKey="m" />
+                      <YAxis width={70} />
+                      <Tooltip />
+                      <Legend />
+                      <Line type="monotone" dataKey="a" name={saveName(picked[0])} stroke="#3b82f6" dot={false} />
+                      <Line type="monotone" dataKey="b" name={saveName(picked[1])} stroke="#ef4444" dot={false} />
+                    </RLineChart>
+                  </ResponsiveContainer>
+                </div>
+              );
+            })()}
+          </div>
+        )}
       </div>
     </div>
   );
@@ -5,6 +5,7 @@ import { App } from '../App'
 import { QueryClient } from '@tanstack/react-query'
 vi.mock('@tauri-apps/api/core', () => ({ invoke: vi.fn() }))
 import { getInvokeMock, setupIpcMock, resetIpcMockState } from '../tests/mocks/ipc'
+import { getSimState, setSession, simSessionClone, simSessionList } from '../api'
 
 describe('IPC wiring', () => {
   beforeEach(() => {
@@ -37,6 +38,44 @@ describe('IPC wiring', () => {
     })
   })
 
+  it('R&D: queues a tapeout targeted at a segment', async () => {
+    const qc = new QueryClient({ defaultOptions: { queries: { retry: 0 } } })
+    render(<App client={qc} />)
+    fireEvent.click(await screen.findByTestId('nav-rd'))
+    fireEvent.change(screen.getByTestId('input-tdp'), { target: { value: '20' } })
+    fireEvent.click(await screen.findByTestId('target-server'))
+    expect((await screen.findByTestId('tapeout-misfit')).textContent).toContain('Server CPUs (40–250 W)')
+    expect((screen.getByTestId('btn-tapeout-queue') as HTMLButtonElement).disabled).toBe(true)
+    fireEvent.change(screen.getByTestId('input-tdp'), { target: { value: '120' } })
+    fireEvent.change(screen.getByTestId('input-clock'), { target: { value: '110' } })
+    fireEvent.click(screen.getByTestId('btn-tapeout-queue'))
+    await vi.waitFor(() => {
+      expect(getInvokeMock()).toHaveBeenCalledWith('sim_override', {
+        ovr: expect.objectContaining({ tapeout: expect.objectContaining({ kind: 'CPU', tdp_w: 120, target_segments: ['server'], clock_frac: 1.1 }) }),
+      })
+    })
+  })
+
+  it('R&D: limits tapeout targets to the product kind', async () => {
+    const qc = new QueryClient({ defaultOptions: { queries: { retry: 0 } } })
+    render(<App client={qc} />)
+    fireEvent.click(await screen.findByTestId('nav-rd'))
+    fireEvent.click(await screen.findByTestId('target-desktop'))
+    fireEvent.change(screen.getByTestId('select-product-kind'), { target: { value: 'GPU' } })
+    expect(screen.getByTestId('product-kind-economics').textContent).toContain('cost 120%, perf 110%')
+    expect((screen.getByTestId('target-server') as HTMLInputElement).disabled).toBe(true)
+    expect(screen.getByTestId('tapeout-off-kind').textContent).toContain('GPU products cannot sell into Desktop CPUs')
+    expect((screen.getByTestId('btn-tapeout-queue') as HTMLButtonElement).disabled).toBe(true)
+    fireEvent.click(screen.getByTestId('target-desktop'))
+    fireEvent.click(screen.getByTestId('target-graphics'))
+    fireEvent.click(screen.getByTestId('btn-tapeout-queue'))
+    await vi.waitFor(() => {
+      expect(getInvokeMock()).toHaveBeenCalledWith('sim_override', {
+        ovr: expect.objectContaining({ tapeout: expect.objectContaining({ kind: 'GPU', target_segments: ['graphics'] }) }),
+      })
+    })
+  })
+
   it('Capacity: requests contract via sim_override', async () => {
     const qc = new QueryClient({ defaultOptions: { queries: { retry: 0 } } })
     render(<App client={qc} />)
@@ -105,4 +144,47 @@ describe('IPC wiring', () => {
       expect(getInvokeMock()).toHaveBeenCalledWith('sim_load', { save_id: expect.any(Number) })
     })
   })
+
+  it('Save/Load: verifies a save on request', async () => {
+    const qc = new QueryClient({ defaultOptions: { queries: { retry: 0 } } })
+    render(<App client={qc} />)
+    fireEvent.click(screen.getByTestId('btn-open-save'))
+    fireEvent.click(screen.getByTestId('btn-save'))
+    const row = await screen.findByTestId('row-save')
+    fireEvent.click(within(row).getByTestId('btn-verify'))
+    await vi.waitFor(() => {
+      expect(getInvokeMock()).toHaveBeenCalledWith('sim_verify_save', { save_id: expect.any(Number), repair: false })
+    })
+    expect(await within(row).findByText('OK')).toBeTruthy()
+  })
+
+  it('Save/Load: compares two saves', async () => {
+    const qc = new QueryClient({ defaultOptions: { queries: { retry: 0 } } })
+    render(<App client={qc} />)
+    fireEvent.click(screen.getByTestId('btn-open-save'))
+    fireEvent.click(screen.getByTestId('btn-save'))
+    await screen.findByTestId('row-save')
+    fireEvent.click(screen.getByTestId('btn-save'))
+    await vi.waitFor(() => expect(screen.getAllByTestId('row-save').length).toBeGreaterThanOrEqual(2))
+    const picks = screen.getAllByTestId('pick-compare')
+    fireEvent.click(picks[0])
+    fireEvent.click(picks[1])
+    fireEvent.click(screen.getByTestId('btn-compare'))
+    await vi.waitFor(() => {
+      expect(getInvokeMock()).toHaveBeenCalledWith('sim_compare_saves', { save_a: expect.any(Number), save_b: expect.any(Number) })
+    })
+    expect(await screen.findByText(/Cash diverged at month 2/)).toBeTruthy()
+  })
+
+  it('Sessions: threads the active session id through commands', async () => {
+    await simSessionClone('b')
+    setSession('b')
+    try {
+      await getSimState()
+      expect(getInvokeMock()).toHaveBeenCalledWith('sim_state', { session_id: 'b' })
+      expect(await simSessionList()).toEqual(['default', 'b'])
+    } finally {
+      setSession(undefined)
+    }
+  })
 })
@@ -1,4 +1,16 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+// Game session targeted by sim_* commands; undefined uses the backend's default session
+let currentSession: string | undefined;
+
+export function setSession(id?: string) {
+  currentSession = id;
+}
+
+export function getSession() {
+  return currentSession;
+}
 
 // Unified, safe IPC invoker with robust error reporting
 export async function invokeSafe<T>(cmd: string, payload?: Record<string, any>): Promise<T> {
@@ -6,6 +18,10 @@ export async function invokeSafe<T>(cmd: string, payload?: Record<string, any>):
     // Support optional global injection for tests/manual runs
     const anyWin = (window as any);
     const inv: typeof invoke = anyWin?.__tauriInvoke ?? invoke;
+    // Thread the active session id unless the caller set one explicitly
+    if (currentSession !== undefined && !(payload && "session_id" in payload)) {
+      payload = { ...payload, session_id: currentSession };
+    }
     return (await inv(cmd, payload)) as T;
   } catch (err: any) {
     const msg = typeof err === "string" ? err : err?.message ?? JSON.stringify(err);
@@ -30,6 +46,8 @@ export type SimSnapshot = {
   output_units: number;
   defect_units: number;
   inventory_units: number;
+  // Hex checksum of the deterministic state; differs as soon as two runs diverge
+  state_hash: string;
 };
 
 export type PlanSummary = { decisions: string[]; expected_score: number };
@@ -42,8 +60,151 @@ export async function simPlanQuarter() {
   return invokeSafe<PlanSummary>("sim_plan_quarter");
 }
 
+export type PlanTraceCandidate = {
+  parent: number;
+  action: unknown;
+  score: number | null;
+  kept: boolean;
+  pruned: "LiquidityFloor" | "BeamWidth" | null;
+};
+
+export type PlanTrace = {
+  quarters: {
+    month_index: number;
+    beam: { score: number; cash_usd: number; share: number; asp_usd: number; decisions: unknown[] }[];
+    candidates: PlanTraceCandidate[];
+  }[];
+  expected_score: number;
+};
+
+export async function simPlanTrace() {
+  return invokeSafe<PlanTrace>("sim_plan_trace");
+}
+
+export type LedgerCategory = "revenue" | "cogs" | "contracts" | "rnd" | "expedite" | "loans" | "interest" | "insurance" | "grants" | "fines" | "logistics" | "relationships" | "patents" | "tax" | "decisions" | "asset_sales" | "research" | "warranty" | "quality_control" | "idle_capacity" | "adjustments";
+
+// "What happened" digest returned with each quarter tick
+export type TurnSummaryDto = {
+  from: string;
+  to: string;
+  demand: { segment: string; demand_from: number; demand_to: number; ref_price_from_cents: number; ref_price_to_cents: number }[];
+  competitors: { share_from: number; share_to: number; cash_delta_cents: [string, number][] };
+  contracts_started: { id: string; foundry_id: string; wafers_per_month: number; date: string }[];
+  contracts_ended: { id: string; foundry_id: string; wafers_per_month: number; date: string }[];
+  products_released: string[];
+  events: { date: string; text: string }[];
+  cash: { start_cents: number; end_cents: number; items: [LedgerCategory, number][] };
+};
+
 export async function simTickQuarter() {
-  return invokeSafe<SimSnapshot>("sim_tick_quarter");
+  return invokeSafe<SimSnapshot & { summary: TurnSummaryDto }>("sim_tick_quarter");
+}
+
+// Long background run; resolves with the snapshot after the last month run (flagged when
+// simRunCancel stopped it early), progress arrives via onRunProgress
+export type RunProgress = {
+  session_id: string;
+  months_done: number;
+  months_total: number;
+  pct: number;
+  date: string;
+};
+
+export async function simRun(months: number) {
+  return invokeSafe<SimSnapshot & { months_done: number; cancelled: boolean; paused: boolean }>("sim_run", { months });
+}
+
+export async function simRunCancel() {
+  return invokeSafe<void>("sim_run_cancel");
+}
+
+export async function onRunProgress(cb: (p: RunProgress) => void): Promise<UnlistenFn> {
+  return listen<RunProgress>("sim-run-progress", (e) => cb(e.payload));
+}
+
+// Auto-advance ("watch the sim run"): months tick on their own at ticks_per_second until stopped;
+// actions taken meanwhile apply between months, and a failed one arrives as a tick with an error
+export type AutoTick = {
+  session_id: string;
+  date?: string | null;
+  snapshot?: SimSnapshot | null;
+  error?: string | null;
+};
+
+export async function simAutoStart(ticksPerSecond: number) {
+  return invokeSafe<void>("sim_auto_start", { ticksPerSecond });
+}
+
+export async function simAutoStop() {
+  return invokeSafe<SimStateDto>("sim_auto_stop");
+}
+
+export async function onAutoTick(cb: (t: AutoTick) => void): Promise<UnlistenFn> {
+  return listen<AutoTick>("sim-auto-tick", (e) => cb(e.payload));
+}
+
+// Auto-advance stopped running months until the pending decisions are resolved with simDecide
+export type AutoPaused = { session_id: string; pending_decisions: DecisionDto[] };
+
+export async function onAutoPaused(cb: (p: AutoPaused) => void): Promise<UnlistenFn> {
+  return listen<AutoPaused>("sim-auto-paused", (e) => cb(e.payload));
+}
+
+// Lockstep: a month's player commands plus the state hash they were issued against; every
+// instance stepping the same frames stays in sync, and a mismatched hash is refused as a desync
+export type PlayerCommand =
+  | { type: "price_delta"; frac: number }
+  | { type: "rd_delta"; cents: number }
+  | { type: "capacity_request"; wafers_per_month: number; months: number; billing_cents_per_wafer?: number | null; take_or_pay_frac?: number | null; ramp_wafers?: number[] | null; min_order_wafers?: number | null; rollover?: boolean }
+  | {
+      type: "tapeout";
+      design: {
+        kind: string; perf_index: number; die_area_mm2: number; tech_node: string;
+        microarch: { ipc_index: number; pipeline_depth: number; cache_l1_kb: number; cache_l2_mb: number; chiplet: boolean };
+        tdp_w: number; target_segments: string[]; clock_frac: number; expedite: boolean; expedite_months?: number | null; family?: string | null;
+      };
+    }
+  | { type: "draw_credit"; cents: number }
+  | { type: "repay_credit"; cents: number }
+  | { type: "regional_price"; region: string; price_cents?: number | null }
+  | { type: "wafer_allocation"; plan: Record<string, number> }
+  | { type: "oem_deal"; oem_id: string }
+  | { type: "oem_terms"; oem_id: string; concession_frac: number; exclusive: boolean }
+  | { type: "oem_invest"; oem_id: string; cents: number }
+  | { type: "roadmap_announce"; node: string; min_perf_index: number; due: string }
+  | { type: "rnd_budgets"; process_cents: number; design_cents: number }
+  | { type: "file_patent"; feature: string }
+  | { type: "bargain_contract"; node: string; wafers_per_month: number; months: number }
+  | { type: "console_bid"; price_frac: number }
+  | { type: "license_out"; terms: LicenseTermsDto };
+export type SeatCommand = { company: number; command: PlayerCommand };
+export type CommandFrame = { month_index: number; state_hash: string; commands: SeatCommand[] };
+export type LockstepOutcome = { month_index: number; state_hash: string; rejected: [number, string][] };
+
+export async function simLockstepFrame(commands: SeatCommand[]) {
+  return invokeSafe<CommandFrame>("sim_lockstep_frame", { commands });
+}
+
+export async function simLockstepStep(frame: CommandFrame) {
+  return invokeSafe<LockstepOutcome>("sim_lockstep_step", { frame });
+}
+
+// Read-only view for a second window or stream overlay, as of the last tick; reads are rate-limited
+export type SpectatorKpi = { date: string; month_index: number; state_hash: string; kpi: SimStateDto["kpi"] };
+export type SpectatorView = SpectatorKpi & {
+  companies: SimStateDto["companies"];
+  segments: SimStateDto["segments"];
+  regions: RegionDto[];
+  reputation: number;
+  news: SimStateDto["news"];
+};
+
+export async function spectatorState() {
+  return invokeSafe<SpectatorView>("spectator_state");
+}
+
+export async function spectatorKpi() {
+  return invokeSafe<SpectatorKpi>("spectator_kpi");
 }
 
 export type OverrideReq = {
@@ -54,12 +215,27 @@ export type OverrideReq = {
     months: number;
     billing_cents_per_wafer?: number;
     take_or_pay_frac?: number;
+    // Committed wafers for the first months; omitted takes the going ramp
+    ramp_wafers?: number[];
+    min_order_wafers?: number;
+    // Bank paid-for wafers left unused as credits
+    rollover?: boolean;
   };
   tapeout?: {
     perf_index: number;
     die_area_mm2: number;
     tech_node: string;
     expedite?: boolean;
+    // Months to pull the tapeout in (1-3); quoted by simExpediteQuote
+    expedite_months?: number;
+    kind?: ProductKind;
+    microarch?: { ipc_index: number; pipeline_depth: number; cache_l1_kb: number; cache_l2_mb: number; chiplet: boolean };
+    tdp_w?: number;
+    target_segments?: string[];
+    // Clock bin as a multiple of the node's baseline frequency
+    clock_frac?: number;
+    // Product family id; the design becomes the family's next generation
+    family?: string;
   };
 };
 
@@ -78,7 +254,7 @@ export type SimStateDto = {
   date: string;
   month_index: number;
   companies: { name: string; cash_cents: number; debt_cents: number }[];
-  segments: { name: string; base_demand_units: number; price_elasticity: number; base_demand_t: number; ref_price_t_cents: number; elasticity: number; trend_pct: number; sold_units: number }[];
+  segments: { name: string; base_demand_units: number; price_elasticity: number; base_demand_t: number; ref_price_t_cents: number; elasticity: number; trend_pct: number; sold_units: number; regions: RegionTrendDto[]; lifecycle_frac: number; estimate_error_frac?: number }[];
   pricing: { asp_cents: number; unit_cost_cents: number };
   kpi: {
     cash_cents: number;
@@ -90,35 +266,599 @@ export type SimStateDto = {
     rd_pct: number;
     output_units: number;
     inventory_units: number;
+    // Last month's share of available wafers whose output sold
+    utilization?: number | null;
   };
   contracts: {
+    // Stable id, kept across saves
+    id?: string;
     foundry_id: string;
     wafers_per_month: number;
     billing_cents_per_wafer: number;
     take_or_pay_frac: number;
     start: string;
     end: string;
+    compensation_cents_per_wafer: number;
+    node?: string | null;
+    ramp_wafers?: number[];
+    min_order_wafers?: number;
+    rollover?: boolean;
+    banked_wafers?: number;
   }[];
+  // Learned delivery reliability per foundry; `preferred` is where new contracts are sourced
+  foundries: { id: string; score: number; months_observed: number; misses: number; preferred: boolean }[];
   pipeline: {
     queue: {
+      // Stable id, shared with the product once released
+      id?: string;
       tech_node: string;
       start: string;
       ready: string;
       expedite: boolean;
       expedite_cost_cents: number;
       perf_index: number;
+      clock_ghz: number | null;
+      // Marketing name for a product in a family, e.g. "Falcon II"
+      name?: string | null;
     }[];
     released: { tech_node: { 0: string } }[] | any[];
   };
   ai_plan: PlanSummary;
   config: { finance: any; product_cost: { usable_die_area_mm2: number; yield_overhead_frac: number } };
   campaign?: { status: string; goals: { kind: string; desc: string; progress: number; deadline: string; done: boolean }[]; start: string; end: string; difficulty?: string } | null;
+  pending_decisions: DecisionDto[];
+  distress: {
+    active: boolean;
+    months_left: number;
+    loan_offer_cents?: number | null;
+    failed: boolean;
+    // Set while the cash forecast's lower band breaches the distress threshold
+    warning?: { months_ahead: number; projected_cash_cents: number; lower_cash_cents: number } | null;
+  };
+  // 12-month KPI forecasts with confidence bands; null until a few months are recorded
+  forecast?: ForecastDto | null;
+  finance: FinanceDto;
+  news: { id?: string; date: string; headline: string }[];
+  grants: GrantDto[];
+  regions: RegionDto[];
+  oems: OemDto[];
+  reputation: number;
+  roadmap: RoadmapDto[];
+  reviews?: LaunchReviewDto[];
+  rnd: RnDTracksDto;
+  quality: QualityDto;
+  products?: ProductOnSaleDto[];
+  patents: PatentsDto;
+  capacity_market: NodeCapacityQuoteDto[];
+  consoles: ConsolesDto;
+  wafer_allocation: WaferAllocationDto;
+  company_exits: CompanyExitDto[];
+  entrants: EntrantDto[];
+  licenses: LicenseDealDto[];
+  clearing: SegmentClearingDto[];
+  market_research: MarketResearchDto;
 };
 
+// Market clearing: a segment's demand split across companies by attractiveness and stock
+export type ClearedSaleDto = { company: string; attractiveness: number; offered_units: number; sold_units: number };
+export type SegmentClearingDto = { id: string; demand_units: number; sales: ClearedSaleDto[]; unmet_units: number };
+
+// A rival that entered mid-game, drawn in by a segment's lasting margins
+export type EntrantDto = { date: string; company: string; persona: string; segment: string; node: string };
+
+// A rival's bankruptcy: the first restructures it, the second takes it out of the market
+export type CompanyExitDto = {
+  date: string;
+  company: string;
+  kind:
+    | { type: "restructured"; debt_written_off_cents: number; capacity_sold_cents: number }
+    | { type: "exited"; auction_cents: number; share: number };
+};
+
+// Regional markets: per-region demand within a segment, and last month's shipments per region
+export type RegionTrendDto = { id: string; base_demand_t: number; elasticity: number; demand_units: number; blocked: boolean };
+export type RegionDto = {
+  id: string;
+  name: string;
+  demand_units: number;
+  sold_units: number;
+  share: number;
+  price_cents: number;
+  logistics_cents: number;
+  price_override_cents?: number | null;
+};
+// Charge a region something other than the global ASP; null clears the override
+export async function simRegionalPrice(region: string, priceCents: number | null) {
+  return invokeSafe<SimStateDto>("sim_regional_price", { region, priceCents });
+}
+
+// Major OEM customer: the relationship score (0-100) gates its volume deal and design wins
+export type OemDto = {
+  id: string;
+  name: string;
+  score: number;
+  concession_frac: number;
+  exclusive: boolean;
+  deal?: { start: string; end: string; units_per_month: number; price_cents: number; committed_units: number; delivered_units: number } | null;
+  deals_completed: number;
+  last_fill?: number | null;
+  design_wins: number;
+  last_design_win?: string | null;
+  invested_cents: number;
+  terms?: {
+    initial_score: number;
+    volume_deal: { min_score: number; units_per_month: number; price_frac: number; months: number };
+    design_win: { min_score: number; appeal_boost: number; cooldown_months: number };
+  } | null;
+};
+export async function simOemDeal(oemId: string) {
+  return invokeSafe<SimStateDto>("sim_oem_deal", { oemId });
+}
+export async function simOemTerms(oemId: string, concessionFrac: number, exclusive: boolean) {
+  return invokeSafe<SimStateDto>("sim_oem_terms", { oemId, concessionFrac, exclusive });
+}
+export async function simOemInvest(oemId: string, cents: number) {
+  return invokeSafe<SimStateDto>("sim_oem_invest", { oemId, cents });
+}
+
+// Publicly announced product: hitting the date builds reputation and OEM trust, slipping costs both
+export type RoadmapDto = {
+  id: number;
+  node: string;
+  min_perf_index: number;
+  due: string;
+  announced: string;
+  status: "pending" | "hit" | "slipped";
+  delivered?: string | null;
+};
+// Press reception of a release; the appeal swing fades out over a few months
+export type LaunchReviewDto = {
+  date: string;
+  product: string;
+  score: number;
+  inputs: { perf_rel: number; price: number; quality: number };
+  appeal_swing: number;
+};
+export async function simRoadmapAnnounce(node: string, minPerfIndex: number, due: string) {
+  return invokeSafe<SimStateDto>("sim_roadmap_announce", { node, minPerfIndex, due });
+}
+
+// R&D split: process (yield ramp, early nodes for fab owners) vs design (perf/IPC, shorter tapeouts)
+export type RnDTracksDto = {
+  process_budget_cents: number;
+  design_budget_cents: number;
+  process: number;
+  design: number;
+  owns_fab: boolean;
+};
+// Quality control: inspection and test spend cuts scrapped dies and defect-driven field failures
+export type QualityDto = {
+  budget_cents: number;
+  spent_cents: number;
+  defects_avoided: number;
+  defect_frac: number;
+  field_failure_factor: number;
+};
+// Product generations on sale: a successor absorbs its predecessor's demand while the old part's price erodes
+export type ProductOnSaleDto = {
+  product: string;
+  line: string;
+  launched: string;
+  succeeded: string | null;
+  demand_share: number;
+  price_frac: number;
+  last_units: number;
+  last_revenue_cents: number;
+  units: number;
+  revenue_cents: number;
+};
+export async function simQcBudget(cents: number) {
+  return invokeSafe<SimStateDto>("sim_qc_budget", { cents });
+}
+// Developer console (builds with the debug-console feature only); returns the command output
+export async function simDebug(cmd: string) {
+  return invokeSafe<string>("sim_debug", { cmd });
+}
+export type PricingRuleKind =
+  | { type: "min_margin"; frac: number }
+  | { type: "undercut_ref"; segment: string; frac: number }
+  | { type: "raise_on_utilization"; above: number; step_frac: number }
+  | { type: "cut_on_inventory"; months: number; step_frac: number };
+export type PricingRuleDto = PricingRuleKind & { id: string; priority: number; enabled: boolean };
+export type RuleOutcome = { outcome: "set" } | { outcome: "overridden"; by: string } | { outcome: "floored" };
+export type PricingRuleFiringDto = RuleOutcome & { date: string; rule: string; from_cents: number; to_cents: number };
+export type PricingRulesDto = { rules: PricingRuleDto[]; firings: PricingRuleFiringDto[] };
+// Standing pricing rules and which of them fired, oldest first
+export async function simPricingRules() {
+  return invokeSafe<PricingRulesDto>("sim_pricing_rules");
+}
+export async function simPricingRulesSet(rules: PricingRuleDto[]) {
+  return invokeSafe<SimStateDto>("sim_pricing_rules_set", { rules });
+}
+export async function simRndBudgets(processCents: number, designCents: number) {
+  return invokeSafe<SimStateDto>("sim_rnd_budgets", { processCents, designCents });
+}
+
+// Patents: filings count toward portfolio strength once granted; strength decides infringement cases
+export type PatentCaseDto = {
+  id: number;
+  rival: string;
+  feature: string;
+  role: "plaintiff" | "defendant";
+  opened: string;
+  verdict_due: string;
+  outcome?: "won" | "settled" | "lost" | null;
+  royalty_until?: string | null;
+  cash_cents: number;
+};
+export type PatentsDto = {
+  filings: { feature: string; filed: string; grant: string; granted: boolean }[];
+  cases: PatentCaseDto[];
+  next_case_id: number;
+  strength: number;
+  filing_cost_cents: number;
+  rivals: { id: string; name: string; strength: number; patents: string[]; products: string[]; units_per_month: number }[];
+};
+export async function simPatentFile(feature: string) {
+  return invokeSafe<SimStateDto>("sim_patent_file", { feature });
+}
+
+// Market research: paid reports on a segment's true elasticity, scheduled events and rival moves
+export type CompetitorHintDto =
+  | { kind: "strength"; date: string; now: number; ahead: number }
+  | { kind: "entrant_pressure"; quarters: number; needed: number }
+  | { kind: "rival_strained"; company: string; cash_cents: number };
+export type MarketResearchReportDto = {
+  segment: string;
+  date: string;
+  until: string;
+  base_demand_units: number;
+  ref_price_cents: number;
+  elasticity: number;
+  trend_pct: number;
+  upcoming_events: { start: string; months: number; base_demand_pct?: number | null; ref_price_pct?: number | null; elasticity_delta?: number | null }[];
+  competitor_hints: CompetitorHintDto[];
+};
+export type MarketResearchDto = {
+  subscriptions: { segment: string; start: string; end: string }[];
+  cents_per_month: number;
+  max_months: number;
+  reports: MarketResearchReportDto[];
+};
+export async function simMarketResearchBuy(segment: string, months: number) {
+  return invokeSafe<SimStateDto>("sim_market_research_buy", { segment, months });
+}
+
+// Capacity market: older nodes get cheaper once a newer node is out; foundries retire very old ones
+export type NodeCapacityQuoteDto = {
+  node: string;
+  year_available: number;
+  price_cents_per_wafer: number;
+  price_frac: number;
+  trailing_edge: boolean;
+  retired: boolean;
+  retires?: string | null;
+};
+export async function simBargainContract(node: string, wafersPerMonth: number, months: number) {
+  return invokeSafe<SimStateDto>("sim_bargain_contract", { node, wafersPerMonth, months });
+}
+
+// Console generations: each cycle's RFQ wins a volume annuity; losing closes part of the console segment
+export type ConsolesDto = {
+  rfq?: { generation: number; closes: string; bid_price_frac?: number | null } | null;
+  annuity?: { start: string; end: string; units_per_month: number; price_cents: number; committed_units: number; delivered_units: number } | null;
+  lockout_until?: string | null;
+  awards: { generation: number; date: string; won: boolean }[];
+  cycle?: { segment: string; oem: string; cycle_months: number; rfq_months: number; units_per_month: number; min_price_frac: number; lockout_frac: number } | null;
+  next_rfq?: string | null;
+  score: number;
+  needed: number;
+};
+export async function simConsoleBid(priceFrac: number) {
+  return invokeSafe<SimStateDto>("sim_console_bid", { priceFrac });
+}
+
+// Technology licensing: a node we shipped on or a patented feature, sold to a rival for a per-unit royalty
+export type LicensedIpDto = { type: "node"; node: string } | { type: "microarch"; feature: string };
+export type LicenseTermsDto = { ip: LicensedIpDto; licensee: string; royalty_cents_per_unit: number; months: number };
+export type LicenseDealDto = { terms: LicenseTermsDto; signed: string; until: string; royalties_cents: number };
+export async function simLicenseQuote(ip: LicensedIpDto, licensee: string) {
+  return invokeSafe<number>("sim_license_quote", { ip, licensee });
+}
+export async function simLicenseOffer(terms: LicenseTermsDto) {
+  return invokeSafe<SimStateDto>("sim_license_offer", { terms });
+}
+
+// Wafer allocation: share of wafers per product line (latest release of each kind); automatic when no plan is set
+export type AllocationLineDto = { line: string; product?: string | null; planned_share?: number | null; share: number; wafers: number; units: number };
+export type WaferAllocationDto = { automatic: boolean; lines: AllocationLineDto[] };
+export async function simWaferAllocation(plan: Record<string, number>) {
+  return invokeSafe<SimStateDto>("sim_wafer_allocation", { plan });
+}
+
+// Subsidy grant accepted through a decision event; clawed back if the obligation is missed
+export type GrantDto = {
+  event_id: string;
+  name: string;
+  accepted: string;
+  deadline: string;
+  terms: {
+    cash_cents: number;
+    tax_credit_cents_per_month: number;
+    clawback_frac: number;
+    obligation: { months: number; min_wafers_per_month: number; foundries: string[]; node?: string | null };
+  };
+  received_cents: number;
+  clawback_cents: number;
+  status: "active" | "fulfilled" | "clawed_back";
+};
+
+// Credit rating (re-rated quarterly) and the borrowing terms it sets
+export type FinanceDto = {
+  debt_cents: number;
+  rating: string;
+  spread_bps: number;
+  rate_on_new_debt: number;
+  credit_line_cents: number;
+  drawn_cents: number;
+  available_cents: number;
+  leverage: number;
+  interest_coverage: number | null;
+  volatility: number;
+  deposit_rate: number;
+  // Rate on debt not booked as a loan
+  debt_rate: number;
+  interest_income_cents: number;
+  interest_expense_cents: number;
+  warranty?: WarrantyReportDto;
+};
+
+// Warranty reserve: accrued on revenue from launch quality history, drawn by claims
+export type WarrantyReportDto = {
+  balance_cents: number;
+  outstanding_cents: number;
+  // Balance over outstanding claims; null with nothing under warranty
+  adequacy: number | null;
+  accrual_frac: number;
+  accrued_cents: number;
+  claims_cents: number;
+  shortfall_cents: number;
+  last_accrual_cents: number;
+  last_claims_cents: number;
+};
+
+export type ForecastPointDto = { month_index: number; mean: number; lower: number; upper: number };
+
+export type ForecastDto = {
+  demand_units: ForecastPointDto[];
+  revenue_cents: ForecastPointDto[];
+  cash_cents: ForecastPointDto[];
+};
+
+export type DecisionDto = {
+  event_id: string;
+  name: string;
+  prompt: string;
+  raised: string;
+  default_choice: string;
+  choices: { id: string; label: string }[];
+};
+
+// Resolve a pending decision event; ticking stays paused until all are resolved
+export async function simDecide(event_id: string, choice: string) {
+  return invokeSafe<SimStateDto>("sim_decide", { event_id, choice });
+}
+
+// Accept the emergency loan offered while the company is in distress
+export async function simAcceptLoan() {
+  return invokeSafe<SimStateDto>("sim_accept_loan");
+}
+
+export async function simCreditDraw(cents: number) {
+  return invokeSafe<SimStateDto>("sim_credit_draw", { cents });
+}
+
+export async function simCreditRepay(cents: number) {
+  return invokeSafe<SimStateDto>("sim_credit_repay", { cents });
+}
+
+// Promotional calendar: time-boxed discounts per market segment
+export type PromoDto = {
+  id: number;
+  segment_id: string;
+  discount_frac: number;
+  reach_frac: number;
+  start: string;
+  end: string;
+  active: boolean;
+};
+export async function simPromoList() {
+  return invokeSafe<PromoDto[]>("sim_promo_list");
+}
+
+export async function simPromoSchedule(
+  segment_id: string,
+  discount_frac: number,
+  months: number,
+  opts?: { reach_frac?: number; start?: string },
+) {
+  return invokeSafe<PromoDto[]>("sim_promo_schedule", {
+    segment_id,
+    discount_frac,
+    months,
+    reach_frac: opts?.reach_frac,
+    start: opts?.start,
+  });
+}
+
+export async function simPromoCancel(id: number) {
+  return invokeSafe<PromoDto[]>("sim_promo_cancel", { id });
+}
+
+// Quarterly budgets: spend vs budget per category, and the board's confidence in management
+export type BudgetCategory = "rnd" | "marketing" | "capacity" | "opex";
+export type BudgetMode = "warn" | "enforce";
+export type BudgetVarianceDto = {
+  quarter: string;
+  category: BudgetCategory;
+  budget_cents: number;
+  actual_cents: number;
+  variance_cents: number;
+};
+export type BudgetDto = {
+  mode: BudgetMode;
+  board_confidence: number;
+  quarter_to_date: BudgetVarianceDto[];
+  history: BudgetVarianceDto[];
+};
+export async function simBudget() {
+  return invokeSafe<BudgetDto>("sim_budget");
+}
+
+// Pass a category to set (or with null cents, clear) its budget; pass a mode to switch warn/enforce
+export async function simBudgetSet(category: BudgetCategory | null, cents: number | null, mode?: BudgetMode) {
+  return invokeSafe<BudgetDto>("sim_budget_set", { category, cents, mode });
+}
+
+// Insurance: products from insurance.yaml, held policies and the claims they paid
+export type InsuranceRisk = "fab_disruption" | "inventory_write_off";
+export type InsuranceProductDto = {
+  id: string;
+  name: string;
+  risk: InsuranceRisk;
+  premium_cents_per_month: number;
+  deductible_cents: number;
+  cover_frac: number;
+  limit_cents: number;
+};
+export type InsuranceClaimDto = { date: string; product_id: string; loss_cents: number; payout_cents: number };
+export type InsuranceDto = {
+  products: InsuranceProductDto[];
+  policies: { product_id: string; start: string }[];
+  premiums_paid_cents: number;
+  claims: InsuranceClaimDto[];
+};
+export async function simInsurance() {
+  return invokeSafe<InsuranceDto>("sim_insurance");
+}
+export async function simInsuranceBuy(productId: string) {
+  return invokeSafe<InsuranceDto>("sim_insurance_buy", { productId });
+}
+export async function simInsuranceCancel(productId: string) {
+  return invokeSafe<InsuranceDto>("sim_insurance_cancel", { productId });
+}
+
+// Export controls: restrictions in force, comply/defy choice per restriction, compliant variants and fines
+export type ExportPolicy = "comply" | "defy";
+export type ExportRestrictionDto = {
+  id: string;
+  name: string;
+  start: string;
+  end: string;
+  regions: string[];
+  nodes: string[];
+  fine_cents_per_unit: number;
+  variant_cost_cents: number;
+  variant_months: number;
+};
+export type ExportControlsDto = {
+  active: ExportRestrictionDto[];
+  policies: Record<string, ExportPolicy>;
+  variants: { restriction_id: string; node: string; ready: string }[];
+  fines: { date: string; restriction_id: string; units: number; cents: number }[];
+};
+export async function simExportControls() {
+  return invokeSafe<ExportControlsDto>("sim_export_controls");
+}
+export async function simExportPolicy(restrictionId: string, policy: ExportPolicy) {
+  return invokeSafe<ExportControlsDto>("sim_export_policy", { restrictionId, policy });
+}
+export async function simExportVariant(restrictionId: string) {
+  return invokeSafe<ExportControlsDto>("sim_export_variant", { restrictionId });
+}
+
+// MRP-lite production plan: recommended wafer starts and shortage/overbuild flags per month
+export type PlanFlag =
+  | { kind: "shortage"; units: number }
+  | { kind: "overbuild"; excess_units: number; idle_wafers: number };
+export type ProductionPlanDto = {
+  months: {
+    date: string;
+    demand_units: number;
+    capacity_wafers: number;
+    recommended_wafer_starts: number;
+    projected_inventory: number;
+    flag: PlanFlag | null;
+  }[];
+  // After months of low utilization: wafers idle in every planned month
+  releasable_wafers?: number | null;
+};
+export async function simProductionPlan(horizon_months?: number) {
+  return invokeSafe<ProductionPlanDto>("sim_production_plan", { horizon_months });
+}
+
+// Advisory contract portfolio: new foundry contracts that meet a service level at least expected cost
+export type ProposedContractDto = { foundry_id: string; wafers_per_month: number; start_month: number; months: number; cents_per_wafer: number };
+export type ContractAdviceDto = { contracts: ProposedContractDto[]; expected_cost_cents: number; service_level: number; met: boolean };
+export async function simContractAdvice(horizon_months?: number, service_level?: number) {
+  return invokeSafe<ContractAdviceDto>("sim_contract_advice", { horizon_months, service_level });
+}
+
+// Gantt view of in-flight work, rebuilt from the live subsystems on every call
+export type ScheduleKind = "tapeout" | "patent" | "variant" | "legal_case" | "contract" | "research" | "roadmap";
+export type ScheduleItemDto = { id: string; kind: ScheduleKind; label: string; start: string; end: string; progress: number; expedited: boolean };
+export type WorkScheduleDto = { date: string; items: ScheduleItemDto[] };
+export async function simSchedule() {
+  return invokeSafe<WorkScheduleDto>("sim_schedule", {});
+}
+
+// Tapeout expedite levels: cost grows with the square of months saved; deeper levels need contracted volume
+export type ExpediteOptionDto = { months: number; cost_cents: number; available: boolean; reason?: string | null };
+export async function simExpediteQuote(techNode: string) {
+  return invokeSafe<ExpediteOptionDto[]>("sim_expedite_quote", { techNode });
+}
+
+// Sensitivity analysis: short dry-runs with one lever moved at a time, for a tornado chart
+export type SensitivityKpisDto = { revenue_cents: number; profit_cents: number; cash_cents: number; market_share: number };
+export type SensitivityReportDto = {
+  months: number;
+  base: SensitivityKpisDto;
+  levers: { lever: "asp" | "capacity" | "rnd"; step_frac: number; down: SensitivityKpisDto; up: SensitivityKpisDto; cash_swing_cents: number }[];
+};
+export async function simSensitivity(months?: number) {
+  return invokeSafe<SensitivityReportDto>("sim_sensitivity", { months });
+}
+
+// Goal seek: the lever move that reaches a share (fraction) or cash (cents) target by a date,
+// with the share of reseeded reruns that still reach it as confidence
+export type GoalSeekDto = { lever: "asp" | "capacity" | "rnd"; metric: "share" | "cash"; target: number; by: string };
+export type GoalSeekResultDto = { lever: "asp" | "capacity" | "rnd"; frac: number; achieved: boolean; value: number; months: number; confidence: number };
+export async function simGoalSeek(seek: GoalSeekDto) {
+  return invokeSafe<GoalSeekResultDto>("sim_goal_seek", { seek });
+}
+
+// Concurrent game sessions (e.g. two strategies side by side)
+export async function simSessionList() {
+  return invokeSafe<string[]>("sim_session_list");
+}
+
+export async function simSessionClone(to: string, from?: string) {
+  return invokeSafe<SimStateDto>("sim_session_clone", { from: from ?? currentSession, to });
+}
+
+export async function simSessionClose(session_id: string) {
+  return invokeSafe<boolean>("sim_session_close", { session_id });
+}
+
+export type ProductKind = "CPU" | "GPU" | "APU" | "ASIC" | "NPU";
+export type DesignSegmentDto = { id: string; name: string; tdp_min_w: number | null; tdp_max_w: number | null };
+export type ProductKindDto = { kind: ProductKind; unit_cost_pct: number; perf_pct: number; appeal: number; segments: string[] };
 export type SimListsDto = {
   tech_nodes: string[];
   foundries: string[];
   segments: string[];
+  design_segments: DesignSegmentDto[];
+  product_kinds: ProductKindDto[];
 };
 
 export async function getSimState() {
@@ -133,10 +873,60 @@ export type CampaignDto = { status: string; goals: { kind: string; desc: string;
 export async function simCampaignReset(which?: string) {
   return invokeSafe("sim_campaign_reset", { which });
 }
+export type EntrantPersonaDto = { name: string; weight: number; margin_frac: number };
+// Starting conditions of a sandbox game; omitted fields take the 1990s campaign's values
+export type SandboxSetupDto = { start_year?: number; years?: number; player_cash_cents?: number; competitors?: number; personas?: EntrantPersonaDto[]; segments?: string[]; difficulty?: string | null; seed?: number; fog?: boolean };
+export async function simSandboxNew(setup: SandboxSetupDto) {
+  return invokeSafe<SimStateDto>("sim_sandbox_new", { setup });
+}
+export type ConfigCacheStatsDto = { entries: number; hits: number; misses: number };
+// Re-parse asset bundles on the next reset, e.g. after editing YAML while the app runs
+export async function simConfigCacheInvalidate() {
+  return invokeSafe<ConfigCacheStatsDto>("sim_config_cache_invalidate");
+}
+export type ConfigKind = "market" | "ai";
+// A swapped config; month and date are unset while it waits for the next month
+export type ConfigVersionDto = { kind: ConfigKind; version: number; hash: string; month: number | null; date: string | null; yaml: string };
+export type ConfigSwapsDto = { pending: ConfigVersionDto[]; applied: ConfigVersionDto[] };
+// Validate a market or AI config (YAML) and apply it from the next month
+export async function simConfigSwap(kind: ConfigKind, yaml: string) {
+  return invokeSafe<SimStateDto>("sim_config_swap", { kind, yaml });
+}
+export async function simConfigVersions() {
+  return invokeSafe<ConfigSwapsDto>("sim_config_versions", {});
+}
+// Crash journal replay: what it covers and, once run, the first month that came out different
+export type JournalRecoveryDto = { save: string; save_id: number; months: number; commands: number; rejected: string[] };
+export async function simJournalStatus() {
+  return invokeSafe<JournalRecoveryDto | null>("sim_journal_status", {});
+}
+export async function simJournalRecover() {
+  return invokeSafe<{ state: SimStateDto; recovery: JournalRecoveryDto }>("sim_journal_recover", {});
+}
+export async function simJournalDiscard() {
+  return invokeSafe<void>("sim_journal_discard", {});
+}
 export type BalanceInfo = { segments: SimStateDto["segments"]; active_mods: { id: string; kind: string; target: string; start: string; end: string }[] };
 export async function simBalanceInfo() {
   return invokeSafe<BalanceInfo>("sim_balance_info");
 }
+// Effect targets by name; segment demand and elasticity name their segment
+export type EffectTargetDto =
+  | { target: "wafer_cost" | "yield" | "segment_growth" | "event_severity" | "ai_min_margin" | "ai_price_epsilon" | "take_or_pay" | "distress_grace_months" | "emergency_loan_cents" | "player_cash" }
+  | { target: "segment_demand" | "segment_elasticity"; segment: string };
+export type EffectOpDto = { op: "set" | "mul" | "add"; value: number };
+export type ModifierTraceDto = { id: string; source: "macro" | "difficulty" | "mod" | "event" | "decision"; op: EffectOpDto; start: string; end?: string | null; overridden: boolean };
+export type ValueProvenanceDto = { target: EffectTargetDto; key?: string | null; base: number; modifiers: ModifierTraceDto[]; value: number };
+// Why a value is what it is; wafer cost and yield take a node id as key, segment growth a segment id
+export async function simValueProvenance(target: EffectTargetDto, key?: string) {
+  return invokeSafe<ValueProvenanceDto>("sim_value_provenance", { target, key });
+}
+export type CompanyStatementDto = { company: string; player: boolean; exited: boolean; revenue_cents: number; cogs_cents: number; opex_cents: number; profit_cents: number };
+export type FinanceConsolidationDto = { companies: CompanyStatementDto[]; revenue_cents: number; cogs_cents: number; opex_cents: number; profit_cents: number; player_revenue_share: number; warranty?: WarrantyReportDto };
+// Each company's books side by side, the player first
+export async function simFinanceConsolidation() {
+  return invokeSafe<FinanceConsolidationDto>("sim_finance_consolidation");
+}
 export async function simCampaignSetDifficulty(level: string) {
   return invokeSafe("sim_campaign_set_difficulty", { level });
 }
@@ -150,6 +940,37 @@ export async function simTutorialState() {
   return invokeSafe<TutorialDto>("sim_tutorial_state");
 }
 
+export type HintKind = "below_cost" | "idle_take_or_pay" | "inventory_pile_up";
+export type HintDto = { kind: HintKind; date: string; message: string };
+export type HintConfigDto = { enabled: boolean; cooldown_months: number; pile_up_months: number };
+export type HintsDto = { config: HintConfigDto; hints: HintDto[] };
+// Mistake hints shown so far, oldest first; pass a config to tune or disable the engine
+export async function simHints(config?: HintConfigDto) {
+  return invokeSafe<HintsDto>("sim_hints", { config });
+}
+export type WarningCode = "price_floored" | "price_below_cost" | "rd_increase_blocked" | "rd_budget_floored" | "take_or_pay_clamped" | "contract_overlap" | "expedite_cut" | "node_not_open" | "qc_increase_blocked";
+export type WarningSeverity = "info" | "adjusted" | "costly";
+export type ActionWarningDto = { code: WarningCode; severity: WarningSeverity; date: string; message: string; fix: string };
+export type WarningsDto = { warnings: ActionWarningDto[]; raised: number };
+// Warnings player actions raised, oldest first
+export async function simWarnings() {
+  return invokeSafe<WarningsDto>("sim_warnings");
+}
+export type HistoryDto = { recording: boolean; first_month: number | null; current_month: number };
+// History kept for scrubbing back in time; pass `record` to start or stop recording
+export async function simHistory(record?: boolean) {
+  return invokeSafe<HistoryDto>("sim_history", { record });
+}
+export type StateAtDto = { month: number; replayed_months: number; state: SimStateDto };
+// The game as it stood at the start of a past month, read-only
+export async function simStateAt(month: number) {
+  return invokeSafe<StateAtDto>("sim_state_at", { month });
+}
+// Hints spotted while auto-advance runs
+export async function onHint(cb: (e: { session_id: string; hint: HintDto }) => void): Promise<UnlistenFn> {
+  return listen<{ session_id: string; hint: HintDto }>("sim-hint", (e) => cb(e.payload));
+}
+
 export type BuildInfo = { version: string; git_sha: string; build_date: string };
 export async function simBuildInfo() {
   return invokeSafe<BuildInfo>("sim_build_info");
@@ -174,10 +995,50 @@ export async function simLoad(save_id: number) {
   return invokeSafe<SimStateDto>("sim_load", { save_id });
 }
 
+export type AuditRow = {
+  session_id: string;
+  command: string;
+  args_json: string;
+  duration_ms: number;
+  ok: boolean;
+  error: string | null;
+  suppressed: number;
+  at_ms: number;
+};
+export async function simAuditLog(save_id: number) {
+  return invokeSafe<AuditRow[]>("sim_audit_log", { save_id });
+}
+
+export type SaveIssue =
+  | { kind: "missing_save" }
+  | { kind: "incomplete"; status: string }
+  | { kind: "no_snapshot" }
+  | { kind: "corrupt_snapshot"; snapshot_id: number; month_index: number; reason: string }
+  | { kind: "checksums_missing" }
+  | { kind: "slice_mismatch"; table: string; expected_rows: number; actual_rows: number }
+  | { kind: "orphaned_rows"; table: string; rows: number };
+export type SaveIntegrityDto = { save_id: number; issues: SaveIssue[]; actions: string[] };
+export async function simVerifySave(save_id: number, repair = false) {
+  return invokeSafe<SaveIntegrityDto>("sim_verify_save", { save_id, repair });
+}
+
+export type RunSeriesDelta = { kpi: string; a: number[]; b: number[]; delta: number[] };
+export type RunComparisonDto = {
+  months: number[];
+  series: RunSeriesDelta[];
+  divergence: { month: number; cash_a: number; cash_b: number; delta_frac: number } | null;
+  finals: { kpi: string; a: number; b: number; delta: number }[];
+};
+export async function simCompareSaves(save_a: number, save_b: number) {
+  return invokeSafe<RunComparisonDto>("sim_compare_saves", { save_a, save_b });
+}
+
 export async function simSetAutosave(on: boolean) {
   return invokeSafe<{ enabled: boolean; max_kept: number }>("sim_set_autosave", { on });
 }
 
-export async function simExportCampaign(path: string, format?: "json" | "parquet") {
+// json/parquet: dry-run monthly projection to campaign end, plus its event log in a
+// `<name>.events.<ext>` sidecar; csv: that event log alone; html/markdown: campaign report so far
+export async function simExportCampaign(path: string, format?: "json" | "parquet" | "csv" | "html" | "markdown") {
   return invokeSafe<void>("sim_export_campaign", { path, format });
 }
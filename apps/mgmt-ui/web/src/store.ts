@@ -1,5 +1,5 @@
 import { create } from "zustand";
-import { SimSnapshot, SimStateDto, SimListsDto } from "./api";
+import { SimSnapshot, SimStateDto, SimListsDto, TurnSummaryDto } from "./api";
 
 type State = {
   snapshot?: SimSnapshot;
@@ -10,6 +10,7 @@ type State = {
   toast?: string;
   stateDto?: SimStateDto;
   lists?: SimListsDto;
+  turnSummary?: TurnSummaryDto;
   setSnapshot: (s: SimSnapshot) => void;
   setLoading: (b: boolean) => void;
   setBusy: (b: boolean) => void;
@@ -17,6 +18,7 @@ type State = {
   showToast: (msg: string) => void;
   setStateDto: (s: SimStateDto) => void;
   setLists: (l: SimListsDto) => void;
+  setTurnSummary: (t?: TurnSummaryDto) => void;
 };
 
 const initial = () => ({
@@ -35,6 +37,7 @@ export const useAppStore = create<State>((set) => ({
   showToast: (msg) => { set({ toast: msg }); setTimeout(() => set({ toast: undefined }), 2000); },
   setStateDto: (s) => set({ stateDto: s }),
   setLists: (l) => set({ lists: l }),
+  setTurnSummary: (t) => set({ turnSummary: t }),
 }));
 
 export function resetAppStore() {
@@ -47,5 +50,6 @@ export function resetAppStore() {
     showToast: useAppStore.getState().showToast,
     setStateDto: useAppStore.getState().setStateDto,
     setLists: useAppStore.getState().setLists,
+    setTurnSummary: useAppStore.getState().setTurnSummary,
   });
 }
@@ -7,6 +7,33 @@ let monthIndex = 0
 let saves: SaveRow[] = []
 let nextSaveId = 1
 let autosave = true
+let sessions: string[] = ['default']
+let promos: any[] = []
+let budget: any = { mode: 'warn', board_confidence: 0.7, quarter_to_date: [], history: [] }
+let policies: any[] = []
+let regions: any[] = [{ id: 'na', name: 'North America', demand_units: 1000, sold_units: 800, share: 0.8, price_cents: 30000, logistics_cents: 120000, price_override_cents: null }]
+let exportControls: any = { active: [], policies: {}, variants: [], fines: [] }
+let roadmap: any[] = []
+let rnd: any = { process_budget_cents: 0, design_budget_cents: 0, process: 0, design: 0, owns_fab: false }
+let quality: any = { budget_cents: 0, spent_cents: 0, defects_avoided: 0, defect_frac: 0.05, field_failure_factor: 1 }
+let capacityMarket: any[] = [
+  { node: 'N600', year_available: 1990, price_cents_per_wafer: 72250, price_frac: 0.7225, trailing_edge: true, retired: false, retires: '1998-01-01' },
+  { node: 'N350', year_available: 1993, price_cents_per_wafer: 120000, price_frac: 1, trailing_edge: false, retired: false, retires: null },
+]
+let bargains: any[] = []
+let licenses: any[] = []
+let configSwaps: any = { pending: [], applied: [] }
+let journal: any = null
+let marketResearch: any = { subscriptions: [], cents_per_month: 5000000, max_months: 24, reports: [] }
+let autoRunning = false
+let waferAllocation: any = { automatic: true, lines: [{ line: 'CPU', planned_share: null, share: 1, wafers: 1000, units: 47500 }] }
+let consoles: any = { rfq: { generation: 1, closes: '1990-07-01', bid_price_frac: null }, annuity: null, lockout_until: null, awards: [], cycle: { segment: 'console', oem: 'sorai', cycle_months: 72, rfq_months: 6, units_per_month: 8000, min_price_frac: 0.6, lockout_frac: 0.6 }, next_rfq: '1996-01-01', score: 40, needed: 70 }
+let patents: any = { filings: [], cases: [], next_case_id: 0, strength: 0, filing_cost_cents: 5000000, rivals: [{ id: 'ardent', name: 'Ardent Micro', strength: 40, patents: ['high_ipc'], products: ['large_l1', 'large_l2'], units_per_month: 30000 }] }
+let oems: any[] = [{ id: 'northgate', name: 'Northgate Systems', score: 45, concession_frac: 0, exclusive: false, deal: null, deals_completed: 0, last_fill: null, design_wins: 0, last_design_win: null, invested_cents: 0, terms: { initial_score: 45, volume_deal: { min_score: 60, units_per_month: 20000, price_frac: 0.85, months: 12 }, design_win: { min_score: 75, appeal_boost: 0.05, cooldown_months: 12 } } }]
+const insuranceProducts = [
+  { id: 'fab_disruption', name: 'Fab disruption cover', risk: 'fab_disruption', premium_cents_per_month: 1500000, deductible_cents: 2500000, cover_frac: 0.8, limit_cents: 200000000 },
+  { id: 'inventory_write_off', name: 'Inventory write-off cover', risk: 'inventory_write_off', premium_cents_per_month: 1000000, deductible_cents: 5000000, cover_frac: 0.6, limit_cents: 100000000 },
+]
 
 function nowIso() {
   return new Date().toISOString()
@@ -16,23 +43,74 @@ export function setupIpcMock() {
   (invoke as any).mockImplementation(async (cmd: string, payload?: any) => {
     switch (cmd) {
       case 'sim_lists':
-        return { tech_nodes: ['N90', 'N65'], foundries: ['F1'], segments: ['Seg'] }
+        return {
+          tech_nodes: ['N90', 'N65'],
+          foundries: ['F1'],
+          segments: ['Seg'],
+          design_segments: [
+            { id: 'desktop', name: 'Desktop CPUs', tdp_min_w: 10, tdp_max_w: 130 },
+            { id: 'server', name: 'Server CPUs', tdp_min_w: 40, tdp_max_w: 250 },
+            { id: 'graphics', name: 'Graphics Cards', tdp_min_w: 10, tdp_max_w: 150 },
+          ],
+          product_kinds: [
+            { kind: 'CPU', unit_cost_pct: 100, perf_pct: 100, appeal: 0, segments: ['desktop', 'server'] },
+            { kind: 'GPU', unit_cost_pct: 120, perf_pct: 110, appeal: 0.05, segments: ['graphics'] },
+          ],
+        }
       case 'sim_state':
         return {
           date: '1990-01-01',
           month_index: monthIndex,
           companies: [{ name: 'A', cash_cents: 1000000, debt_cents: 0 }],
-          segments: [{ name: 'Seg', base_demand_units: 1000, price_elasticity: -1.2, base_demand_t: 1000, ref_price_t_cents: 30000, elasticity: -1.2, trend_pct: 8.0, sold_units: 800 }],
+          segments: [{ name: 'Seg', base_demand_units: 1000, price_elasticity: -1.2, base_demand_t: 1000, ref_price_t_cents: 30000, elasticity: -1.2, trend_pct: 8.0, sold_units: 800, regions: [], lifecycle_frac: 1, estimate_error_frac: 0.3 }],
           pricing: { asp_cents: 30000, unit_cost_cents: 20000 },
-          kpi: { cash_cents: 1000000, revenue_cents: 0, cogs_cents: 0, contract_costs_cents: 0, profit_cents: 0, share: 0.2, rd_pct: 0.1, output_units: 1000, inventory_units: 950 },
-          contracts: [],
+          kpi: { cash_cents: 1000000, revenue_cents: 0, cogs_cents: 0, contract_costs_cents: 0, profit_cents: 0, share: 0.2, rd_pct: 0.1, output_units: 1000, inventory_units: 950, utilization: 0.8 },
+          contracts: bargains,
+          foundries: [{ id: 'FND-A', score: 0.9, months_observed: 0, misses: 0, preferred: true }],
           pipeline: { queue: [], released: [] },
           ai_plan: { decisions: ['ASP-5%'], expected_score: 0.5 },
           config: { finance: {}, product_cost: { usable_die_area_mm2: 6200, yield_overhead_frac: 0.05 } },
           campaign: null,
+          pending_decisions: [],
+          distress: { active: false, months_left: 0, loan_offer_cents: null, failed: false, warning: null },
+          forecast: null,
+          finance: { debt_cents: 0, rating: 'BBB', spread_bps: 200, rate_on_new_debt: 0.07, credit_line_cents: 0, drawn_cents: 0, available_cents: 0, leverage: 0, interest_coverage: null, volatility: 0, deposit_rate: 0.02, debt_rate: 0.08, interest_income_cents: 0, interest_expense_cents: 0, warranty: { balance_cents: 0, outstanding_cents: 0, adequacy: null, accrual_frac: 0, accrued_cents: 0, claims_cents: 0, shortfall_cents: 0, last_accrual_cents: 0, last_claims_cents: 0 } },
+          news: [],
+          grants: [],
+          regions,
+          oems,
+          reputation: 50,
+          roadmap,
+          reviews: [],
+          rnd,
+          quality,
+          products: [],
+          patents,
+          capacity_market: capacityMarket,
+          consoles,
+          wafer_allocation: waferAllocation,
+          company_exits: [],
+          entrants: [],
+          licenses,
+          clearing: [],
+          market_research: marketResearch,
         }
       case 'sim_tutorial_state':
         return { active: false, current_step: 0, steps: [] }
+      case 'sim_hints':
+        return {
+          config: payload?.config ?? { enabled: true, cooldown_months: 6, pile_up_months: 6 },
+          hints: [{ kind: 'below_cost', date: '1990-02-01', message: 'Selling below cost.' }],
+        }
+      case 'sim_warnings':
+        return {
+          warnings: [{ code: 'price_below_cost', severity: 'costly', date: '1990-02-01', message: 'The price is under unit cost.', fix: 'Raise the price above unit cost.' }],
+          raised: 1,
+        }
+      case 'sim_history':
+        return { recording: payload?.record ?? false, first_month: payload?.record ? 0 : null, current_month: 0 }
+      case 'sim_pricing_rules':
+        return { rules: [], firings: [] }
       case 'sim_build_info':
         return { version: '0.1.0', git_sha: 'deadbeef', build_date: 'today' }
       case 'sim_help_markdown':
@@ -53,22 +131,254 @@ export function setupIpcMock() {
           output_units: 1000,
           defect_units: 0,
           inventory_units: 950,
+          state_hash: monthIndex.toString(16).padStart(16, '0'),
         }
       case 'sim_tick_quarter':
         monthIndex += 3
-        return { months_run: monthIndex }
+        return {
+          months_run: monthIndex,
+          summary: {
+            from: '1990-01-01', to: '1990-04-01',
+            demand: [{ segment: 'Seg', demand_from: 1000, demand_to: 1020, ref_price_from_cents: 30000, ref_price_to_cents: 29500 }],
+            competitors: { share_from: 0.8, share_to: 0.78, cash_delta_cents: [] },
+            contracts_started: [], contracts_ended: [], products_released: [], events: [],
+            cash: { start_cents: 1000000, end_cents: 1200000, items: [['revenue', 500000], ['cogs', -250000], ['contracts', -50000]] },
+          },
+        }
+      case 'sim_run':
+        monthIndex += (payload?.months ?? 0)
+        return { months_run: monthIndex, months_done: payload?.months ?? 0, cancelled: false, paused: false }
+      case 'sim_run_cancel':
+        return null
+      case 'sim_auto_start':
+        if (!(payload?.ticksPerSecond > 0 && payload?.ticksPerSecond <= 60)) throw new Error('ticks per second must be within (0, 60]')
+        autoRunning = true
+        return null
+      case 'sim_lockstep_frame':
+        return { month_index: monthIndex, state_hash: monthIndex.toString(16).padStart(16, '0'), commands: payload?.commands ?? [] }
+      case 'sim_lockstep_step': {
+        const hash = monthIndex.toString(16).padStart(16, '0')
+        if (payload?.frame?.state_hash !== hash) throw new Error(`desync before month ${monthIndex}`)
+        monthIndex += 1
+        const rejected = (payload.frame.commands as any[]).flatMap((c, i) => (c.company === 0 ? [] : [[i, `no company in seat ${c.company}`]]))
+        return { month_index: monthIndex, state_hash: monthIndex.toString(16).padStart(16, '0'), rejected }
+      }
+      case 'sim_auto_stop':
+        if (!autoRunning) throw new Error('auto-advance is not running')
+        autoRunning = false
+        return (await (invoke as any)('sim_state'))
+      case 'spectator_state':
+      case 'spectator_kpi': {
+        const st = await (invoke as any)('sim_state')
+        const view = { date: st.date, month_index: st.month_index, state_hash: monthIndex.toString(16).padStart(16, '0'), kpi: st.kpi }
+        if (cmd === 'spectator_kpi') return view
+        return { ...view, companies: st.companies, segments: st.segments, regions: st.regions, reputation: st.reputation, news: st.news.slice(-10) }
+      }
       case 'sim_plan_quarter':
         return { decisions: ['ASP-5%', 'Capacity+1000u/mo', 'Tapeout (expedite)'], expected_score: 0.42 }
+      case 'sim_plan_trace':
+        return {
+          quarters: [
+            {
+              month_index: 1,
+              beam: [{ score: 0, cash_usd: 1000000, share: 0.3, asp_usd: 300, decisions: [] }],
+              candidates: [
+                { parent: 0, action: { AdjustPriceFrac: -0.05 }, score: 0.41, kept: true, pruned: null },
+                { parent: 0, action: { RequestCapacity: 10000 }, score: null, kept: false, pruned: 'LiquidityFloor' },
+              ],
+            },
+          ],
+          expected_score: 0.42,
+        }
       case 'sim_override':
         // Light payload validation to catch test regressions
         if (payload?.ovr?.price_delta_frac !== undefined && typeof payload.ovr.price_delta_frac !== 'number') throw new Error('price_delta_frac not number')
         if (payload?.ovr?.rd_delta_cents !== undefined && typeof payload.ovr.rd_delta_cents !== 'number') throw new Error('rd_delta_cents not number')
         return { asp_cents: 30000 }
       case 'sim_campaign_reset':
+      case 'sim_sandbox_new':
         monthIndex = 0
         return (await (invoke as any)('sim_state'))
+      case 'sim_decide':
+      case 'sim_accept_loan':
+      case 'sim_credit_draw':
+      case 'sim_credit_repay':
+        return (await (invoke as any)('sim_state'))
       case 'sim_campaign_set_difficulty':
         return {}
+      case 'sim_promo_list':
+        return promos
+      case 'sim_promo_schedule':
+        if (typeof payload?.discount_frac !== 'number') throw new Error('discount_frac not number')
+        promos = [
+          ...promos,
+          {
+            id: promos.length + 1,
+            segment_id: payload?.segment_id,
+            discount_frac: payload?.discount_frac,
+            reach_frac: payload?.reach_frac ?? 1,
+            start: payload?.start ?? '1990-01-01',
+            end: '1990-02-01',
+            active: true,
+          },
+        ]
+        return promos
+      case 'sim_promo_cancel':
+        promos = promos.filter((p) => p.id !== payload?.id)
+        return promos
+      case 'sim_budget':
+        return budget
+      case 'sim_budget_set':
+        {
+          const rows = budget.quarter_to_date.filter((r: any) => r.category !== payload?.category)
+          if (payload?.category && payload?.cents != null) {
+            rows.push({ quarter: '1990-01-01', category: payload.category, budget_cents: payload.cents, actual_cents: 0, variance_cents: -payload.cents })
+          }
+          budget = { ...budget, mode: payload?.mode ?? budget.mode, quarter_to_date: rows }
+          return budget
+        }
+      case 'sim_insurance_buy':
+        if (!policies.some((p) => p.product_id === payload?.productId)) {
+          policies = [...policies, { product_id: payload?.productId, start: '1990-01-01' }]
+        }
+        return { products: insuranceProducts, policies, premiums_paid_cents: 0, claims: [] }
+      case 'sim_insurance_cancel':
+        policies = policies.filter((p) => p.product_id !== payload?.productId)
+        return { products: insuranceProducts, policies, premiums_paid_cents: 0, claims: [] }
+      case 'sim_insurance':
+        return { products: insuranceProducts, policies, premiums_paid_cents: 0, claims: [] }
+      case 'sim_regional_price':
+        regions = regions.map((r) => r.id === payload?.region ? { ...r, price_override_cents: payload?.priceCents ?? null } : r)
+        return (await (invoke as any)('sim_state'))
+      case 'sim_oem_deal':
+        {
+          const o = oems.find((x) => x.id === payload?.oemId)
+          if (!o || o.score < o.terms.volume_deal.min_score) throw new Error('relationship too weak for a volume deal')
+          oems = oems.map((x) => x === o ? { ...x, deal: { start: '1990-01-01', end: '1991-01-01', units_per_month: 20000, price_cents: 25500, committed_units: 0, delivered_units: 0 } } : x)
+          return (await (invoke as any)('sim_state'))
+        }
+      case 'sim_oem_terms':
+        oems = oems.map((x) => x.id === payload?.oemId ? { ...x, concession_frac: payload?.concessionFrac, exclusive: payload?.exclusive } : { ...x, exclusive: payload?.exclusive ? false : x.exclusive })
+        return (await (invoke as any)('sim_state'))
+      case 'sim_oem_invest':
+        oems = oems.map((x) => x.id === payload?.oemId ? { ...x, score: Math.min(100, x.score + payload?.cents / 1000000), invested_cents: x.invested_cents + payload?.cents } : x)
+        return (await (invoke as any)('sim_state'))
+      case 'sim_roadmap_announce':
+        roadmap = [...roadmap, { id: roadmap.length, node: payload?.node, min_perf_index: payload?.minPerfIndex, due: payload?.due, announced: '1990-01-01', status: 'pending', delivered: null }]
+        return (await (invoke as any)('sim_state'))
+      case 'sim_rnd_budgets':
+        rnd = { ...rnd, process_budget_cents: payload?.processCents, design_budget_cents: payload?.designCents }
+        return (await (invoke as any)('sim_state'))
+      case 'sim_debug':
+        // Like a build without the debug-console feature, which has no such command
+        throw new Error('Command sim_debug not found')
+      case 'sim_qc_budget': {
+        const bp = 100 + Math.floor(400 * 5000000 / (5000000 + payload?.cents))
+        quality = { ...quality, budget_cents: payload?.cents, defect_frac: bp / 10000, field_failure_factor: 0.5 + 0.5 * bp / 500 }
+        return (await (invoke as any)('sim_state'))
+      }
+      case 'sim_bargain_contract': {
+        const q = capacityMarket.find((x) => x.node === payload?.node)
+        if (!q?.trailing_edge) throw new Error(`${payload?.node} is not a trailing-edge node`)
+        bargains = [...bargains, { id: `0AB1PN4X00000000000000000${bargains.length}`, foundry_id: 'FND-A', wafers_per_month: payload?.wafersPerMonth, billing_cents_per_wafer: q.price_cents_per_wafer, take_or_pay_frac: 1, start: '1990-02-01', end: '1991-02-01', compensation_cents_per_wafer: Math.round(q.price_cents_per_wafer / 2), node: q.node, ramp_wafers: [], min_order_wafers: 0, rollover: false, banked_wafers: 0 }]
+        return (await (invoke as any)('sim_state'))
+      }
+      case 'sim_console_bid':
+        if (!consoles.rfq) throw new Error('no console RFQ is open')
+        if (payload?.priceFrac < consoles.cycle.min_price_frac || payload?.priceFrac > 1) throw new Error('bid price out of range')
+        consoles = { ...consoles, rfq: { ...consoles.rfq, bid_price_frac: payload?.priceFrac }, score: 40 + (1 - payload?.priceFrac) * 50 }
+        return (await (invoke as any)('sim_state'))
+      case 'sim_license_quote':
+        if (payload?.licensee !== 'Ardent Micro') throw new Error(`no rival named ${payload?.licensee}`)
+        return payload?.ip?.type === 'node' ? 120000000 : 60000000
+      case 'sim_license_offer': {
+        const terms = payload?.terms
+        if (!(terms?.royalty_cents_per_unit > 0)) throw new Error('royalty must be positive')
+        if (licenses.some((l) => l.terms.licensee === terms.licensee && l.terms.ip.type === terms.ip.type)) throw new Error(`${terms.licensee} already licenses our IP`)
+        licenses = [...licenses, { terms, signed: '1990-01-01', until: '1995-01-01', royalties_cents: 0 }]
+        return (await (invoke as any)('sim_state'))
+      }
+      case 'sim_wafer_allocation': {
+        const plan: Record<string, number> = payload?.plan ?? {}
+        if (Object.keys(plan).some((l) => !waferAllocation.lines.some((x: any) => x.line === l))) throw new Error('no product line')
+        const total = Object.values(plan).reduce((a, b) => a + b, 0)
+        waferAllocation = { automatic: total === 0, lines: waferAllocation.lines.map((x: any) => ({ ...x, planned_share: total === 0 ? null : (plan[x.line] ?? 0) / total })) }
+        return (await (invoke as any)('sim_state'))
+      }
+      case 'sim_market_research_buy': {
+        const until = '1990-07-01'
+        marketResearch = {
+          ...marketResearch,
+          subscriptions: [...marketResearch.subscriptions, { segment: payload?.segment, start: '1990-01-01', end: until }],
+          reports: [...marketResearch.reports, { segment: payload?.segment, date: '1990-01-01', until, base_demand_units: 100000, ref_price_cents: 30000, elasticity: -1.2, trend_pct: 10, upcoming_events: [{ start: '1990-06-01', months: 3, base_demand_pct: -20 }], competitor_hints: [{ kind: 'strength', date: '1991-01-01', now: 1, ahead: 1.2 }] }],
+        }
+        return (await (invoke as any)('sim_state'))
+      }
+      case 'sim_patent_file':
+        patents = { ...patents, filings: [...patents.filings, { feature: payload?.feature, filed: '1990-01-01', grant: '1991-01-01', granted: false }] }
+        return (await (invoke as any)('sim_state'))
+      case 'sim_export_controls':
+        return exportControls
+      case 'sim_export_policy':
+        exportControls = { ...exportControls, policies: { ...exportControls.policies, [payload?.restrictionId]: payload?.policy } }
+        return exportControls
+      case 'sim_export_variant':
+        exportControls = { ...exportControls, variants: [...exportControls.variants, { restriction_id: payload?.restrictionId, node: 'N600', ready: '1990-07-01' }] }
+        return exportControls
+      case 'sim_sensitivity': {
+        const k = (cash: number) => ({ revenue_cents: 4000000, profit_cents: 1000000, cash_cents: cash, market_share: 0.2 })
+        return {
+          months: payload?.months ?? 6,
+          base: k(2000000),
+          levers: [
+            { lever: 'capacity', step_frac: 0.2, down: k(1700000), up: k(2300000), cash_swing_cents: 600000 },
+            { lever: 'asp', step_frac: 0.05, down: k(1800000), up: k(2200000), cash_swing_cents: 400000 },
+            { lever: 'rnd', step_frac: 0.5, down: k(2010000), up: k(1990000), cash_swing_cents: 20000 },
+          ],
+        }
+      }
+      case 'sim_goal_seek':
+        return { lever: payload?.seek?.lever ?? 'asp', frac: -0.12, achieved: true, value: payload?.seek?.target ?? 0, months: 12, confidence: 0.75 }
+      case 'sim_schedule':
+        return {
+          date: '1990-01-01',
+          items: [
+            { id: 'tapeout-0', kind: 'tapeout', label: 'CPU on N90', start: '1990-01-01', end: '1990-07-01', progress: 0, expedited: true },
+            { id: 'contract-0', kind: 'contract', label: '1000 wpm at FND-A', start: '1990-04-01', end: '1991-03-01', progress: 0, expedited: false },
+          ],
+        }
+      case 'sim_expedite_quote':
+        return [
+          { months: 1, cost_cents: 250000, available: true, reason: null },
+          { months: 2, cost_cents: 1000000, available: false, reason: 'needs 500 wafers/month under contract (now 0)' },
+          { months: 3, cost_cents: 2250000, available: false, reason: 'needs 2000 wafers/month under contract (now 0)' },
+        ]
+      case 'sim_contract_advice':
+        return {
+          contracts: [{ foundry_id: 'FND-A', wafers_per_month: 600, start_month: 3, months: 12, cents_per_wafer: 10000 }],
+          expected_cost_cents: 72000000,
+          service_level: payload?.service_level ?? 0.95,
+          met: true,
+        }
+      case 'sim_production_plan':
+        return {
+          months: [
+            { date: '1990-01-01', demand_units: 40000, capacity_wafers: 2000, recommended_wafer_starts: 1264, projected_inventory: 20000, flag: { kind: 'overbuild', excess_units: 1000, idle_wafers: 736 } },
+          ],
+          releasable_wafers: null,
+        }
+      case 'sim_session_list':
+        return sessions
+      case 'sim_session_clone':
+        if (sessions.includes(payload?.to)) throw new Error(`session ${payload?.to} already exists`)
+        sessions = [...sessions, payload?.to]
+        return (await (invoke as any)('sim_state'))
+      case 'sim_session_close':
+        {
+          const had = sessions.includes(payload?.session_id)
+          sessions = sessions.filter((s) => s !== payload?.session_id)
+          return had
+        }
       case 'sim_save':
         {
           const name: string = payload?.name || `manual-${nextSaveId}`
@@ -81,6 +391,17 @@ export function setupIpcMock() {
         return saves
       case 'sim_load':
         return (await (invoke as any)('sim_state'))
+      case 'sim_audit_log':
+        return []
+      case 'sim_verify_save':
+        return { save_id: payload?.save_id, issues: [], actions: [] }
+      case 'sim_compare_saves':
+        return {
+          months: [0, 1, 2],
+          series: [{ kpi: 'cash', a: [100, 110, 120], b: [100, 90, 80], delta: [0, -20, -40] }],
+          divergence: { month: 1, cash_a: 110, cash_b: 90, delta_frac: 0.18 },
+          finals: [{ kpi: 'cash', a: 120, b: 80, delta: -40 }],
+        }
       case 'sim_set_autosave':
         autosave = !!payload?.on
         return { enabled: autosave, max_kept: 6 }
@@ -88,6 +409,31 @@ export function setupIpcMock() {
         return {}
       case 'sim_balance_info':
         return { segments: [], active_mods: [] }
+      case 'sim_value_provenance':
+        if (payload?.target?.target === 'wafer_cost' && !payload?.key) throw new Error("a node's wafer cost or yield needs a key")
+        return { target: payload?.target, key: payload?.key ?? null, base: 1000, modifiers: [{ id: 'evt_1991_foundry_bump', source: 'mod', op: { op: 'mul', value: 1.1 }, start: '1991-07-01', end: '1992-01-01', overridden: false }], value: 1100 }
+      case 'sim_config_cache_invalidate':
+        return { entries: 6, hits: 12, misses: 6 }
+      case 'sim_config_swap': {
+        if (!String(payload?.yaml ?? '').includes('segments')) throw new Error('market config: missing field `segments`')
+        configSwaps = { ...configSwaps, pending: [{ kind: payload?.kind, version: configSwaps.applied.length + 1, hash: '5f1c0e2a9b7d4e31', month: null, date: null, yaml: payload?.yaml }] }
+        return (await (invoke as any)('sim_state'))
+      }
+      case 'sim_config_versions':
+        return configSwaps
+      case 'sim_journal_status':
+        return journal
+      case 'sim_journal_recover': {
+        if (!journal) throw new Error('nothing to recover')
+        const recovery = { ...journal, rejected: [] }
+        journal = null
+        return { state: (await (invoke as any)('sim_state')), recovery }
+      }
+      case 'sim_journal_discard':
+        journal = null
+        return null
+      case 'sim_finance_consolidation':
+        return { companies: [{ company: 'A', player: true, exited: false, revenue_cents: 30000000, cogs_cents: 18000000, opex_cents: 0, profit_cents: 12000000 }, { company: 'Rival A', player: false, exited: false, revenue_cents: 70000000, cogs_cents: 49000000, opex_cents: 10000000, profit_cents: 11000000 }], revenue_cents: 100000000, cogs_cents: 67000000, opex_cents: 10000000, profit_cents: 23000000, player_revenue_share: 0.3, warranty: { balance_cents: 0, outstanding_cents: 0, adequacy: null, accrual_frac: 0, accrued_cents: 0, claims_cents: 0, shortfall_cents: 0, last_accrual_cents: 0, last_claims_cents: 0 } }
       default:
         return {}
     }
@@ -103,4 +449,21 @@ export function resetIpcMockState() {
   saves = []
   nextSaveId = 1
   autosave = true
+  sessions = ['default']
+  promos = []
+  budget = { mode: 'warn', board_confidence: 0.7, quarter_to_date: [], history: [] }
+  policies = []
+  regions = [{ id: 'na', name: 'North America', demand_units: 1000, sold_units: 800, share: 0.8, price_cents: 30000, logistics_cents: 120000, price_override_cents: null }]
+  exportControls = { active: [], policies: {}, variants: [], fines: [] }
+  roadmap = []
+  rnd = { process_budget_cents: 0, design_budget_cents: 0, process: 0, design: 0, owns_fab: false }
+  quality = { budget_cents: 0, spent_cents: 0, defects_avoided: 0, defect_frac: 0.05, field_failure_factor: 1 }
+  bargains = []
+  autoRunning = false
+  waferAllocation = { automatic: true, lines: [{ line: 'CPU', planned_share: null, share: 1, wafers: 1000, units: 47500 }] }
+  consoles = { rfq: { generation: 1, closes: '1990-07-01', bid_price_frac: null }, annuity: null, lockout_until: null, awards: [], cycle: { segment: 'console', oem: 'sorai', cycle_months: 72, rfq_months: 6, units_per_month: 8000, min_price_frac: 0.6, lockout_frac: 0.6 }, next_rfq: '1996-01-01', score: 40, needed: 70 }
+  patents = { filings: [], cases: [], next_case_id: 0, strength: 0, filing_cost_cents: 5000000, rivals: [{ id: 'ardent', name: 'Ardent Micro', strength: 40, patents: ['high_ipc'], products: ['large_l1', 'large_l2'], units_per_month: 30000 }] }
+  marketResearch = { subscriptions: [], cents_per_month: 5000000, max_months: 24, reports: [] }
+  journal = null
+  oems = [{ id: 'northgate', name: 'Northgate Systems', score: 45, concession_frac: 0, exclusive: false, deal: null, deals_completed: 0, last_fill: null, design_wins: 0, last_design_win: null, invested_cents: 0, terms: { initial_score: 45, volume_deal: { min_score: 60, units_per_month: 20000, price_frac: 0.85, months: 12 }, design_win: { min_score: 75, appeal_boost: 0.05, cooldown_months: 12 } } }]
 }
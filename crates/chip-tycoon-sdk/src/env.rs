@@ -0,0 +1,343 @@
+//! A reinforcement-learning environment over [`Simulation`], shaped like gym's `reset`/`step`.
+//!
+//! Each step applies one agent action, runs `months_per_step` months with decisions taking
+//! their defaults, and returns a fixed-length observation plus a reward built from
+//! [`Reward`]. Episodes start from the configured template; `reset` can swap its seed.
+//!
+//! Example:
+//! let mut env = Env::new(EnvConfig::default())?;
+//! let mut obs = env.reset(Some(3))?;
+//! loop {
+//!     let step = env.step(&EnvAction::Discrete(policy(&obs)));
+//!     if step.terminated || step.truncated { break; }
+//!     obs = step.observation;
+//! }
+
+use serde::{Deserialize, Serialize};
+use sim_runtime as runtime;
+
+use crate::{Action, Error, Simulation, Snapshot, Start, Tapeout};
+
+/// Observation features before the per-month history columns, in vector order.
+pub const OBSERVATION: [&str; 10] = [
+    "progress",
+    "cash_musd",
+    "debt_musd",
+    "asp_usd",
+    "unit_cost_usd",
+    "market_share",
+    "inventory_munits",
+    "demand_munits",
+    "revenue_musd",
+    "margin_musd",
+];
+
+/// Environment setup; every field has a default.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnvConfig {
+    /// Where each episode starts.
+    pub start: Start,
+    pub months_per_step: u32,
+    /// Episodes are truncated after this many months.
+    pub episode_months: u32,
+    /// Months of sold units and revenue appended to the observation, newest first.
+    pub history_months: usize,
+    pub actions: ActionSpace,
+    pub reward: Reward,
+}
+
+impl Default for EnvConfig {
+    fn default() -> Self {
+        Self {
+            start: Start::Scenario {
+                name: "campaign_1990s".into(),
+                seed: 1,
+            },
+            months_per_step: 1,
+            episode_months: 120,
+            history_months: 3,
+            actions: ActionSpace::default(),
+            reward: Reward::default(),
+        }
+    }
+}
+
+/// How agent actions map onto player [`Action`]s.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ActionSpace {
+    /// The agent picks one entry; each entry is applied as a batch, an empty one holds.
+    Discrete { choices: Vec<Vec<Action>> },
+    /// The agent sets three levers in [-1, 1]: price change, R&D budget change and, above
+    /// zero, new foundry capacity, each scaled to its maximum.
+    Continuous {
+        max_price_frac: f32,
+        max_rnd_cents: i64,
+        max_wafers_per_month: u32,
+        contract_months: u16,
+    },
+}
+
+impl Default for ActionSpace {
+    fn default() -> Self {
+        Self::Discrete {
+            choices: vec![
+                vec![],
+                vec![Action::AdjustPrice { frac: -0.05 }],
+                vec![Action::AdjustPrice { frac: 0.05 }],
+                vec![Action::AdjustRnd { cents: 100_000_000 }],
+                vec![Action::AdjustRnd {
+                    cents: -100_000_000,
+                }],
+                vec![Action::RequestCapacity {
+                    wafers_per_month: 1_000,
+                    months: 12,
+                }],
+                vec![Action::Tapeout(Tapeout::default())],
+            ],
+        }
+    }
+}
+
+impl ActionSpace {
+    /// Number of discrete choices, or of continuous levers.
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Discrete { choices } => choices.len(),
+            Self::Continuous { .. } => 3,
+        }
+    }
+
+    fn actions(&self, action: &EnvAction) -> Result<Vec<Action>, String> {
+        match (self, action) {
+            (Self::Discrete { choices }, EnvAction::Discrete(i)) => choices
+                .get(*i)
+                .cloned()
+                .ok_or_else(|| format!("choice {i} out of range 0..{}", choices.len())),
+            (
+                Self::Continuous {
+                    max_price_frac,
+                    max_rnd_cents,
+                    max_wafers_per_month,
+                    contract_months,
+                },
+                EnvAction::Continuous(v),
+            ) => {
+                if v.len() != 3 {
+                    return Err(format!("expected 3 levers, got {}", v.len()));
+                }
+                let lever = |i: usize| {
+                    let x = v[i].clamp(-1.0, 1.0);
+                    if x.is_nan() {
+                        0.0
+                    } else {
+                        x
+                    }
+                };
+                let mut out = Vec::new();
+                let frac = lever(0) * max_price_frac;
+                if frac != 0.0 {
+                    out.push(Action::AdjustPrice { frac });
+                }
+                let cents = (f64::from(lever(1)) * *max_rnd_cents as f64).round() as i64;
+                if cents != 0 {
+                    out.push(Action::AdjustRnd { cents });
+                }
+                let wafers = (lever(2).max(0.0) * *max_wafers_per_month as f32).round() as u32;
+                if wafers > 0 {
+                    out.push(Action::RequestCapacity {
+                        wafers_per_month: wafers,
+                        months: *contract_months,
+                    });
+                }
+                Ok(out)
+            }
+            (Self::Discrete { .. }, _) => Err("expected a discrete choice".into()),
+            (Self::Continuous { .. }, _) => Err("expected continuous levers".into()),
+        }
+    }
+}
+
+/// What the agent does in one step.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum EnvAction {
+    Discrete(usize),
+    Continuous(Vec<f32>),
+}
+
+/// Reward per step: a weighted sum of the step's changes, plus a bonus or penalty once the
+/// campaign is won or lost.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Reward {
+    /// Per million dollars of cash gained.
+    pub cash: f64,
+    /// Per million dollars of profit made.
+    pub profit: f64,
+    /// Per unit of market share held at the end of the step.
+    pub share: f64,
+    pub success: f64,
+    pub failure: f64,
+}
+
+impl Default for Reward {
+    fn default() -> Self {
+        Self {
+            cash: 0.0,
+            profit: 1.0,
+            share: 0.0,
+            success: 100.0,
+            failure: -100.0,
+        }
+    }
+}
+
+/// The result of one [`Env::step`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Step {
+    pub observation: Vec<f32>,
+    pub reward: f64,
+    /// The campaign was won or lost; negative cash alone does not end the episode.
+    pub terminated: bool,
+    /// The episode hit `episode_months`.
+    pub truncated: bool,
+    /// Why the game refused any of the step's actions; the rest were still applied.
+    pub rejected: Vec<String>,
+    pub snapshot: Snapshot,
+}
+
+/// A gym-style environment; call [`Env::reset`] to start each episode.
+pub struct Env {
+    config: EnvConfig,
+    sim: Simulation,
+}
+
+impl Env {
+    pub fn new(config: EnvConfig) -> Result<Self, Error> {
+        if config.months_per_step == 0 || config.episode_months == 0 {
+            return Err(Error::Start(
+                "months_per_step and episode_months must be > 0".into(),
+            ));
+        }
+        if config.actions.size() == 0 {
+            return Err(Error::Start("the action space has no choices".into()));
+        }
+        let sim = Simulation::start(&config.start)?;
+        Ok(Self { config, sim })
+    }
+
+    pub fn config(&self) -> &EnvConfig {
+        &self.config
+    }
+
+    /// The game of the current episode.
+    pub fn simulation(&self) -> &Simulation {
+        &self.sim
+    }
+
+    /// Names of the observation features, parallel to the vectors `reset` and `step` return.
+    pub fn observation_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = OBSERVATION.iter().map(|s| s.to_string()).collect();
+        for col in ["sold_munits", "revenue_musd"] {
+            names.extend((1..=self.config.history_months).map(|m| format!("{col}_t-{m}")));
+        }
+        names
+    }
+
+    /// Start a new episode from the template, with `seed` in place of its own if given.
+    pub fn reset(&mut self, seed: Option<u64>) -> Result<Vec<f32>, Error> {
+        let mut start = self.config.start.clone();
+        if let Some(seed) = seed {
+            match &mut start {
+                Start::Scenario { seed: s, .. } => *s = seed,
+                Start::Sandbox(s) => s.seed = seed,
+            }
+        }
+        self.sim = Simulation::start(&start)?;
+        Ok(self.observe())
+    }
+
+    pub fn step(&mut self, action: &EnvAction) -> Step {
+        let before = self.sim.snapshot();
+        let mut rejected = Vec::new();
+        match self.config.actions.actions(action) {
+            Ok(actions) => {
+                for a in &actions {
+                    if let Err(e) = self.sim.act(a) {
+                        rejected.push(e.to_string());
+                    }
+                }
+            }
+            Err(e) => rejected.push(e),
+        }
+        let months = self
+            .config
+            .months_per_step
+            .min(self.config.episode_months.saturating_sub(before.months_run))
+            .max(1);
+        let snapshot = self.sim.tick(months);
+
+        let outcome = self.outcome();
+        let r = &self.config.reward;
+        let musd = |cents: i64| cents as f64 / 1e8;
+        let mut reward = r.cash * musd(snapshot.cash_cents - before.cash_cents)
+            + r.profit * musd(snapshot.profit_cents - before.profit_cents)
+            + r.share * f64::from(snapshot.market_share);
+        match outcome {
+            runtime::CampaignOutcome::Success => reward += r.success,
+            runtime::CampaignOutcome::Failed => reward += r.failure,
+            runtime::CampaignOutcome::InProgress => {}
+        }
+        Step {
+            observation: self.observe(),
+            reward,
+            terminated: outcome != runtime::CampaignOutcome::InProgress,
+            truncated: snapshot.months_run >= self.config.episode_months,
+            rejected,
+            snapshot,
+        }
+    }
+
+    fn outcome(&self) -> runtime::CampaignOutcome {
+        self.sim
+            .world
+            .get_resource::<runtime::CampaignStateRes>()
+            .map(|c| c.outcome.clone())
+            .unwrap_or_default()
+    }
+
+    fn observe(&self) -> Vec<f32> {
+        let snap = self.sim.snapshot();
+        let musd = |cents: i64| (cents as f64 / 1e8) as f32;
+        let debt: i64 = self.sim.companies().first().map_or(0, |c| c.debt_cents);
+        let demand: u64 = self.sim.segments().iter().map(|s| s.demand_units).sum();
+        let months = self.sim.telemetry();
+        let last = months.last();
+        let mut obs = vec![
+            snap.months_run as f32 / self.config.episode_months as f32,
+            musd(snap.cash_cents),
+            musd(debt),
+            snap.asp_cents as f32 / 100.0,
+            snap.unit_cost_cents as f32 / 100.0,
+            snap.market_share,
+            snap.inventory_units as f32 / 1e6,
+            demand as f32 / 1e6,
+            musd(last.map_or(0, |m| m.revenue_cents)),
+            musd(last.map_or(0, |m| m.margin_cents)),
+        ];
+        let recent = |m: usize| months.len().checked_sub(m).map(|i| &months[i]);
+        obs.extend(
+            (1..=self.config.history_months)
+                .map(|m| recent(m).map_or(0.0, |row| row.sold_units as f32 / 1e6)),
+        );
+        obs.extend(
+            (1..=self.config.history_months)
+                .map(|m| recent(m).map_or(0.0, |row| musd(row.revenue_cents))),
+        );
+        obs
+    }
+}
@@ -0,0 +1,457 @@
+#![deny(warnings)]
+
+//! Stable API for embedding the chip-tycoon simulation in other tools.
+//!
+//! A [`Simulation`] starts from a shipped scenario or a sandbox setup, advances month by
+//! month, takes player [`Action`]s and reports its state as plain data. The ECS world and the
+//! runtime's resources stay private, so this surface follows semver while the simulation
+//! underneath changes: enums and read-only structs are `#[non_exhaustive]`, and input
+//! structs implement `Default` so callers can fill them with `..Default::default()`.
+//!
+//! Example:
+//! let mut sim = Simulation::start(&Start::Scenario { name: "campaign_1990s".into(), seed: 7 })?;
+//! sim.act(&Action::AdjustPrice { frac: -0.05 })?;
+//! let snap = sim.tick(12);
+//! println!("{} cash {}", snap.date, snap.cash_cents);
+
+use serde::{Deserialize, Serialize};
+use sim_runtime as runtime;
+use sim_runtime::World;
+
+pub mod env;
+#[cfg(feature = "python")]
+mod python;
+
+/// Why a start or an action failed.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// The scenario or sandbox setup could not be built.
+    #[error("invalid start: {0}")]
+    Start(String),
+    /// The simulation refused the action; its state is unchanged.
+    #[error("action rejected: {0}")]
+    Rejected(String),
+    /// A file the simulation writes to could not be opened or written.
+    #[error("i/o failed: {0}")]
+    Io(String),
+}
+
+/// Names of the shipped scenarios [`Start::Scenario`] accepts.
+pub fn scenarios() -> Vec<&'static str> {
+    runtime::SCENARIOS.iter().map(|(n, _)| *n).collect()
+}
+
+/// Where a game starts.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Start {
+    /// A shipped scenario, see [`scenarios`].
+    Scenario { name: String, seed: u64 },
+    /// A custom start.
+    Sandbox(Sandbox),
+}
+
+/// Starting conditions of a sandbox game; defaults match the 1990s campaign.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Sandbox {
+    pub start_year: i32,
+    pub years: u32,
+    pub player_cash_cents: i64,
+    pub competitors: usize,
+    /// Market segment ids in play; empty keeps every segment.
+    pub segments: Vec<String>,
+    /// A difficulty level, e.g. "easy" or "hard".
+    pub difficulty: Option<String>,
+    pub seed: u64,
+}
+
+impl Default for Sandbox {
+    fn default() -> Self {
+        let d = runtime::SandboxSetup::default();
+        Self {
+            start_year: d.start_year,
+            years: d.years,
+            player_cash_cents: d.player_cash_cents,
+            competitors: d.competitors,
+            segments: d.segments,
+            difficulty: d.difficulty,
+            seed: d.seed,
+        }
+    }
+}
+
+/// A product design to tape out.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Tapeout {
+    pub perf_index: f32,
+    pub die_area_mm2: f32,
+    pub tech_node: String,
+    /// Product family the design continues; it takes the next generation's name.
+    pub family: Option<String>,
+}
+
+impl Default for Tapeout {
+    fn default() -> Self {
+        Self {
+            perf_index: 0.7,
+            die_area_mm2: 100.0,
+            tech_node: "N90".into(),
+            family: None,
+        }
+    }
+}
+
+/// Something the player does between months.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Action {
+    /// Move the ASP by a fraction, e.g. -0.05 for a 5% cut; never below the margin floor.
+    AdjustPrice {
+        frac: f32,
+    },
+    /// Add to (or, negative, take from) the monthly R&D budget.
+    AdjustRnd {
+        cents: i64,
+    },
+    /// Contract foundry capacity at the going terms.
+    RequestCapacity {
+        wafers_per_month: u32,
+        months: u16,
+    },
+    Tapeout(Tapeout),
+    DrawCredit {
+        cents: i64,
+    },
+    RepayCredit {
+        cents: i64,
+    },
+    /// Answer a pending decision, see [`Simulation::pending_decisions`].
+    Decide {
+        event_id: String,
+        choice_id: String,
+    },
+}
+
+/// KPIs of the player's company after the last month run.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Snapshot {
+    pub months_run: u32,
+    /// ISO date of the month the game is in.
+    pub date: String,
+    pub cash_cents: i64,
+    pub revenue_cents: i64,
+    pub profit_cents: i64,
+    pub asp_cents: i64,
+    pub unit_cost_cents: i64,
+    pub market_share: f32,
+    pub inventory_units: u64,
+    /// Equal across runs and processes exactly while their states match.
+    pub state_hash: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Company {
+    pub name: String,
+    pub player: bool,
+    pub cash_cents: i64,
+    pub debt_cents: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Segment {
+    pub id: String,
+    pub name: String,
+    /// Base demand this month.
+    pub demand_units: u64,
+    pub ref_price_cents: i64,
+    /// The player's units sold into the segment last month.
+    pub sold_units: u64,
+}
+
+/// A decision the game is waiting on.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Decision {
+    pub event_id: String,
+    pub prompt: String,
+    pub choices: Vec<String>,
+    pub default_choice: String,
+}
+
+/// The player's results for one month run, see [`Simulation::telemetry`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Month {
+    /// Months since the start of the game, 1 for the first, across every [`Simulation::tick`].
+    pub month_index: u32,
+    pub output_units: u64,
+    pub sold_units: u64,
+    pub asp_cents: i64,
+    pub unit_cost_cents: i64,
+    pub revenue_cents: i64,
+    pub margin_cents: i64,
+    pub rd_budget_cents: i64,
+    /// Price level against the start of the game; divide money by it for real terms.
+    pub price_index: f64,
+}
+
+impl Month {
+    /// `row` of a run that started `months_before` months into the game.
+    fn from_row(row: &runtime::MonthlyTelemetry, months_before: u32) -> Self {
+        let cents = |d| persistence::decimal_to_cents_i64(d).unwrap_or(0);
+        Self {
+            month_index: months_before + row.month_index,
+            output_units: row.output_units,
+            sold_units: row.sold_units,
+            asp_cents: cents(row.asp_usd),
+            unit_cost_cents: cents(row.unit_cost_usd),
+            revenue_cents: cents(row.revenue_usd),
+            margin_cents: cents(row.margin_usd),
+            rd_budget_cents: row.rd_budget_cents,
+            price_index: row.price_index,
+        }
+    }
+}
+
+/// `{field: [value per month]}` for `months`, the column-wise telemetry the Python bindings
+/// hand out, one list per [`Month`] field.
+#[cfg(any(feature = "python", test))]
+fn columns(months: &[Month]) -> serde_json::Map<String, serde_json::Value> {
+    let mut cols = serde_json::Map::new();
+    for month in months {
+        if let Ok(serde_json::Value::Object(row)) = serde_json::to_value(month) {
+            for (k, v) in row {
+                if let serde_json::Value::Array(col) = cols
+                    .entry(k)
+                    .or_insert_with(|| serde_json::Value::Array(Vec::new()))
+                {
+                    col.push(v);
+                }
+            }
+        }
+    }
+    cols
+}
+
+/// A running game.
+pub struct Simulation {
+    world: World,
+    history: runtime::TelemetryLog<Month>,
+}
+
+impl Simulation {
+    pub fn start(start: &Start) -> Result<Self, Error> {
+        let (world, _) = match start {
+            Start::Scenario { name, seed } => runtime::scenario_world(name, *seed),
+            Start::Sandbox(s) => runtime::GameContent::shipped().and_then(|content| {
+                runtime::SandboxSetup {
+                    start_year: s.start_year,
+                    years: s.years,
+                    player_cash_cents: s.player_cash_cents,
+                    competitors: s.competitors,
+                    segments: s.segments.clone(),
+                    difficulty: s.difficulty.clone(),
+                    seed: s.seed,
+                    ..Default::default()
+                }
+                .build(&content)
+            }),
+        }
+        .map_err(Error::Start)?;
+        Ok(Self {
+            world,
+            history: runtime::TelemetryLog::default(),
+        })
+    }
+
+    /// An independent copy that advances separately, e.g. to try an action out.
+    pub fn fork(&self) -> Self {
+        Self {
+            world: runtime::clone_world_state(&self.world),
+            history: self.history.detached(),
+        }
+    }
+
+    /// Run up to `months` months. Stops early while a decision is pending if
+    /// [`Simulation::pause_on_decisions`] is set; otherwise defaults are taken.
+    pub fn tick(&mut self, months: u32) -> Snapshot {
+        // Telemetry counts from 1 on each run; history counts from the start of the game
+        let before = self.world.resource::<runtime::Stats>().months_run;
+        let (_, rows) = runtime::run_months_in_place(&mut self.world, months);
+        self.history
+            .extend(rows.iter().map(|r| Month::from_row(r, before)));
+        self.snapshot()
+    }
+
+    /// Pause ticking on decisions instead of taking their default choice.
+    pub fn pause_on_decisions(&mut self, pause: bool) {
+        self.world.insert_resource(if pause {
+            runtime::DecisionMode::Pause
+        } else {
+            runtime::DecisionMode::AutoDefault
+        });
+    }
+
+    pub fn act(&mut self, action: &Action) -> Result<(), Error> {
+        use runtime::PlayerCommand as C;
+        let command = match action {
+            Action::AdjustPrice { frac } => C::PriceDelta { frac: *frac },
+            Action::AdjustRnd { cents } => C::RdDelta { cents: *cents },
+            Action::RequestCapacity {
+                wafers_per_month,
+                months,
+            } => C::CapacityRequest {
+                wafers_per_month: *wafers_per_month,
+                months: *months,
+                billing_cents_per_wafer: None,
+                take_or_pay_frac: None,
+                ramp_wafers: None,
+                min_order_wafers: None,
+                rollover: false,
+            },
+            Action::Tapeout(t) => C::Tapeout {
+                design: runtime::TapeoutDesign {
+                    family: t.family.clone(),
+                    ..runtime::TapeoutDesign::new(t.perf_index, t.die_area_mm2, t.tech_node.clone())
+                },
+            },
+            Action::DrawCredit { cents } => C::DrawCredit { cents: *cents },
+            Action::RepayCredit { cents } => C::RepayCredit { cents: *cents },
+            Action::Decide {
+                event_id,
+                choice_id,
+            } => {
+                return runtime::apply_decision(&mut self.world, event_id, choice_id)
+                    .map_err(Error::Rejected)
+            }
+        };
+        command.apply(&mut self.world).map_err(Error::Rejected)
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        let s = runtime::build_snapshot(&self.world);
+        Snapshot {
+            months_run: s.months_run,
+            date: self.dom().macro_state.date.to_string(),
+            cash_cents: s.cash_cents,
+            revenue_cents: s.revenue_cents,
+            profit_cents: s.profit_cents,
+            asp_cents: s.asp_cents,
+            unit_cost_cents: s.unit_cost_cents,
+            market_share: s.market_share,
+            inventory_units: s.inventory_units,
+            state_hash: s.state_hash,
+        }
+    }
+
+    /// The player first, then rivals still in the market.
+    pub fn companies(&self) -> Vec<Company> {
+        let cents = |d| persistence::decimal_to_cents_i64(d).unwrap_or(0);
+        self.dom()
+            .companies
+            .iter()
+            .enumerate()
+            .map(|(i, c)| Company {
+                name: c.name.clone(),
+                player: i == 0,
+                cash_cents: cents(c.cash_usd),
+                debt_cents: cents(c.debt_usd),
+            })
+            .collect()
+    }
+
+    /// Segments on the market, as of the last month run.
+    pub fn segments(&self) -> Vec<Segment> {
+        self.world
+            .get_resource::<runtime::MarketTrends>()
+            .map(|t| {
+                t.0.iter()
+                    .map(|t| Segment {
+                        id: t.id.clone(),
+                        name: t.name.clone(),
+                        demand_units: t.base_demand_t,
+                        ref_price_cents: t.ref_price_t_cents,
+                        sold_units: t.sold_units,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Every month run so far, oldest first, or the latest ones under
+    /// [`Simulation::limit_telemetry`]; a fork keeps its parent's months.
+    pub fn telemetry(&self) -> &[Month] {
+        self.history.rows()
+    }
+
+    /// Keep only the latest `months` months of telemetry in memory, so century-long runs
+    /// stay bounded. Older months are dropped, after being appended to `spill` as JSON lines
+    /// if given; a fork keeps the limit but not the spill file.
+    pub fn limit_telemetry(
+        &mut self,
+        months: usize,
+        spill: Option<&std::path::Path>,
+    ) -> Result<(), Error> {
+        self.history.limit(months, spill).map_err(Error::Io)
+    }
+
+    pub fn pending_decisions(&self) -> Vec<Decision> {
+        self.world
+            .get_resource::<runtime::DecisionQueue>()
+            .map(|q| {
+                q.pending
+                    .iter()
+                    .map(|p| Decision {
+                        event_id: p.event_id.clone(),
+                        prompt: p.prompt.clone(),
+                        choices: p.choices.iter().map(|c| c.id.clone()).collect(),
+                        default_choice: p.default_choice.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn dom(&self) -> &sim_core::World {
+        &self.world.resource::<runtime::DomainWorld>().0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn telemetry_columns_hold_one_value_per_month_and_field() {
+        assert!(columns(&[]).is_empty());
+        let mut sim = Simulation::start(&Start::Scenario {
+            name: "campaign_1990s".into(),
+            seed: 7,
+        })
+        .unwrap();
+        sim.tick(3);
+        let cols = columns(sim.telemetry());
+        let row = serde_json::to_value(&sim.telemetry()[0]).unwrap();
+        let fields: Vec<&String> = row.as_object().unwrap().keys().collect();
+        assert_eq!(cols.keys().collect::<Vec<_>>(), fields);
+        for (field, col) in &cols {
+            let col = col.as_array().unwrap();
+            assert_eq!(col.len(), 3, "{field}");
+            for (month, v) in sim.telemetry().iter().zip(col) {
+                assert_eq!(&serde_json::to_value(month).unwrap()[field.as_str()], v);
+            }
+        }
+        assert_eq!(cols["month_index"], serde_json::json!([1, 2, 3]));
+        // Numbers stay numbers, ready for `numpy.asarray`
+        assert!(cols["price_index"].as_array().unwrap()[0].is_f64());
+        assert!(cols["revenue_cents"].as_array().unwrap()[0].is_i64());
+    }
+}
@@ -0,0 +1,186 @@
+//! Python bindings, built with the `python` feature (`maturin develop` in this crate).
+//!
+//! Starts, actions and results cross over as plain dicts in the same wire format the Rust
+//! API serializes to, so Python code reads like the JSON examples. Telemetry comes back
+//! column-wise, one list per [`Month`] field, ready for `numpy.asarray` or `pandas.DataFrame`.
+//!
+//! Example:
+//! sim = chip_tycoon.Simulation({"type": "scenario", "name": "campaign_1990s", "seed": 7})
+//! sim.act({"type": "adjust_price", "frac": -0.05})
+//! snap = sim.tick(12)
+//! sold = numpy.asarray(sim.telemetry()["sold_units"])
+
+// The `#[pymethods]` expansion converts `PyErr` into itself.
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::env::{ActionSpace, Env, EnvAction};
+use crate::{columns, Error, Simulation};
+
+impl From<Error> for PyErr {
+    fn from(e: Error) -> Self {
+        PyValueError::new_err(e.to_string())
+    }
+}
+
+/// Python value -> Rust, through Python's own `json` module.
+fn from_py<T: DeserializeOwned>(value: &Bound<'_, PyAny>) -> PyResult<T> {
+    let text: String = value
+        .py()
+        .import_bound("json")?
+        .call_method1("dumps", (value,))?
+        .extract()?;
+    serde_json::from_str(&text).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+fn to_py<T: Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    let text = serde_json::to_string(value).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(py
+        .import_bound("json")?
+        .call_method1("loads", (text,))?
+        .unbind())
+}
+
+#[pyclass(name = "Simulation", module = "chip_tycoon")]
+struct PySimulation(Simulation);
+
+#[pymethods]
+impl PySimulation {
+    #[new]
+    fn new(start: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Self(Simulation::start(&from_py(start)?)?))
+    }
+
+    fn fork(&self) -> Self {
+        Self(self.0.fork())
+    }
+
+    #[pyo3(signature = (months = 1))]
+    fn tick(&mut self, py: Python<'_>, months: u32) -> PyResult<PyObject> {
+        let snap = py.allow_threads(|| self.0.tick(months));
+        to_py(py, &snap)
+    }
+
+    fn pause_on_decisions(&mut self, pause: bool) {
+        self.0.pause_on_decisions(pause);
+    }
+
+    /// Raises `ValueError` with the reason when the game refuses the action.
+    fn act(&mut self, action: &Bound<'_, PyAny>) -> PyResult<()> {
+        Ok(self.0.act(&from_py(action)?)?)
+    }
+
+    fn snapshot(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_py(py, &self.0.snapshot())
+    }
+
+    fn companies(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_py(py, &self.0.companies())
+    }
+
+    fn segments(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_py(py, &self.0.segments())
+    }
+
+    fn pending_decisions(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_py(py, &self.0.pending_decisions())
+    }
+
+    fn telemetry(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_py(py, &columns(self.0.telemetry()))
+    }
+
+    #[pyo3(signature = (months, spill = None))]
+    fn limit_telemetry(
+        &mut self,
+        months: usize,
+        spill: Option<std::path::PathBuf>,
+    ) -> PyResult<()> {
+        Ok(self.0.limit_telemetry(months, spill.as_deref())?)
+    }
+}
+
+/// The gym-style environment; `reset` and `step` return what gymnasium's `Env` does, and
+/// `action_space` describes the space to build (`Discrete(n)` or a `Box` of levers).
+#[pyclass(name = "Env", module = "chip_tycoon")]
+struct PyEnv(Env);
+
+#[pymethods]
+impl PyEnv {
+    #[new]
+    #[pyo3(signature = (config = None))]
+    fn new(config: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let config = match config {
+            Some(c) => from_py(c)?,
+            None => Default::default(),
+        };
+        Ok(Self(Env::new(config)?))
+    }
+
+    #[getter]
+    fn observation_names(&self) -> Vec<String> {
+        self.0.observation_names()
+    }
+
+    #[getter]
+    fn action_space(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let space = match &self.0.config().actions {
+            ActionSpace::Discrete { choices } => {
+                serde_json::json!({ "type": "discrete", "n": choices.len() })
+            }
+            ActionSpace::Continuous { .. } => serde_json::json!({
+                "type": "box",
+                "low": -1.0,
+                "high": 1.0,
+                "shape": [self.0.config().actions.size()]
+            }),
+        };
+        to_py(py, &space)
+    }
+
+    /// `(observation, info)`
+    #[pyo3(signature = (seed = None))]
+    fn reset(&mut self, py: Python<'_>, seed: Option<u64>) -> PyResult<(Vec<f32>, PyObject)> {
+        let obs = py.allow_threads(|| self.0.reset(seed))?;
+        Ok((obs, to_py(py, &self.0.simulation().snapshot())?))
+    }
+
+    /// `(observation, reward, terminated, truncated, info)`; `action` is a choice index or a
+    /// list of lever values.
+    fn step(
+        &mut self,
+        py: Python<'_>,
+        action: &Bound<'_, PyAny>,
+    ) -> PyResult<(Vec<f32>, f64, bool, bool, PyObject)> {
+        let action = match action.extract::<usize>() {
+            Ok(i) => EnvAction::Discrete(i),
+            Err(_) => EnvAction::Continuous(action.extract()?),
+        };
+        let step = py.allow_threads(|| self.0.step(&action));
+        let info = serde_json::json!({ "snapshot": step.snapshot, "rejected": step.rejected });
+        Ok((
+            step.observation,
+            step.reward,
+            step.terminated,
+            step.truncated,
+            to_py(py, &info)?,
+        ))
+    }
+}
+
+#[pyfunction]
+fn scenarios() -> Vec<&'static str> {
+    crate::scenarios()
+}
+
+#[pymodule]
+fn chip_tycoon(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(scenarios, m)?)?;
+    m.add_class::<PySimulation>()?;
+    m.add_class::<PyEnv>()?;
+    Ok(())
+}
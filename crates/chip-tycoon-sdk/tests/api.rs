@@ -0,0 +1,330 @@
+//! Pins the SDK's public surface: signatures, wire formats and behaviour embedders rely on.
+//! A change that breaks these tests needs a major version bump.
+
+use chip_tycoon_sdk::env::{ActionSpace, Env, EnvAction, EnvConfig, Reward, OBSERVATION};
+use chip_tycoon_sdk::{
+    scenarios, Action, Company, Decision, Error, Month, Sandbox, Segment, Simulation, Snapshot,
+    Start, Tapeout,
+};
+use serde_json::json;
+
+fn campaign(seed: u64) -> Simulation {
+    Simulation::start(&Start::Scenario {
+        name: "campaign_1990s".into(),
+        seed,
+    })
+    .unwrap()
+}
+
+#[test]
+fn signatures_are_stable() {
+    let _: fn() -> Vec<&'static str> = scenarios;
+    let _: fn(&Start) -> Result<Simulation, Error> = Simulation::start;
+    let _: fn(&Simulation) -> Simulation = Simulation::fork;
+    let _: fn(&mut Simulation, u32) -> Snapshot = Simulation::tick;
+    let _: fn(&mut Simulation, bool) = Simulation::pause_on_decisions;
+    let _: fn(&mut Simulation, &Action) -> Result<(), Error> = Simulation::act;
+    let _: fn(&Simulation) -> Snapshot = Simulation::snapshot;
+    let _: fn(&Simulation) -> Vec<Company> = Simulation::companies;
+    let _: fn(&Simulation) -> Vec<Segment> = Simulation::segments;
+    let _: fn(&Simulation) -> Vec<Decision> = Simulation::pending_decisions;
+    let _: fn(&Simulation) -> &[Month] = Simulation::telemetry;
+    let _: fn(&mut Simulation, usize, Option<&std::path::Path>) -> Result<(), Error> =
+        Simulation::limit_telemetry;
+    fn error_trait<E: std::error::Error + Send + Sync + 'static>() {}
+    error_trait::<Error>();
+    fn send<T: Send>() {}
+    send::<Simulation>();
+}
+
+#[test]
+fn wire_formats_are_stable() {
+    assert_eq!(
+        serde_json::to_value(Start::Scenario {
+            name: "campaign_1990s".into(),
+            seed: 7
+        })
+        .unwrap(),
+        json!({ "type": "scenario", "name": "campaign_1990s", "seed": 7 })
+    );
+    let start: Start =
+        serde_json::from_value(json!({ "type": "sandbox", "competitors": 1 })).unwrap();
+    assert_eq!(
+        start,
+        Start::Sandbox(Sandbox {
+            competitors: 1,
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(Action::AdjustPrice { frac: -0.5 }).unwrap(),
+        json!({ "type": "adjust_price", "frac": -0.5 })
+    );
+    assert_eq!(
+        serde_json::to_value(Action::Tapeout(Tapeout {
+            family: Some("hawk".into()),
+            ..Default::default()
+        }))
+        .unwrap()["family"],
+        json!("hawk")
+    );
+    let action: Action =
+        serde_json::from_value(json!({ "type": "decide", "event_id": "e", "choice_id": "c" }))
+            .unwrap();
+    assert_eq!(
+        action,
+        Action::Decide {
+            event_id: "e".into(),
+            choice_id: "c".into()
+        }
+    );
+    let snap = serde_json::to_value(campaign(1).snapshot()).unwrap();
+    let mut keys: Vec<&str> = snap
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(|k| k.as_str())
+        .collect();
+    keys.sort_unstable();
+    assert_eq!(
+        keys,
+        [
+            "asp_cents",
+            "cash_cents",
+            "date",
+            "inventory_units",
+            "market_share",
+            "months_run",
+            "profit_cents",
+            "revenue_cents",
+            "state_hash",
+            "unit_cost_cents"
+        ]
+    );
+}
+
+#[test]
+fn starts_ticks_and_inspects() {
+    assert!(scenarios().contains(&"campaign_1990s"));
+    assert!(matches!(
+        Simulation::start(&Start::Scenario {
+            name: "nope".into(),
+            seed: 1
+        }),
+        Err(Error::Start(_))
+    ));
+    let mut sim = campaign(7);
+    let start = sim.snapshot();
+    assert_eq!((start.months_run, start.date.as_str()), (0, "1990-01-01"));
+    let companies = sim.companies();
+    assert!(companies[0].player && companies[1..].iter().all(|c| !c.player));
+
+    // Same start, same actions: same state, in this process or any other
+    let mut twin = campaign(7);
+    for s in [&mut sim, &mut twin] {
+        s.act(&Action::AdjustPrice { frac: -0.05 }).unwrap();
+        s.act(&Action::Tapeout(Tapeout::default())).unwrap();
+    }
+    let snap = sim.tick(6);
+    assert_eq!(snap.months_run, 6);
+    assert_eq!(snap.date, "1990-07-01");
+    assert_eq!(twin.tick(6), snap);
+    let months = sim.telemetry();
+    assert_eq!(months.len(), 6);
+    assert_eq!(months, twin.telemetry());
+    assert!(months
+        .iter()
+        .any(|m| m.sold_units > 0 && m.revenue_cents > 0));
+    assert!(sim
+        .segments()
+        .iter()
+        .any(|s| s.id == "desktop" && s.demand_units > 0));
+
+    // A fork runs on its own
+    let mut fork = sim.fork();
+    fork.tick(1);
+    assert_eq!(sim.snapshot(), snap);
+    assert_ne!(fork.snapshot().state_hash, snap.state_hash);
+    assert_eq!((sim.telemetry().len(), fork.telemetry().len()), (6, 7));
+
+    // Rejected actions say why and leave the game as it was
+    let bad = Action::Tapeout(Tapeout {
+        die_area_mm2: -1.0,
+        ..Default::default()
+    });
+    assert!(matches!(sim.act(&bad), Err(Error::Rejected(_))));
+    assert_eq!(sim.snapshot(), snap);
+
+    let sandbox = Simulation::start(&Start::Sandbox(Sandbox {
+        competitors: 1,
+        segments: vec!["desktop".into()],
+        ..Default::default()
+    }))
+    .unwrap();
+    assert_eq!(sandbox.companies().len(), 2);
+    assert!(matches!(
+        Simulation::start(&Start::Sandbox(Sandbox {
+            start_year: 1800,
+            ..Default::default()
+        })),
+        Err(Error::Start(_))
+    ));
+}
+
+#[test]
+fn telemetry_spills_past_its_limit() {
+    let path = std::env::temp_dir().join(format!("telemetry-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut sim = campaign(7);
+    sim.tick(3);
+    sim.limit_telemetry(2, Some(&path)).unwrap();
+    assert_eq!(sim.telemetry().len(), 2);
+    sim.tick(4);
+    let months: Vec<u32> = sim.telemetry().iter().map(|m| m.month_index).collect();
+    assert_eq!(months, [6, 7]);
+    // Older months are in the spill file, oldest first
+    let spilled: Vec<Month> = std::fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    let indexes: Vec<u32> = spilled.iter().map(|m| m.month_index).collect();
+    assert_eq!(indexes, [1, 2, 3, 4, 5]);
+    // A fork keeps the limit but leaves the file alone
+    let mut fork = sim.fork();
+    fork.tick(3);
+    assert_eq!(fork.telemetry().len(), 2);
+    assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 5);
+    assert!(matches!(
+        sim.limit_telemetry(1, Some(&std::env::temp_dir().join("missing/dir/t.jsonl"))),
+        Err(Error::Io(_))
+    ));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn decisions_pause_until_answered() {
+    let mut sim = campaign(3);
+    sim.pause_on_decisions(true);
+    let snap = sim.tick(36);
+    assert!(snap.months_run < 36);
+    let pending = sim.pending_decisions();
+    let d = pending
+        .iter()
+        .find(|d| d.event_id == "national_chip_program_1992")
+        .unwrap();
+    assert!(d.choices.contains(&d.default_choice));
+    assert!(matches!(
+        sim.act(&Action::Decide {
+            event_id: d.event_id.clone(),
+            choice_id: "nope".into()
+        }),
+        Err(Error::Rejected(_))
+    ));
+    for d in pending {
+        sim.act(&Action::Decide {
+            event_id: d.event_id,
+            choice_id: d.default_choice,
+        })
+        .unwrap();
+    }
+    assert!(sim.tick(1).months_run > snap.months_run);
+}
+
+#[test]
+fn env_steps_and_resets() {
+    let mut env = Env::new(EnvConfig {
+        history_months: 2,
+        ..Default::default()
+    })
+    .unwrap();
+    let names = env.observation_names();
+    assert_eq!(names.len(), OBSERVATION.len() + 4);
+    assert_eq!(names.last().unwrap(), "revenue_musd_t-2");
+
+    // Same seed, same choices: same episode
+    let run = |env: &mut Env, seed| {
+        let obs = env.reset(Some(seed)).unwrap();
+        assert_eq!(obs.len(), names.len());
+        (0..6)
+            .map(|i| env.step(&EnvAction::Discrete(i % 3)))
+            .collect::<Vec<_>>()
+    };
+    let a = run(&mut env, 5);
+    assert_eq!(a, run(&mut env, 5));
+    assert_ne!(
+        a.last().unwrap().snapshot,
+        run(&mut env, 6).last().unwrap().snapshot
+    );
+    let last = a.last().unwrap();
+    assert_eq!(last.snapshot.months_run, 6);
+    assert!(last.observation[OBSERVATION.len()] > 0.0 && last.reward != 0.0);
+
+    // Out-of-range choices are reported, not fatal
+    let step = env.step(&EnvAction::Discrete(99));
+    assert_eq!(step.rejected.len(), 1);
+    assert!(Env::new(EnvConfig {
+        actions: ActionSpace::Discrete { choices: vec![] },
+        ..Default::default()
+    })
+    .is_err());
+
+    // Continuous levers, reward on cash only, truncated at the episode length
+    let config: EnvConfig = serde_json::from_value(json!({
+        "episode_months": 4,
+        "months_per_step": 3,
+        "actions": {
+            "type": "continuous",
+            "max_price_frac": 0.1,
+            "max_rnd_cents": 100000000,
+            "max_wafers_per_month": 2000,
+            "contract_months": 12
+        },
+        "reward": { "cash": 1.0, "profit": 0.0 }
+    }))
+    .unwrap();
+    assert_eq!(
+        config.reward,
+        Reward {
+            cash: 1.0,
+            profit: 0.0,
+            ..Default::default()
+        }
+    );
+    let mut env = Env::new(config).unwrap();
+    env.reset(None).unwrap();
+    let cash = env.simulation().snapshot().cash_cents;
+    let step = env.step(&EnvAction::Continuous(vec![-0.5, 0.0, 1.0]));
+    assert!(step.rejected.is_empty() && !step.truncated);
+    assert_eq!(step.snapshot.months_run, 3);
+    assert!((step.reward - (step.snapshot.cash_cents - cash) as f64 / 1e8).abs() < 1e-9);
+    let step = env.step(&EnvAction::Continuous(vec![0.0, 0.0, 0.0]));
+    assert_eq!((step.snapshot.months_run, step.truncated), (4, true));
+    assert_eq!(
+        env.step(&EnvAction::Discrete(0)).rejected,
+        ["expected continuous levers"]
+    );
+}
+
+#[test]
+fn env_runs_on_through_negative_cash() {
+    // Burn cash on R&D: the campaign only fails at -$100M, so the first months in the red
+    // keep the episode going
+    let mut env = Env::new(EnvConfig {
+        actions: ActionSpace::Discrete {
+            choices: vec![vec![Action::AdjustRnd {
+                cents: 1_000_000_000,
+            }]],
+        },
+        ..Default::default()
+    })
+    .unwrap();
+    env.reset(None).unwrap();
+    let step = (0..12)
+        .map(|_| env.step(&EnvAction::Discrete(0)))
+        .find(|s| s.snapshot.cash_cents < 0)
+        .expect("cash goes negative");
+    assert!(step.snapshot.cash_cents > -100_000_000);
+    assert!(!step.terminated && !step.truncated);
+    assert!(step.observation[1] < 0.0);
+}
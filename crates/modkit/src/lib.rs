@@ -8,6 +8,7 @@ use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use rust_decimal::Decimal;
 use serde::Deserialize;
 use sim_core as core;
+use std::cell::Cell;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
@@ -23,6 +24,9 @@ pub struct ModMeta {
     pub engine_schema_version: u32,
     pub compat: Option<String>,
     pub hooks: Option<Vec<String>>, // e.g., ["time_trigger"]
+    /// Named demand, share and cost model parameterizations market configs can pick.
+    #[serde(default)]
+    pub models: sim_econ::ModelPresets,
 }
 
 #[derive(Debug, Error)]
@@ -86,6 +90,16 @@ pub struct ActiveEffect {
     patches: Vec<Patch>,
 }
 
+thread_local! {
+    static DIR_LOADS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Mod directories read by [`ModEngine::load_all`] on this thread so far. Engines are not
+/// `Send`, so this counts the disk loads behind the worlds a thread builds.
+pub fn dir_loads_on_thread() -> u64 {
+    DIR_LOADS.with(Cell::get)
+}
+
 /// Mod engine: loads mods and applies effects when triggers fire.
 pub struct ModEngine {
     root: PathBuf,
@@ -104,7 +118,19 @@ impl ModEngine {
         }
     }
 
+    /// Same mods and active effects on a fresh script engine, without reading the mod
+    /// directory again.
+    pub fn fork(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            engine: Engine::new(),
+            mods: self.mods.clone(),
+            active: self.active.clone(),
+        }
+    }
+
     pub fn load_all(&mut self) -> Result<(), ModError> {
+        DIR_LOADS.with(|n| n.set(n.get() + 1));
         let entries = fs::read_dir(&self.root)?;
         self.mods.clear();
         for ent in entries {
@@ -121,6 +147,9 @@ impl ModEngine {
             let meta_text = fs::read_to_string(&meta_path)?;
             let meta: ModMeta = serde_yaml::from_str(&meta_text)
                 .map_err(|e| ModError::InvalidMeta(e.to_string()))?;
+            meta.models
+                .validate()
+                .map_err(|e| ModError::InvalidMeta(format!("{}: {e}", meta.id)))?;
             let mtime = fs::metadata(&script_path)?
                 .modified()
                 .unwrap_or(SystemTime::UNIX_EPOCH);
@@ -151,36 +180,39 @@ impl ModEngine {
     /// Progress simulation date and apply or expire effects.
     pub fn tick(&mut self, world: &mut core::World, date: NaiveDate) -> Result<(), ModError> {
         self.expire_effects(world, date);
-        let mut to_apply: Vec<EffectSpec> = Vec::new();
-        for m in &self.mods {
-            if let Some(spec) = self.eval_time_trigger_with_meta(m)? {
-                let end = add_months(spec.start, spec.months);
-                if spec.start == date && !self.is_effect_active(&m.meta.id, spec.start, end) {
-                    to_apply.push(spec);
-                }
+        for (id, spec) in self.due_effects(date)? {
+            let end = add_months(spec.start, spec.months);
+            if !self.is_effect_active(&id, spec.start, end) {
+                self.apply_effect_with_id(world, &spec, Some(&id));
             }
         }
-        for spec in to_apply {
-            // Determine the mod id for this spec by re-evaluating metadata source
-            // We find the first loaded mod that matches the trigger for this start date.
-            // Fallback to a generic id if not found (tests).
-            let mut id_opt: Option<String> = None;
-            for m in &self.mods {
-                if let Ok(Some(s2)) = self.eval_time_trigger_with_meta(m) {
-                    if s2.start == spec.start
-                        && s2.months == spec.months
-                        && (s2.cost_increase_pct - spec.cost_increase_pct).abs() < f32::EPSILON
-                        && (s2.yield_delta - spec.yield_delta).abs() < f32::EPSILON
-                    {
-                        id_opt = Some(m.meta.id.clone());
-                        break;
-                    }
+        Ok(())
+    }
+
+    /// Model presets of the loaded mods; where two mods register the same name, the one
+    /// with the smaller mod id wins.
+    pub fn model_presets(&self) -> sim_econ::ModelPresets {
+        let mut mods: Vec<&LoadedMod> = self.mods.iter().collect();
+        mods.sort_by(|a, b| a.meta.id.cmp(&b.meta.id));
+        let mut out = sim_econ::ModelPresets::default();
+        for m in mods {
+            out.merge(&m.meta.models);
+        }
+        out
+    }
+
+    /// Tech effects of loaded mods whose trigger starts on `date`, by mod id. Callers that
+    /// keep their own effect state apply these instead of going through `tick`.
+    pub fn due_effects(&self, date: NaiveDate) -> Result<Vec<(String, EffectSpec)>, ModError> {
+        let mut due = Vec::new();
+        for m in &self.mods {
+            if let Some(spec) = self.eval_time_trigger_with_meta(m)? {
+                if spec.start == date {
+                    due.push((m.meta.id.clone(), spec));
                 }
             }
-            let id_str = id_opt.as_deref();
-            self.apply_effect_with_id(world, &spec, id_str);
         }
-        Ok(())
+        Ok(due)
     }
 
     fn is_effect_active(&self, id: &str, start: NaiveDate, end: NaiveDate) -> bool {
@@ -327,6 +359,17 @@ impl ModEngine {
         self.active = still_active;
     }
 
+    /// Apply a tech effect originating outside of loaded mods (e.g., a player decision).
+    /// The effect is tracked and expired like script-driven effects; re-applying the same
+    /// id/window is a no-op.
+    pub fn apply_external_effect(&mut self, world: &mut core::World, spec: &EffectSpec, id: &str) {
+        let end = add_months(spec.start, spec.months);
+        if self.is_effect_active(id, spec.start, end) {
+            return;
+        }
+        self.apply_effect_with_id(world, spec, Some(id));
+    }
+
     /// Return a summary of active tech effects (id/start/end) for telemetry/UI.
     pub fn active_effects_summary(&self) -> Vec<(String, NaiveDate, NaiveDate)> {
         self.active
@@ -424,6 +467,120 @@ pub fn cost_multiplier(x: Decimal) -> Decimal {
     }
 }
 
+/// Script operations allowed per `on_month` call; a runaway loop errors instead of hanging.
+pub const STRATEGY_MAX_OPERATIONS: u64 = 1_000_000;
+
+/// A player strategy script. It defines `fn on_month(view)`, which gets the player's view
+/// of the game as a map and returns an array of action maps (or nothing); `this` inside it
+/// is a map the script keeps between months.
+///
+/// Scripts run without the clock, file or module access of a full engine, so the same
+/// views give the same actions.
+///
+/// Example:
+/// fn on_month(view) {
+///     if view.tapeouts_queued == 0 { [#{ type: "tapeout", design: view.tapeout_template }] }
+/// }
+pub struct StrategyScript {
+    engine: Engine,
+    ast: rhai::AST,
+    memory: rhai::Dynamic,
+}
+
+impl StrategyScript {
+    pub fn compile(source: &str) -> Result<Self, ModError> {
+        let engine = strategy_engine();
+        let ast = engine.compile(source)?;
+        if !ast
+            .iter_functions()
+            .any(|f| f.name == "on_month" && f.params.len() == 1)
+        {
+            return Err(ModError::InvalidMeta(
+                "strategy script has no fn on_month(view)".into(),
+            ));
+        }
+        Ok(Self {
+            engine,
+            ast,
+            memory: rhai::Map::new().into(),
+        })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ModError> {
+        Self::compile(&fs::read_to_string(path)?)
+    }
+
+    /// Same script and memory on a fresh engine.
+    pub fn fork(&self) -> Self {
+        Self {
+            engine: strategy_engine(),
+            ast: self.ast.clone(),
+            memory: self.memory.clone(),
+        }
+    }
+
+    /// Call `on_month` with `view_json`, a JSON object, and return its actions as JSON
+    /// objects, in order.
+    pub fn on_month(&mut self, view_json: &str) -> Result<Vec<String>, ModError> {
+        let view = self
+            .engine
+            .parse_json(view_json, true)
+            .map_err(|e| ModError::Rhai(e.to_string()))?;
+        let options = rhai::CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut self.memory);
+        let out: rhai::Dynamic = self
+            .engine
+            .call_fn_with_options(
+                options,
+                &mut rhai::Scope::new(),
+                &self.ast,
+                "on_month",
+                (view,),
+            )
+            .map_err(|e| ModError::Rhai(e.to_string()))?;
+        if out.is_unit() {
+            return Ok(Vec::new());
+        }
+        let actions = out
+            .try_cast::<rhai::Array>()
+            .ok_or_else(|| ModError::Rhai("on_month must return an array of maps".into()))?;
+        actions
+            .into_iter()
+            .map(|a| {
+                a.try_cast::<rhai::Map>()
+                    .map(|m| rhai::format_map_as_json(&m))
+                    .ok_or_else(|| ModError::Rhai("every action must be a map".into()))
+            })
+            .collect()
+    }
+
+    /// What the script keeps in `this`, as JSON.
+    pub fn memory_json(&self) -> String {
+        match self.memory.read_lock::<rhai::Map>() {
+            Some(m) => rhai::format_map_as_json(&m),
+            None => "{}".into(),
+        }
+    }
+}
+
+fn strategy_engine() -> Engine {
+    use rhai::packages::{
+        BasicArrayPackage, BasicMapPackage, BasicMathPackage, CorePackage, LogicPackage,
+        MoreStringPackage, Package,
+    };
+    let mut engine = Engine::new_raw();
+    engine.register_global_module(CorePackage::new().as_shared_module());
+    engine.register_global_module(LogicPackage::new().as_shared_module());
+    engine.register_global_module(BasicMathPackage::new().as_shared_module());
+    engine.register_global_module(BasicArrayPackage::new().as_shared_module());
+    engine.register_global_module(BasicMapPackage::new().as_shared_module());
+    engine.register_global_module(MoreStringPackage::new().as_shared_module());
+    engine.set_max_operations(STRATEGY_MAX_OPERATIONS);
+    engine.on_print(|s| info!(target: "strategy", "{s}"));
+    engine
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -505,6 +662,80 @@ mod tests {
         assert_eq!(node.yield_baseline, Decimal::new(90, 2));
     }
 
+    #[test]
+    fn fork_keeps_active_effects() {
+        let mut world = core::World {
+            macro_state: core::MacroState {
+                date: NaiveDate::from_ymd_opt(1998, 1, 1).unwrap(),
+                inflation_annual: 0.0,
+                interest_rate: 0.0,
+                fx_usd_index: 100.0,
+            },
+            tech_tree: vec![core::TechNode {
+                id: core::TechNodeId("N".into()),
+                year_available: 1990,
+                density_mtr_per_mm2: Decimal::new(1, 0),
+                freq_ghz_baseline: Decimal::new(1, 0),
+                leakage_index: Decimal::new(1, 0),
+                yield_baseline: Decimal::new(90, 2),
+                wafer_cost_usd: Decimal::new(1000, 0),
+                mask_set_cost_usd: Decimal::new(5000, 0),
+                dependencies: vec![],
+            }],
+            companies: vec![],
+            segments: vec![],
+        };
+        let mut eng = ModEngine::new(".");
+        let spec = EffectSpec {
+            start: NaiveDate::from_ymd_opt(1998, 1, 1).unwrap(),
+            months: 6,
+            cost_increase_pct: 15.0,
+            yield_delta: 0.0,
+        };
+        eng.apply_effect_with_id(&mut world, &spec, Some("shortage"));
+        let mut fork = eng.fork();
+        let mut copy = world.clone();
+        // The fork expires the effect it inherited, leaving the original untouched
+        fork.tick(&mut copy, NaiveDate::from_ymd_opt(1998, 7, 1).unwrap())
+            .unwrap();
+        assert_eq!(copy.tech_tree[0].wafer_cost_usd, Decimal::new(1000, 0));
+        assert_eq!(world.tech_tree[0].wafer_cost_usd, Decimal::new(1150, 0));
+        assert_eq!(eng.active.len(), 1);
+    }
+
+    #[test]
+    fn strategy_scripts_return_actions_and_remember() {
+        let mut s = StrategyScript::compile(
+            "fn on_month(view) { this.n = (this.n ?? 0) + 1; [#{ type: \"hold\", n: this.n, cash: view.cash }] }",
+        )
+        .unwrap();
+        assert_eq!(
+            s.on_month(r#"{ "cash": 5 }"#).unwrap(),
+            [r#"{"cash":5,"n":1,"type":"hold"}"#]
+        );
+        s.on_month("{}").unwrap();
+        let mut fork = s.fork();
+        assert_eq!(s.memory_json(), r#"{"n":2}"#);
+        fork.on_month("{}").unwrap();
+        assert_eq!(
+            (s.memory_json(), fork.memory_json()),
+            (r#"{"n":2}"#.into(), r#"{"n":3}"#.into())
+        );
+
+        let mut quiet = StrategyScript::compile("fn on_month(view) { }").unwrap();
+        assert!(quiet.on_month("{}").unwrap().is_empty());
+        for bad in [
+            "fn on_month(view) { 42 }",
+            "fn on_month(view) { [1] }",
+            "fn on_month(view) { loop { } }",
+            "fn on_month(view) { timestamp(); [] }",
+        ] {
+            let mut s = StrategyScript::compile(bad).unwrap();
+            assert!(matches!(s.on_month("{}"), Err(ModError::Rhai(_))), "{bad}");
+        }
+        assert!(StrategyScript::compile("fn on_month() { [] }").is_err());
+    }
+
     #[test]
     fn test_cost_multiplier() {
         use rust_decimal::Decimal as D;
@@ -5,13 +5,14 @@
 use anyhow::{anyhow, Result};
 use parquet::basic::{Repetition, Type as PhysicalType};
 use parquet::column::writer::ColumnWriter;
+use parquet::data_type::{ByteArrayType, DataType, DoubleType, Int32Type, Int64Type};
 use parquet::file::properties::WriterProperties;
 use parquet::file::writer::SerializedFileWriter;
 use parquet::schema::types::Type;
 use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use rust_decimal::Decimal;
 use sim_core as core;
-use sqlx::{migrate::Migrator, Pool, Row, Sqlite, SqlitePool};
+use sqlx::{migrate::Migrator, Pool, Row, Sqlite, SqliteConnection, SqliteExecutor, SqlitePool};
 use std::fs::File;
 use std::path::Path;
 use std::sync::Arc;
@@ -48,7 +49,7 @@ pub async fn create_save(
 
 /// Create a save with an explicit status (e.g., 'in_progress' for autosaves).
 pub async fn create_save_with_status(
-    pool: &Pool<Sqlite>,
+    db: impl SqliteExecutor<'_>,
     name: &str,
     description: Option<&str>,
     status: &str,
@@ -59,17 +60,21 @@ pub async fn create_save_with_status(
     .bind(name)
     .bind(description)
     .bind(status)
-    .fetch_one(pool)
+    .fetch_one(db)
     .await?;
     Ok(rec.try_get("id").unwrap_or(0))
 }
 
 /// Update save status.
-pub async fn update_save_status(pool: &Pool<Sqlite>, save_id: i64, status: &str) -> Result<()> {
+pub async fn update_save_status(
+    db: impl SqliteExecutor<'_>,
+    save_id: i64,
+    status: &str,
+) -> Result<()> {
     let _ = sqlx::query(r#"UPDATE saves SET status = ?1 WHERE id = ?2"#)
         .bind(status)
         .bind(save_id)
-        .execute(pool)
+        .execute(db)
         .await?;
     Ok(())
 }
@@ -122,23 +127,24 @@ pub fn deserialize_world_bincode(bytes: &[u8]) -> Result<core::World> {
     Ok(bincode::deserialize(bytes)?)
 }
 
-/// Store a snapshot blob for a given save.
+/// Store a snapshot blob for a given save, with its checksum.
 pub async fn insert_snapshot(
-    pool: &Pool<Sqlite>,
+    db: impl SqliteExecutor<'_>,
     save_id: i64,
     month_index: i64,
     format: &str,
     data: &[u8],
 ) -> Result<i64> {
     let rec = sqlx::query(
-        r#"INSERT INTO snapshots (save_id, month_index, format, data)
-           VALUES (?1, ?2, ?3, ?4) RETURNING id"#,
+        r#"INSERT INTO snapshots (save_id, month_index, format, data, checksum)
+           VALUES (?1, ?2, ?3, ?4, ?5) RETURNING id"#,
     )
     .bind(save_id)
     .bind(month_index)
     .bind(format)
     .bind(data)
-    .fetch_one(pool)
+    .bind(checksum64(data))
+    .fetch_one(db)
     .await?;
     let id: i64 = rec.try_get("id").unwrap_or(0);
     Ok(id)
@@ -177,13 +183,34 @@ pub struct ContractRow {
     pub lead_time_months: i64,
     pub start: String,
     pub end: String,
+    pub compensation_cents_per_wafer: i64,
+    /// Tech node of node-bound capacity; `None` for general capacity.
+    #[serde(default)]
+    pub node: Option<String>,
+    /// Committed wafers for the first months of the term; empty runs flat.
+    #[serde(default)]
+    pub ramp_wafers: Vec<i64>,
+    #[serde(default)]
+    pub min_order_wafers: i64,
+    /// Whether unused paid wafers are banked as credits, and how many are banked.
+    #[serde(default)]
+    pub rollover: bool,
+    #[serde(default)]
+    pub banked_wafers: i64,
+    /// Stable id given when the contract was signed; empty in older saves.
+    #[serde(default)]
+    pub contract_id: String,
 }
 
-pub async fn insert_contract(pool: &Pool<Sqlite>, save_id: i64, c: &ContractRow) -> Result<i64> {
+pub async fn insert_contract(
+    db: impl SqliteExecutor<'_>,
+    save_id: i64,
+    c: &ContractRow,
+) -> Result<i64> {
     let rec = sqlx::query(
         r#"INSERT INTO foundry_contracts
-            (save_id, foundry_id, wafers_per_month, price_per_wafer_cents, take_or_pay_frac, billing_cents_per_wafer, billing_model, lead_time_months, start, end)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10) RETURNING id"#,
+            (save_id, foundry_id, wafers_per_month, price_per_wafer_cents, take_or_pay_frac, billing_cents_per_wafer, billing_model, lead_time_months, start, end, compensation_cents_per_wafer, node, ramp_wafers, min_order_wafers, rollover, banked_wafers, contract_id)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17) RETURNING id"#,
     )
     .bind(save_id)
     .bind(&c.foundry_id)
@@ -195,18 +222,25 @@ pub async fn insert_contract(pool: &Pool<Sqlite>, save_id: i64, c: &ContractRow)
     .bind(c.lead_time_months)
     .bind(&c.start)
     .bind(&c.end)
-    .fetch_one(pool)
+    .bind(c.compensation_cents_per_wafer)
+    .bind(&c.node)
+    .bind(serde_json::to_string(&c.ramp_wafers)?)
+    .bind(c.min_order_wafers)
+    .bind(c.rollover)
+    .bind(c.banked_wafers)
+    .bind(&c.contract_id)
+    .fetch_one(db)
     .await?;
     Ok(rec.try_get("id").unwrap_or(0))
 }
 
-pub async fn list_contracts(pool: &Pool<Sqlite>, save_id: i64) -> Result<Vec<ContractRow>> {
+pub async fn list_contracts(db: impl SqliteExecutor<'_>, save_id: i64) -> Result<Vec<ContractRow>> {
     let rows = sqlx::query(
-        r#"SELECT foundry_id, wafers_per_month, price_per_wafer_cents, take_or_pay_frac, billing_cents_per_wafer, billing_model, lead_time_months, start, end
+        r#"SELECT foundry_id, wafers_per_month, price_per_wafer_cents, take_or_pay_frac, billing_cents_per_wafer, billing_model, lead_time_months, start, end, compensation_cents_per_wafer, node, ramp_wafers, min_order_wafers, rollover, banked_wafers, contract_id
             FROM foundry_contracts WHERE save_id = ?1 ORDER BY id"#,
     )
     .bind(save_id)
-    .fetch_all(pool)
+    .fetch_all(db)
     .await?;
     Ok(rows
         .into_iter()
@@ -220,6 +254,67 @@ pub async fn list_contracts(pool: &Pool<Sqlite>, save_id: i64) -> Result<Vec<Con
             lead_time_months: r.try_get("lead_time_months").unwrap_or(0),
             start: r.try_get("start").unwrap_or_default(),
             end: r.try_get("end").unwrap_or_default(),
+            compensation_cents_per_wafer: r.try_get("compensation_cents_per_wafer").unwrap_or(0),
+            node: r.try_get("node").unwrap_or(None),
+            ramp_wafers: r
+                .try_get::<String, _>("ramp_wafers")
+                .ok()
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or_default(),
+            min_order_wafers: r.try_get("min_order_wafers").unwrap_or(0),
+            rollover: r.try_get("rollover").unwrap_or(false),
+            banked_wafers: r.try_get("banked_wafers").unwrap_or(0),
+            contract_id: r.try_get("contract_id").unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Learned delivery reliability of one foundry.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct FoundryScoreRow {
+    pub foundry_id: String,
+    pub score: f32,
+    pub months_observed: i64,
+    pub misses: i64,
+}
+
+pub async fn insert_foundry_score(
+    db: impl SqliteExecutor<'_>,
+    save_id: i64,
+    f: &FoundryScoreRow,
+) -> Result<i64> {
+    let rec = sqlx::query(
+        r#"INSERT INTO foundry_scores
+            (save_id, foundry_id, score, months_observed, misses)
+            VALUES (?1, ?2, ?3, ?4, ?5) RETURNING id"#,
+    )
+    .bind(save_id)
+    .bind(&f.foundry_id)
+    .bind(f.score)
+    .bind(f.months_observed)
+    .bind(f.misses)
+    .fetch_one(db)
+    .await?;
+    Ok(rec.try_get("id").unwrap_or(0))
+}
+
+pub async fn list_foundry_scores(
+    db: impl SqliteExecutor<'_>,
+    save_id: i64,
+) -> Result<Vec<FoundryScoreRow>> {
+    let rows = sqlx::query(
+        r#"SELECT foundry_id, score, months_observed, misses FROM foundry_scores WHERE save_id = ?1 ORDER BY id"#,
+    )
+    .bind(save_id)
+    .fetch_all(db)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| FoundryScoreRow {
+            foundry_id: r.try_get("foundry_id").unwrap_or_default(),
+            score: r.try_get("score").unwrap_or(0.0),
+            months_observed: r.try_get("months_observed").unwrap_or(0),
+            misses: r.try_get("misses").unwrap_or(0),
         })
         .collect())
 }
@@ -235,7 +330,7 @@ pub struct TapeoutRow {
 }
 
 pub async fn insert_tapeout_request(
-    pool: &Pool<Sqlite>,
+    db: impl SqliteExecutor<'_>,
     save_id: i64,
     t: &TapeoutRow,
 ) -> Result<i64> {
@@ -251,17 +346,20 @@ pub async fn insert_tapeout_request(
     .bind(&t.ready)
     .bind(t.expedite)
     .bind(t.expedite_cost_cents)
-    .fetch_one(pool)
+    .fetch_one(db)
     .await?;
     Ok(rec.try_get("id").unwrap_or(0))
 }
 
-pub async fn list_tapeout_requests(pool: &Pool<Sqlite>, save_id: i64) -> Result<Vec<TapeoutRow>> {
+pub async fn list_tapeout_requests(
+    db: impl SqliteExecutor<'_>,
+    save_id: i64,
+) -> Result<Vec<TapeoutRow>> {
     let rows = sqlx::query(
         r#"SELECT product_json, tech_node, start, ready, expedite, expedite_cost_cents FROM tapeout_queue WHERE save_id = ?1 ORDER BY id"#,
     )
     .bind(save_id)
-    .fetch_all(pool)
+    .fetch_all(db)
     .await?;
     Ok(rows
         .into_iter()
@@ -276,6 +374,54 @@ pub async fn list_tapeout_requests(pool: &Pool<Sqlite>, save_id: i64) -> Result<
         .collect())
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct PromoRow {
+    pub promo_id: i64,
+    pub segment_id: String,
+    pub discount_frac: f32,
+    pub reach_frac: f32,
+    pub start: String,
+    pub months: i64,
+}
+
+pub async fn insert_promo(db: impl SqliteExecutor<'_>, save_id: i64, p: &PromoRow) -> Result<i64> {
+    let rec = sqlx::query(
+        r#"INSERT INTO promos
+            (save_id, promo_id, segment_id, discount_frac, reach_frac, start, months)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7) RETURNING id"#,
+    )
+    .bind(save_id)
+    .bind(p.promo_id)
+    .bind(&p.segment_id)
+    .bind(p.discount_frac)
+    .bind(p.reach_frac)
+    .bind(&p.start)
+    .bind(p.months)
+    .fetch_one(db)
+    .await?;
+    Ok(rec.try_get("id").unwrap_or(0))
+}
+
+pub async fn list_promos(db: impl SqliteExecutor<'_>, save_id: i64) -> Result<Vec<PromoRow>> {
+    let rows = sqlx::query(
+        r#"SELECT promo_id, segment_id, discount_frac, reach_frac, start, months FROM promos WHERE save_id = ?1 ORDER BY id"#,
+    )
+    .bind(save_id)
+    .fetch_all(db)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| PromoRow {
+            promo_id: r.try_get("promo_id").unwrap_or(0),
+            segment_id: r.try_get("segment_id").unwrap_or_default(),
+            discount_frac: r.try_get("discount_frac").unwrap_or(0.0),
+            reach_frac: r.try_get("reach_frac").unwrap_or(1.0),
+            start: r.try_get("start").unwrap_or_default(),
+            months: r.try_get("months").unwrap_or(0),
+        })
+        .collect())
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
 pub struct ReleasedRow {
     pub product_json: String,
@@ -283,7 +429,7 @@ pub struct ReleasedRow {
 }
 
 pub async fn insert_released_product(
-    pool: &Pool<Sqlite>,
+    db: impl SqliteExecutor<'_>,
     save_id: i64,
     r: &ReleasedRow,
 ) -> Result<i64> {
@@ -295,17 +441,20 @@ pub async fn insert_released_product(
     .bind(save_id)
     .bind(&r.product_json)
     .bind(&r.released_at)
-    .fetch_one(pool)
+    .fetch_one(db)
     .await?;
     Ok(rec.try_get("id").unwrap_or(0))
 }
 
-pub async fn list_released_products(pool: &Pool<Sqlite>, save_id: i64) -> Result<Vec<ReleasedRow>> {
+pub async fn list_released_products(
+    db: impl SqliteExecutor<'_>,
+    save_id: i64,
+) -> Result<Vec<ReleasedRow>> {
     let rows = sqlx::query(
         r#"SELECT product_json, released_at FROM released_products WHERE save_id = ?1 ORDER BY id"#,
     )
     .bind(save_id)
-    .fetch_all(pool)
+    .fetch_all(db)
     .await?;
     Ok(rows
         .into_iter()
@@ -316,6 +465,487 @@ pub async fn list_released_products(pool: &Pool<Sqlite>, save_id: i64) -> Result
         .collect())
 }
 
+/// A runtime resource stored with a save as JSON, under a key chosen by the runtime.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct SaveResourceRow {
+    pub key: String,
+    pub json: String,
+}
+
+pub async fn insert_save_resource(
+    db: impl SqliteExecutor<'_>,
+    save_id: i64,
+    r: &SaveResourceRow,
+) -> Result<i64> {
+    let rec = sqlx::query(
+        r#"INSERT INTO save_resources (save_id, key, json) VALUES (?1, ?2, ?3) RETURNING id"#,
+    )
+    .bind(save_id)
+    .bind(&r.key)
+    .bind(&r.json)
+    .fetch_one(db)
+    .await?;
+    Ok(rec.try_get("id").unwrap_or(0))
+}
+
+pub async fn list_save_resources(
+    db: impl SqliteExecutor<'_>,
+    save_id: i64,
+) -> Result<Vec<SaveResourceRow>> {
+    let rows =
+        sqlx::query(r#"SELECT key, json FROM save_resources WHERE save_id = ?1 ORDER BY id"#)
+            .bind(save_id)
+            .fetch_all(db)
+            .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| SaveResourceRow {
+            key: r.try_get("key").unwrap_or_default(),
+            json: r.try_get("json").unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// One audited IPC command. `suppressed` counts earlier calls of the same command that
+/// were dropped by rate limiting since the previous recorded entry.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct AuditRow {
+    pub session_id: String,
+    pub command: String,
+    pub args_json: String,
+    pub duration_ms: i64,
+    pub ok: bool,
+    pub error: Option<String>,
+    pub suppressed: i64,
+    /// Milliseconds since process start when the command finished.
+    pub at_ms: i64,
+}
+
+/// Append audit rows to a save in one transaction.
+pub async fn insert_audit_rows(pool: &Pool<Sqlite>, save_id: i64, rows: &[AuditRow]) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    audit_rows_on(&mut tx, save_id, rows).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn audit_rows_on(conn: &mut SqliteConnection, save_id: i64, rows: &[AuditRow]) -> Result<()> {
+    for a in rows {
+        sqlx::query(
+            r#"INSERT INTO ipc_audit
+                (save_id, session_id, command, args_json, duration_ms, ok, error, suppressed, at_ms)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"#,
+        )
+        .bind(save_id)
+        .bind(&a.session_id)
+        .bind(&a.command)
+        .bind(&a.args_json)
+        .bind(a.duration_ms)
+        .bind(a.ok)
+        .bind(&a.error)
+        .bind(a.suppressed)
+        .bind(a.at_ms)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+pub async fn list_audit_rows(db: impl SqliteExecutor<'_>, save_id: i64) -> Result<Vec<AuditRow>> {
+    let rows = sqlx::query(
+        r#"SELECT session_id, command, args_json, duration_ms, ok, error, suppressed, at_ms
+            FROM ipc_audit WHERE save_id = ?1 ORDER BY id"#,
+    )
+    .bind(save_id)
+    .fetch_all(db)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| AuditRow {
+            session_id: r.try_get("session_id").unwrap_or_default(),
+            command: r.try_get("command").unwrap_or_default(),
+            args_json: r.try_get("args_json").unwrap_or_default(),
+            duration_ms: r.try_get("duration_ms").unwrap_or(0),
+            ok: r.try_get("ok").unwrap_or(false),
+            error: r.try_get("error").unwrap_or(None),
+            suppressed: r.try_get("suppressed").unwrap_or(0),
+            at_ms: r.try_get("at_ms").unwrap_or(0),
+        })
+        .collect())
+}
+
+/// FNV-1a over `bytes`, stored as SQLite's signed INTEGER.
+fn checksum64(bytes: &[u8]) -> i64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in bytes {
+        h ^= u64::from(*b);
+        h = h.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    h as i64
+}
+
+/// Per-save tables covered by slice checksums.
+pub const SAVE_SLICE_TABLES: &[&str] = &[
+    "snapshots",
+    "foundry_contracts",
+    "foundry_scores",
+    "tapeout_queue",
+    "released_products",
+    "promos",
+    "ipc_audit",
+    "save_resources",
+];
+
+/// Row count and checksum of one table's rows for a save, as read back through the
+/// regular list functions so verification sees exactly what a load would.
+async fn slice_checksum(
+    conn: &mut SqliteConnection,
+    save_id: i64,
+    table: &str,
+) -> Result<(i64, i64)> {
+    fn sum<T: serde::Serialize>(rows: &[T]) -> Result<(i64, i64)> {
+        Ok((rows.len() as i64, checksum64(&serde_json::to_vec(rows)?)))
+    }
+    match table {
+        "snapshots" => {
+            let rows: Vec<(i64, String, Option<i64>)> = sqlx::query_as(
+                r#"SELECT month_index, format, checksum FROM snapshots WHERE save_id = ?1 ORDER BY id"#,
+            )
+            .bind(save_id)
+            .fetch_all(&mut *conn)
+            .await?;
+            sum(&rows)
+        }
+        "foundry_contracts" => sum(&list_contracts(&mut *conn, save_id).await?),
+        "foundry_scores" => sum(&list_foundry_scores(&mut *conn, save_id).await?),
+        "tapeout_queue" => sum(&list_tapeout_requests(&mut *conn, save_id).await?),
+        "released_products" => sum(&list_released_products(&mut *conn, save_id).await?),
+        "promos" => sum(&list_promos(&mut *conn, save_id).await?),
+        "ipc_audit" => sum(&list_audit_rows(&mut *conn, save_id).await?),
+        "save_resources" => sum(&list_save_resources(&mut *conn, save_id).await?),
+        other => Err(anyhow!("no slice checksum for table {other}")),
+    }
+}
+
+/// Record (or refresh) the slice checksums of a save; call once every table is written.
+pub async fn record_save_checksums(pool: &Pool<Sqlite>, save_id: i64) -> Result<()> {
+    record_checksums_on(&mut *pool.acquire().await?, save_id).await
+}
+
+async fn record_checksums_on(conn: &mut SqliteConnection, save_id: i64) -> Result<()> {
+    for table in SAVE_SLICE_TABLES {
+        let (rows, checksum) = slice_checksum(conn, save_id, table).await?;
+        sqlx::query(
+            r#"INSERT INTO save_checksums (save_id, table_name, row_count, checksum)
+               VALUES (?1, ?2, ?3, ?4)
+               ON CONFLICT (save_id, table_name) DO UPDATE SET row_count = ?3, checksum = ?4"#,
+        )
+        .bind(save_id)
+        .bind(table)
+        .bind(rows)
+        .bind(checksum)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+/// One integrity problem found by [`verify_save`].
+#[derive(Debug, Clone, serde::Serialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SaveIssue {
+    /// No save with that id.
+    MissingSave,
+    /// The save was never marked done (e.g. the game crashed during an autosave).
+    Incomplete {
+        status: String,
+    },
+    NoSnapshot,
+    /// Snapshot blob whose checksum does not match or that no longer decodes.
+    CorruptSnapshot {
+        snapshot_id: i64,
+        month_index: i64,
+        reason: String,
+    },
+    /// The save has no recorded checksums (written before they existed, or interrupted).
+    ChecksumsMissing,
+    /// A table's rows for the save differ from what was recorded.
+    SliceMismatch {
+        table: String,
+        expected_rows: i64,
+        actual_rows: i64,
+    },
+    /// Rows of a per-save table whose save no longer exists.
+    OrphanedRows {
+        table: String,
+        rows: i64,
+    },
+}
+
+/// Result of [`verify_save`]; `issues` is empty for an intact save.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SaveVerification {
+    pub save_id: i64,
+    pub issues: Vec<SaveIssue>,
+}
+
+impl SaveVerification {
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Decode check for a snapshot blob: checksum first, then the format's deserializer.
+fn snapshot_problem(data: &[u8], format: &str, checksum: Option<i64>) -> Option<String> {
+    if checksum.is_some_and(|c| c != checksum64(data)) {
+        return Some("checksum mismatch".into());
+    }
+    let decoded = match format {
+        "json" => serde_json::from_slice::<core::World>(data).map_err(|e| e.to_string()),
+        _ => deserialize_world_bincode(data).map_err(|e| e.to_string()),
+    };
+    decoded.err()
+}
+
+/// Rows of per-save tables that point at no save, by table.
+async fn orphaned_rows(pool: &Pool<Sqlite>) -> Result<Vec<(String, i64)>> {
+    let mut out = Vec::new();
+    for table in SAVE_SLICE_TABLES.iter().chain(&["save_checksums"]) {
+        let (n,): (i64,) = sqlx::query_as(&format!(
+            "SELECT COUNT(*) FROM {table} WHERE save_id NOT IN (SELECT id FROM saves)"
+        ))
+        .fetch_one(pool)
+        .await?;
+        if n > 0 {
+            out.push((table.to_string(), n));
+        }
+    }
+    Ok(out)
+}
+
+/// Check a save for missing or corrupt data: status, snapshot blobs, table slices against
+/// their recorded checksums, and orphaned rows left behind by an interrupted write.
+pub async fn verify_save(pool: &Pool<Sqlite>, save_id: i64) -> Result<SaveVerification> {
+    let mut issues = Vec::new();
+    let status: Option<(String,)> = sqlx::query_as(r#"SELECT status FROM saves WHERE id = ?1"#)
+        .bind(save_id)
+        .fetch_optional(pool)
+        .await?;
+    match status {
+        None => issues.push(SaveIssue::MissingSave),
+        Some((status,)) if status == "in_progress" => issues.push(SaveIssue::Incomplete { status }),
+        Some(_) => {}
+    }
+    if issues.contains(&SaveIssue::MissingSave) {
+        return Ok(SaveVerification { save_id, issues });
+    }
+    let snaps = sqlx::query(
+        r#"SELECT id, month_index, format, data, checksum FROM snapshots WHERE save_id = ?1 ORDER BY id"#,
+    )
+    .bind(save_id)
+    .fetch_all(pool)
+    .await?;
+    if snaps.is_empty() {
+        issues.push(SaveIssue::NoSnapshot);
+    }
+    for r in &snaps {
+        let data: Vec<u8> = r.try_get("data").unwrap_or_default();
+        let format: String = r.try_get("format").unwrap_or_default();
+        let checksum: Option<i64> = r.try_get("checksum").unwrap_or(None);
+        if let Some(reason) = snapshot_problem(&data, &format, checksum) {
+            issues.push(SaveIssue::CorruptSnapshot {
+                snapshot_id: r.try_get("id").unwrap_or(0),
+                month_index: r.try_get("month_index").unwrap_or(0),
+                reason,
+            });
+        }
+    }
+    let recorded: Vec<(String, i64, i64)> = sqlx::query_as(
+        r#"SELECT table_name, row_count, checksum FROM save_checksums WHERE save_id = ?1"#,
+    )
+    .bind(save_id)
+    .fetch_all(pool)
+    .await?;
+    if recorded.is_empty() {
+        issues.push(SaveIssue::ChecksumsMissing);
+    }
+    let mut conn = pool.acquire().await?;
+    for (table, expected_rows, checksum) in recorded {
+        let (actual_rows, actual) = slice_checksum(&mut conn, save_id, &table).await?;
+        if actual_rows != expected_rows || actual != checksum {
+            issues.push(SaveIssue::SliceMismatch {
+                table,
+                expected_rows,
+                actual_rows,
+            });
+        }
+    }
+    for (table, rows) in orphaned_rows(pool).await? {
+        issues.push(SaveIssue::OrphanedRows { table, rows });
+    }
+    Ok(SaveVerification { save_id, issues })
+}
+
+/// What [`repair_save`] did, and the save's state afterwards.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SaveRepair {
+    pub actions: Vec<String>,
+    pub after: SaveVerification,
+}
+
+/// Best-effort repair: drop orphaned rows and corrupt snapshots (loading then falls back to
+/// the newest intact one), mark an interrupted save done and re-record its checksums so the
+/// remaining data is accepted. Fails when no intact snapshot is left to load.
+pub async fn repair_save(pool: &Pool<Sqlite>, save_id: i64) -> Result<SaveRepair> {
+    let before = verify_save(pool, save_id).await?;
+    let mut actions = Vec::new();
+    for issue in &before.issues {
+        match issue {
+            SaveIssue::MissingSave => return Err(anyhow!("save {save_id} does not exist")),
+            SaveIssue::OrphanedRows { table, rows } => {
+                sqlx::query(&format!(
+                    "DELETE FROM {table} WHERE save_id NOT IN (SELECT id FROM saves)"
+                ))
+                .execute(pool)
+                .await?;
+                actions.push(format!("dropped {rows} orphaned row(s) from {table}"));
+            }
+            SaveIssue::CorruptSnapshot {
+                snapshot_id,
+                month_index,
+                reason,
+            } => {
+                sqlx::query(r#"DELETE FROM snapshots WHERE id = ?1"#)
+                    .bind(snapshot_id)
+                    .execute(pool)
+                    .await?;
+                actions.push(format!(
+                    "dropped corrupt snapshot of month {month_index} ({reason})"
+                ));
+            }
+            SaveIssue::SliceMismatch {
+                table,
+                expected_rows,
+                actual_rows,
+            } => actions.push(format!(
+                "accepted {table} as found ({actual_rows} of {expected_rows} row(s))"
+            )),
+            SaveIssue::Incomplete { .. } | SaveIssue::NoSnapshot | SaveIssue::ChecksumsMissing => {}
+        }
+    }
+    let Some((_, month_index, _, _)) = latest_snapshot(pool, save_id).await? else {
+        return Err(anyhow!(
+            "save {save_id} has no intact snapshot to fall back to"
+        ));
+    };
+    if before
+        .issues
+        .iter()
+        .any(|i| matches!(i, SaveIssue::CorruptSnapshot { .. }))
+    {
+        actions.push(format!("fell back to the snapshot of month {month_index}"));
+    }
+    if before
+        .issues
+        .iter()
+        .any(|i| matches!(i, SaveIssue::Incomplete { .. }))
+    {
+        update_save_status(pool, save_id, "done").await?;
+        actions.push("marked the interrupted save done".into());
+    }
+    if !before.is_ok() {
+        record_save_checksums(pool, save_id).await?;
+    }
+    if !actions.is_empty() {
+        info!(save_id, ?actions, "save repaired");
+    }
+    Ok(SaveRepair {
+        actions,
+        after: verify_save(pool, save_id).await?,
+    })
+}
+
+/// Everything written for one save; new per-save tables get a field here.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct SaveData {
+    pub name: String,
+    pub description: Option<String>,
+    pub month_index: i64,
+    /// Bincode-encoded domain world.
+    pub snapshot: Vec<u8>,
+    pub contracts: Vec<ContractRow>,
+    pub foundry_scores: Vec<FoundryScoreRow>,
+    pub tapeouts: Vec<TapeoutRow>,
+    pub released: Vec<ReleasedRow>,
+    pub promos: Vec<PromoRow>,
+    pub audit: Vec<AuditRow>,
+    pub resources: Vec<SaveResourceRow>,
+}
+
+/// Write a whole save in one transaction and return its id. Either every table and the
+/// checksums are written and the save is marked done, or (on any error) the transaction
+/// rolls back and nothing is left behind.
+pub async fn write_save(pool: &Pool<Sqlite>, data: &SaveData) -> Result<i64> {
+    let mut tx = pool.begin().await?;
+    let sid = create_save_with_status(
+        &mut *tx,
+        &data.name,
+        data.description.as_deref(),
+        "in_progress",
+    )
+    .await?;
+    insert_snapshot(&mut *tx, sid, data.month_index, "bincode", &data.snapshot).await?;
+    for c in &data.contracts {
+        insert_contract(&mut *tx, sid, c).await?;
+    }
+    for f in &data.foundry_scores {
+        insert_foundry_score(&mut *tx, sid, f).await?;
+    }
+    for t in &data.tapeouts {
+        insert_tapeout_request(&mut *tx, sid, t).await?;
+    }
+    for r in &data.released {
+        insert_released_product(&mut *tx, sid, r).await?;
+    }
+    for p in &data.promos {
+        insert_promo(&mut *tx, sid, p).await?;
+    }
+    audit_rows_on(&mut tx, sid, &data.audit).await?;
+    for r in &data.resources {
+        insert_save_resource(&mut *tx, sid, r).await?;
+    }
+    record_checksums_on(&mut tx, sid).await?;
+    update_save_status(&mut *tx, sid, "done").await?;
+    tx.commit().await?;
+    Ok(sid)
+}
+
+/// Read a save back: its latest snapshot and every table, the inverse of [`write_save`].
+pub async fn read_save(pool: &Pool<Sqlite>, save_id: i64) -> Result<SaveData> {
+    let (name, description): (String, Option<String>) =
+        sqlx::query_as(r#"SELECT name, description FROM saves WHERE id = ?1"#)
+            .bind(save_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| anyhow!("save {save_id} does not exist"))?;
+    let (_, month_index, snapshot, _) = latest_snapshot(pool, save_id)
+        .await?
+        .ok_or_else(|| anyhow!("save {save_id} has no snapshot"))?;
+    Ok(SaveData {
+        name,
+        description,
+        month_index,
+        snapshot,
+        contracts: list_contracts(pool, save_id).await?,
+        foundry_scores: list_foundry_scores(pool, save_id).await?,
+        tapeouts: list_tapeout_requests(pool, save_id).await?,
+        released: list_released_products(pool, save_id).await?,
+        promos: list_promos(pool, save_id).await?,
+        audit: list_audit_rows(pool, save_id).await?,
+        resources: list_save_resources(pool, save_id).await?,
+    })
+}
+
 /// Row format for telemetry exports.
 #[derive(Clone, Debug)]
 pub struct TelemetryRow {
@@ -326,6 +956,16 @@ pub struct TelemetryRow {
     pub unit_cost_cents: i64,
     pub margin_cents: i64,
     pub revenue_cents: i64,
+    pub price_delta_frac: f64,
+    pub capacity_requested_wafers: u64,
+    pub tapeouts_scheduled: u32,
+    pub rd_budget_cents: i64,
+    /// Units sold at a promo price (subset of `sold_units`).
+    pub promo_units: u64,
+    /// Average promo selling price; 0 when no promo ran.
+    pub promo_asp_cents: i64,
+    /// Full-price buyers who took the promo price instead.
+    pub cannibalized_units: u64,
 }
 
 /// Convert a Decimal USD value to cents (i64), rounding to 2 decimals.
@@ -369,6 +1009,27 @@ pub fn write_telemetry_parquet<P: AsRef<Path>>(path: P, rows: &[TelemetryRow]) -
         Type::primitive_type_builder("revenue_cents", PhysicalType::INT64)
             .with_repetition(Repetition::REQUIRED)
             .build()?,
+        Type::primitive_type_builder("price_delta_frac", PhysicalType::DOUBLE)
+            .with_repetition(Repetition::REQUIRED)
+            .build()?,
+        Type::primitive_type_builder("capacity_requested_wafers", PhysicalType::INT64)
+            .with_repetition(Repetition::REQUIRED)
+            .build()?,
+        Type::primitive_type_builder("tapeouts_scheduled", PhysicalType::INT32)
+            .with_repetition(Repetition::REQUIRED)
+            .build()?,
+        Type::primitive_type_builder("rd_budget_cents", PhysicalType::INT64)
+            .with_repetition(Repetition::REQUIRED)
+            .build()?,
+        Type::primitive_type_builder("promo_units", PhysicalType::INT64)
+            .with_repetition(Repetition::REQUIRED)
+            .build()?,
+        Type::primitive_type_builder("promo_asp_cents", PhysicalType::INT64)
+            .with_repetition(Repetition::REQUIRED)
+            .build()?,
+        Type::primitive_type_builder("cannibalized_units", PhysicalType::INT64)
+            .with_repetition(Repetition::REQUIRED)
+            .build()?,
     ];
     let fields_ptrs: Vec<Arc<Type>> = fields.into_iter().map(Arc::new).collect();
     let schema = Type::group_type_builder("telemetry")
@@ -488,12 +1149,254 @@ pub fn write_telemetry_parquet<P: AsRef<Path>>(path: P, rows: &[TelemetryRow]) -
         }
         col.close()?;
     }
+    // Applied actions
+    let col7: Vec<f64> = rows.iter().map(|r| r.price_delta_frac).collect();
+    write_column::<DoubleType>(&mut row_group, "price_delta_frac", &col7)?;
+    let col8: Vec<i64> = rows
+        .iter()
+        .map(|r| r.capacity_requested_wafers as i64)
+        .collect();
+    write_column::<Int64Type>(&mut row_group, "capacity_requested_wafers", &col8)?;
+    let col9: Vec<i32> = rows.iter().map(|r| r.tapeouts_scheduled as i32).collect();
+    write_column::<Int32Type>(&mut row_group, "tapeouts_scheduled", &col9)?;
+    let col10: Vec<i64> = rows.iter().map(|r| r.rd_budget_cents).collect();
+    write_column::<Int64Type>(&mut row_group, "rd_budget_cents", &col10)?;
+    // Promo vs. base pricing
+    let col11: Vec<i64> = rows.iter().map(|r| r.promo_units as i64).collect();
+    write_column::<Int64Type>(&mut row_group, "promo_units", &col11)?;
+    let col12: Vec<i64> = rows.iter().map(|r| r.promo_asp_cents).collect();
+    write_column::<Int64Type>(&mut row_group, "promo_asp_cents", &col12)?;
+    let col13: Vec<i64> = rows.iter().map(|r| r.cannibalized_units as i64).collect();
+    write_column::<Int64Type>(&mut row_group, "cannibalized_units", &col13)?;
     row_group.close()?;
     writer.close()?;
     info!("parquet written");
     Ok(())
 }
 
+/// One aggregated row of an AI parameter sweep (one parameter combination over all seeds).
+#[derive(Clone, Debug)]
+pub struct SweepRow {
+    pub combo_index: u32,
+    pub params: String,
+    pub runs: u32,
+    pub win_rate: f64,
+    pub mean_cash_cents: i64,
+    pub min_cash_cents: i64,
+    pub mean_profit_cents: i64,
+    pub mean_share: f64,
+}
+
+/// Write `vals` to the next column of `rg`, which must have `T`'s physical type.
+fn write_column<T: DataType>(
+    rg: &mut parquet::file::writer::SerializedRowGroupWriter<'_, File>,
+    name: &str,
+    vals: &[T::T],
+) -> Result<()> {
+    let mut col = rg.next_column()?.ok_or_else(|| anyhow!("no column"))?;
+    let w = T::get_column_writer_mut(col.untyped())
+        .ok_or_else(|| anyhow!("unexpected column type for {name}"))?;
+    let _ = w.write_batch(vals, None, None)?;
+    col.close()?;
+    Ok(())
+}
+
+fn write_str_column(
+    rg: &mut parquet::file::writer::SerializedRowGroupWriter<'_, File>,
+    name: &str,
+    vals: &[&str],
+) -> Result<()> {
+    let vals: Vec<parquet::data_type::ByteArray> = vals
+        .iter()
+        .map(|v| parquet::data_type::ByteArray::from(*v))
+        .collect();
+    write_column::<ByteArrayType>(rg, name, &vals)
+}
+
+/// Write AI sweep results to a Parquet file at the given path.
+pub fn write_sweep_parquet<P: AsRef<Path>>(path: P, rows: &[SweepRow]) -> Result<()> {
+    let required = |name: &str, t: PhysicalType| {
+        Type::primitive_type_builder(name, t)
+            .with_repetition(Repetition::REQUIRED)
+            .build()
+    };
+    let fields = vec![
+        required("combo_index", PhysicalType::INT32)?,
+        Type::primitive_type_builder("params", PhysicalType::BYTE_ARRAY)
+            .with_repetition(Repetition::REQUIRED)
+            .with_converted_type(parquet::basic::ConvertedType::UTF8)
+            .build()?,
+        required("runs", PhysicalType::INT32)?,
+        required("win_rate", PhysicalType::DOUBLE)?,
+        required("mean_cash_cents", PhysicalType::INT64)?,
+        required("min_cash_cents", PhysicalType::INT64)?,
+        required("mean_profit_cents", PhysicalType::INT64)?,
+        required("mean_share", PhysicalType::DOUBLE)?,
+    ];
+    let fields_ptrs: Vec<Arc<Type>> = fields.into_iter().map(Arc::new).collect();
+    let schema = Type::group_type_builder("ai_sweep")
+        .with_fields(fields_ptrs)
+        .build()?;
+
+    if let Some(parent) = path.as_ref().parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = File::create(path)?;
+    let props = WriterProperties::builder().build();
+    let mut writer = SerializedFileWriter::new(file, Arc::new(schema), Arc::new(props))?;
+    let mut rg = writer.next_row_group()?;
+
+    let combo: Vec<i32> = rows.iter().map(|r| r.combo_index as i32).collect();
+    write_column::<Int32Type>(&mut rg, "combo_index", &combo)?;
+    let params: Vec<&str> = rows.iter().map(|r| r.params.as_str()).collect();
+    write_str_column(&mut rg, "params", &params)?;
+    let runs: Vec<i32> = rows.iter().map(|r| r.runs as i32).collect();
+    write_column::<Int32Type>(&mut rg, "runs", &runs)?;
+    let win: Vec<f64> = rows.iter().map(|r| r.win_rate).collect();
+    write_column::<DoubleType>(&mut rg, "win_rate", &win)?;
+    let cash: Vec<i64> = rows.iter().map(|r| r.mean_cash_cents).collect();
+    write_column::<Int64Type>(&mut rg, "mean_cash_cents", &cash)?;
+    let min_cash: Vec<i64> = rows.iter().map(|r| r.min_cash_cents).collect();
+    write_column::<Int64Type>(&mut rg, "min_cash_cents", &min_cash)?;
+    let profit: Vec<i64> = rows.iter().map(|r| r.mean_profit_cents).collect();
+    write_column::<Int64Type>(&mut rg, "mean_profit_cents", &profit)?;
+    let share: Vec<f64> = rows.iter().map(|r| r.mean_share).collect();
+    write_column::<DoubleType>(&mut rg, "mean_share", &share)?;
+
+    rg.close()?;
+    writer.close()?;
+    info!(rows = rows.len(), "sweep parquet written");
+    Ok(())
+}
+
+/// One logged simulation event, flattened for export. `payload` is a JSON object.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct EventLogRow {
+    pub month_index: u32,
+    pub date: String,
+    pub kind: String,
+    pub payload: String,
+}
+
+/// Sidecar path for the event log exported next to a telemetry file:
+/// `runs/a.parquet` becomes `runs/a.events.parquet`.
+pub fn event_log_path<P: AsRef<Path>>(telemetry_path: P) -> std::path::PathBuf {
+    let p = telemetry_path.as_ref();
+    let stem = p
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("telemetry");
+    let name = match p.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{stem}.events.{ext}"),
+        None => format!("{stem}.events"),
+    };
+    p.with_file_name(name)
+}
+
+/// Write event log rows to `path`, picking the format from its extension
+/// (`.parquet`, `.csv` or `.json`).
+pub fn write_event_log<P: AsRef<Path>>(path: P, rows: &[EventLogRow]) -> Result<()> {
+    let path = path.as_ref();
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("parquet") => write_event_log_parquet(path, rows),
+        Some("csv") => write_event_log_csv(path, rows),
+        Some("json") => {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            // Payloads go out as JSON values, not as escaped strings.
+            let out: Vec<serde_json::Value> = rows
+                .iter()
+                .map(|r| {
+                    serde_json::json!({
+                        "month_index": r.month_index,
+                        "date": r.date,
+                        "kind": r.kind,
+                        "payload": serde_json::from_str::<serde_json::Value>(&r.payload)
+                            .unwrap_or_else(|_| serde_json::Value::String(r.payload.clone())),
+                    })
+                })
+                .collect();
+            std::fs::write(path, serde_json::to_string_pretty(&out)?)?;
+            Ok(())
+        }
+        _ => Err(anyhow!(
+            "unknown event log extension for {}; use .parquet, .csv or .json",
+            path.display()
+        )),
+    }
+}
+
+/// Write event log rows to a Parquet file at the given path.
+pub fn write_event_log_parquet<P: AsRef<Path>>(path: P, rows: &[EventLogRow]) -> Result<()> {
+    let text = |name: &str| {
+        Type::primitive_type_builder(name, PhysicalType::BYTE_ARRAY)
+            .with_repetition(Repetition::REQUIRED)
+            .with_converted_type(parquet::basic::ConvertedType::UTF8)
+            .build()
+    };
+    let fields = vec![
+        Type::primitive_type_builder("month_index", PhysicalType::INT32)
+            .with_repetition(Repetition::REQUIRED)
+            .build()?,
+        text("date")?,
+        text("kind")?,
+        text("payload")?,
+    ];
+    let fields_ptrs: Vec<Arc<Type>> = fields.into_iter().map(Arc::new).collect();
+    let schema = Type::group_type_builder("event_log")
+        .with_fields(fields_ptrs)
+        .build()?;
+
+    if let Some(parent) = path.as_ref().parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = File::create(path)?;
+    let props = WriterProperties::builder().build();
+    let mut writer = SerializedFileWriter::new(file, Arc::new(schema), Arc::new(props))?;
+    let mut rg = writer.next_row_group()?;
+
+    let month: Vec<i32> = rows.iter().map(|r| r.month_index as i32).collect();
+    write_column::<Int32Type>(&mut rg, "month_index", &month)?;
+    let date: Vec<&str> = rows.iter().map(|r| r.date.as_str()).collect();
+    write_str_column(&mut rg, "date", &date)?;
+    let kind: Vec<&str> = rows.iter().map(|r| r.kind.as_str()).collect();
+    write_str_column(&mut rg, "kind", &kind)?;
+    let payload: Vec<&str> = rows.iter().map(|r| r.payload.as_str()).collect();
+    write_str_column(&mut rg, "payload", &payload)?;
+
+    rg.close()?;
+    writer.close()?;
+    info!(rows = rows.len(), "event log parquet written");
+    Ok(())
+}
+
+/// Write event log rows as CSV with a `month_index,date,kind,payload` header.
+pub fn write_event_log_csv<P: AsRef<Path>>(path: P, rows: &[EventLogRow]) -> Result<()> {
+    fn field(v: &str) -> String {
+        if v.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", v.replace('"', "\"\""))
+        } else {
+            v.to_string()
+        }
+    }
+    if let Some(parent) = path.as_ref().parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut out = String::from("month_index,date,kind,payload\n");
+    for r in rows {
+        out.push_str(&format!(
+            "{},{},{},{}\n",
+            r.month_index,
+            field(&r.date),
+            field(&r.kind),
+            field(&r.payload)
+        ));
+    }
+    std::fs::write(path, out)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -504,6 +1407,79 @@ mod tests {
         assert!(default_sqlite_url().starts_with("sqlite://"));
     }
 
+    #[test]
+    fn sweep_parquet_writes_all_rows() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+        let dir = std::env::temp_dir().join(format!("ct_sweep_{}", std::process::id()));
+        let path = dir.join("sweep.parquet");
+        let rows: Vec<SweepRow> = (0..3)
+            .map(|i| SweepRow {
+                combo_index: i,
+                params: format!("planner.price_step_frac={}", 0.01 * i as f64),
+                runs: 2,
+                win_rate: 0.5,
+                mean_cash_cents: 1_000,
+                min_cash_cents: -500,
+                mean_profit_cents: 250,
+                mean_share: 0.3,
+            })
+            .collect();
+        write_sweep_parquet(&path, &rows).unwrap();
+        let reader = SerializedFileReader::new(File::open(&path).unwrap()).unwrap();
+        let meta = reader.metadata().file_metadata();
+        assert_eq!(meta.num_rows(), 3);
+        assert_eq!(meta.schema_descr().num_columns(), 8);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn event_log_exports_in_every_format() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+        let dir = std::env::temp_dir().join(format!("ct_events_{}", std::process::id()));
+        let rows = vec![
+            EventLogRow {
+                month_index: 0,
+                date: "1990-02-01".into(),
+                kind: "news".into(),
+                payload: r#"{"headline":"Fab fire, prices up"}"#.into(),
+            },
+            EventLogRow {
+                month_index: 3,
+                date: "1990-05-01".into(),
+                kind: "distress".into(),
+                payload: r#""entered""#.into(),
+            },
+        ];
+        let telemetry = dir.join("run.parquet");
+        let path = event_log_path(&telemetry);
+        assert_eq!(path, dir.join("run.events.parquet"));
+        write_event_log(&path, &rows).unwrap();
+        let reader = SerializedFileReader::new(File::open(&path).unwrap()).unwrap();
+        let meta = reader.metadata().file_metadata();
+        assert_eq!(meta.num_rows(), 2);
+        assert_eq!(meta.schema_descr().num_columns(), 4);
+
+        let csv_path = event_log_path(dir.join("run.csv"));
+        write_event_log(&csv_path, &rows).unwrap();
+        let csv = std::fs::read_to_string(&csv_path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "month_index,date,kind,payload");
+        assert_eq!(
+            lines[1],
+            r#"0,1990-02-01,news,"{""headline"":""Fab fire, prices up""}""#
+        );
+
+        let json_path = event_log_path(dir.join("run.json"));
+        write_event_log(&json_path, &rows).unwrap();
+        let v: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(v[0]["payload"]["headline"], "Fab fire, prices up");
+        assert_eq!(v[1]["month_index"], 3);
+
+        assert!(write_event_log(dir.join("run.events.txt"), &rows).is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
     #[test]
     fn snapshot_roundtrip_in_memory() {
         let rt = Runtime::new().unwrap();
@@ -549,11 +1525,27 @@ mod tests {
                 lead_time_months: 3,
                 start: "1990-01-01".into(),
                 end: "1991-01-01".into(),
+                compensation_cents_per_wafer: 500,
+                node: Some("N600".into()),
+                ramp_wafers: vec![750, 1500],
+                min_order_wafers: 250,
+                rollover: true,
+                banked_wafers: 420,
+                contract_id: "0AB1PN4X0000BF3K9QX0000007".into(),
             };
             let _id = insert_contract(&pool, save_id, &c).await.unwrap();
             let rows = list_contracts(&pool, save_id).await.unwrap();
             assert_eq!(rows[0], c);
+            let f = FoundryScoreRow {
+                foundry_id: "F1".into(),
+                score: 0.75,
+                months_observed: 14,
+                misses: 3,
+            };
+            let _ = insert_foundry_score(&pool, save_id, &f).await.unwrap();
+            assert_eq!(list_foundry_scores(&pool, save_id).await.unwrap(), vec![f]);
             let spec = sim_core::ProductSpec {
+                id: String::new(),
                 kind: sim_core::ProductKind::CPU,
                 tech_node: sim_core::TechNodeId("N90".into()),
                 microarch: sim_core::MicroArch {
@@ -567,6 +1559,9 @@ mod tests {
                 perf_index: 0.5,
                 tdp_w: 65.0,
                 bom_usd: 50.0,
+                target_segments: vec![],
+                clock_frac: 1.0,
+                family: None,
             };
             let t = TapeoutRow {
                 product_json: serde_json::to_string(&spec).unwrap(),
@@ -582,6 +1577,27 @@ mod tests {
         });
     }
 
+    #[test]
+    fn promos_roundtrip_and_cascade() {
+        let rt = Runtime::new().unwrap();
+        rt.block_on(async move {
+            let pool = init_db("sqlite::memory:").await.unwrap();
+            let save_id = create_save(&pool, "test", None).await.unwrap();
+            let p = PromoRow {
+                promo_id: 2,
+                segment_id: "desktop".into(),
+                discount_frac: 0.15,
+                reach_frac: 0.5,
+                start: "1990-03-01".into(),
+                months: 2,
+            };
+            let _ = insert_promo(&pool, save_id, &p).await.unwrap();
+            assert_eq!(list_promos(&pool, save_id).await.unwrap(), vec![p]);
+            delete_save(&pool, save_id).await.unwrap();
+            assert!(list_promos(&pool, save_id).await.unwrap().is_empty());
+        });
+    }
+
     #[test]
     fn released_products_roundtrip() {
         let rt = Runtime::new().unwrap();
@@ -589,6 +1605,7 @@ mod tests {
             let pool = init_db("sqlite::memory:").await.unwrap();
             let save_id = create_save(&pool, "test", None).await.unwrap();
             let spec = sim_core::ProductSpec {
+                id: String::new(),
                 kind: sim_core::ProductKind::CPU,
                 tech_node: sim_core::TechNodeId("N90".into()),
                 microarch: sim_core::MicroArch {
@@ -602,6 +1619,9 @@ mod tests {
                 perf_index: 0.7,
                 tdp_w: 65.0,
                 bom_usd: 50.0,
+                target_segments: vec![],
+                clock_frac: 1.0,
+                family: None,
             };
             let row = ReleasedRow {
                 product_json: serde_json::to_string(&spec).unwrap(),
@@ -613,6 +1633,240 @@ mod tests {
         });
     }
 
+    #[test]
+    fn audit_rows_roundtrip_and_cascade() {
+        let rt = Runtime::new().unwrap();
+        rt.block_on(async move {
+            let pool = init_db("sqlite::memory:").await.unwrap();
+            let save_id = create_save(&pool, "test", None).await.unwrap();
+            let rows = vec![
+                AuditRow {
+                    session_id: "default".into(),
+                    command: "sim_tick".into(),
+                    args_json: r#"{"months":1}"#.into(),
+                    duration_ms: 12,
+                    ok: true,
+                    error: None,
+                    suppressed: 0,
+                    at_ms: 1_000,
+                },
+                AuditRow {
+                    session_id: "default".into(),
+                    command: "sim_decide".into(),
+                    args_json: r#"{"event_id":"x","choice":"y"}"#.into(),
+                    duration_ms: 1,
+                    ok: false,
+                    error: Some("unknown event".into()),
+                    suppressed: 3,
+                    at_ms: 2_000,
+                },
+            ];
+            insert_audit_rows(&pool, save_id, &rows).await.unwrap();
+            assert_eq!(list_audit_rows(&pool, save_id).await.unwrap(), rows);
+            delete_save(&pool, save_id).await.unwrap();
+            assert!(list_audit_rows(&pool, save_id).await.unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn save_verification_detects_and_repairs_damage() {
+        let rt = Runtime::new().unwrap();
+        rt.block_on(async move {
+            let pool = init_db("sqlite::memory:").await.unwrap();
+            let save_id = create_save(&pool, "auto-1", None).await.unwrap();
+            let world = core::World {
+                macro_state: core::MacroState {
+                    date: chrono::NaiveDate::from_ymd_opt(1990, 1, 1).unwrap(),
+                    inflation_annual: 0.02,
+                    interest_rate: 0.05,
+                    fx_usd_index: 100.0,
+                },
+                tech_tree: vec![],
+                companies: vec![],
+                segments: vec![],
+            };
+            let bytes = serialize_world_bincode(&world).unwrap();
+            for month in [11, 12] {
+                insert_snapshot(&pool, save_id, month, "bincode", &bytes)
+                    .await
+                    .unwrap();
+            }
+            let c = ContractRow {
+                foundry_id: "F1".into(),
+                wafers_per_month: 3000,
+                price_per_wafer_cents: 1000,
+                take_or_pay_frac: 1.0,
+                billing_cents_per_wafer: 1000,
+                billing_model: "take_or_pay".into(),
+                lead_time_months: 3,
+                start: "1990-01-01".into(),
+                end: "1991-01-01".into(),
+                compensation_cents_per_wafer: 0,
+                node: None,
+                ramp_wafers: Vec::new(),
+                min_order_wafers: 0,
+                rollover: false,
+                banked_wafers: 0,
+                contract_id: String::new(),
+            };
+            insert_contract(&pool, save_id, &c).await.unwrap();
+            assert_eq!(
+                verify_save(&pool, save_id).await.unwrap().issues,
+                vec![SaveIssue::ChecksumsMissing]
+            );
+            record_save_checksums(&pool, save_id).await.unwrap();
+            assert!(verify_save(&pool, save_id).await.unwrap().is_ok());
+
+            // Damage: lost contract, corrupt latest blob, orphaned rows, interrupted status
+            sqlx::query("DELETE FROM foundry_contracts WHERE save_id = ?1")
+                .bind(save_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+            sqlx::query("UPDATE snapshots SET data = x'00ff' WHERE month_index = 12")
+                .execute(&pool)
+                .await
+                .unwrap();
+            let mut conn = pool.acquire().await.unwrap();
+            sqlx::query("PRAGMA foreign_keys = OFF")
+                .execute(&mut *conn)
+                .await
+                .unwrap();
+            sqlx::query(
+                "INSERT INTO promos (save_id, promo_id, segment_id, discount_frac, reach_frac, start, months) VALUES (999, 1, 'desktop', 0.1, 1.0, '1990-01-01', 1)",
+            )
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+            sqlx::query("PRAGMA foreign_keys = ON")
+                .execute(&mut *conn)
+                .await
+                .unwrap();
+            drop(conn);
+            update_save_status(&pool, save_id, "in_progress")
+                .await
+                .unwrap();
+            let v = verify_save(&pool, save_id).await.unwrap();
+            assert!(v.issues.contains(&SaveIssue::Incomplete {
+                status: "in_progress".into()
+            }));
+            assert!(v.issues.contains(&SaveIssue::SliceMismatch {
+                table: "foundry_contracts".into(),
+                expected_rows: 1,
+                actual_rows: 0,
+            }));
+            assert!(v.issues.contains(&SaveIssue::OrphanedRows {
+                table: "promos".into(),
+                rows: 1,
+            }));
+            assert!(v.issues.iter().any(|i| matches!(
+                i,
+                SaveIssue::CorruptSnapshot {
+                    month_index: 12,
+                    ..
+                }
+            )));
+
+            let repair = repair_save(&pool, save_id).await.unwrap();
+            assert!(repair.after.is_ok(), "{:?}", repair.after);
+            assert!(repair
+                .actions
+                .iter()
+                .any(|a| a == "fell back to the snapshot of month 11"));
+            assert_eq!(latest_snapshot(&pool, save_id).await.unwrap().unwrap().1, 11);
+            let saves = list_saves_by_prefix(&pool, "auto-").await.unwrap();
+            assert_eq!(saves[0].status, "done");
+
+            // Nothing left to fall back to: repair refuses rather than pretending
+            sqlx::query("UPDATE snapshots SET data = x'00ff'")
+                .execute(&pool)
+                .await
+                .unwrap();
+            assert!(repair_save(&pool, save_id).await.is_err());
+            assert_eq!(
+                verify_save(&pool, 4242).await.unwrap().issues,
+                vec![SaveIssue::MissingSave]
+            );
+        });
+    }
+
+    #[test]
+    fn write_save_is_all_or_nothing() {
+        let rt = Runtime::new().unwrap();
+        rt.block_on(async move {
+            let pool = init_db("sqlite::memory:").await.unwrap();
+            let world = core::World {
+                macro_state: core::MacroState {
+                    date: chrono::NaiveDate::from_ymd_opt(1990, 1, 1).unwrap(),
+                    inflation_annual: 0.02,
+                    interest_rate: 0.05,
+                    fx_usd_index: 100.0,
+                },
+                tech_tree: vec![],
+                companies: vec![],
+                segments: vec![],
+            };
+            let data = SaveData {
+                name: "auto-1990-03".into(),
+                month_index: 3,
+                snapshot: serialize_world_bincode(&world).unwrap(),
+                contracts: vec![ContractRow {
+                    foundry_id: "F1".into(),
+                    wafers_per_month: 3000,
+                    price_per_wafer_cents: 1000,
+                    take_or_pay_frac: 1.0,
+                    billing_cents_per_wafer: 1000,
+                    billing_model: "take_or_pay".into(),
+                    lead_time_months: 3,
+                    start: "1990-01-01".into(),
+                    end: "1991-01-01".into(),
+                    compensation_cents_per_wafer: 0,
+                    node: None,
+                    ramp_wafers: Vec::new(),
+                    min_order_wafers: 0,
+                    rollover: false,
+                    banked_wafers: 0,
+                    contract_id: String::new(),
+                }],
+                promos: vec![PromoRow {
+                    promo_id: 1,
+                    segment_id: "desktop".into(),
+                    discount_frac: 0.1,
+                    reach_frac: 1.0,
+                    start: "1990-02-01".into(),
+                    months: 2,
+                }],
+                ..SaveData::default()
+            };
+            let sid = write_save(&pool, &data).await.unwrap();
+            assert!(verify_save(&pool, sid).await.unwrap().is_ok());
+            assert_eq!(list_contracts(&pool, sid).await.unwrap(), data.contracts);
+            assert_eq!(list_saves_by_prefix(&pool, "auto-").await.unwrap()[0].status, "done");
+
+            // Fail halfway: contracts and the snapshot are written before promos abort
+            sqlx::query(
+                "CREATE TRIGGER fail_promos BEFORE INSERT ON promos BEGIN SELECT RAISE(ABORT, 'disk full'); END",
+            )
+            .execute(&pool)
+            .await
+            .unwrap();
+            let err = write_save(&pool, &data).await.unwrap_err();
+            assert!(err.to_string().contains("disk full"), "{err}");
+            let saves = list_saves_by_prefix(&pool, "auto-").await.unwrap();
+            assert_eq!(saves.len(), 1, "no partial save is left behind");
+            for table in SAVE_SLICE_TABLES {
+                let (n,): (i64,) = sqlx::query_as(&format!(
+                    "SELECT COUNT(*) FROM {table} WHERE save_id <> ?1"
+                ))
+                .bind(sid)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+                assert_eq!(n, 0, "{table} kept rows of the failed save");
+            }
+        });
+    }
+
     #[test]
     fn init_db_on_disk() {
         let rt = Runtime::new().unwrap();
@@ -0,0 +1,1113 @@
+#![deny(warnings)]
+
+//! Headless scenario regression pack.
+//!
+//! Loads every embedded scenario, runs it without a UI on several seeds (the AI planner
+//! drives the player company, decisions take their defaults) and checks the assertions
+//! declared in `assets/scenarios/regression_pack.yaml`, so content changes are validated
+//! the same way code changes are. A scripted bot can play the player role on top of the
+//! autopilot for century-long soak runs and per-difficulty win rates.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sim_runtime as runtime;
+use sim_runtime::World;
+pub use sim_runtime::{
+    scenario_world, DifficultyPreset, DifficultyPresets, SandboxSetup, SANDBOX_MAX_COMPETITORS,
+    SANDBOX_START_YEARS, SCENARIOS,
+};
+
+// ---------------- Embedded assets ----------------
+
+/// Shipped player strategy scripts, by name.
+pub const SCRIPTS: &[(&str, &str)] = &[(
+    "steady_launcher",
+    include_str!("../../../assets/scripts/steady_launcher.rhai"),
+)];
+
+/// The regression pack shipped with the game content.
+pub const PACK_YAML: &str = include_str!("../../../assets/scenarios/regression_pack.yaml");
+
+/// Example difficulty calibration spec.
+pub const CALIBRATION_YAML: &str = include_str!("../../../assets/scenarios/calibration.yaml");
+
+/// Integer that may be written with `_` separators (YAML parses those as strings).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+enum I64OrStr {
+    I(i64),
+    S(String),
+}
+
+impl I64OrStr {
+    fn val(&self) -> Result<i64, String> {
+        match self {
+            I64OrStr::I(v) => Ok(*v),
+            I64OrStr::S(s) => s.replace('_', "").parse::<i64>().map_err(|e| e.to_string()),
+        }
+    }
+}
+
+// ---------------- Scenario loading ----------------
+
+/// The shipped game data: 1990s tech and markets, campaign events and difficulty levels.
+pub fn content() -> Result<runtime::GameContent, String> {
+    runtime::GameContent::shipped()
+}
+
+/// The difficulty presets shipped with the game content.
+pub fn difficulty_presets() -> Result<runtime::DifficultyPresets, String> {
+    runtime::DifficultyPresets::shipped()
+}
+
+/// Compile a shipped strategy script.
+pub fn strategy_script(name: &str) -> Result<runtime::StrategyScript, String> {
+    let text = SCRIPTS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, t)| *t)
+        .ok_or_else(|| format!("unknown script {name}"))?;
+    runtime::StrategyScript::compile(text).map_err(|e| format!("{name}: {e}"))
+}
+
+// ---------------- Difficulty ----------------
+
+/// Apply a `difficulty.yaml` preset, mirroring the UI's difficulty selector.
+pub fn apply_difficulty(world: &mut World, level: &str) -> Result<(), String> {
+    difficulty_presets()?.apply(world, level)
+}
+
+/// Apply `preset` under the name `level`, whether or not it comes from `difficulty.yaml`.
+/// It replaces any earlier difficulty instead of compounding with it.
+pub fn apply_preset(world: &mut World, level: &str, preset: &DifficultyPreset) {
+    runtime::set_difficulty_effects(world, level, &preset.effects());
+}
+
+// ---------------- Pack spec ----------------
+
+/// Regression pack: scenario cases run on a shared list of seeds.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PackSpec {
+    pub seeds: Vec<u64>,
+    pub cases: Vec<PackCase>,
+}
+
+/// One scenario at one difficulty, with the assertions it must satisfy.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PackCase {
+    pub scenario: String,
+    #[serde(default)]
+    pub difficulty: Option<String>,
+    /// Months to run; defaults to the scenario's full length.
+    #[serde(default)]
+    pub months: Option<u32>,
+    /// Drive the player with the scripted bot on top of the autopilot.
+    #[serde(default)]
+    pub bot: Option<BotPolicy>,
+    /// Name of a shipped strategy script (see [`SCRIPTS`]) playing on top of the autopilot.
+    #[serde(default)]
+    pub script: Option<String>,
+    pub assert: Vec<Assertion>,
+}
+
+/// Cross-seed checks; medians and rates are taken over every seed of the pack.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Assertion {
+    /// Median player cash at `month` lies within the bounds.
+    MedianCashAt {
+        month: u32,
+        #[serde(default)]
+        min_cents: Option<I64Bound>,
+        #[serde(default)]
+        max_cents: Option<I64Bound>,
+    },
+    /// Median market share at `month` lies within the bounds.
+    MedianShareAt {
+        month: u32,
+        #[serde(default)]
+        min: Option<f32>,
+        #[serde(default)]
+        max: Option<f32>,
+    },
+    /// Fraction of seeds whose campaign ended in success.
+    WinRate {
+        #[serde(default)]
+        min: Option<f32>,
+        #[serde(default)]
+        max: Option<f32>,
+    },
+    /// Fraction of seeds whose campaign failed (fail condition or distress).
+    FailRate {
+        #[serde(default)]
+        min: Option<f32>,
+        #[serde(default)]
+        max: Option<f32>,
+    },
+    /// No runtime invariant violations at any checkpoint.
+    NoInvariantViolations,
+}
+
+/// Cent amount in the pack YAML; accepts `_` separators like the scenario files.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct I64Bound(I64OrStr);
+
+impl I64Bound {
+    pub fn cents(&self) -> Result<i64, String> {
+        self.0.val()
+    }
+}
+
+impl PackSpec {
+    pub fn from_yaml_str(text: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(text)
+    }
+}
+
+impl Assertion {
+    fn checkpoint(&self) -> Option<u32> {
+        match self {
+            Assertion::MedianCashAt { month, .. } | Assertion::MedianShareAt { month, .. } => {
+                Some(*month)
+            }
+            _ => None,
+        }
+    }
+}
+
+// ---------------- Runner ----------------
+
+/// Snapshot of one seed at a checkpoint month.
+#[derive(Clone, Debug)]
+pub struct Checkpoint {
+    pub month: u32,
+    pub cash_cents: i64,
+    pub market_share: f32,
+    pub invariant_violations: usize,
+}
+
+/// Result of running one case on one seed.
+#[derive(Clone, Debug)]
+pub struct SeedRun {
+    pub seed: u64,
+    pub checkpoints: Vec<Checkpoint>,
+    pub outcome: runtime::CampaignOutcome,
+}
+
+/// Outcome of one case across all seeds; `failures` is empty when every assertion held.
+#[derive(Clone, Debug)]
+pub struct CaseReport {
+    pub label: String,
+    pub runs: Vec<SeedRun>,
+    pub failures: Vec<String>,
+}
+
+/// Run a case on every seed, stopping at each month an assertion refers to.
+pub fn run_case(case: &PackCase, seeds: &[u64]) -> Result<Vec<SeedRun>, String> {
+    let mut out = Vec::with_capacity(seeds.len());
+    for &seed in seeds {
+        let (mut world, full) = scenario_world(&case.scenario, seed)?;
+        if let Some(level) = &case.difficulty {
+            apply_difficulty(&mut world, level)?;
+        }
+        if let Some(name) = &case.script {
+            runtime::attach_player_script(&mut world, strategy_script(name)?);
+        }
+        let months = case.months.unwrap_or(full);
+        let mut stops: Vec<u32> = case
+            .assert
+            .iter()
+            .filter_map(Assertion::checkpoint)
+            .filter(|m| *m <= months)
+            .chain([months])
+            .collect();
+        stops.sort_unstable();
+        stops.dedup();
+        let mut done = 0u32;
+        let mut checkpoints = Vec::with_capacity(stops.len());
+        for stop in stops {
+            let snap = match &case.bot {
+                Some(policy) => {
+                    let mut snap = None;
+                    for _ in done..stop {
+                        bot_act(&mut world, policy);
+                        snap = Some(runtime::run_months_in_place(&mut world, 1).0);
+                    }
+                    snap.unwrap_or_else(|| runtime::run_months_in_place(&mut world, 0).0)
+                }
+                None => runtime::run_months_in_place(&mut world, stop - done).0,
+            };
+            done = stop;
+            let reported = world
+                .get_resource::<runtime::InvariantReport>()
+                .map(|r| r.violations.len())
+                .unwrap_or(0);
+            checkpoints.push(Checkpoint {
+                month: stop,
+                cash_cents: snap.cash_cents,
+                market_share: snap.market_share,
+                invariant_violations: reported + runtime::validate_runtime_state(&world).len(),
+            });
+        }
+        let outcome = world
+            .get_resource::<runtime::CampaignStateRes>()
+            .map(|s| s.outcome.clone())
+            .unwrap_or_default();
+        out.push(SeedRun {
+            seed,
+            checkpoints,
+            outcome,
+        });
+    }
+    Ok(out)
+}
+
+fn median<T: Copy + PartialOrd>(mut xs: Vec<T>) -> Option<T> {
+    if xs.is_empty() {
+        return None;
+    }
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    Some(xs[(xs.len() - 1) / 2])
+}
+
+fn at_month(runs: &[SeedRun], month: u32) -> Result<Vec<&Checkpoint>, String> {
+    runs.iter()
+        .map(|r| {
+            r.checkpoints
+                .iter()
+                .find(|c| c.month == month)
+                .ok_or_else(|| format!("month {month} is past the end of the run"))
+        })
+        .collect()
+}
+
+fn check_rate(what: &str, rate: f32, min: Option<f32>, max: Option<f32>) -> Option<String> {
+    if min.is_some_and(|m| rate < m) || max.is_some_and(|m| rate > m) {
+        Some(format!("{what} {rate:.2} outside [{min:?}, {max:?}]"))
+    } else {
+        None
+    }
+}
+
+/// Evaluate a case's assertions against its runs; returns one message per failed check.
+pub fn check_case(case: &PackCase, runs: &[SeedRun]) -> Result<Vec<String>, String> {
+    let mut failures = Vec::new();
+    let n = runs.len().max(1) as f32;
+    for a in &case.assert {
+        let failure = match a {
+            Assertion::MedianCashAt {
+                month,
+                min_cents,
+                max_cents,
+            } => {
+                let cash = median(
+                    at_month(runs, *month)?
+                        .iter()
+                        .map(|c| c.cash_cents)
+                        .collect(),
+                )
+                .unwrap_or(0);
+                let lo = min_cents.as_ref().map(I64Bound::cents).transpose()?;
+                let hi = max_cents.as_ref().map(I64Bound::cents).transpose()?;
+                (lo.is_some_and(|m| cash < m) || hi.is_some_and(|m| cash > m)).then(|| {
+                    format!("median cash at month {month} = {cash} cents outside [{lo:?}, {hi:?}]")
+                })
+            }
+            Assertion::MedianShareAt { month, min, max } => {
+                let share = median(
+                    at_month(runs, *month)?
+                        .iter()
+                        .map(|c| c.market_share)
+                        .collect(),
+                )
+                .unwrap_or(0.0);
+                check_rate(&format!("median share at month {month}"), share, *min, *max)
+            }
+            Assertion::WinRate { min, max } => {
+                let wins = runs
+                    .iter()
+                    .filter(|r| r.outcome == runtime::CampaignOutcome::Success)
+                    .count();
+                check_rate("win rate", wins as f32 / n, *min, *max)
+            }
+            Assertion::FailRate { min, max } => {
+                let fails = runs
+                    .iter()
+                    .filter(|r| r.outcome == runtime::CampaignOutcome::Failed)
+                    .count();
+                check_rate("fail rate", fails as f32 / n, *min, *max)
+            }
+            Assertion::NoInvariantViolations => runs
+                .iter()
+                .flat_map(|r| r.checkpoints.iter().map(move |c| (r.seed, c)))
+                .find(|(_, c)| c.invariant_violations > 0)
+                .map(|(seed, c)| {
+                    format!(
+                        "seed {seed}: {} invariant violation(s) by month {}",
+                        c.invariant_violations, c.month
+                    )
+                }),
+        };
+        failures.extend(failure);
+    }
+    Ok(failures)
+}
+
+/// Run every case of the pack and collect per-case reports.
+pub fn run_pack(spec: &PackSpec) -> Result<Vec<CaseReport>, String> {
+    spec.cases
+        .iter()
+        .map(|case| {
+            let runs = run_case(case, &spec.seeds)?;
+            let failures = check_case(case, &runs)?;
+            Ok(CaseReport {
+                label: match &case.difficulty {
+                    Some(d) => format!("{} ({d})", case.scenario),
+                    None => case.scenario.clone(),
+                },
+                runs,
+                failures,
+            })
+        })
+        .collect()
+}
+
+// ---------------- Report baselines ----------------
+
+/// KPI trajectory of the AI autopilot on `scenario` at `level`, for `months` months;
+/// campaign reports compare the player against it at the same month.
+pub fn difficulty_baseline(
+    scenario: &str,
+    level: &str,
+    months: u32,
+    seed: u64,
+) -> Result<runtime::ReportBaseline, String> {
+    let (world, full) = scenario_world(scenario, seed)?;
+    let preset = difficulty_presets()?
+        .levels
+        .remove(level)
+        .ok_or_else(|| format!("unknown difficulty {level}"))?;
+    let spec = runtime::BaselineSpec {
+        difficulty: Some((level.to_string(), preset.effects())),
+        months: months.min(full),
+    };
+    Ok(spec.run(world))
+}
+
+// ---------------- Bot player ----------------
+
+/// Scripted player for soak and balance runs. The AI autopilot still prices and plans
+/// capacity; the bot takes the player-only actions the autopilot never does.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct BotPolicy {
+    /// Queue a tapeout on the newest available node whenever the pipeline is empty.
+    pub tapeout_when_idle: bool,
+    pub perf_index: f32,
+    pub die_area_mm2: f32,
+    /// Ask for expedited tapeouts (refused while distressed or over the R&D budget).
+    pub expedite: bool,
+    /// Accept an emergency loan as soon as one is offered.
+    pub accept_emergency_loans: bool,
+}
+
+impl Default for BotPolicy {
+    fn default() -> Self {
+        Self {
+            tapeout_when_idle: true,
+            perf_index: 0.8,
+            die_area_mm2: 120.0,
+            expedite: false,
+            accept_emergency_loans: true,
+        }
+    }
+}
+
+/// A player action taken by the bot.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BotAction {
+    Tapeout { node: String, ready: NaiveDate },
+    EmergencyLoan { cents: i64 },
+}
+
+/// Take this month's player actions under `policy`.
+pub fn bot_act(world: &mut World, policy: &BotPolicy) -> Vec<BotAction> {
+    let mut out = Vec::new();
+    if policy.accept_emergency_loans {
+        if let Ok(cents) = runtime::accept_emergency_loan(world) {
+            out.push(BotAction::EmergencyLoan { cents });
+        }
+    }
+    if policy.tapeout_when_idle && world.resource::<runtime::Pipeline>().0.queue.is_empty() {
+        let date = world.resource::<runtime::DomainWorld>().0.macro_state.date;
+        let tracks = world
+            .get_resource::<runtime::RnDTracks>()
+            .cloned()
+            .unwrap_or_default();
+        let newest = world
+            .resource::<runtime::DomainWorld>()
+            .0
+            .tech_tree
+            .iter()
+            .filter(|n| runtime::node_available_from(n, &tracks) <= date)
+            .max_by_key(|n| n.year_available)
+            .map(|n| n.id.0.clone());
+        if let Some(node) = newest {
+            let ready = runtime::apply_tapeout_request(
+                world,
+                policy.perf_index,
+                policy.die_area_mm2,
+                node.clone(),
+                policy.expedite,
+            );
+            out.push(BotAction::Tapeout { node, ready });
+        }
+    }
+    out
+}
+
+/// Result of one bot run.
+#[derive(Clone, Debug)]
+pub struct BotRun {
+    pub seed: u64,
+    pub months: u32,
+    pub outcome: runtime::CampaignOutcome,
+    /// Month at which the campaign was won or lost, if it was.
+    pub decided_month: Option<u32>,
+    pub actions: Vec<(u32, BotAction)>,
+    /// Invariant violations, tagged with the month they were seen after.
+    pub violations: Vec<(u32, runtime::InvariantViolation)>,
+    pub cash_cents: i64,
+}
+
+/// Per-month check run by [`run_bot`]; an error aborts the run with the month attached.
+pub type BotHook<'a> = &'a mut dyn FnMut(u32, &World) -> Result<(), String>;
+
+/// Play `scenario` with the bot for `months` months (the scenario's length by default),
+/// validating runtime invariants after every month and calling `hook` with the world.
+/// The run continues past the campaign's outcome; only a hook error stops it early.
+pub fn run_bot(
+    scenario: &str,
+    difficulty: Option<&str>,
+    seed: u64,
+    months: Option<u32>,
+    policy: &BotPolicy,
+    hook: BotHook<'_>,
+) -> Result<BotRun, String> {
+    let (mut world, full) = scenario_world(scenario, seed)?;
+    if let Some(level) = difficulty {
+        apply_difficulty(&mut world, level)?;
+    }
+    play_bot(world, seed, months.unwrap_or(full), policy, hook)
+}
+
+fn play_bot(
+    mut world: World,
+    seed: u64,
+    months: u32,
+    policy: &BotPolicy,
+    hook: BotHook<'_>,
+) -> Result<BotRun, String> {
+    let mut run = BotRun {
+        seed,
+        months,
+        outcome: runtime::CampaignOutcome::InProgress,
+        decided_month: None,
+        actions: Vec::new(),
+        violations: Vec::new(),
+        cash_cents: 0,
+    };
+    for month in 1..=months {
+        let acts = bot_act(&mut world, policy);
+        run.actions.extend(acts.into_iter().map(|a| (month, a)));
+        run.cash_cents = runtime::run_months_in_place(&mut world, 1).0.cash_cents;
+        run.violations.extend(
+            runtime::validate_runtime_state(&world)
+                .into_iter()
+                .map(|v| (month, v)),
+        );
+        if run.decided_month.is_none() {
+            run.outcome = world
+                .get_resource::<runtime::CampaignStateRes>()
+                .map(|s| s.outcome.clone())
+                .unwrap_or_default();
+            if run.outcome != runtime::CampaignOutcome::InProgress {
+                run.decided_month = Some(month);
+            }
+        }
+        hook(month, &world).map_err(|e| format!("seed {seed}, month {month}: {e}"))?;
+    }
+    Ok(run)
+}
+
+/// Bot results at one difficulty across seeds.
+#[derive(Clone, Debug)]
+pub struct BotWinRate {
+    pub difficulty: String,
+    pub runs: usize,
+    pub win_rate: f32,
+    pub fail_rate: f32,
+    pub invariant_violations: usize,
+}
+
+/// Bot win and fail rates on `scenario` for every difficulty in `levels`, each seed played
+/// to the scenario's end.
+pub fn bot_win_rates(
+    scenario: &str,
+    levels: &[&str],
+    seeds: &[u64],
+    policy: &BotPolicy,
+) -> Result<Vec<BotWinRate>, String> {
+    levels
+        .iter()
+        .map(|level| {
+            let runs = seeds
+                .iter()
+                .map(|&seed| {
+                    run_bot(
+                        scenario,
+                        Some(level),
+                        seed,
+                        None,
+                        policy,
+                        &mut |_, _| Ok(()),
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let n = runs.len().max(1) as f32;
+            let rate = |o: runtime::CampaignOutcome| {
+                runs.iter().filter(|r| r.outcome == o).count() as f32 / n
+            };
+            Ok(BotWinRate {
+                difficulty: level.to_string(),
+                runs: runs.len(),
+                win_rate: rate(runtime::CampaignOutcome::Success),
+                fail_rate: rate(runtime::CampaignOutcome::Failed),
+                invariant_violations: runs.iter().map(|r| r.violations.len()).sum(),
+            })
+        })
+        .collect()
+}
+
+// ---------------- Difficulty calibration ----------------
+
+/// Calibration request: bot win-rate targets per difficulty on one scenario.
+#[derive(Clone, Debug, Deserialize)]
+pub struct CalibrationSpec {
+    pub scenario: String,
+    pub seeds: Vec<u64>,
+    /// Target bot win rate by difficulty level name.
+    pub targets: std::collections::BTreeMap<String, f32>,
+    /// Stop searching a level once its win rate is this close to the target.
+    #[serde(default = "default_calibration_tolerance")]
+    pub tolerance: f32,
+    #[serde(default = "default_calibration_iterations")]
+    pub max_iterations: u32,
+    #[serde(default)]
+    pub bounds: CalibrationBounds,
+    #[serde(default)]
+    pub bot: BotPolicy,
+}
+
+fn default_calibration_tolerance() -> f32 {
+    0.1
+}
+
+fn default_calibration_iterations() -> u32 {
+    6
+}
+
+/// Searched parameter ranges, each written `[lenient, harsh]`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct CalibrationBounds {
+    pub cash_multiplier: [f32; 2],
+    pub annual_growth_pct_multiplier: [f32; 2],
+    pub event_severity_multiplier: [f32; 2],
+}
+
+impl Default for CalibrationBounds {
+    fn default() -> Self {
+        Self {
+            cash_multiplier: [4.0, 0.1],
+            annual_growth_pct_multiplier: [1.3, 0.4],
+            event_severity_multiplier: [0.5, 3.0],
+        }
+    }
+}
+
+impl CalibrationSpec {
+    pub fn from_yaml_str(text: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(text)
+    }
+}
+
+impl CalibrationBounds {
+    /// `base` with the searched parameters set for harshness `h` in `[0, 1]`. Cash scales
+    /// geometrically so each step moves the starting balance by a similar ratio.
+    fn preset_at(&self, base: &DifficultyPreset, h: f32) -> DifficultyPreset {
+        let round = |x: f32| (x * 100.0).round() / 100.0;
+        let lerp = |[a, b]: [f32; 2]| round(a + (b - a) * h);
+        let [c0, c1] = self.cash_multiplier.map(|c| c.max(0.01).ln());
+        DifficultyPreset {
+            cash_multiplier: round((c0 + (c1 - c0) * h).exp()).max(0.01),
+            annual_growth_pct_multiplier: lerp(self.annual_growth_pct_multiplier),
+            event_severity_multiplier: lerp(self.event_severity_multiplier),
+            ..base.clone()
+        }
+    }
+}
+
+/// One evaluated preset during the search.
+#[derive(Clone, Debug)]
+pub struct CalibrationTrial {
+    pub harshness: f32,
+    pub win_rate: f32,
+    pub fail_rate: f32,
+}
+
+/// Search result for one difficulty level.
+#[derive(Clone, Debug)]
+pub struct CalibratedLevel {
+    pub level: String,
+    pub target: f32,
+    /// Win rate of the shipped preset, for comparison.
+    pub current_win_rate: f32,
+    pub win_rate: f32,
+    pub preset: DifficultyPreset,
+    pub trials: Vec<CalibrationTrial>,
+}
+
+/// Bot win and fail rates for `preset` across `seeds`, each played to the scenario's end.
+fn preset_rates(
+    scenario: &str,
+    level: &str,
+    preset: &DifficultyPreset,
+    seeds: &[u64],
+    policy: &BotPolicy,
+) -> Result<(f32, f32), String> {
+    let mut wins = 0usize;
+    let mut fails = 0usize;
+    for &seed in seeds {
+        let (mut world, full) = scenario_world(scenario, seed)?;
+        apply_preset(&mut world, level, preset);
+        let run = play_bot(world, seed, full, policy, &mut |_, _| Ok(()))?;
+        match run.outcome {
+            runtime::CampaignOutcome::Success => wins += 1,
+            runtime::CampaignOutcome::Failed => fails += 1,
+            runtime::CampaignOutcome::InProgress => {}
+        }
+    }
+    let n = seeds.len().max(1) as f32;
+    Ok((wins as f32 / n, fails as f32 / n))
+}
+
+/// Bisect each level's harshness until the bot's win rate is within tolerance of the
+/// target, keeping the closest preset seen. Parameters outside the searched ones keep
+/// their shipped values.
+pub fn calibrate_difficulty(spec: &CalibrationSpec) -> Result<Vec<CalibratedLevel>, String> {
+    let shipped = difficulty_presets()?;
+    let mut out = Vec::with_capacity(spec.targets.len());
+    for (level, &target) in &spec.targets {
+        let base = shipped
+            .levels
+            .get(level)
+            .ok_or_else(|| format!("unknown difficulty {level}"))?;
+        let (current_win_rate, _) =
+            preset_rates(&spec.scenario, level, base, &spec.seeds, &spec.bot)?;
+        let (mut lo, mut hi) = (0.0f32, 1.0f32);
+        let mut trials: Vec<CalibrationTrial> = Vec::new();
+        for _ in 0..spec.max_iterations.max(1) {
+            let h = (lo + hi) / 2.0;
+            let preset = spec.bounds.preset_at(base, h);
+            let (win_rate, fail_rate) =
+                preset_rates(&spec.scenario, level, &preset, &spec.seeds, &spec.bot)?;
+            trials.push(CalibrationTrial {
+                harshness: h,
+                win_rate,
+                fail_rate,
+            });
+            if (win_rate - target).abs() <= spec.tolerance {
+                break;
+            }
+            // Too easy: search the harsher half
+            if win_rate > target {
+                lo = h;
+            } else {
+                hi = h;
+            }
+        }
+        // Ties go to the latest trial, the furthest the search got
+        let best = trials
+            .iter()
+            .rev()
+            .min_by(|a, b| {
+                (a.win_rate - target)
+                    .abs()
+                    .total_cmp(&(b.win_rate - target).abs())
+            })
+            .cloned()
+            .ok_or_else(|| format!("{level}: no calibration trials"))?;
+        out.push(CalibratedLevel {
+            level: level.clone(),
+            target,
+            current_win_rate,
+            win_rate: best.win_rate,
+            preset: spec.bounds.preset_at(base, best.harshness),
+            trials,
+        });
+    }
+    Ok(out)
+}
+
+/// Render a `difficulty.yaml` proposal: the shipped presets with calibrated levels replaced,
+/// headed by the target and measured win rates.
+pub fn calibration_proposal_yaml(
+    spec: &CalibrationSpec,
+    levels: &[CalibratedLevel],
+) -> Result<String, String> {
+    let mut presets = difficulty_presets()?;
+    let mut out = format!(
+        "# Difficulty presets proposed by calibration on {} ({} seeds, bot win rates)\n#\n",
+        spec.scenario,
+        spec.seeds.len()
+    );
+    for l in levels {
+        out.push_str(&format!(
+            "# {}: target {:.0}%, measured {:.0}% (shipped preset {:.0}%){}\n",
+            l.level,
+            l.target * 100.0,
+            l.win_rate * 100.0,
+            l.current_win_rate * 100.0,
+            if (l.win_rate - l.target).abs() > spec.tolerance {
+                ", target not reached within the bounds"
+            } else {
+                ""
+            }
+        ));
+        presets.levels.insert(l.level.clone(), l.preset.clone());
+    }
+    out.push('\n');
+    out.push_str(&serde_yaml::to_string(&presets).map_err(|e| e.to_string())?);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_embedded_scenario_builds() {
+        for (name, _) in SCENARIOS {
+            let (world, months) = scenario_world(name, 1).unwrap();
+            assert!(months > 0, "{name} has no months");
+            assert!(world
+                .get_resource::<runtime::CampaignScenarioRes>()
+                .is_some());
+        }
+        let spec = PackSpec::from_yaml_str(PACK_YAML).unwrap();
+        for (name, _) in SCENARIOS {
+            assert!(
+                spec.cases.iter().any(|c| c.scenario == *name),
+                "scenario {name} has no regression case"
+            );
+        }
+    }
+
+    #[test]
+    fn campaign_world_applies_scenario_fab() {
+        let (_, text) = SCENARIOS[0];
+        let content = content().unwrap();
+        let (world, _) = content.campaign_world(text, 1).unwrap();
+        assert!(!world.resource::<runtime::RnDTracks>().owns_fab);
+        let with_fab = format!("{text}\nowns_fab: true\n");
+        let (world, months) = content.campaign_world(&with_fab, 1).unwrap();
+        assert!(world.resource::<runtime::RnDTracks>().owns_fab);
+        assert_eq!(months, scenario_world(SCENARIOS[0].0, 1).unwrap().1);
+    }
+
+    #[test]
+    fn sandbox_setup_builds_and_validates() {
+        let setup = SandboxSetup {
+            start_year: 1995,
+            years: 5,
+            player_cash_cents: 200_000_000,
+            competitors: 2,
+            personas: vec![runtime::EntrantPersona {
+                name: "budget".into(),
+                weight: 0.5,
+                margin_frac: 0.05,
+            }],
+            segments: vec!["desktop".into(), "server".into()],
+            difficulty: Some("hard".into()),
+            seed: 7,
+            fog: false,
+        };
+        let content = content().unwrap();
+        let (world, months) = setup.build(&content).unwrap();
+        assert_eq!(months, 60);
+        let dom = &world.resource::<runtime::DomainWorld>().0;
+        assert_eq!(
+            dom.macro_state.date,
+            NaiveDate::from_ymd_opt(1995, 1, 1).unwrap()
+        );
+        assert_eq!(dom.companies.len(), 3);
+        assert_eq!(dom.segments.len(), 2);
+        let markets = world.resource::<runtime::MarketConfigRes>();
+        assert!(markets.console_cycle.is_none());
+        let books = world.resource::<runtime::RivalBooks>();
+        assert_eq!(books.founded, 2);
+        assert!(books
+            .rivals
+            .iter()
+            .all(|r| r.weight == 0.5 && r.persona.as_deref() == Some("budget")));
+        let cfg = world.resource::<runtime::CampaignScenarioRes>();
+        assert_eq!(cfg.end, NaiveDate::from_ymd_opt(2000, 1, 1).unwrap());
+        assert_eq!(cfg.difficulty.as_deref(), Some("hard"));
+        assert!(!world.resource::<runtime::MarketFog>().enabled);
+
+        let bad = SandboxSetup {
+            start_year: 1980,
+            player_cash_cents: 0,
+            segments: vec!["quantum".into()],
+            difficulty: Some("nightmare".into()),
+            ..Default::default()
+        };
+        let err = bad.build(&content).unwrap_err();
+        for part in [
+            "start year",
+            "starting cash",
+            "unknown segment quantum",
+            "unknown difficulty nightmare",
+        ] {
+            assert!(err.contains(part), "{err}");
+        }
+        assert!(SandboxSetup::default().validate(&content).is_ok());
+    }
+
+    #[test]
+    fn failing_assertions_are_reported() {
+        let case = PackCase {
+            scenario: "tutorial_24m".into(),
+            difficulty: Some("easy".into()),
+            months: Some(6),
+            bot: None,
+            script: None,
+            assert: vec![
+                Assertion::MedianCashAt {
+                    month: 3,
+                    min_cents: Some(I64Bound(I64OrStr::S("1_000_000_000_000_00".into()))),
+                    max_cents: None,
+                },
+                Assertion::WinRate {
+                    min: None,
+                    max: Some(1.0),
+                },
+            ],
+        };
+        let runs = run_case(&case, &[1, 2]).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].checkpoints.len(), 2);
+        let failures = check_case(&case, &runs).unwrap();
+        assert_eq!(failures.len(), 1, "{failures:?}");
+        assert!(failures[0].contains("median cash at month 3"));
+        // Checkpoints past the end of the run are a spec error, not a silent pass
+        let late = PackCase {
+            assert: vec![Assertion::MedianShareAt {
+                month: 12,
+                min: Some(0.0),
+                max: None,
+            }],
+            ..case
+        };
+        assert!(check_case(&late, &runs).is_err());
+    }
+
+    #[test]
+    fn difficulty_baseline_feeds_campaign_report() {
+        let base = difficulty_baseline("tutorial_24m", "normal", 6, 1).unwrap();
+        assert_eq!(base.kpis.len(), 6);
+        assert_eq!(base.kpis.last().unwrap().month, 5);
+        assert!(difficulty_baseline("tutorial_24m", "brutal", 6, 1).is_err());
+        // The same seed replays the baseline itself: every KPI matches
+        let (mut world, _) = scenario_world("tutorial_24m", 1).unwrap();
+        apply_difficulty(&mut world, "normal").unwrap();
+        runtime::run_months_in_place(&mut world, 4);
+        let report = runtime::campaign_report(&world, Some(&base));
+        let cmp = report.baseline.expect("comparison");
+        assert_eq!(cmp.month, 3);
+        assert!(cmp.rows.iter().all(|r| r.player == r.baseline), "{cmp:?}");
+    }
+
+    #[test]
+    fn shipped_scripts_play_the_tutorial() {
+        assert!(strategy_script("nope").is_err());
+        for (name, _) in SCRIPTS {
+            let (mut world, months) = scenario_world("tutorial_24m", 1).unwrap();
+            runtime::attach_player_script(&mut world, strategy_script(name).unwrap());
+            runtime::run_months_in_place(&mut world, months);
+            let log = world.resource::<runtime::PlayerScriptLog>();
+            assert!(log.applied > 0, "{name}");
+            let failed: Vec<_> = log.entries.iter().filter(|e| e.error.is_some()).collect();
+            assert!(failed.is_empty(), "{name}: {failed:?}");
+        }
+    }
+
+    #[test]
+    fn regression_pack_holds() {
+        let spec = PackSpec::from_yaml_str(PACK_YAML).unwrap();
+        let reports = run_pack(&spec).unwrap();
+        let failed: Vec<String> = reports
+            .iter()
+            .flat_map(|r| r.failures.iter().map(move |f| format!("{}: {f}", r.label)))
+            .collect();
+        assert!(
+            failed.is_empty(),
+            "regression pack failures:\n{}",
+            failed.join("\n")
+        );
+    }
+
+    #[test]
+    fn bot_soaks_a_century_without_invalid_states() {
+        let mut last = None;
+        let mut footprints = Vec::new();
+        let mut hook = |month: u32, world: &World| {
+            let date = world.resource::<runtime::DomainWorld>().0.macro_state.date;
+            if last.is_some_and(|d| d >= date) {
+                return Err(format!("date did not advance past {date}"));
+            }
+            last = Some(date);
+            assert!(month <= 1200);
+            if month % 120 == 0 {
+                let f = runtime::state_footprint(world)?;
+                if f.bytes > runtime::STATE_BYTES_BUDGET {
+                    return Err(format!("state of {} bytes is over budget", f.bytes));
+                }
+                footprints.push(f);
+            }
+            Ok(())
+        };
+        let run = run_bot(
+            "campaign_1990s",
+            Some("normal"),
+            7,
+            Some(1200),
+            &BotPolicy::default(),
+            &mut hook,
+        )
+        .unwrap();
+        assert_eq!(run.months, 1200);
+        assert!(run.violations.is_empty(), "{:?}", run.violations);
+        assert!(run
+            .actions
+            .iter()
+            .any(|(_, a)| matches!(a, BotAction::Tapeout { .. })));
+        // Compaction holds the growing collections steady over the second half-century
+        let (mid, end) = (&footprints[4], &footprints[9]);
+        assert!(end.released <= mid.released * 2, "{mid:?} {end:?}");
+        assert!(end.contracts <= mid.contracts * 2, "{mid:?} {end:?}");
+        assert!(end.reviews <= mid.reviews * 2, "{mid:?} {end:?}");
+        assert!(end.news <= runtime::NEWS_ITEM_CAP);
+        // A failing hook stops the run and names the month
+        let err = run_bot(
+            "tutorial_24m",
+            None,
+            1,
+            None,
+            &BotPolicy::default(),
+            &mut |m, _| if m == 3 { Err("boom".into()) } else { Ok(()) },
+        )
+        .unwrap_err();
+        assert!(err.contains("month 3: boom"), "{err}");
+    }
+
+    #[test]
+    fn bot_reports_win_rate_per_difficulty() {
+        let rates = bot_win_rates(
+            "campaign_1990s",
+            &["easy", "normal", "hard"],
+            &[1, 42],
+            &BotPolicy::default(),
+        )
+        .unwrap();
+        assert_eq!(rates.len(), 3);
+        for r in &rates {
+            assert_eq!(r.runs, 2);
+            assert_eq!(r.invariant_violations, 0, "{}", r.difficulty);
+        }
+        // Harder settings never make the same bot win more or fail less
+        for pair in rates.windows(2) {
+            let (easier, harder) = (&pair[0], &pair[1]);
+            assert!(
+                easier.win_rate >= harder.win_rate,
+                "{} {}",
+                easier.difficulty,
+                harder.difficulty
+            );
+            assert!(
+                easier.fail_rate <= harder.fail_rate,
+                "{} {}",
+                easier.difficulty,
+                harder.difficulty
+            );
+        }
+        // The bot wins every level here, so compare how: hard leaves it poorer than easy
+        let cash = |level: &str| {
+            run_bot(
+                "campaign_1990s",
+                Some(level),
+                42,
+                None,
+                &BotPolicy::default(),
+                &mut |_, _| Ok(()),
+            )
+            .unwrap()
+            .cash_cents
+        };
+        assert!(cash("hard") < cash("easy"));
+    }
+
+    #[test]
+    fn calibration_searches_toward_target_win_rates() {
+        let spec = CalibrationSpec::from_yaml_str(
+            "scenario: tutorial_24m\nseeds: [1, 2]\nmax_iterations: 3\ntargets: { easy: 1.0, hard: 0.0 }\n",
+        )
+        .unwrap();
+        let levels = calibrate_difficulty(&spec).unwrap();
+        assert_eq!(levels.len(), 2);
+        // Met on the first trial: the search stops there
+        let easy = &levels[0];
+        assert_eq!((easy.level.as_str(), easy.trials.len()), ("easy", 1));
+        assert_eq!(easy.win_rate, 1.0);
+        // The bot keeps winning, so every step heads for the harsh end of the bounds
+        let hard = &levels[1];
+        let steps: Vec<f32> = hard.trials.iter().map(|t| t.harshness).collect();
+        assert_eq!(steps, vec![0.5, 0.75, 0.875]);
+        assert!(hard.preset.cash_multiplier < 0.5);
+        assert!(hard.preset.event_severity_multiplier > 2.0);
+        assert_eq!(hard.preset.min_margin_frac, 0.10);
+        let yaml = calibration_proposal_yaml(&spec, &levels).unwrap();
+        assert!(
+            yaml.contains("# easy: target 100%, measured 100%"),
+            "{yaml}"
+        );
+        assert!(yaml.contains("target not reached"), "{yaml}");
+        let proposal: DifficultyPresets = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(proposal.levels.len(), 3);
+        assert_eq!(proposal.levels["normal"].cash_multiplier, 1.0);
+        assert_eq!(
+            proposal.levels["hard"].cash_multiplier,
+            hard.preset.cash_multiplier
+        );
+        let (mut world, _) = scenario_world("tutorial_24m", 1).unwrap();
+        apply_preset(&mut world, "hard", &proposal.levels["hard"]);
+        assert_eq!(
+            world
+                .resource::<runtime::DistressConfig>()
+                .emergency_loan_cents,
+            100_000_000
+        );
+    }
+}
@@ -107,12 +107,13 @@ pub fn utility_score(metrics: &CompanyMetrics, w: &ScoreWeights) -> f32 {
 /// - share_12m: provided by caller or derived from runtime stats.
 /// - margin_ratio: approximated from revenue/profit if ASP is unknown.
 /// - liquidity_k: computed from first company's cash/debt.
-/// - portfolio_div: simple proxy based on number of segments (more segments => higher).
+/// - portfolio_div: provided by caller, see [`portfolio_diversification`].
 pub fn metrics_from_world(
     world: &core::World,
     share_12m: f32,
     revenue_usd: Decimal,
     profit_usd: Decimal,
+    portfolio_div: f32,
 ) -> CompanyMetrics {
     let revenue_f = revenue_usd.to_f32().unwrap_or(0.0);
     let profit_f = profit_usd.to_f32().unwrap_or(0.0);
@@ -131,9 +132,6 @@ pub fn metrics_from_world(
         (debt.to_f32().unwrap_or(0.0) + 1.0).max(1.0),
     )
     .max(0.0);
-    let segs = world.segments.len().max(1) as f32;
-    let portfolio_div = (segs / 5.0).clamp(0.0, 1.0);
-
     CompanyMetrics {
         share_12m,
         margin_ratio,
@@ -142,6 +140,21 @@ pub fn metrics_from_world(
     }
 }
 
+/// Distinct product kinds at which the kind half of diversification saturates.
+pub const PORTFOLIO_FULL_KINDS: usize = 3;
+
+/// Portfolio diversification in [0,1] of the product lines a company ships: half from the
+/// number of distinct product kinds, half from the share of market segments they can sell into.
+pub fn portfolio_diversification(kinds: usize, covered_segments: usize, segments: usize) -> f32 {
+    let kind_part = (kinds as f32 / PORTFOLIO_FULL_KINDS as f32).min(1.0);
+    let seg_part = if segments == 0 {
+        0.0
+    } else {
+        covered_segments.min(segments) as f32 / segments as f32
+    };
+    0.5 * kind_part + 0.5 * seg_part
+}
+
 // -------------- Tests for utility scoring --------------
 
 #[cfg(test)]
@@ -196,6 +209,26 @@ pub struct CurrentKpis {
     pub debt_usd: Decimal,
     pub share: f32,
     pub rd_progress: f32,
+    /// Monthly cash already committed (contract take-or-pay billing, R&D budget).
+    pub committed_outflow_usd: Decimal,
+    /// Diversification of the released portfolio, see [`portfolio_diversification`].
+    #[serde(default)]
+    pub portfolio_div: f32,
+    /// Capacity has sat underused for months: shrink commitments, never request more.
+    #[serde(default)]
+    pub underused_capacity: bool,
+    /// Appeal and launch timing, aged by [`PlannerConfig::staleness`].
+    #[serde(default)]
+    pub launch: LaunchCadence,
+}
+
+/// Where the product line stands between launches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LaunchCadence {
+    pub appeal: f32,
+    pub months_since_launch: u32,
+    /// Months until the earliest tapeout in flight launches.
+    pub next_launch_in_months: Option<u32>,
 }
 
 /// Planner configuration controlling breadth/depth and economics.
@@ -210,6 +243,120 @@ pub struct PlannerConfig {
     pub capacity_step_units: u64,
     pub price_pref_beta: f32,
     pub competitor_attractiveness: f32,
+    /// Spending actions are pruned if projected cash dips below this floor within a quarter;
+    /// no floor beyond solvency by default.
+    #[serde(default)]
+    pub liquidity_floor_cents: i64,
+    /// Monthly take-or-pay billing per requested capacity unit.
+    #[serde(default = "default_capacity_cost_cents_per_unit")]
+    pub capacity_cost_cents_per_unit: i64,
+    /// One-off cost of an expedited tapeout.
+    #[serde(default = "default_expedite_cost_cents")]
+    pub expedite_cost_cents: i64,
+    /// Enable per-segment pricing and allocation-shift actions (needs >= 2 segments). The
+    /// runtime applies them to its segment prices and wafer plan.
+    #[serde(default)]
+    pub segment_actions: bool,
+    /// Fraction of capacity moved between product lines by one allocation shift.
+    #[serde(default = "default_alloc_step_frac")]
+    pub alloc_step_frac: f32,
+    /// Record beam contents, candidate scores and pruning into `PlanResult::trace`.
+    #[serde(default)]
+    pub trace: bool,
+    /// Quarterly spending headroom; spending actions that exceed it are pruned.
+    #[serde(default)]
+    pub budget: Option<PlanBudget>,
+    /// Cash one OEM relationship investment spends; 0 disables the action.
+    #[serde(default)]
+    pub relationship_step_cents: i64,
+    /// Share the predictor credits one relationship investment with (won design-ins).
+    #[serde(default)]
+    pub relationship_share_gain: f32,
+    /// Monthly budget one process or design R&D funding step adds; 0 disables both actions.
+    #[serde(default)]
+    pub rnd_step_cents: i64,
+    /// Fraction of unit cost the predictor removes per process step (faster yield ramp).
+    #[serde(default)]
+    pub process_rnd_cost_gain: f32,
+    /// Share the predictor credits one design step with (better perf, earlier tapeouts).
+    #[serde(default)]
+    pub design_rnd_share_gain: f32,
+    /// Monthly budget one quality-control step adds; 0 disables the action.
+    #[serde(default)]
+    pub qc_step_cents: i64,
+    /// Fraction of unit cost the predictor removes per quality-control step (fewer scrapped
+    /// dies, fewer warranty claims).
+    #[serde(default)]
+    pub qc_cost_gain: f32,
+    /// Diversification the predictor credits a tapeout with (a product line of a new kind).
+    #[serde(default)]
+    pub tapeout_portfolio_gain: f32,
+    /// Buyer weights and the product's other attributes per world segment (in order); where
+    /// set, share follows the weighted attractiveness of the price instead of price alone.
+    #[serde(default)]
+    pub attractiveness: Vec<Option<SegmentAttractiveness>>,
+    /// Rival strength per world segment (in order) where the market sets one; the rest, and
+    /// whole-market predictions, use `competitor_attractiveness`.
+    #[serde(default)]
+    pub competitor_strength: Vec<Option<f32>>,
+    /// Appeal staleness the predictor ages the current appeal with, so plans schedule
+    /// tapeouts before appeal decays; `None` leaves appeal out of predictions.
+    #[serde(default)]
+    pub staleness: Option<sim_econ::StalenessModel>,
+    /// Months from scheduling a tapeout to its launch.
+    #[serde(default = "default_tapeout_lead_months")]
+    pub tapeout_lead_months: u32,
+}
+
+fn default_capacity_cost_cents_per_unit() -> i64 {
+    200 // ~$100/wafer over ~50 good dies
+}
+
+fn default_expedite_cost_cents() -> i64 {
+    100_000
+}
+
+fn default_unbudgeted_cents() -> i64 {
+    i64::MAX
+}
+
+fn default_alloc_step_frac() -> f32 {
+    0.1
+}
+
+fn default_tapeout_lead_months() -> u32 {
+    9
+}
+
+impl PlannerConfig {
+    /// Competitor attractiveness `segment`'s share is pulled against.
+    pub fn competitor_for(&self, segment: usize) -> f32 {
+        self.competitor_strength
+            .get(segment)
+            .copied()
+            .flatten()
+            .unwrap_or(self.competitor_attractiveness)
+    }
+}
+
+/// How buyers in one segment score the product, for the predictor's share.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SegmentAttractiveness {
+    pub weights: sim_econ::AttractivenessWeights,
+    /// Attributes other than price, which is scored from the predicted ASP.
+    pub attributes: sim_econ::ProductAttributes,
+}
+
+/// Budget headroom the planner must respect, in cents per quarter.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PlanBudget {
+    /// Recurring quarterly capacity billing that new requests may add.
+    pub capacity_cents: i64,
+    /// R&D spend (expedites, added track budgets) still available in a quarter.
+    pub rnd_cents: i64,
+    /// Opex (relationship investments, quality control) still available in a quarter.
+    #[serde(default = "default_unbudgeted_cents")]
+    pub opex_cents: i64,
 }
 
 impl Default for PlannerConfig {
@@ -224,18 +371,77 @@ impl Default for PlannerConfig {
             capacity_step_units: 10_000,
             price_pref_beta: 1.5,
             competitor_attractiveness: 1.0,
+            liquidity_floor_cents: 0,
+            capacity_cost_cents_per_unit: default_capacity_cost_cents_per_unit(),
+            expedite_cost_cents: default_expedite_cost_cents(),
+            segment_actions: false,
+            alloc_step_frac: default_alloc_step_frac(),
+            trace: false,
+            budget: None,
+            relationship_step_cents: 0,
+            relationship_share_gain: 0.01,
+            rnd_step_cents: 0,
+            process_rnd_cost_gain: 0.01,
+            design_rnd_share_gain: 0.005,
+            qc_step_cents: 0,
+            qc_cost_gain: 0.005,
+            tapeout_portfolio_gain: 0.1,
+            attractiveness: Vec::new(),
+            competitor_strength: Vec::new(),
+            staleness: None,
+            tapeout_lead_months: default_tapeout_lead_months(),
         }
     }
 }
 
 /// A single action considered by the planner at quarterly decision points.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum PlanAction {
     AdjustPriceFrac(f32),  // +/- fraction of current ASP
     RequestCapacity(u64),  // units/month
     AllocateRndBoost(f32), // +/- boost to R&D progress per month
-    ScheduleTapeout { expedite: bool },
-    // ScheduleTapeout is omitted in this phase's simplified predictor
+    ScheduleTapeout {
+        expedite: bool,
+    },
+    /// +/- fraction of the ASP in one segment (index into `World::segments`)
+    AdjustSegmentPriceFrac {
+        segment: usize,
+        frac: f32,
+    },
+    /// Move a fraction of wafer capacity from one segment's product line to another's
+    ShiftAllocation {
+        from: usize,
+        to: usize,
+        frac: f32,
+    },
+    /// Spend cash on the OEM relationship closest to unlocking a volume deal
+    InvestRelationship {
+        cents: i64,
+    },
+    /// Raise the monthly process R&D budget (yield ramp, node access for fab owners)
+    FundProcessRnd {
+        cents: i64,
+    },
+    /// Raise the monthly design R&D budget (perf index, shorter tapeouts)
+    FundDesignRnd {
+        cents: i64,
+    },
+    /// Raise the monthly quality-control budget (fewer defects and field failures)
+    FundQualityControl {
+        cents: i64,
+    },
+}
+
+/// Predictor state of one segment's product line.
+#[derive(Debug, Clone)]
+struct SegmentPlanState {
+    asp: Decimal,
+    ref_price: Decimal,
+    share: f32,
+    /// Fraction of total capacity allocated to this line.
+    alloc: f32,
+    /// Unconstrained demand for our product in the last simulated month.
+    last_demand: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -248,9 +454,57 @@ struct PlannerState {
     share: f32,
     rd_progress: f32,
     ref_price: Decimal,
+    committed_outflow: Decimal,
+    portfolio_div: f32,
+    /// Monthly capacity billing added by this plan's requests, in cents.
+    added_billing_cents: i64,
+    /// Per-segment lines; empty means the single-product predictor.
+    segments: Vec<SegmentPlanState>,
+    launch: LaunchCadence,
+    /// Appeal when planning began.
+    start_appeal: f32,
 }
 
-#[derive(Debug, Clone)]
+impl PlannerState {
+    /// Share multiplier for appeal lost to staleness since planning began, after the
+    /// market's `(1 + appeal)` term in attractiveness.
+    fn staleness_factor(&self) -> f32 {
+        (1.0 + self.launch.appeal) / (1.0 + self.start_appeal).max(1e-3)
+    }
+
+    /// Share held once a tapeout scheduled now could launch, given the launches already
+    /// planned: what the company can still change, so decisions see decay coming.
+    fn share_outlook(&self, cfg: &PlannerConfig) -> f32 {
+        let Some(model) = cfg.staleness else {
+            return self.share;
+        };
+        let mut launch = self.launch;
+        for _ in 0..cfg.tapeout_lead_months {
+            age_cadence(&mut launch, self.start_appeal, &model);
+        }
+        let factor = (1.0 + launch.appeal) / (1.0 + self.launch.appeal).max(1e-3);
+        (self.share * factor).clamp(0.0, 1.0)
+    }
+}
+
+/// Age a launch cadence by a month: a launch due now resets the clock and wins back the
+/// appeal lost to staleness, otherwise appeal decays per the model.
+fn age_cadence(l: &mut LaunchCadence, start_appeal: f32, model: &sim_econ::StalenessModel) {
+    match l.next_launch_in_months {
+        Some(n) if n <= 1 => {
+            l.next_launch_in_months = None;
+            l.months_since_launch = 0;
+            l.appeal = l.appeal.max(start_appeal);
+        }
+        next => {
+            l.next_launch_in_months = next.map(|n| n - 1);
+            l.months_since_launch = l.months_since_launch.saturating_add(1);
+            l.appeal = model.decay(l.appeal, l.months_since_launch);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlanStepDecision {
     pub month_index: u32,
     pub action: PlanAction,
@@ -260,6 +514,56 @@ pub struct PlanStepDecision {
 pub struct PlanResult {
     pub decisions: Vec<PlanStepDecision>,
     pub expected_score: f32,
+    /// Search trace, present only when `PlannerConfig::trace` is set.
+    pub trace: Option<PlanTrace>,
+}
+
+/// Why a candidate did not survive a decision step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PruneReason {
+    /// Spending action would take projected cash below the liquidity floor.
+    LiquidityFloor,
+    /// Spending action would exceed the quarterly budget.
+    Budget,
+    /// Scored, but fell outside the top `beam_width`.
+    BeamWidth,
+}
+
+/// A beam entry at the start of a decision step.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceBeamNode {
+    pub score: f32,
+    pub cash_usd: f64,
+    pub share: f32,
+    pub asp_usd: f64,
+    pub decisions: Vec<PlanStepDecision>,
+}
+
+/// One expanded (beam node, action) pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceCandidate {
+    /// Index into the step's `beam`.
+    pub parent: usize,
+    pub action: PlanAction,
+    /// Cumulative discounted score; `None` when pruned before scoring.
+    pub score: Option<f32>,
+    pub kept: bool,
+    pub pruned: Option<PruneReason>,
+}
+
+/// Search record for one decision month (quarter).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarterTrace {
+    pub month_index: u32,
+    pub beam: Vec<TraceBeamNode>,
+    pub candidates: Vec<TraceCandidate>,
+}
+
+/// Full planner trace for debugging AI choices.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlanTrace {
+    pub quarters: Vec<QuarterTrace>,
+    pub expected_score: f32,
 }
 
 fn price_attractiveness_ratio(asp: Decimal, ref_price: Decimal, beta: f32) -> f32 {
@@ -275,19 +579,49 @@ fn expected_share_from_price(asp: Decimal, ref_price: Decimal, beta: f32, comp_a
     (a / denom).clamp(0.05, 0.95)
 }
 
+/// Target share of `segment` at `asp` against rivals of attractiveness `comp_attr`: the
+/// segment's weighted attractiveness relative to selling at the reference price when
+/// configured, else the price ratio alone.
+fn expected_share(
+    cfg: &PlannerConfig,
+    segment: usize,
+    asp: Decimal,
+    ref_price: Decimal,
+    comp_attr: f32,
+) -> f32 {
+    let Some(seg) = cfg.attractiveness.get(segment).and_then(Option::as_ref) else {
+        return expected_share_from_price(asp, ref_price, cfg.price_pref_beta, comp_attr);
+    };
+    let at = |p: Decimal| {
+        seg.weights.score(&sim_econ::ProductAttributes {
+            price: sim_econ::price_score(p, ref_price),
+            ..seg.attributes
+        })
+    };
+    let a = (at(asp) / at(ref_price).max(1e-3)).powf(cfg.price_pref_beta);
+    (a / (a + comp_attr.max(1e-3))).clamp(0.05, 0.95)
+}
+
 fn simulate_month(
     state: &mut PlannerState,
     world: &core::World,
     w: &ScoreWeights,
     cfg: &PlannerConfig,
 ) -> f32 {
+    if let Some(model) = cfg.staleness {
+        age_cadence(&mut state.launch, state.start_appeal, &model);
+    }
+    if !state.segments.is_empty() {
+        return simulate_month_segments(state, world, w, cfg);
+    }
     // Update share based on price attractiveness drifting 10% towards target per month
-    let target_share = expected_share_from_price(
+    let target_share = expected_share(
+        cfg,
+        0,
         state.asp,
         state.ref_price,
-        cfg.price_pref_beta,
         cfg.competitor_attractiveness,
-    );
+    ) * state.staleness_factor();
     state.share += (target_share - state.share) * 0.1;
     state.share = state.share.clamp(0.05, 0.95);
 
@@ -306,11 +640,11 @@ fn simulate_month(
     let revenue = state.asp * Decimal::from(sell);
     let cost = state.unit_cost * Decimal::from(sell);
     let profit = revenue - cost;
-    state.cash += profit;
+    state.cash += profit - state.committed_outflow;
 
     // Utility contribution this month
     let m = CompanyMetrics {
-        share_12m: state.share,
+        share_12m: state.share_outlook(cfg),
         margin_ratio: if revenue > Decimal::ZERO {
             (profit / revenue).to_f32().unwrap_or(0.0).clamp(0.0, 1.0)
         } else {
@@ -320,34 +654,287 @@ fn simulate_month(
             state.cash.to_f32().unwrap_or(0.0),
             (state.debt.to_f32().unwrap_or(0.0) + 1.0).max(1.0),
         ),
-        portfolio_div: (world.segments.len().max(1) as f32 / 5.0).clamp(0.0, 1.0),
+        portfolio_div: state.portfolio_div,
     };
     utility_score(&m, w)
 }
 
+/// Segment-level predictor: each line sells into its own segment at its own price, limited
+/// by its share of capacity; margins and share aggregate across lines.
+fn simulate_month_segments(
+    state: &mut PlannerState,
+    world: &core::World,
+    w: &ScoreWeights,
+    cfg: &PlannerConfig,
+) -> f32 {
+    let mut revenue = Decimal::ZERO;
+    let mut cost = Decimal::ZERO;
+    let mut share_num = 0.0f32;
+    let mut share_den = 0.0f32;
+    let stale = state.staleness_factor();
+    for (i, seg) in state.segments.iter_mut().enumerate() {
+        let target = expected_share(cfg, i, seg.asp, seg.ref_price, cfg.competitor_for(i)) * stale;
+        seg.share = (seg.share + (target - seg.share) * 0.1).clamp(0.05, 0.95);
+        let (base_demand, elasticity) = world
+            .segments
+            .get(i)
+            .map(|s| (s.base_demand_units, s.price_elasticity))
+            .unwrap_or((100_000, -1.2));
+        let q_total = sim_econ::demand(base_demand, seg.asp, seg.ref_price, elasticity)
+            .unwrap_or(base_demand);
+        let q_our = ((q_total as f32) * seg.share).floor() as u64;
+        let cap = ((state.capacity as f32) * seg.alloc.clamp(0.0, 1.0)).floor() as u64;
+        let sell = q_our.min(cap);
+        seg.last_demand = q_our;
+        revenue += seg.asp * Decimal::from(sell);
+        cost += state.unit_cost * Decimal::from(sell);
+        share_num += seg.share * q_total as f32;
+        share_den += q_total as f32;
+    }
+    state.share = if share_den > 0.0 {
+        (share_num / share_den).clamp(0.05, 0.95)
+    } else {
+        state.share
+    };
+    let profit = revenue - cost;
+    state.cash += profit - state.committed_outflow;
+    let m = CompanyMetrics {
+        share_12m: state.share_outlook(cfg),
+        margin_ratio: if revenue > Decimal::ZERO {
+            (profit / revenue).to_f32().unwrap_or(0.0).clamp(0.0, 1.0)
+        } else {
+            0.0
+        },
+        liquidity_k: safe_ratio(
+            state.cash.to_f32().unwrap_or(0.0),
+            (state.debt.to_f32().unwrap_or(0.0) + 1.0).max(1.0),
+        ),
+        portfolio_div: state.portfolio_div,
+    };
+    utility_score(&m, w)
+}
+
+fn adjust_price(asp: Decimal, df: f32, unit_cost: Decimal, cfg: &PlannerConfig) -> Decimal {
+    let factor = Decimal::from_f32_retain(1.0 + df).unwrap_or(Decimal::ONE);
+    let asp = asp * factor;
+    if !respects_min_margin(asp, unit_cost, cfg.min_margin_frac) {
+        min_price(unit_cost, cfg.min_margin_frac)
+    } else {
+        asp
+    }
+}
+
 fn apply_action(state: &mut PlannerState, action: PlanAction, cfg: &PlannerConfig) {
     match action {
         PlanAction::AdjustPriceFrac(df) => {
-            let factor = Decimal::from_f32_retain(1.0 + df).unwrap_or(Decimal::ONE);
-            let mut asp = state.asp * factor;
-            if !respects_min_margin(asp, state.unit_cost, cfg.min_margin_frac) {
-                asp = min_price(state.unit_cost, cfg.min_margin_frac);
+            state.asp = adjust_price(state.asp, df, state.unit_cost, cfg);
+            let unit_cost = state.unit_cost;
+            for seg in &mut state.segments {
+                seg.asp = adjust_price(seg.asp, df, unit_cost, cfg);
+            }
+        }
+        PlanAction::AdjustSegmentPriceFrac { segment, frac } => {
+            let unit_cost = state.unit_cost;
+            if let Some(seg) = state.segments.get_mut(segment) {
+                seg.asp = adjust_price(seg.asp, frac, unit_cost, cfg);
+            }
+        }
+        PlanAction::ShiftAllocation { from, to, frac } => {
+            if from != to && from < state.segments.len() && to < state.segments.len() {
+                let moved = state.segments[from].alloc.min(frac.max(0.0));
+                state.segments[from].alloc -= moved;
+                state.segments[to].alloc += moved;
             }
-            state.asp = asp;
         }
         PlanAction::RequestCapacity(units) => {
             state.capacity = state.capacity.saturating_add(units);
+            state.committed_outflow +=
+                Decimal::from(units) * Decimal::new(cfg.capacity_cost_cents_per_unit, 2);
+            state.added_billing_cents = state
+                .added_billing_cents
+                .saturating_add((units as i64).saturating_mul(cfg.capacity_cost_cents_per_unit));
         }
         PlanAction::AllocateRndBoost(boost) => {
             state.rd_progress = (state.rd_progress + boost).clamp(0.0, 1.0);
         }
-        PlanAction::ScheduleTapeout { expedite: _ } => {
+        PlanAction::ScheduleTapeout { expedite } => {
             // Predictor: slight near-term utility bonus to represent pipeline progress.
             state.rd_progress = (state.rd_progress + 0.005).clamp(0.0, 1.0);
+            state.portfolio_div = (state.portfolio_div + cfg.tapeout_portfolio_gain).min(1.0);
+            let lead = cfg.tapeout_lead_months.max(1);
+            let next = &mut state.launch.next_launch_in_months;
+            *next = Some(next.map_or(lead, |n| n.min(lead)));
+            if expedite {
+                state.cash -= Decimal::new(cfg.expedite_cost_cents, 2);
+            }
+        }
+        PlanAction::InvestRelationship { cents } => {
+            state.cash -= Decimal::new(cents, 2);
+            let gain = cfg.relationship_share_gain;
+            state.share = (state.share + gain).clamp(0.05, 0.95);
+            for seg in &mut state.segments {
+                seg.share = (seg.share + gain).clamp(0.05, 0.95);
+            }
+        }
+        PlanAction::FundProcessRnd { cents } => {
+            state.committed_outflow += Decimal::new(cents, 2);
+            let keep = Decimal::from_f32_retain(1.0 - cfg.process_rnd_cost_gain.clamp(0.0, 0.5))
+                .unwrap_or(Decimal::ONE);
+            state.unit_cost *= keep;
+        }
+        PlanAction::FundDesignRnd { cents } => {
+            state.committed_outflow += Decimal::new(cents, 2);
+            let gain = cfg.design_rnd_share_gain;
+            state.rd_progress = (state.rd_progress + gain).clamp(0.0, 1.0);
+            state.share = (state.share + gain).clamp(0.05, 0.95);
+            for seg in &mut state.segments {
+                seg.share = (seg.share + gain).clamp(0.05, 0.95);
+            }
+        }
+        PlanAction::FundQualityControl { cents } => {
+            state.committed_outflow += Decimal::new(cents, 2);
+            let keep = Decimal::from_f32_retain(1.0 - cfg.qc_cost_gain.clamp(0.0, 0.5))
+                .unwrap_or(Decimal::ONE);
+            state.unit_cost *= keep;
         }
     }
 }
 
+/// Whether an action adds new cash commitments.
+fn is_spending_action(action: PlanAction) -> bool {
+    matches!(
+        action,
+        PlanAction::RequestCapacity(_)
+            | PlanAction::ScheduleTapeout { expedite: true }
+            | PlanAction::InvestRelationship { .. }
+            | PlanAction::FundProcessRnd { .. }
+            | PlanAction::FundDesignRnd { .. }
+            | PlanAction::FundQualityControl { .. }
+    )
+}
+
+/// Whether a spending action takes the plan past its quarterly budget.
+fn exceeds_budget(state: &PlannerState, action: PlanAction, cfg: &PlannerConfig) -> bool {
+    let Some(b) = cfg.budget else {
+        return false;
+    };
+    match action {
+        PlanAction::RequestCapacity(_) => {
+            state
+                .added_billing_cents
+                .saturating_mul(i64::from(cfg.quarter_step.max(1)))
+                > b.capacity_cents
+        }
+        PlanAction::ScheduleTapeout { expedite: true } => cfg.expedite_cost_cents > b.rnd_cents,
+        PlanAction::FundProcessRnd { cents } | PlanAction::FundDesignRnd { cents } => {
+            cents.saturating_mul(i64::from(cfg.quarter_step.max(1))) > b.rnd_cents
+        }
+        PlanAction::InvestRelationship { cents } => cents > b.opex_cents,
+        PlanAction::FundQualityControl { cents } => {
+            cents.saturating_mul(i64::from(cfg.quarter_step.max(1))) > b.opex_cents
+        }
+        _ => false,
+    }
+}
+
+/// Project cash over the next quarter and report whether it dips below the liquidity
+/// floor. Conservative: new commitments must be funded from current operations, so the
+/// projection runs at `funded_capacity` (capacity before the action) with all outflows.
+fn breaches_liquidity_floor(
+    state: &PlannerState,
+    funded_capacity: u64,
+    world: &core::World,
+    w: &ScoreWeights,
+    cfg: &PlannerConfig,
+) -> bool {
+    let floor = Decimal::new(cfg.liquidity_floor_cents, 2);
+    if state.cash < floor {
+        return true;
+    }
+    let mut s = state.clone();
+    s.capacity = funded_capacity.min(s.capacity);
+    for _ in 0..cfg.quarter_step.max(1) {
+        simulate_month(&mut s, world, w, cfg);
+        if s.cash < floor {
+            return true;
+        }
+    }
+    false
+}
+
+/// Per-segment lines seeded at the current price, with capacity split by base demand.
+fn initial_segments(
+    world: &core::World,
+    current: &CurrentKpis,
+    cfg: &PlannerConfig,
+) -> Vec<SegmentPlanState> {
+    if !cfg.segment_actions || world.segments.len() < 2 {
+        return vec![];
+    }
+    let total: u64 = world.segments.iter().map(|s| s.base_demand_units).sum();
+    world
+        .segments
+        .iter()
+        .map(|s| SegmentPlanState {
+            asp: current.asp_usd,
+            ref_price: current.asp_usd,
+            share: current.share.clamp(0.05, 0.95),
+            alloc: if total > 0 {
+                s.base_demand_units as f32 / total as f32
+            } else {
+                1.0 / world.segments.len() as f32
+            },
+            last_demand: 0,
+        })
+        .collect()
+}
+
+/// Segment-level candidates: +/- price per line, and one allocation shift from the most
+/// over-supplied line to the most capacity-constrained one.
+fn segment_actions(state: &PlannerState, cfg: &PlannerConfig) -> Vec<PlanAction> {
+    let mut out = Vec::new();
+    if state.segments.len() < 2 {
+        return out;
+    }
+    for i in 0..state.segments.len() {
+        out.push(PlanAction::AdjustSegmentPriceFrac {
+            segment: i,
+            frac: -cfg.price_step_frac,
+        });
+        out.push(PlanAction::AdjustSegmentPriceFrac {
+            segment: i,
+            frac: cfg.price_step_frac,
+        });
+    }
+    // Coverage = allocated capacity / demand; shift from highest to lowest
+    let coverage: Vec<f32> = state
+        .segments
+        .iter()
+        .map(|sg| {
+            let cap = state.capacity as f32 * sg.alloc;
+            cap / (sg.last_demand.max(1) as f32)
+        })
+        .collect();
+    let mut from = 0;
+    let mut to = 0;
+    for i in 1..coverage.len() {
+        if coverage[i] > coverage[from] && state.segments[i].alloc > 0.0 {
+            from = i;
+        }
+        if coverage[i] < coverage[to] {
+            to = i;
+        }
+    }
+    if from != to && state.segments[from].alloc > 0.0 {
+        out.push(PlanAction::ShiftAllocation {
+            from,
+            to,
+            frac: cfg.alloc_step_frac,
+        });
+    }
+    out
+}
+
 /// Run a coarse beam search over the next horizon and return a compact plan.
 ///
 /// This uses a lightweight predictor independent of the main ECS world to keep it fast.
@@ -364,6 +951,8 @@ pub fn plan_horizon(
         state: PlannerState,
         score: f32,
         decisions: Vec<PlanStepDecision>,
+        /// Index of this node's entry in the current step's trace candidates.
+        trace_idx: Option<usize>,
     }
 
     let ref_price = current.asp_usd; // treat current as the reference for now
@@ -376,21 +965,45 @@ pub fn plan_horizon(
         share: current.share.clamp(0.05, 0.95),
         rd_progress: current.rd_progress,
         ref_price,
+        committed_outflow: current.committed_outflow_usd.max(Decimal::ZERO),
+        portfolio_div: current.portfolio_div.clamp(0.0, 1.0),
+        added_billing_cents: 0,
+        segments: initial_segments(world, current, cfg),
+        launch: current.launch,
+        start_appeal: current.launch.appeal,
     };
 
     let mut beam = vec![Node {
         state: init_state.clone(),
         score: 0.0,
         decisions: vec![],
+        trace_idx: None,
     }];
+    let mut trace = cfg.trace.then(PlanTrace::default);
     let mut discount_pow = 1.0f32;
     for month in 1..=cfg.months {
         let at_decision = month % cfg.quarter_step == 1; // month 1,4,7,...
         let mut candidates: Vec<Node> = Vec::new();
         if at_decision {
-            for n in &beam {
+            if let Some(t) = trace.as_mut() {
+                t.quarters.push(QuarterTrace {
+                    month_index: month,
+                    beam: beam
+                        .iter()
+                        .map(|n| TraceBeamNode {
+                            score: n.score,
+                            cash_usd: n.state.cash.to_f64().unwrap_or(0.0),
+                            share: n.state.share,
+                            asp_usd: n.state.asp.to_f64().unwrap_or(0.0),
+                            decisions: n.decisions.clone(),
+                        })
+                        .collect(),
+                    candidates: vec![],
+                });
+            }
+            for (parent, n) in beam.iter().enumerate() {
                 // Consider a small, curated action set
-                let actions: Vec<PlanAction> = if n.state.share < 0.2 {
+                let mut actions: Vec<PlanAction> = if n.state.share < 0.2 {
                     vec![
                         PlanAction::AdjustPriceFrac(-cfg.price_step_frac),
                         PlanAction::AdjustPriceFrac(0.0),
@@ -408,14 +1021,70 @@ pub fn plan_horizon(
                         PlanAction::AllocateRndBoost(0.01),
                     ]
                 };
+                actions.extend(segment_actions(&n.state, cfg));
+                if cfg.relationship_step_cents > 0 {
+                    actions.push(PlanAction::InvestRelationship {
+                        cents: cfg.relationship_step_cents,
+                    });
+                }
+                if cfg.rnd_step_cents > 0 {
+                    actions.push(PlanAction::FundProcessRnd {
+                        cents: cfg.rnd_step_cents,
+                    });
+                    actions.push(PlanAction::FundDesignRnd {
+                        cents: cfg.rnd_step_cents,
+                    });
+                }
+                if cfg.qc_step_cents > 0 {
+                    actions.push(PlanAction::FundQualityControl {
+                        cents: cfg.qc_step_cents,
+                    });
+                }
+                if current.underused_capacity {
+                    actions.retain(|a| !matches!(a, PlanAction::RequestCapacity(_)));
+                }
                 for &a in &actions {
                     let mut s = n.state.clone();
                     apply_action(&mut s, a, cfg);
+                    // Never commit to spending the company cannot fund or has not budgeted
+                    let pruned = if !is_spending_action(a) {
+                        None
+                    } else if exceeds_budget(&s, a, cfg) {
+                        Some(PruneReason::Budget)
+                    } else if breaches_liquidity_floor(&s, n.state.capacity, world, w, cfg) {
+                        Some(PruneReason::LiquidityFloor)
+                    } else {
+                        None
+                    };
+                    if pruned.is_some() {
+                        if let Some(q) = trace.as_mut().and_then(|t| t.quarters.last_mut()) {
+                            q.candidates.push(TraceCandidate {
+                                parent,
+                                action: a,
+                                score: None,
+                                kept: false,
+                                pruned,
+                            });
+                        }
+                        continue;
+                    }
                     let mut s2 = s.clone();
                     let util = simulate_month(&mut s2, world, w, cfg);
+                    let score = n.score + discount_pow * util;
+                    let trace_idx = trace.as_mut().and_then(|t| t.quarters.last_mut()).map(|q| {
+                        q.candidates.push(TraceCandidate {
+                            parent,
+                            action: a,
+                            score: Some(score),
+                            kept: false,
+                            pruned: Some(PruneReason::BeamWidth),
+                        });
+                        q.candidates.len() - 1
+                    });
                     candidates.push(Node {
                         state: s2,
-                        score: n.score + discount_pow * util,
+                        score,
+                        trace_idx,
                         decisions: {
                             let mut d = n.decisions.clone();
                             d.push(PlanStepDecision {
@@ -435,12 +1104,21 @@ pub fn plan_horizon(
                     state: s2,
                     score: n.score + discount_pow * util,
                     decisions: n.decisions.clone(),
+                    trace_idx: None,
                 });
             }
         }
         // Keep top-k by score
         candidates.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
         candidates.truncate(cfg.beam_width.max(1));
+        if at_decision {
+            if let Some(q) = trace.as_mut().and_then(|t| t.quarters.last_mut()) {
+                for i in candidates.iter().filter_map(|n| n.trace_idx) {
+                    q.candidates[i].kept = true;
+                    q.candidates[i].pruned = None;
+                }
+            }
+        }
         beam = candidates;
         discount_pow *= cfg.discount;
     }
@@ -450,9 +1128,13 @@ pub fn plan_horizon(
         .into_iter()
         .max_by(|a, b| a.score.partial_cmp(&b.score).unwrap_or(Ordering::Equal))
         .unwrap();
+    if let Some(t) = trace.as_mut() {
+        t.expected_score = best.score;
+    }
     PlanResult {
         decisions: best.decisions,
         expected_score: best.score,
+        trace,
     }
 }
 
@@ -484,6 +1166,36 @@ mod planner_tests {
         }
     }
 
+    #[test]
+    fn planner_config_loads_without_newer_fields() {
+        // A planner section as configs and mods wrote it before the economics knobs
+        let cfg: PlannerConfig = serde_yaml::from_str(
+            r#"
+beam_width: 3
+months: 24
+quarter_step: 3
+discount: 0.99
+min_margin_frac: 0.05
+price_step_frac: 0.05
+capacity_step_units: 10000
+price_pref_beta: 1.5
+competitor_attractiveness: 1.0
+"#,
+        )
+        .unwrap();
+        let d = PlannerConfig::default();
+        assert_eq!(cfg.liquidity_floor_cents, d.liquidity_floor_cents);
+        assert_eq!(
+            cfg.capacity_cost_cents_per_unit,
+            d.capacity_cost_cents_per_unit
+        );
+        assert_eq!(cfg.expedite_cost_cents, d.expedite_cost_cents);
+        assert_eq!(cfg.segment_actions, d.segment_actions);
+        assert_eq!(cfg.alloc_step_frac, d.alloc_step_frac);
+        assert_eq!(cfg.trace, d.trace);
+        assert_eq!(cfg.tapeout_lead_months, d.tapeout_lead_months);
+    }
+
     #[test]
     fn low_share_prefers_price_down_not_negative_margin() {
         let world = minimal_world();
@@ -505,6 +1217,10 @@ mod planner_tests {
             debt_usd: Decimal::ZERO,
             share: 0.1,
             rd_progress: 0.1,
+            committed_outflow_usd: Decimal::ZERO,
+            portfolio_div: 0.2,
+            underused_capacity: false,
+            launch: LaunchCadence::default(),
         };
         let plan = plan_horizon(&world, &current, &w, &cfg);
         // First decision should include a price down or no change, but never cause negative margin
@@ -523,12 +1239,75 @@ mod planner_tests {
             share: current.share,
             rd_progress: current.rd_progress,
             ref_price: current.asp_usd,
+            committed_outflow: current.committed_outflow_usd,
+            portfolio_div: current.portfolio_div,
+            added_billing_cents: 0,
+            segments: vec![],
+            launch: LaunchCadence::default(),
+            start_appeal: 0.0,
         };
         apply_action(&mut st, first.action, &cfg);
         let min_price = st.unit_cost * Decimal::from_f32_retain(1.0 + cfg.min_margin_frac).unwrap();
         assert!(st.asp >= min_price);
     }
 
+    #[test]
+    fn segment_weights_set_price_sensitivity() {
+        let attrs = sim_econ::ProductAttributes {
+            perf: 0.8,
+            appeal: 0.4,
+            tdp_fit: 1.0,
+            brand: 0.5,
+            ..Default::default()
+        };
+        let cfg = |perf: f32, price: f32| PlannerConfig {
+            attractiveness: vec![
+                Some(SegmentAttractiveness {
+                    weights: sim_econ::AttractivenessWeights {
+                        perf,
+                        price,
+                        ..Default::default()
+                    },
+                    attributes: attrs,
+                }),
+                None,
+            ],
+            ..Default::default()
+        };
+        let (r, cut) = (Decimal::new(100, 0), Decimal::new(80, 0));
+        let (perf_led, price_led) = (cfg(0.9, 0.1), cfg(0.3, 0.7));
+        // At the reference both match the legacy price-only share
+        let legacy = expected_share(&PlannerConfig::default(), 0, r, r, 1.0);
+        assert!((expected_share(&perf_led, 0, r, r, 1.0) - legacy).abs() < 1e-6);
+        assert!((expected_share(&price_led, 0, r, r, 1.0) - legacy).abs() < 1e-6);
+        // A price cut wins more share where buyers weigh price
+        let gain = |c: &PlannerConfig| expected_share(c, 0, cut, r, 1.0) - legacy;
+        assert!(gain(&perf_led) > 0.0 && gain(&price_led) > gain(&perf_led));
+        // Segments without weights keep the legacy response
+        assert_eq!(
+            expected_share(&price_led, 1, cut, r, 1.0),
+            expected_share(&PlannerConfig::default(), 0, cut, r, 1.0)
+        );
+    }
+
+    #[test]
+    fn segment_competitor_strength_overrides_global() {
+        let cfg = PlannerConfig {
+            competitor_attractiveness: 1.0,
+            competitor_strength: vec![Some(2.5), None, Some(0.4)],
+            ..Default::default()
+        };
+        assert_eq!(
+            [0, 1, 2, 3].map(|i| cfg.competitor_for(i)),
+            [2.5, 1.0, 0.4, 1.0]
+        );
+        let r = Decimal::new(100, 0);
+        let share = |i: usize| expected_share(&cfg, i, r, r, cfg.competitor_for(i));
+        // At the reference price share is 1 / (1 + strength)
+        assert!((share(0) - 1.0 / 3.5).abs() < 1e-6);
+        assert!(share(0) < share(1) && share(1) < share(2));
+    }
+
     #[test]
     fn capacity_shortage_prefers_capacity_over_price_cut() {
         let world = minimal_world();
@@ -548,6 +1327,10 @@ mod planner_tests {
             debt_usd: Decimal::ZERO,
             share: 0.4,
             rd_progress: 0.2,
+            committed_outflow_usd: Decimal::ZERO,
+            portfolio_div: 0.2,
+            underused_capacity: false,
+            launch: LaunchCadence::default(),
         };
         let plan = plan_horizon(&world, &current, &w, &cfg);
         assert!(!plan.decisions.is_empty());
@@ -561,6 +1344,575 @@ mod planner_tests {
             _ => {}
         }
     }
+
+    #[test]
+    fn cash_poor_company_stops_requesting_capacity() {
+        let world = minimal_world();
+        let w = ScoreWeights::default();
+        let cfg = PlannerConfig {
+            months: 6,
+            beam_width: 4,
+            capacity_step_units: 200_000,
+            ..Default::default()
+        };
+        // Severe shortage: capacity is attractive, but $400k/month of take-or-pay billing
+        // cannot be funded from $100k/month of current gross profit and $50k of cash.
+        let poor = CurrentKpis {
+            asp_usd: Decimal::new(300, 0),
+            unit_cost_usd: Decimal::new(200, 0),
+            capacity_units_per_month: 1_000,
+            cash_usd: Decimal::new(50_000, 0),
+            debt_usd: Decimal::new(100_000_000, 0), // keeps liquidity utility unsaturated
+            share: 0.4,
+            rd_progress: 0.2,
+            committed_outflow_usd: Decimal::ZERO,
+            portfolio_div: 0.2,
+            underused_capacity: false,
+            launch: LaunchCadence::default(),
+        };
+        let plan = plan_horizon(&world, &poor, &w, &cfg);
+        assert!(!plan.decisions.is_empty());
+        assert!(plan
+            .decisions
+            .iter()
+            .all(|d| !matches!(d.action, PlanAction::RequestCapacity(_))));
+        // With ample cash the same company requests capacity
+        let rich = CurrentKpis {
+            cash_usd: Decimal::new(100_000_000, 0),
+            ..poor
+        };
+        let plan = plan_horizon(&world, &rich, &w, &cfg);
+        assert!(plan
+            .decisions
+            .iter()
+            .any(|d| matches!(d.action, PlanAction::RequestCapacity(_))));
+        // Not while existing capacity sits idle
+        let idle = CurrentKpis {
+            underused_capacity: true,
+            ..rich
+        };
+        let plan = plan_horizon(&world, &idle, &w, &cfg);
+        assert!(!plan.decisions.is_empty());
+        assert!(plan
+            .decisions
+            .iter()
+            .all(|d| !matches!(d.action, PlanAction::RequestCapacity(_))));
+    }
+
+    #[test]
+    fn budget_caps_capacity_requests() {
+        let world = minimal_world();
+        let w = ScoreWeights::default();
+        let current = CurrentKpis {
+            asp_usd: Decimal::new(300, 0),
+            unit_cost_usd: Decimal::new(200, 0),
+            capacity_units_per_month: 1_000,
+            cash_usd: Decimal::new(100_000_000, 0),
+            debt_usd: Decimal::new(100_000_000, 0),
+            share: 0.4,
+            rd_progress: 0.2,
+            committed_outflow_usd: Decimal::ZERO,
+            portfolio_div: 0.2,
+            underused_capacity: false,
+            launch: LaunchCadence::default(),
+        };
+        let cfg = PlannerConfig {
+            months: 6,
+            beam_width: 4,
+            capacity_step_units: 200_000,
+            trace: true,
+            ..Default::default()
+        };
+        let requests = |plan: &PlanResult| {
+            plan.decisions
+                .iter()
+                .filter(|d| matches!(d.action, PlanAction::RequestCapacity(_)))
+                .count()
+        };
+        assert!(requests(&plan_horizon(&world, &current, &w, &cfg)) > 0);
+        // One step bills 200k units * $2 = $400k/month, $1.2M a quarter
+        let budgeted = |capacity_cents: i64| PlannerConfig {
+            budget: Some(PlanBudget {
+                capacity_cents,
+                rnd_cents: 0,
+                opex_cents: i64::MAX,
+            }),
+            ..cfg.clone()
+        };
+        let plan = plan_horizon(&world, &current, &w, &budgeted(0));
+        assert_eq!(requests(&plan), 0);
+        assert!(plan.trace.unwrap().quarters[0].candidates.iter().any(|c| {
+            matches!(c.action, PlanAction::RequestCapacity(_))
+                && c.pruned == Some(PruneReason::Budget)
+        }));
+        assert_eq!(
+            requests(&plan_horizon(&world, &current, &w, &budgeted(120_000_000))),
+            1
+        );
+    }
+
+    #[test]
+    fn planner_schedules_tapeouts_ahead_of_stale_appeal() {
+        let world = minimal_world();
+        let w = ScoreWeights::default();
+        let current = CurrentKpis {
+            asp_usd: Decimal::new(300, 0),
+            unit_cost_usd: Decimal::new(200, 0),
+            capacity_units_per_month: 500_000,
+            cash_usd: Decimal::new(10_000_000, 0),
+            debt_usd: Decimal::ZERO,
+            share: 0.3,
+            rd_progress: 0.1,
+            committed_outflow_usd: Decimal::ZERO,
+            portfolio_div: 0.2,
+            underused_capacity: false,
+            // Eight months since the last launch, nothing in flight
+            launch: LaunchCadence {
+                appeal: 0.45,
+                months_since_launch: 8,
+                next_launch_in_months: None,
+            },
+        };
+        let tapeouts = |cfg: &PlannerConfig, current: &CurrentKpis| {
+            plan_horizon(&world, current, &w, cfg)
+                .decisions
+                .iter()
+                .filter(|d| matches!(d.action, PlanAction::ScheduleTapeout { .. }))
+                .map(|d| d.month_index)
+                .collect::<Vec<_>>()
+        };
+        let blind = PlannerConfig {
+            tapeout_portfolio_gain: 0.0,
+            ..Default::default()
+        };
+        assert!(tapeouts(&blind, &current).is_empty());
+        // Appeal goes stale from month 5 and a tapeout takes 9: start one now
+        let aware = PlannerConfig {
+            staleness: Some(sim_econ::StalenessModel::default()),
+            ..blind.clone()
+        };
+        assert_eq!(tapeouts(&aware, &current).first(), Some(&1));
+        // Not while a launch already in flight lands before the grace runs out
+        let in_flight = CurrentKpis {
+            launch: LaunchCadence {
+                next_launch_in_months: Some(2),
+                ..current.launch
+            },
+            ..current
+        };
+        assert_ne!(tapeouts(&aware, &in_flight).first(), Some(&1));
+
+        // The predictor itself: share drifts down once appeal decays
+        let mut st = PlannerState {
+            asp: Decimal::new(300, 0),
+            unit_cost: Decimal::new(200, 0),
+            capacity: 500_000,
+            cash: Decimal::new(1_000_000, 0),
+            debt: Decimal::ZERO,
+            share: 0.3,
+            rd_progress: 0.0,
+            ref_price: Decimal::new(300, 0),
+            committed_outflow: Decimal::ZERO,
+            portfolio_div: 0.2,
+            added_billing_cents: 0,
+            segments: vec![],
+            launch: current.launch,
+            start_appeal: current.launch.appeal,
+        };
+        let mut fresh = st.clone();
+        for _ in 0..12 {
+            simulate_month(&mut st, &world, &w, &aware);
+            simulate_month(&mut fresh, &world, &w, &blind);
+        }
+        assert!(st.launch.appeal < 0.45 && st.share < fresh.share);
+        apply_action(
+            &mut st,
+            PlanAction::ScheduleTapeout { expedite: false },
+            &aware,
+        );
+        for _ in 0..aware.tapeout_lead_months {
+            simulate_month(&mut st, &world, &w, &aware);
+        }
+        assert_eq!(st.launch.months_since_launch, 0);
+        assert_eq!(st.launch.appeal, 0.45);
+    }
+
+    #[test]
+    fn committed_outflows_reduce_projected_cash() {
+        let world = minimal_world();
+        let w = ScoreWeights::default();
+        let cfg = PlannerConfig::default();
+        let mut st = PlannerState {
+            asp: Decimal::new(300, 0),
+            unit_cost: Decimal::new(200, 0),
+            capacity: 0, // no sales: cash moves only by commitments
+            cash: Decimal::new(1_000, 0),
+            debt: Decimal::ZERO,
+            share: 0.3,
+            rd_progress: 0.0,
+            ref_price: Decimal::new(300, 0),
+            committed_outflow: Decimal::new(250, 0),
+            portfolio_div: 0.2,
+            added_billing_cents: 0,
+            segments: vec![],
+            launch: LaunchCadence::default(),
+            start_appeal: 0.0,
+        };
+        simulate_month(&mut st, &world, &w, &cfg);
+        assert_eq!(st.cash, Decimal::new(750, 0));
+        apply_action(
+            &mut st,
+            PlanAction::ScheduleTapeout { expedite: true },
+            &cfg,
+        );
+        assert_eq!(st.cash, Decimal::new(-250, 0));
+        assert!(breaches_liquidity_floor(&st, 0, &world, &w, &cfg));
+    }
+
+    #[test]
+    fn trace_records_beam_and_pruning_only_when_enabled() {
+        let world = minimal_world();
+        let w = ScoreWeights::default();
+        let current = CurrentKpis {
+            asp_usd: Decimal::new(300, 0),
+            unit_cost_usd: Decimal::new(200, 0),
+            capacity_units_per_month: 1_000,
+            cash_usd: Decimal::new(50_000, 0),
+            debt_usd: Decimal::new(100_000_000, 0),
+            share: 0.3,
+            rd_progress: 0.0,
+            committed_outflow_usd: Decimal::ZERO,
+            portfolio_div: 0.2,
+            underused_capacity: false,
+            launch: LaunchCadence::default(),
+        };
+        let cfg = PlannerConfig {
+            months: 6,
+            beam_width: 2,
+            capacity_step_units: 200_000,
+            ..Default::default()
+        };
+        let plain = plan_horizon(&world, &current, &w, &cfg);
+        assert!(plain.trace.is_none());
+        let traced = plan_horizon(
+            &world,
+            &current,
+            &w,
+            &PlannerConfig {
+                trace: true,
+                ..cfg.clone()
+            },
+        );
+        // Tracing must not change the plan itself
+        assert_eq!(traced.expected_score, plain.expected_score);
+        let t = traced.trace.expect("trace recorded");
+        assert_eq!(t.quarters.len(), 2); // months 1 and 4
+        assert_eq!(t.quarters[0].beam.len(), 1);
+        for q in &t.quarters {
+            let kept = q.candidates.iter().filter(|c| c.kept).count();
+            assert!(kept <= cfg.beam_width && kept > 0);
+            assert!(q.candidates.iter().all(|c| c.parent < q.beam.len()));
+        }
+        assert!(t.quarters[0].candidates.iter().any(|c| {
+            matches!(c.action, PlanAction::RequestCapacity(_))
+                && c.pruned == Some(PruneReason::LiquidityFloor)
+                && c.score.is_none()
+        }));
+        assert!(serde_yaml::to_string(&t).is_ok());
+    }
+
+    fn two_segment_world() -> core::World {
+        let mut world = minimal_world();
+        world.segments = vec![
+            core::MarketSegment {
+                name: "Server".into(),
+                base_demand_units: 200_000,
+                price_elasticity: -0.3,
+            },
+            core::MarketSegment {
+                name: "Desktop".into(),
+                base_demand_units: 2_000_000,
+                price_elasticity: -6.0,
+            },
+        ];
+        world
+    }
+
+    #[test]
+    fn shifting_allocation_to_constrained_segment_raises_profit() {
+        let world = two_segment_world();
+        let w = ScoreWeights::default();
+        let cfg = PlannerConfig {
+            segment_actions: true,
+            ..Default::default()
+        };
+        let line = |alloc: f32| SegmentPlanState {
+            asp: Decimal::new(300, 0),
+            ref_price: Decimal::new(300, 0),
+            share: 0.3,
+            alloc,
+            last_demand: 0,
+        };
+        // Even split: Server (60k demand) has a glut, Desktop (600k demand) is constrained
+        let mut base = PlannerState {
+            asp: Decimal::new(300, 0),
+            unit_cost: Decimal::new(200, 0),
+            capacity: 1_000_000,
+            cash: Decimal::new(1_000_000, 0),
+            debt: Decimal::ZERO,
+            share: 0.3,
+            rd_progress: 0.0,
+            ref_price: Decimal::new(300, 0),
+            committed_outflow: Decimal::ZERO,
+            portfolio_div: 0.2,
+            added_billing_cents: 0,
+            segments: vec![line(0.5), line(0.5)],
+            launch: LaunchCadence::default(),
+            start_appeal: 0.0,
+        };
+        simulate_month(&mut base, &world, &w, &cfg);
+        let shift = segment_actions(&base, &cfg)
+            .into_iter()
+            .find(|a| matches!(a, PlanAction::ShiftAllocation { .. }))
+            .expect("allocation shift offered");
+        assert_eq!(
+            shift,
+            PlanAction::ShiftAllocation {
+                from: 0,
+                to: 1,
+                frac: cfg.alloc_step_frac
+            }
+        );
+        let mut shifted = base.clone();
+        apply_action(&mut shifted, shift, &cfg);
+        let mut unshifted = base.clone();
+        simulate_month(&mut unshifted, &world, &w, &cfg);
+        simulate_month(&mut shifted, &world, &w, &cfg);
+        assert!(shifted.cash > unshifted.cash);
+    }
+
+    #[test]
+    fn planner_uses_segment_levers_when_enabled() {
+        let world = two_segment_world();
+        let w = ScoreWeights::default();
+        let current = CurrentKpis {
+            asp_usd: Decimal::new(300, 0),
+            unit_cost_usd: Decimal::new(200, 0),
+            capacity_units_per_month: 100_000,
+            cash_usd: Decimal::new(1_000_000, 0),
+            debt_usd: Decimal::new(1_000_000_000, 0),
+            share: 0.3,
+            rd_progress: 0.0,
+            committed_outflow_usd: Decimal::ZERO,
+            // Fully diversified, so new product lines gain nothing
+            portfolio_div: 1.0,
+            underused_capacity: false,
+            launch: LaunchCadence::default(),
+        };
+        let off = plan_horizon(&world, &current, &w, &PlannerConfig::default());
+        assert!(off.decisions.iter().all(|d| !matches!(
+            d.action,
+            PlanAction::AdjustSegmentPriceFrac { .. } | PlanAction::ShiftAllocation { .. }
+        )));
+        let cfg = PlannerConfig {
+            segment_actions: true,
+            ..Default::default()
+        };
+        let on = plan_horizon(&world, &current, &w, &cfg);
+        assert!(on.decisions.iter().any(|d| matches!(
+            d.action,
+            PlanAction::AdjustSegmentPriceFrac { .. } | PlanAction::ShiftAllocation { .. }
+        )));
+    }
+
+    #[test]
+    fn planner_tapes_out_new_lines_to_diversify() {
+        assert!((portfolio_diversification(0, 0, 6)).abs() < 1e-6);
+        assert!((portfolio_diversification(1, 3, 6) - (0.5 / 3.0 + 0.25)).abs() < 1e-6);
+        assert!((portfolio_diversification(5, 6, 6) - 1.0).abs() < 1e-6);
+        let world = minimal_world();
+        let w = ScoreWeights {
+            portfolio: 1.0,
+            ..ScoreWeights::default()
+        };
+        let current = CurrentKpis {
+            asp_usd: Decimal::new(300, 0),
+            unit_cost_usd: Decimal::new(200, 0),
+            capacity_units_per_month: 500_000,
+            cash_usd: Decimal::new(1_000_000, 0),
+            debt_usd: Decimal::ZERO,
+            share: 0.3,
+            rd_progress: 0.0,
+            committed_outflow_usd: Decimal::ZERO,
+            portfolio_div: 0.2,
+            underused_capacity: false,
+            launch: LaunchCadence::default(),
+        };
+        let cfg = PlannerConfig {
+            months: 6,
+            ..Default::default()
+        };
+        let plan = plan_horizon(&world, &current, &w, &cfg);
+        assert!(matches!(
+            plan.decisions[0].action,
+            PlanAction::ScheduleTapeout { .. }
+        ));
+        let off = PlannerConfig {
+            tapeout_portfolio_gain: 0.0,
+            ..cfg
+        };
+        assert!(plan_horizon(&world, &current, &w, &off).expected_score < plan.expected_score);
+    }
+
+    #[test]
+    fn relationship_investment_is_a_funded_spending_action() {
+        let world = minimal_world();
+        let w = ScoreWeights::default();
+        let current = CurrentKpis {
+            asp_usd: Decimal::new(300, 0),
+            unit_cost_usd: Decimal::new(200, 0),
+            capacity_units_per_month: 500_000,
+            cash_usd: Decimal::new(1_000_000, 0),
+            debt_usd: Decimal::new(1_000_000_000, 0),
+            share: 0.1,
+            rd_progress: 0.0,
+            committed_outflow_usd: Decimal::ZERO,
+            portfolio_div: 0.2,
+            underused_capacity: false,
+            launch: LaunchCadence::default(),
+        };
+        let invests = |plan: &PlanResult| {
+            plan.decisions
+                .iter()
+                .any(|d| matches!(d.action, PlanAction::InvestRelationship { .. }))
+        };
+        let cfg = PlannerConfig {
+            months: 6,
+            relationship_share_gain: 0.05,
+            trace: true,
+            ..Default::default()
+        };
+        assert!(!invests(&plan_horizon(&world, &current, &w, &cfg)));
+        let on = PlannerConfig {
+            relationship_step_cents: 1_000_000,
+            ..cfg.clone()
+        };
+        assert!(invests(&plan_horizon(&world, &current, &w, &on)));
+        // An investment the company cannot fund from cash or sales is pruned
+        let broke = CurrentKpis {
+            cash_usd: Decimal::new(5_000, 0),
+            capacity_units_per_month: 0,
+            ..current
+        };
+        let plan = plan_horizon(&world, &broke, &w, &on);
+        assert!(!invests(&plan));
+        assert!(plan.trace.unwrap().quarters[0].candidates.iter().any(|c| {
+            matches!(c.action, PlanAction::InvestRelationship { .. })
+                && c.pruned == Some(PruneReason::LiquidityFloor)
+        }));
+    }
+
+    #[test]
+    fn rnd_track_funding_respects_step_and_budget() {
+        let world = minimal_world();
+        let w = ScoreWeights::default();
+        let current = CurrentKpis {
+            asp_usd: Decimal::new(300, 0),
+            unit_cost_usd: Decimal::new(200, 0),
+            capacity_units_per_month: 500_000,
+            cash_usd: Decimal::new(1_000_000, 0),
+            debt_usd: Decimal::new(1_000_000_000, 0),
+            share: 0.1,
+            rd_progress: 0.0,
+            committed_outflow_usd: Decimal::ZERO,
+            portfolio_div: 0.2,
+            underused_capacity: false,
+            launch: LaunchCadence::default(),
+        };
+        let funds = |plan: &PlanResult| {
+            plan.decisions.iter().any(|d| {
+                matches!(
+                    d.action,
+                    PlanAction::FundProcessRnd { .. } | PlanAction::FundDesignRnd { .. }
+                )
+            })
+        };
+        let cfg = PlannerConfig {
+            months: 6,
+            process_rnd_cost_gain: 0.1,
+            design_rnd_share_gain: 0.05,
+            trace: true,
+            ..Default::default()
+        };
+        assert!(!funds(&plan_horizon(&world, &current, &w, &cfg)));
+        let on = PlannerConfig {
+            rnd_step_cents: 100_000,
+            ..cfg.clone()
+        };
+        assert!(funds(&plan_horizon(&world, &current, &w, &on)));
+        // A quarter of the added budget must fit the R&D headroom
+        let tight = PlannerConfig {
+            budget: Some(PlanBudget {
+                capacity_cents: i64::MAX,
+                rnd_cents: 200_000,
+                opex_cents: i64::MAX,
+            }),
+            ..on
+        };
+        let plan = plan_horizon(&world, &current, &w, &tight);
+        assert!(!funds(&plan));
+        assert!(plan.trace.unwrap().quarters[0].candidates.iter().any(|c| {
+            matches!(c.action, PlanAction::FundDesignRnd { .. })
+                && c.pruned == Some(PruneReason::Budget)
+        }));
+    }
+
+    #[test]
+    fn quality_control_funding_respects_opex_budget() {
+        let world = minimal_world();
+        let w = ScoreWeights::default();
+        let current = CurrentKpis {
+            asp_usd: Decimal::new(300, 0),
+            unit_cost_usd: Decimal::new(200, 0),
+            capacity_units_per_month: 500_000,
+            cash_usd: Decimal::new(1_000_000, 0),
+            debt_usd: Decimal::new(1_000_000_000, 0),
+            share: 0.1,
+            rd_progress: 0.0,
+            committed_outflow_usd: Decimal::ZERO,
+            portfolio_div: 0.2,
+            underused_capacity: false,
+            launch: LaunchCadence::default(),
+        };
+        let funds = |plan: &PlanResult| {
+            plan.decisions
+                .iter()
+                .any(|d| matches!(d.action, PlanAction::FundQualityControl { .. }))
+        };
+        let cfg = PlannerConfig {
+            months: 6,
+            qc_step_cents: 100_000,
+            qc_cost_gain: 0.1,
+            trace: true,
+            ..Default::default()
+        };
+        assert!(funds(&plan_horizon(&world, &current, &w, &cfg)));
+        // A quarter of the added spend must fit the opex headroom
+        let tight = PlannerConfig {
+            budget: Some(PlanBudget {
+                capacity_cents: i64::MAX,
+                rnd_cents: i64::MAX,
+                opex_cents: 200_000,
+            }),
+            ..cfg
+        };
+        let plan = plan_horizon(&world, &current, &w, &tight);
+        assert!(!funds(&plan));
+        assert!(plan.trace.unwrap().quarters[0].candidates.iter().any(|c| {
+            matches!(c.action, PlanAction::FundQualityControl { .. })
+                && c.pruned == Some(PruneReason::Budget)
+        }));
+    }
 }
 
 // -------------- Tactics (behavior tree style) --------------
@@ -651,6 +2003,12 @@ pub struct AiConfig {
     pub tactics: TacticsConfig,
     pub product_weights: ProductWeights,
     pub product_cost: ProductCostCfg,
+    /// Launch review model applied to player releases.
+    #[serde(default)]
+    pub reviews: sim_econ::ReviewModel,
+    /// How the player's appeal goes stale between launches.
+    #[serde(default)]
+    pub staleness: sim_econ::StalenessModel,
 }
 
 /// Default YAML baked in from the assets directory.
@@ -662,12 +2020,30 @@ impl AiConfig {
     }
 }
 
-/// Weights for product attractiveness in sales.
+/// Weights for product attractiveness in sales, used for segments whose market config sets
+/// none.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProductWeights {
     pub perf: f32,
     pub price_rel: f32,
     pub appeal: f32,
+    #[serde(default)]
+    pub tdp_fit: f32,
+    #[serde(default)]
+    pub brand: f32,
+}
+
+impl ProductWeights {
+    /// The same weights in the market's attractiveness model.
+    pub fn attractiveness(&self) -> sim_econ::AttractivenessWeights {
+        sim_econ::AttractivenessWeights {
+            perf: self.perf,
+            price: self.price_rel,
+            appeal: self.appeal,
+            tdp_fit: self.tdp_fit,
+            brand: self.brand,
+        }
+    }
 }
 
 impl Default for ProductWeights {
@@ -676,6 +2052,8 @@ impl Default for ProductWeights {
             perf: 0.7,
             price_rel: 0.0,
             appeal: 0.3,
+            tdp_fit: 0.0,
+            brand: 0.0,
         }
     }
 }
@@ -706,6 +2084,148 @@ pub fn respects_min_margin(asp: Decimal, unit_cost: Decimal, min_margin_frac: f3
     asp >= min_price(unit_cost, min_margin_frac)
 }
 
+// -------------- Behavior cloning --------------
+
+/// One month of recorded play: what monthly tactics would have seen, and what the player did.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TacticsSample {
+    /// Share lost against the 12-month share, as `decide_tactics` measures it.
+    pub share_drop: f32,
+    pub demand_supply_ratio: f32,
+    pub liquidity_k: f32,
+    /// (ASP - unit cost) / unit cost after the player's price change.
+    pub margin_after_frac: f32,
+    /// Net ASP change the player made, e.g. -0.05 for a 5% cut.
+    pub price_delta_frac: f32,
+    /// Sign of the player's R&D budget change: -1 cut, 0 none, 1 raise.
+    pub rd_change: i8,
+}
+
+/// Price changes smaller than this count as holding the price.
+const CLONE_PRICE_DEADBAND: f32 = 1e-4;
+
+/// Tactics fitted to recorded play.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TacticsFit {
+    pub tactics: TacticsConfig,
+    pub samples: usize,
+    /// Share of samples where the fitted tactics move price and R&D the way the player did.
+    pub agreement: f32,
+}
+
+/// Threshold `t` for "act when x > t" (or x < t with `below`) that matches the most points;
+/// ties go to the threshold closest to `prior`, so thin data keeps the shipped value.
+fn fit_stump(points: &[(f32, bool)], below: bool, prior: f32) -> f32 {
+    let fires = |x: f32, t: f32| if below { x < t } else { x > t };
+    let hits = |t: f32| {
+        points
+            .iter()
+            .filter(|(x, acted)| fires(*x, t) == *acted)
+            .count()
+    };
+    let mut best = (hits(prior), prior);
+    for &(t, _) in points {
+        let h = hits(t);
+        if h > best.0 || (h == best.0 && (t - prior).abs() < (best.1 - prior).abs()) {
+            best = (h, t);
+        }
+    }
+    best.1
+}
+
+fn median(mut v: Vec<f32>) -> Option<f32> {
+    if v.is_empty() {
+        return None;
+    }
+    v.sort_by(f32::total_cmp);
+    Some(v[v.len() / 2])
+}
+
+fn price_sign(df: f32) -> i8 {
+    if df > CLONE_PRICE_DEADBAND {
+        1
+    } else if df < -CLONE_PRICE_DEADBAND {
+        -1
+    } else {
+        0
+    }
+}
+
+/// Fit monthly tactics to recorded human play: decision thresholds from when the player cut or
+/// raised prices and R&D, step sizes and the margin floor from how far they went. R&D step
+/// sizes have no counterpart in budget cents and stay at `prior`'s.
+pub fn fit_tactics(samples: &[TacticsSample], prior: &TacticsConfig) -> Result<TacticsFit, String> {
+    if samples.is_empty() {
+        return Err("no recorded months to fit tactics to".into());
+    }
+    let cuts: Vec<&TacticsSample> = samples
+        .iter()
+        .filter(|s| price_sign(s.price_delta_frac) < 0)
+        .collect();
+    let raises: Vec<f32> = samples
+        .iter()
+        .filter(|s| price_sign(s.price_delta_frac) > 0)
+        .map(|s| s.price_delta_frac)
+        .collect();
+    let points = |x: fn(&TacticsSample) -> f32, acted: fn(&TacticsSample) -> bool| {
+        samples.iter().map(|s| (x(s), acted(s))).collect::<Vec<_>>()
+    };
+    let tactics = TacticsConfig {
+        share_drop_delta: fit_stump(
+            &points(|s| s.share_drop, |s| price_sign(s.price_delta_frac) < 0),
+            false,
+            prior.share_drop_delta,
+        ),
+        price_epsilon_frac: median(cuts.iter().map(|s| -s.price_delta_frac).collect())
+            .unwrap_or(prior.price_epsilon_frac),
+        min_margin_frac: cuts
+            .iter()
+            .map(|s| s.margin_after_frac)
+            .min_by(f32::total_cmp)
+            .map_or(prior.min_margin_frac, |m| m.max(0.0)),
+        shortage_raise_threshold: fit_stump(
+            &points(
+                |s| s.demand_supply_ratio,
+                |s| price_sign(s.price_delta_frac) > 0,
+            ),
+            false,
+            prior.shortage_raise_threshold,
+        ),
+        shortage_raise_epsilon_frac: median(raises).unwrap_or(prior.shortage_raise_epsilon_frac),
+        cash_liquidity_floor_k: fit_stump(
+            &points(|s| s.liquidity_k, |s| s.rd_change < 0),
+            true,
+            prior.cash_liquidity_floor_k,
+        ),
+        ..prior.clone()
+    };
+    let agree = samples
+        .iter()
+        .filter(|s| {
+            let cut = s.share_drop > tactics.share_drop_delta;
+            let raise = s.demand_supply_ratio > tactics.shortage_raise_threshold;
+            let df = if raise {
+                tactics.shortage_raise_epsilon_frac
+            } else {
+                0.0
+            } - if cut { tactics.price_epsilon_frac } else { 0.0 };
+            let rd = if s.liquidity_k < tactics.cash_liquidity_floor_k {
+                -1
+            } else if cut {
+                1
+            } else {
+                0
+            };
+            price_sign(df) == price_sign(s.price_delta_frac) && rd == s.rd_change
+        })
+        .count();
+    Ok(TacticsFit {
+        tactics,
+        samples: samples.len(),
+        agreement: agree as f32 / samples.len() as f32,
+    })
+}
+
 #[cfg(test)]
 mod tactics_tests {
     use super::*;
@@ -754,4 +2274,49 @@ mod tactics_tests {
         let (df, _rd) = decide_tactics(&m, 0.3, 1.5, unit_cost, asp, &cfg);
         assert!(df > 0.0);
     }
+    #[test]
+    fn fits_thresholds_and_steps_to_recorded_play() {
+        // A player who cuts 4% once share slips 3+ points, raises 2% when demand runs 1.5x
+        // supply, and trims R&D when liquidity drops under 0.4
+        let samples: Vec<TacticsSample> = (0..40)
+            .map(|i| {
+                let share_drop = (i % 8) as f32 * 0.01;
+                let ratio = 0.8 + (i % 5) as f32 * 0.2;
+                let liquidity_k = (i % 10) as f32 * 0.1;
+                let price_delta_frac = if share_drop > 0.03 {
+                    -0.04
+                } else if ratio > 1.5 {
+                    0.02
+                } else {
+                    0.0
+                };
+                TacticsSample {
+                    share_drop,
+                    demand_supply_ratio: ratio,
+                    liquidity_k,
+                    margin_after_frac: 0.1 + share_drop,
+                    price_delta_frac,
+                    rd_change: if liquidity_k < 0.4 { -1 } else { 0 },
+                }
+            })
+            .collect();
+        let fit = fit_tactics(&samples, &TacticsConfig::default()).unwrap();
+        let t = &fit.tactics;
+        assert!((0.03..0.04).contains(&t.share_drop_delta));
+        assert!((1.4..1.6).contains(&t.shortage_raise_threshold));
+        assert!((0.3..=0.4).contains(&t.cash_liquidity_floor_k));
+        assert_eq!(
+            (t.price_epsilon_frac, t.shortage_raise_epsilon_frac),
+            (0.04, 0.02)
+        );
+        assert!((t.min_margin_frac - 0.14).abs() < 1e-6);
+        // R&D step sizes have no recorded counterpart
+        assert_eq!(
+            t.rd_cut_on_cash_low,
+            TacticsConfig::default().rd_cut_on_cash_low
+        );
+        assert_eq!(fit.samples, 40);
+        assert!(fit.agreement > 0.5);
+        assert!(fit_tactics(&[], &TacticsConfig::default()).is_err());
+    }
 }
@@ -71,6 +71,10 @@ pub struct MicroArch {
 /// A specific product specification for manufacturing.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ProductSpec {
+    /// Stable id assigned when the design is taped out, shared with its tapeout; empty
+    /// before then.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
     /// Product kind.
     pub kind: ProductKind,
     /// Target technology node ID.
@@ -85,8 +89,116 @@ pub struct ProductSpec {
     pub tdp_w: f32,
     /// Bill of materials cost in USD (>= 0).
     pub bom_usd: f32,
+    /// Market segment ids the design is tuned for; empty addresses the whole market.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub target_segments: Vec<String>,
+    /// Target clock as a multiple of the node's `freq_ghz_baseline`.
+    #[serde(default = "baseline_clock", skip_serializing_if = "is_baseline_clock")]
+    pub clock_frac: f32,
+    /// Family and generation; a new generation succeeds the family's previous one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub family: Option<ProductFamily>,
 }
 
+impl ProductSpec {
+    /// Marketing name from the family, e.g. "Falcon II"; `None` outside a family.
+    pub fn marketing_name(&self) -> Option<String> {
+        self.family.as_ref().map(ProductFamily::marketing_name)
+    }
+}
+
+/// Place of a product in a family of successive tapeouts.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProductFamily {
+    /// Family id, e.g. "falcon" or "night_hawk".
+    pub id: String,
+    /// 1 for the family's first product.
+    pub generation: u32,
+}
+
+impl ProductFamily {
+    /// Title-cased family id followed by the generation in roman numerals:
+    /// "night_hawk" generation 3 is "Night Hawk III".
+    pub fn marketing_name(&self) -> String {
+        let family: Vec<String> = self
+            .id
+            .split(['_', '-', ' '])
+            .filter(|w| !w.is_empty())
+            .map(|w| {
+                let mut c = w.chars();
+                c.next()
+                    .map(|f| f.to_uppercase().chain(c).collect())
+                    .unwrap_or_default()
+            })
+            .collect();
+        format!("{} {}", family.join(" "), roman_numeral(self.generation))
+    }
+
+    /// The generation this one succeeds, if any.
+    pub fn predecessor(&self) -> Option<ProductFamily> {
+        (self.generation > 1).then(|| ProductFamily {
+            id: self.id.clone(),
+            generation: self.generation - 1,
+        })
+    }
+}
+
+/// `n` in roman numerals (decimal digits past 3999).
+fn roman_numeral(n: u32) -> String {
+    if n == 0 || n >= 4000 {
+        return n.to_string();
+    }
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut rest = n;
+    let mut out = String::new();
+    for (value, numeral) in NUMERALS {
+        while rest >= value {
+            out.push_str(numeral);
+            rest -= value;
+        }
+    }
+    out
+}
+
+/// Latest generation of `family` among `products`, 0 when it has none.
+pub fn latest_generation<'a>(
+    products: impl IntoIterator<Item = &'a ProductSpec>,
+    family: &str,
+) -> u32 {
+    products
+        .into_iter()
+        .filter_map(|p| p.family.as_ref())
+        .filter(|f| f.id == family)
+        .map(|f| f.generation)
+        .max()
+        .unwrap_or(0)
+}
+
+fn baseline_clock() -> f32 {
+    1.0
+}
+
+fn is_baseline_clock(c: &f32) -> bool {
+    *c == 1.0
+}
+
+/// Clock targets a design may pick, as multiples of the node's baseline frequency.
+pub const CLOCK_FRAC_RANGE: std::ops::RangeInclusive<f32> = 0.5..=1.5;
+
 /// Macro-economic state for a given date.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MacroState {
@@ -188,6 +300,12 @@ pub enum ValidationError {
     /// Missing dependency in tech tree.
     #[error("dependency not found: {0}")]
     DependencyNotFound(String),
+    /// Clock target outside `CLOCK_FRAC_RANGE`.
+    #[error("clock target must be within 0.5–1.5x the node baseline")]
+    ClockOutOfRange,
+    /// Product family without an id or with generation 0.
+    #[error("product family needs an id and a generation of at least 1")]
+    InvalidFamily,
 }
 
 /// Validate a technology node.
@@ -233,6 +351,15 @@ pub fn validate_product_spec(p: &ProductSpec) -> Result<(), ValidationError> {
     if p.tdp_w < 0.0 || p.bom_usd < 0.0 {
         return Err(ValidationError::NegativeMoney);
     }
+    if !CLOCK_FRAC_RANGE.contains(&p.clock_frac) {
+        return Err(ValidationError::ClockOutOfRange);
+    }
+    if p.family
+        .as_ref()
+        .is_some_and(|f| f.id.trim().is_empty() || f.generation == 0)
+    {
+        return Err(ValidationError::InvalidFamily);
+    }
     Ok(())
 }
 
@@ -366,6 +493,7 @@ mod tests {
                                  tdp in 0.0f32..500.0,
                                  bom in 0.0f32..100_000.0) {
             let p = ProductSpec {
+                id: String::new(),
                 kind: ProductKind::CPU,
                 tech_node: TechNodeId("N7".to_string()),
                 microarch: MicroArch { ipc_index: 1.0, pipeline_depth: 10, cache_l1_kb: 64, cache_l2_mb: 1.0, chiplet: false },
@@ -373,6 +501,9 @@ mod tests {
                 perf_index: 0.5,
                 tdp_w: tdp,
                 bom_usd: bom,
+                target_segments: vec![],
+                clock_frac: 1.0,
+                family: None,
             };
             prop_assert!(validate_product_spec(&p).is_ok());
         }
@@ -384,6 +515,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn families_name_their_generations() {
+        let f = ProductFamily {
+            id: "night_hawk".into(),
+            generation: 14,
+        };
+        assert_eq!(f.marketing_name(), "Night Hawk XIV");
+        let prev = f.predecessor().unwrap();
+        assert_eq!(prev.generation, 13);
+        assert_eq!(prev.marketing_name(), "Night Hawk XIII");
+        let first = ProductFamily {
+            id: "falcon".into(),
+            generation: 1,
+        };
+        assert_eq!(first.marketing_name(), "Falcon I");
+        assert!(first.predecessor().is_none());
+        assert_eq!(roman_numeral(1994), "MCMXCIV");
+    }
+
     #[test]
     fn test_add_decimal() {
         let a = Decimal::new(10, 0);
@@ -6,6 +6,17 @@
 //! - Optimal monopoly markup under constant elasticity demand
 //! - Demand curve evaluation with optional seeded noise
 //! - Simple promotional pricing and average selling price (ASP)
+//! - Promo demand split with cannibalization of full-price sales
+//! - Bass diffusion adoption curves for new product categories
+//! - Exponential-smoothing forecasts with confidence bands
+//! - Speed-bin yield of clock targets relative to a node's baseline frequency
+//! - Multi-attribute product attractiveness (performance, price, appeal, TDP fit, brand)
+//! - Launch review scores and the appeal swing they cause
+//! - Appeal going stale while no new product launches
+//! - Market clearing of segment demand across competing sellers' stock
+//! - Greedy foundry contract portfolios that meet a service level at least expected cost
+//! - Pluggable demand, market share and unit cost models ([`DemandModel`], [`ShareModel`],
+//!   [`CostModel`]) that configs pick by spec or preset name
 
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
@@ -25,6 +36,24 @@ pub enum EconError {
     /// Numeric conversion to floating point failed.
     #[error("non-finite numeric conversion")]
     NonFinite,
+    /// Bass coefficients must be finite with innovation > 0 and imitation >= 0.
+    #[error("invalid diffusion coefficients: p={0}, q={1}")]
+    InvalidDiffusion(f64, f64),
+    /// Forecasts need at least two observations.
+    #[error("insufficient history: {0} observations")]
+    InsufficientHistory(usize),
+    /// Smoothing weights must lie in (0, 1].
+    #[error("invalid smoothing weights")]
+    InvalidSmoothing,
+    /// Clock targets must be finite and positive.
+    #[error("invalid clock target: {0}")]
+    InvalidClock(f64),
+    /// Contract portfolio inputs are inconsistent.
+    #[error("invalid contract portfolio: {0}")]
+    InvalidPortfolio(String),
+    /// A model spec has out-of-range parameters or names no known preset.
+    #[error("invalid model: {0}")]
+    InvalidModel(String),
 }
 
 /// Compute a trivial price as cost plus a margin.
@@ -166,6 +195,61 @@ pub fn promo_price(price: Decimal, discount_frac: f32) -> Result<Decimal, EconEr
     Ok(price * f)
 }
 
+/// Units sold during a promotion, split by price point.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PromoSplit {
+    /// Buyers the promo did not reach; they still pay full price.
+    pub full_price_units: u64,
+    /// Units sold at the promo price (cannibalized plus incremental).
+    pub promo_units: u64,
+    /// Buyers who would have paid full price but took the discount instead.
+    pub cannibalized_units: u64,
+}
+
+impl PromoSplit {
+    /// Units the discount added on top of the full-price baseline.
+    pub fn incremental_units(&self) -> u64 {
+        self.promo_units.saturating_sub(self.cannibalized_units)
+    }
+}
+
+/// Split `full_price_units` of baseline demand when a promo of `discount_frac` reaches
+/// `reach_frac` of the buyers.
+///
+/// Reached buyers all switch to the promo price (cannibalization), and the lower price
+/// lifts their demand along the constant-elasticity curve: promo = reached * (1-d)^ε.
+///
+/// Example:
+/// let s = promo_demand_split(1000, 0.2, -2.0, 0.5).unwrap();
+/// assert_eq!(s.full_price_units, 500);
+/// assert_eq!(s.cannibalized_units, 500);
+/// assert!(s.promo_units > 500);
+pub fn promo_demand_split(
+    full_price_units: u64,
+    discount_frac: f32,
+    elasticity: f32,
+    reach_frac: f32,
+) -> Result<PromoSplit, EconError> {
+    if !elasticity.is_finite() || elasticity >= 0.0 {
+        return Err(EconError::InvalidElasticity(elasticity));
+    }
+    if !(0.0..1.0).contains(&discount_frac) || !(0.0..=1.0).contains(&reach_frac) {
+        return Err(EconError::NonFinite);
+    }
+    let reached = ((full_price_units as f64) * reach_frac as f64).floor();
+    let lift = (1.0 - discount_frac as f64).powf(elasticity as f64);
+    let promo = (reached * lift).floor();
+    if !promo.is_finite() {
+        return Err(EconError::NonFinite);
+    }
+    let cannibalized = reached as u64;
+    Ok(PromoSplit {
+        full_price_units: full_price_units - cannibalized,
+        promo_units: promo.min(u64::MAX as f64) as u64,
+        cannibalized_units: cannibalized,
+    })
+}
+
 /// Average selling price computed as sum(p_i * q_i) / sum(q_i).
 /// Returns None when total quantity is zero.
 ///
@@ -193,12 +277,1453 @@ pub fn asp(prices: &[Decimal], quantities: &[u64]) -> Option<Decimal> {
     Some(num / den_dec)
 }
 
+/// Bass diffusion model parameters (coefficients per year).
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct BassParams {
+    /// Coefficient of innovation `p`, typically 0.01–0.03.
+    pub innovation: f64,
+    /// Coefficient of imitation `q`, typically 0.3–0.5.
+    pub imitation: f64,
+    /// Market potential `m`: total adopters over the category's life.
+    pub market_size_units: u64,
+}
+
+/// Cumulative adopted fraction of the market `t_years` after launch.
+///
+/// F(t) = (1 - e^{-(p+q)t}) / (1 + (q/p) e^{-(p+q)t}); zero before launch.
+///
+/// Example:
+/// let f = bass_adoption_frac(0.03, 0.38, 5.0).unwrap();
+/// assert!(f > 0.0 && f < 1.0);
+pub fn bass_adoption_frac(innovation: f64, imitation: f64, t_years: f64) -> Result<f64, EconError> {
+    if !(innovation.is_finite() && innovation > 0.0 && imitation.is_finite() && imitation >= 0.0) {
+        return Err(EconError::InvalidDiffusion(innovation, imitation));
+    }
+    if !t_years.is_finite() {
+        return Err(EconError::NonFinite);
+    }
+    if t_years <= 0.0 {
+        return Ok(0.0);
+    }
+    let e = (-(innovation + imitation) * t_years).exp();
+    Ok(((1.0 - e) / (1.0 + (imitation / innovation) * e)).clamp(0.0, 1.0))
+}
+
+/// New adopters between `t0_years` and `t1_years` after launch: m * (F(t1) - F(t0)), floored.
+///
+/// Example:
+/// let params = BassParams { innovation: 0.03, imitation: 0.38, market_size_units: 1_000_000 };
+/// let first_month = bass_adopters(&params, 0.0, 1.0 / 12.0).unwrap();
+/// assert!(first_month > 0);
+pub fn bass_adopters(params: &BassParams, t0_years: f64, t1_years: f64) -> Result<u64, EconError> {
+    let f0 = bass_adoption_frac(params.innovation, params.imitation, t0_years)?;
+    let f1 = bass_adoption_frac(params.innovation, params.imitation, t1_years)?;
+    let q = (params.market_size_units as f64) * (f1 - f0).max(0.0);
+    Ok(q.floor().min(params.market_size_units as f64) as u64)
+}
+
+/// Centre of the die maximum-clock distribution, relative to the node's baseline frequency.
+pub const CLOCK_BIN_CENTER: f64 = 1.24;
+/// Logistic scale of the die maximum-clock distribution.
+pub const CLOCK_BIN_SPREAD: f64 = 0.12;
+
+/// Share of dies fast enough for a `clock_frac` × baseline bin, relative to the baseline bin:
+/// above 1 for slower bins, falling off quickly for aggressive ones.
+///
+/// Die maximum clocks follow a logistic distribution around `CLOCK_BIN_CENTER`.
+///
+/// Example:
+/// assert!((clock_bin_yield(1.0).unwrap() - 1.0).abs() < 1e-12);
+/// assert!(clock_bin_yield(1.2).unwrap() < clock_bin_yield(1.1).unwrap());
+pub fn clock_bin_yield(clock_frac: f64) -> Result<f64, EconError> {
+    if !(clock_frac.is_finite() && clock_frac > 0.0) {
+        return Err(EconError::InvalidClock(clock_frac));
+    }
+    let reach = |c: f64| 1.0 / (1.0 + ((c - CLOCK_BIN_CENTER) / CLOCK_BIN_SPREAD).exp());
+    Ok(reach(clock_frac) / reach(1.0))
+}
+
+/// How much buyers in a segment weigh each product attribute; weights should sum to 1.
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AttractivenessWeights {
+    #[serde(default)]
+    pub perf: f32,
+    /// Price relative to the segment's reference price, see [`price_score`].
+    #[serde(default)]
+    pub price: f32,
+    #[serde(default)]
+    pub appeal: f32,
+    /// Fit of the product's TDP in the segment's envelope, see [`tdp_fit_score`].
+    #[serde(default)]
+    pub tdp_fit: f32,
+    /// Company reputation.
+    #[serde(default)]
+    pub brand: f32,
+}
+
+/// A product's score on each attribute, in [0, 1].
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProductAttributes {
+    pub perf: f32,
+    pub price: f32,
+    pub appeal: f32,
+    pub tdp_fit: f32,
+    pub brand: f32,
+}
+
+impl AttractivenessWeights {
+    /// Weighted attractiveness in [0, 1].
+    ///
+    /// Example:
+    /// let w = AttractivenessWeights { perf: 0.5, price: 0.5, ..Default::default() };
+    /// let a = ProductAttributes { perf: 1.0, price: 0.5, ..Default::default() };
+    /// assert!((w.score(&a) - 0.75).abs() < 1e-6);
+    pub fn score(&self, a: &ProductAttributes) -> f32 {
+        (a.perf * self.perf
+            + a.price * self.price
+            + a.appeal * self.appeal
+            + a.tdp_fit * self.tdp_fit
+            + a.brand * self.brand)
+            .clamp(0.0, 1.0)
+    }
+}
+
+/// Price attribute of selling at `price` where the segment's reference is `ref_price`:
+/// r/(1+r) with r = ref/price, so 0.5 at the reference, rising towards 1 below it.
+///
+/// Example:
+/// assert!((price_score(Decimal::new(100, 0), Decimal::new(100, 0)) - 0.5).abs() < 1e-6);
+pub fn price_score(price: Decimal, ref_price: Decimal) -> f32 {
+    let p = price.to_f32().unwrap_or(0.0).max(0.01);
+    let r = ref_price.to_f32().unwrap_or(p).max(0.01) / p;
+    r / (1.0 + r)
+}
+
+/// TDP attribute of a `tdp_w` product in a segment with envelope `(min, max)` watts: 1
+/// inside it (or without one), else the ratio of the nearest bound to the TDP.
+///
+/// Example:
+/// assert_eq!(tdp_fit_score(65.0, Some((35.0, 95.0))), 1.0);
+/// assert!((tdp_fit_score(130.0, Some((35.0, 65.0))) - 0.5).abs() < 1e-6);
+pub fn tdp_fit_score(tdp_w: f32, envelope: Option<(f32, f32)>) -> f32 {
+    match envelope {
+        Some((_, max)) if tdp_w > max => (max / tdp_w).clamp(0.0, 1.0),
+        Some((min, _)) if tdp_w < min && min > 0.0 => (tdp_w / min).clamp(0.0, 1.0),
+        _ => 1.0,
+    }
+}
+
+/// How launch reviews score a product and how far the reception moves its appeal.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ReviewModel {
+    pub perf_weight: f32,
+    pub price_weight: f32,
+    pub quality_weight: f32,
+    /// Largest reviewer disagreement, in score points either way.
+    pub noise_points: f32,
+    /// Appeal added by a perfect score (and taken by a zero one).
+    pub max_appeal_swing: f32,
+    /// Months over which the swing fades back out.
+    pub fade_months: u32,
+}
+
+impl Default for ReviewModel {
+    fn default() -> Self {
+        Self {
+            perf_weight: 0.5,
+            price_weight: 0.3,
+            quality_weight: 0.2,
+            noise_points: 8.0,
+            max_appeal_swing: 0.05,
+            fade_months: 6,
+        }
+    }
+}
+
+/// What reviewers see in a launch; each attribute is 0.5 at par with the competition.
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ReviewInputs {
+    /// Performance relative to contemporary products (1 = on par).
+    pub perf_rel: f32,
+    /// See [`price_score`].
+    pub price: f32,
+    /// Drivers, stability and thermals in [0, 1].
+    pub quality: f32,
+}
+
+impl ReviewModel {
+    /// Review score in [0, 100]: the weighted attributes (50 at par) plus `noise` in [-1, 1]
+    /// times `noise_points`.
+    ///
+    /// Example:
+    /// let par = ReviewInputs { perf_rel: 1.0, price: 0.5, quality: 0.5 };
+    /// assert!((ReviewModel::default().score(&par, 0.0) - 50.0).abs() < 1e-4);
+    pub fn score(&self, inputs: &ReviewInputs, noise: f32) -> f32 {
+        let rel = inputs.perf_rel.max(0.0);
+        let perf = rel / (1.0 + rel);
+        let total = self.perf_weight + self.price_weight + self.quality_weight;
+        let weighted = if total > 0.0 {
+            (perf * self.perf_weight
+                + inputs.price.clamp(0.0, 1.0) * self.price_weight
+                + inputs.quality.clamp(0.0, 1.0) * self.quality_weight)
+                / total
+        } else {
+            0.5
+        };
+        (weighted * 100.0 + noise.clamp(-1.0, 1.0) * self.noise_points).clamp(0.0, 100.0)
+    }
+
+    /// Appeal change of a launch reviewed at `score`: none at 50, up to `max_appeal_swing`
+    /// either way.
+    pub fn appeal_swing(&self, score: f32) -> f32 {
+        (score.clamp(0.0, 100.0) - 50.0) / 50.0 * self.max_appeal_swing
+    }
+}
+
+/// How appeal goes stale while no new product launches: after `grace_months` without one it
+/// loses `decay_frac` of its distance to `floor` each month. Appeal under the floor is left as
+/// it is.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct StalenessModel {
+    pub grace_months: u32,
+    /// 0 turns staleness off.
+    pub decay_frac: f32,
+    pub floor: f32,
+}
+
+impl Default for StalenessModel {
+    fn default() -> Self {
+        Self {
+            grace_months: 12,
+            decay_frac: 0.05,
+            floor: 0.05,
+        }
+    }
+}
+
+impl StalenessModel {
+    /// Appeal after a month that ends `months_since_launch` months after the latest launch.
+    ///
+    /// Example:
+    /// let m = StalenessModel::default();
+    /// assert_eq!(m.decay(0.45, 12), 0.45);
+    /// assert!((m.decay(0.45, 13) - 0.43).abs() < 1e-6);
+    pub fn decay(&self, appeal: f32, months_since_launch: u32) -> f32 {
+        if months_since_launch <= self.grace_months || appeal <= self.floor {
+            return appeal;
+        }
+        appeal - (appeal - self.floor) * self.decay_frac.clamp(0.0, 1.0)
+    }
+}
+
+/// One seller's offer into a segment.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MarketOffer {
+    /// Pull on buyers relative to the other offers; 0 sells nothing.
+    pub attractiveness: f32,
+    pub available_units: u64,
+}
+
+/// Clear `demand_units` across `offers`: buyers split by attractiveness among sellers with
+/// stock left, and demand a seller cannot meet spills over to the others until demand or
+/// stock runs out. Returns the units each offer sells.
+///
+/// Example:
+/// let offers = [
+///     MarketOffer { attractiveness: 1.0, available_units: 100 },
+///     MarketOffer { attractiveness: 1.0, available_units: 1_000 },
+/// ];
+/// assert_eq!(clear_market(1_000, &offers), vec![100, 900]);
+pub fn clear_market(demand_units: u64, offers: &[MarketOffer]) -> Vec<u64> {
+    let mut sold = vec![0u64; offers.len()];
+    let mut demand = demand_units;
+    // Each round either meets all remaining demand or sells someone out
+    while demand > 0 {
+        let open: Vec<usize> = (0..offers.len())
+            .filter(|&i| offers[i].attractiveness > 0.0 && sold[i] < offers[i].available_units)
+            .collect();
+        if open.is_empty() {
+            break;
+        }
+        let total: f64 = open
+            .iter()
+            .map(|&i| f64::from(offers[i].attractiveness))
+            .sum();
+        // Largest-remainder split of the demand left, in offer order on ties
+        let ideal: Vec<f64> = open
+            .iter()
+            .map(|&i| demand as f64 * f64::from(offers[i].attractiveness) / total)
+            .collect();
+        let mut want: Vec<u64> = ideal.iter().map(|x| x.floor() as u64).collect();
+        let mut rest = demand.saturating_sub(want.iter().sum());
+        let mut order: Vec<usize> = (0..open.len()).collect();
+        order.sort_by(|&a, &b| {
+            let frac = |k: usize| ideal[k] - ideal[k].floor();
+            frac(b).total_cmp(&frac(a))
+        });
+        for k in order {
+            if rest == 0 {
+                break;
+            }
+            want[k] += 1;
+            rest -= 1;
+        }
+        for (k, &i) in open.iter().enumerate() {
+            let take = want[k].min(offers[i].available_units - sold[i]);
+            sold[i] += take;
+            demand -= take;
+        }
+    }
+    sold
+}
+
+/// Smoothing weights and band width for `forecast`.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ForecastCfg {
+    /// Level weight.
+    pub alpha: f64,
+    /// Trend weight.
+    pub beta: f64,
+    /// Seasonal weight; seasonality is used once the history covers two full seasons.
+    pub gamma: f64,
+    /// Season length in periods (12 for monthly data; < 2 disables seasonality).
+    pub season: usize,
+    /// Band half-width in standard errors (1.28 = 80%, 1.96 = 95%).
+    pub z: f64,
+}
+
+impl Default for ForecastCfg {
+    fn default() -> Self {
+        Self {
+            alpha: 0.5,
+            beta: 0.2,
+            gamma: 0.3,
+            season: 12,
+            z: 1.28,
+        }
+    }
+}
+
+/// One forecast period with its confidence band.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ForecastPoint {
+    /// Periods ahead of the last observation (1-based).
+    pub step: usize,
+    pub mean: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// Forecast `horizon` periods past `history` with Holt's linear trend, adding additive
+/// seasonality (Holt-Winters) when at least two seasons are available.
+///
+/// The band is `mean ± z·σ·√step`, where σ is the RMS one-step-ahead error over the history.
+///
+/// Example:
+/// let f = forecast(&[10.0, 12.0, 14.0, 16.0], 2, &ForecastCfg::default()).unwrap();
+/// assert!(f[1].mean > f[0].mean);
+/// assert!(f[0].lower <= f[0].mean && f[0].mean <= f[0].upper);
+pub fn forecast(
+    history: &[f64],
+    horizon: usize,
+    cfg: &ForecastCfg,
+) -> Result<Vec<ForecastPoint>, EconError> {
+    if history.len() < 2 {
+        return Err(EconError::InsufficientHistory(history.len()));
+    }
+    if history.iter().any(|v| !v.is_finite()) || !cfg.z.is_finite() || cfg.z < 0.0 {
+        return Err(EconError::NonFinite);
+    }
+    let weight_ok = |w: f64| w > 0.0 && w <= 1.0;
+    if !weight_ok(cfg.alpha) || !weight_ok(cfg.beta) || !weight_ok(cfg.gamma) {
+        return Err(EconError::InvalidSmoothing);
+    }
+    let m = cfg.season;
+    let seasonal = m >= 2 && history.len() >= 2 * m;
+    let mut sq_err = 0.0;
+    let mut n_err = 0usize;
+    let (level, trend, season) = if seasonal {
+        let mean = |s: &[f64]| s.iter().sum::<f64>() / s.len() as f64;
+        let mut level = mean(&history[..m]);
+        let mut trend = (mean(&history[m..2 * m]) - level) / m as f64;
+        let mut season: Vec<f64> = history[..m].iter().map(|y| y - level).collect();
+        for (t, &y) in history.iter().enumerate().skip(m) {
+            let s = season[t % m];
+            let err = y - (level + trend + s);
+            sq_err += err * err;
+            n_err += 1;
+            let prev = level;
+            level = cfg.alpha * (y - s) + (1.0 - cfg.alpha) * (level + trend);
+            trend = cfg.beta * (level - prev) + (1.0 - cfg.beta) * trend;
+            season[t % m] = cfg.gamma * (y - level) + (1.0 - cfg.gamma) * s;
+        }
+        (level, trend, season)
+    } else {
+        let mut level = history[0];
+        let mut trend = history[1] - history[0];
+        for &y in &history[1..] {
+            let err = y - (level + trend);
+            sq_err += err * err;
+            n_err += 1;
+            let prev = level;
+            level = cfg.alpha * y + (1.0 - cfg.alpha) * (level + trend);
+            trend = cfg.beta * (level - prev) + (1.0 - cfg.beta) * trend;
+        }
+        (level, trend, Vec::new())
+    };
+    let sigma = (sq_err / n_err.max(1) as f64).sqrt();
+    let n = history.len();
+    Ok((1..=horizon)
+        .map(|h| {
+            let s = if season.is_empty() {
+                0.0
+            } else {
+                season[(n + h - 1) % m]
+            };
+            let mean = level + trend * h as f64 + s;
+            let half = cfg.z * sigma * (h as f64).sqrt();
+            ForecastPoint {
+                step: h,
+                mean,
+                lower: mean - half,
+                upper: mean + half,
+            }
+        })
+        .collect())
+}
+
+/// A foundry's standing terms for new contract capacity.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CapacityOffer {
+    pub foundry_id: String,
+    /// Volume tiers as (minimum wafers per month, cents per wafer), smallest first; a
+    /// contract's size picks one tier for all its wafers.
+    pub tiers: Vec<(u32, i64)>,
+    /// Most wafers a month the offer's contracts add up to.
+    pub max_wafers_per_month: u32,
+    /// Contract lengths on offer, in months.
+    pub durations: Vec<u32>,
+    /// Months from now until new capacity can start.
+    pub lead_months: u32,
+    /// Expected share of committed wafers delivered.
+    pub reliability: f32,
+    /// Share of delivered wafers billed whether used or not.
+    pub take_or_pay_frac: f32,
+}
+
+impl CapacityOffer {
+    /// Price per wafer for a contract of `wafers` a month; `None` below the smallest tier.
+    pub fn price_for(&self, wafers: u32) -> Option<i64> {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|(min, _)| wafers >= *min)
+            .map(|&(_, p)| p)
+    }
+}
+
+/// Inputs to `optimize_contract_portfolio`, one entry per month from now.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PortfolioProblem {
+    pub demand_wafers: Vec<u64>,
+    /// Wafers current contracts are expected to deliver.
+    pub existing_wafers: Vec<u64>,
+    pub offers: Vec<CapacityOffer>,
+    /// Share of demand wafers that expected deliveries must cover over the horizon.
+    pub service_level: f32,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProposedContract {
+    pub foundry_id: String,
+    pub wafers_per_month: u32,
+    /// Months from now until the contract starts.
+    pub start_month: u32,
+    pub months: u32,
+    pub cents_per_wafer: i64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PortfolioPlan {
+    pub contracts: Vec<ProposedContract>,
+    /// Expected bill of the proposed contracts over their whole terms.
+    pub expected_cost_cents: i64,
+    /// Share of demand wafers covered with the proposal.
+    pub service_level: f32,
+    /// The proposal reaches the requested service level.
+    pub met: bool,
+}
+
+/// Most contracts a portfolio proposes.
+const PORTFOLIO_MAX_CONTRACTS: usize = 32;
+/// Most contracts added or grown while looking for a portfolio.
+const PORTFOLIO_MAX_ROUNDS: usize = 128;
+
+/// Expected (cost in cents, demand wafers served within the horizon) of `contracts` on top
+/// of the existing ones. Each month the cheapest deliveries are used first; take-or-pay
+/// bills the unused rest. Past the horizon, demand and existing supply hold at their last
+/// month, so long terms are charged for what they would likely sit idle.
+fn portfolio_outcome(
+    problem: &PortfolioProblem,
+    offers: &[(usize, ProposedContract)],
+) -> (f64, f64) {
+    let n = problem.demand_wafers.len();
+    let end = offers
+        .iter()
+        .map(|(_, c)| (c.start_month + c.months) as usize)
+        .max()
+        .unwrap_or(0)
+        .max(n);
+    let at = |v: &[u64], t: usize| v.get(t.min(n.saturating_sub(1))).copied().unwrap_or(0) as f64;
+    let mut order: Vec<&(usize, ProposedContract)> = offers.iter().collect();
+    order.sort_by_key(|(_, c)| c.cents_per_wafer);
+    let (mut cost, mut served) = (0.0, 0.0);
+    for t in 0..end {
+        let demand = at(&problem.demand_wafers, t);
+        let existing = at(&problem.existing_wafers, t);
+        let mut need = (demand - existing).max(0.0);
+        let mut covered = demand.min(existing);
+        for (i, c) in &order {
+            if t < c.start_month as usize || t >= (c.start_month + c.months) as usize {
+                continue;
+            }
+            let offer = &problem.offers[*i];
+            let delivered = f64::from(c.wafers_per_month) * f64::from(offer.reliability);
+            let used = need.min(delivered);
+            need -= used;
+            covered += used;
+            let billed = used.max(f64::from(offer.take_or_pay_frac) * delivered);
+            cost += billed * c.cents_per_wafer as f64;
+        }
+        if t < n {
+            served += covered;
+        }
+    }
+    (cost, served)
+}
+
+type Portfolio = Vec<(usize, ProposedContract)>;
+
+/// Expected supply each month of the horizon, existing contracts plus `chosen`.
+fn portfolio_supply(problem: &PortfolioProblem, chosen: &Portfolio) -> Vec<f64> {
+    let n = problem.demand_wafers.len();
+    let mut supply: Vec<f64> = problem.existing_wafers.iter().map(|&w| w as f64).collect();
+    for (i, c) in chosen {
+        let end = ((c.start_month + c.months) as usize).min(n);
+        for s in &mut supply[(c.start_month as usize).min(end)..end] {
+            *s += f64::from(c.wafers_per_month) * f64::from(problem.offers[*i].reliability);
+        }
+    }
+    supply
+}
+
+/// Wafers a month offer `i` still has room for over `start..start + months`.
+fn offer_room(
+    problem: &PortfolioProblem,
+    chosen: &Portfolio,
+    i: usize,
+    start: u32,
+    months: u32,
+) -> u32 {
+    let booked = (start..start + months)
+        .map(|t| {
+            chosen
+                .iter()
+                .filter(|(j, c)| *j == i && (c.start_month..c.start_month + c.months).contains(&t))
+                .map(|(_, c)| c.wafers_per_month)
+                .sum::<u32>()
+        })
+        .max()
+        .unwrap_or(0);
+    problem.offers[i]
+        .max_wafers_per_month
+        .saturating_sub(booked)
+}
+
+/// `chosen` with `wafers` more from offer `i` on the given term, merged into a contract with
+/// the same terms (which may reach a cheaper tier); `None` if no tier fits.
+fn with_contract(
+    problem: &PortfolioProblem,
+    chosen: &Portfolio,
+    i: usize,
+    start: u32,
+    months: u32,
+    wafers: u32,
+) -> Option<Portfolio> {
+    let o = &problem.offers[i];
+    let mut trial = chosen.clone();
+    let same = trial
+        .iter()
+        .position(|(j, c)| *j == i && c.start_month == start && c.months == months);
+    let total = same.map_or(0, |k| trial[k].1.wafers_per_month) + wafers;
+    let price = o.price_for(total)?;
+    match same {
+        Some(k) => {
+            trial[k].1.wafers_per_month = total;
+            trial[k].1.cents_per_wafer = price;
+        }
+        None if trial.len() < PORTFOLIO_MAX_CONTRACTS => trial.push((
+            i,
+            ProposedContract {
+                foundry_id: o.foundry_id.clone(),
+                wafers_per_month: total,
+                start_month: start,
+                months,
+                cents_per_wafer: price,
+            },
+        )),
+        None => return None,
+    }
+    Some(trial)
+}
+
+/// Candidate additions: each offer and length from the first month it can reach that is
+/// short of supply, sized to each tier minimum and to the largest shortfall over the term.
+fn portfolio_moves(problem: &PortfolioProblem, chosen: &Portfolio) -> Vec<Portfolio> {
+    let n = problem.demand_wafers.len();
+    let supply = portfolio_supply(problem, chosen);
+    let gap = |t: usize| (problem.demand_wafers[t] as f64 - supply[t]).max(0.0);
+    let mut moves = Vec::new();
+    for (i, o) in problem.offers.iter().enumerate() {
+        let Some(start) = (o.lead_months as usize..n).find(|&t| gap(t) > 0.0) else {
+            continue;
+        };
+        for &months in &o.durations {
+            let peak = (start..(start + months as usize).min(n))
+                .map(gap)
+                .fold(0.0, f64::max);
+            let fit = (peak / f64::from(o.reliability)).ceil() as u32;
+            let room = offer_room(problem, chosen, i, start as u32, months);
+            let mut sizes: Vec<u32> = o.tiers.iter().map(|&(min, _)| min).collect();
+            sizes.push(fit.max(o.tiers[0].0));
+            for wafers in sizes {
+                let wafers = wafers.min(room);
+                if wafers > 0 {
+                    moves.extend(with_contract(
+                        problem,
+                        chosen,
+                        i,
+                        start as u32,
+                        months,
+                        wafers,
+                    ));
+                }
+            }
+        }
+    }
+    moves
+}
+
+/// Grow `chosen` greedily until it serves `target` of demand, then drop contracts and trim
+/// sizes while the cost falls and the service level holds.
+fn grow_portfolio(
+    problem: &PortfolioProblem,
+    mut chosen: Portfolio,
+    target: f64,
+) -> (Portfolio, f64, f64) {
+    let total: f64 = problem.demand_wafers.iter().map(|&d| d as f64).sum();
+    let level = |served: f64| if total > 0.0 { served / total } else { 1.0 };
+    let (mut cost, mut served) = portfolio_outcome(problem, &chosen);
+    for _ in 0..PORTFOLIO_MAX_ROUNDS {
+        if level(served) >= target {
+            break;
+        }
+        let best = portfolio_moves(problem, &chosen)
+            .into_iter()
+            .filter_map(|trial| {
+                let (c2, s2) = portfolio_outcome(problem, &trial);
+                (s2 > served + 1e-9).then(|| ((c2 - cost) / (s2 - served), trial, c2, s2))
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+        let Some((_, trial, c2, s2)) = best else {
+            break;
+        };
+        (chosen, cost, served) = (trial, c2, s2);
+    }
+    let floor = target.min(level(served));
+    loop {
+        let mut trials: Vec<Portfolio> = (0..chosen.len())
+            .map(|k| {
+                let mut trial = chosen.clone();
+                trial.remove(k);
+                trial
+            })
+            .collect();
+        // The smallest size of each contract that still holds the service level
+        for k in 0..chosen.len() {
+            let (i, c) = &chosen[k];
+            let (mut lo, mut hi) = (0u32, c.wafers_per_month);
+            while lo + 1 < hi {
+                let mid = (lo + hi) / 2;
+                let mut trial = chosen.clone();
+                trial[k].1.wafers_per_month = mid;
+                if level(portfolio_outcome(problem, &trial).1) >= floor {
+                    hi = mid;
+                } else {
+                    lo = mid;
+                }
+            }
+            if let Some(price) = problem.offers[*i].price_for(hi) {
+                let mut trial = chosen.clone();
+                trial[k].1.wafers_per_month = hi;
+                trial[k].1.cents_per_wafer = price;
+                trials.push(trial);
+            }
+        }
+        let best = trials
+            .into_iter()
+            .filter_map(|trial| {
+                let (c2, s2) = portfolio_outcome(problem, &trial);
+                (level(s2) >= floor && c2 < cost - 1e-9).then_some((trial, c2, s2))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        let Some((trial, c2, s2)) = best else {
+            break;
+        };
+        (chosen, cost, served) = (trial, c2, s2);
+    }
+    (chosen, cost, level(served))
+}
+
+/// Propose new foundry contracts (foundry, size, start, length and tier price) that cover
+/// `service_level` of forecast wafer demand at the least expected cost. A greedy search adds
+/// (or grows, possibly into a cheaper tier) the contract with the lowest extra cost per extra
+/// wafer served until the service level is met, then drops and trims contracts it no longer
+/// needs. It restarts from each single contract that covers an offer's first shortfall and
+/// keeps the cheapest portfolio, preferring one that meets the service level.
+///
+/// Example:
+/// let plan = optimize_contract_portfolio(&problem)?;
+/// assert!(plan.met && plan.service_level >= problem.service_level);
+pub fn optimize_contract_portfolio(problem: &PortfolioProblem) -> Result<PortfolioPlan, EconError> {
+    let bad = |m: &str| Err(EconError::InvalidPortfolio(m.into()));
+    let n = problem.demand_wafers.len();
+    if n == 0 || problem.existing_wafers.len() != n {
+        return bad("demand and existing supply need the same, non-zero number of months");
+    }
+    if !(0.0..=1.0).contains(&problem.service_level) {
+        return bad("service level must be within [0, 1]");
+    }
+    for o in &problem.offers {
+        let sorted = o.tiers.windows(2).all(|w| w[0].0 < w[1].0);
+        if o.tiers.is_empty() || !sorted || o.tiers.iter().any(|&(_, p)| p < 0) {
+            return bad("offers need price tiers in increasing size with non-negative prices");
+        }
+        if o.durations.is_empty() || o.durations.contains(&0) {
+            return bad("offers need positive contract lengths");
+        }
+        let reliable = o.reliability > 0.0 && o.reliability <= 1.0;
+        if !reliable || !(0.0..=1.0).contains(&o.take_or_pay_frac) {
+            return bad("reliability must be within (0, 1] and take-or-pay within [0, 1]");
+        }
+    }
+    let target = f64::from(problem.service_level);
+    let mut starts = vec![Vec::new()];
+    starts.extend(portfolio_moves(problem, &Vec::new()));
+    let (contracts, cost, level) = starts
+        .into_iter()
+        .map(|seed| grow_portfolio(problem, seed, target))
+        .min_by(|a, b| {
+            // Met beats unmet; then the cheaper if met, the closer if not
+            let key = |p: &(Portfolio, f64, f64)| {
+                let met = p.2 >= target;
+                (!met, if met { p.1 } else { -p.2 }, p.1)
+            };
+            key(a)
+                .partial_cmp(&key(b))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .expect("at least the empty start");
+    Ok(PortfolioPlan {
+        met: level >= target,
+        contracts: contracts.into_iter().map(|(_, c)| c).collect(),
+        expected_cost_cents: cost.round() as i64,
+        service_level: level as f32,
+    })
+}
+
+/// Units a segment buys at `price` against its reference price.
+///
+/// Implementations must be pure functions of their inputs (no clocks, globals or RNGs of
+/// their own) so a seeded run replays exactly.
+pub trait DemandModel: std::fmt::Debug + Send + Sync {
+    fn demand(
+        &self,
+        base: u64,
+        price: Decimal,
+        ref_price: Decimal,
+        elasticity: f32,
+    ) -> Result<u64, EconError>;
+}
+
+/// How a segment's demand splits across competing offers: units sold per offer, in offer
+/// order, never more than an offer has. Must be deterministic, like [`DemandModel`].
+pub trait ShareModel: std::fmt::Debug + Send + Sync {
+    fn clear(&self, demand_units: u64, offers: &[MarketOffer]) -> Vec<u64>;
+}
+
+/// What goes into the cost of one good die.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DieCost {
+    pub wafer_cost: Decimal,
+    /// Wafer area dies can be placed on, in mm².
+    pub usable_area_mm2: f32,
+    pub die_area_mm2: f32,
+    /// Fraction of dies that work and make their clock bin.
+    pub yield_frac: Decimal,
+}
+
+/// Cost of one good die. Must be deterministic, like [`DemandModel`].
+pub trait CostModel: std::fmt::Debug + Send + Sync {
+    fn unit_cost(&self, die: &DieCost) -> Decimal;
+}
+
+/// [`demand`]: constant elasticity against the reference price.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ConstantElasticity;
+
+impl DemandModel for ConstantElasticity {
+    fn demand(
+        &self,
+        base: u64,
+        price: Decimal,
+        ref_price: Decimal,
+        elasticity: f32,
+    ) -> Result<u64, EconError> {
+        demand(base, price, ref_price, elasticity)
+    }
+}
+
+/// A straight line through `base` at the reference price with the elasticity as its slope
+/// there: Q = base (1 + ε (p/p0 - 1)), floored at zero, so demand chokes off at a finite
+/// price and does not explode as prices fall.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LinearDemand;
+
+impl DemandModel for LinearDemand {
+    fn demand(
+        &self,
+        base: u64,
+        price: Decimal,
+        ref_price: Decimal,
+        elasticity: f32,
+    ) -> Result<u64, EconError> {
+        if !elasticity.is_finite() || elasticity >= 0.0 {
+            return Err(EconError::InvalidElasticity(elasticity));
+        }
+        if price <= Decimal::ZERO || ref_price <= Decimal::ZERO {
+            return Err(EconError::InvalidPrice);
+        }
+        let ratio = (price / ref_price).to_f64().ok_or(EconError::NonFinite)?;
+        let q = base as f64 * (1.0 + f64::from(elasticity) * (ratio - 1.0));
+        Ok(q.floor().clamp(0.0, u64::MAX as f64) as u64)
+    }
+}
+
+/// Constant elasticity capped at `max_frac` times the base: however far prices fall, a
+/// segment only buys so many more units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SaturatingDemand {
+    pub max_frac: f64,
+}
+
+impl DemandModel for SaturatingDemand {
+    fn demand(
+        &self,
+        base: u64,
+        price: Decimal,
+        ref_price: Decimal,
+        elasticity: f32,
+    ) -> Result<u64, EconError> {
+        let cap = (base as f64 * self.max_frac)
+            .floor()
+            .clamp(0.0, u64::MAX as f64) as u64;
+        Ok(demand(base, price, ref_price, elasticity)?.min(cap))
+    }
+}
+
+/// [`clear_market`] on attractiveness raised to `exponent`: 1 splits demand in proportion
+/// to attractiveness, higher values hand more of it to the most attractive offers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ProportionalShare {
+    pub exponent: f32,
+}
+
+impl Default for ProportionalShare {
+    fn default() -> Self {
+        Self { exponent: 1.0 }
+    }
+}
+
+impl ShareModel for ProportionalShare {
+    fn clear(&self, demand_units: u64, offers: &[MarketOffer]) -> Vec<u64> {
+        if self.exponent == 1.0 {
+            return clear_market(demand_units, offers);
+        }
+        let sharpened: Vec<MarketOffer> = offers
+            .iter()
+            .map(|o| MarketOffer {
+                attractiveness: o.attractiveness.max(0.0).powf(self.exponent),
+                ..*o
+            })
+            .collect();
+        clear_market(demand_units, &sharpened)
+    }
+}
+
+/// Wafer cost over the good dies per wafer, after `edge_loss_frac` of the usable area goes
+/// to partial dies at the edge.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WaferYieldCost {
+    pub edge_loss_frac: f32,
+}
+
+impl CostModel for WaferYieldCost {
+    fn unit_cost(&self, die: &DieCost) -> Decimal {
+        let area = die.usable_area_mm2 * (1.0 - self.edge_loss_frac);
+        let dies = ((area / die.die_area_mm2).floor() as i64).max(1);
+        let good = Decimal::from(dies) * die.yield_frac;
+        if good > Decimal::ZERO {
+            die.wafer_cost / good
+        } else {
+            die.wafer_cost
+        }
+    }
+}
+
+/// A shipped [`DemandModel`] and its parameters, as configs and mods write them.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DemandSpec {
+    #[default]
+    ConstantElasticity,
+    Linear,
+    Saturating {
+        max_frac: f64,
+    },
+}
+
+/// A shipped [`ShareModel`] and its parameters.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ShareSpec {
+    Proportional {
+        #[serde(default = "one")]
+        exponent: f32,
+    },
+}
+
+impl Default for ShareSpec {
+    fn default() -> Self {
+        Self::Proportional { exponent: 1.0 }
+    }
+}
+
+/// A shipped [`CostModel`] and its parameters.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CostSpec {
+    WaferYield {
+        #[serde(default)]
+        edge_loss_frac: f32,
+    },
+}
+
+impl Default for CostSpec {
+    fn default() -> Self {
+        Self::WaferYield {
+            edge_loss_frac: 0.0,
+        }
+    }
+}
+
+fn one() -> f32 {
+    1.0
+}
+
+impl DemandSpec {
+    pub fn build(&self) -> Result<std::sync::Arc<dyn DemandModel>, EconError> {
+        Ok(match *self {
+            Self::ConstantElasticity => std::sync::Arc::new(ConstantElasticity),
+            Self::Linear => std::sync::Arc::new(LinearDemand),
+            Self::Saturating { max_frac } => {
+                if !(max_frac.is_finite() && max_frac >= 1.0) {
+                    return Err(EconError::InvalidModel(format!(
+                        "saturating demand needs max_frac >= 1, got {max_frac}"
+                    )));
+                }
+                std::sync::Arc::new(SaturatingDemand { max_frac })
+            }
+        })
+    }
+}
+
+impl ShareSpec {
+    pub fn build(&self) -> Result<std::sync::Arc<dyn ShareModel>, EconError> {
+        match *self {
+            Self::Proportional { exponent } => {
+                if !(exponent.is_finite() && exponent > 0.0) {
+                    return Err(EconError::InvalidModel(format!(
+                        "proportional share needs exponent > 0, got {exponent}"
+                    )));
+                }
+                Ok(std::sync::Arc::new(ProportionalShare { exponent }))
+            }
+        }
+    }
+}
+
+impl CostSpec {
+    pub fn build(&self) -> Result<std::sync::Arc<dyn CostModel>, EconError> {
+        match *self {
+            Self::WaferYield { edge_loss_frac } => {
+                if !(0.0..1.0).contains(&edge_loss_frac) {
+                    return Err(EconError::InvalidModel(format!(
+                        "wafer yield cost needs edge_loss_frac in [0, 1), got {edge_loss_frac}"
+                    )));
+                }
+                Ok(std::sync::Arc::new(WaferYieldCost { edge_loss_frac }))
+            }
+        }
+    }
+}
+
+/// A model picked by config: a spec, or the name of a parameterization in
+/// [`ModelPresets`].
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum ModelChoice<S> {
+    Named(String),
+    Spec(S),
+}
+
+impl<S: Default> Default for ModelChoice<S> {
+    fn default() -> Self {
+        Self::Spec(S::default())
+    }
+}
+
+impl<S: Clone> ModelChoice<S> {
+    fn resolve(&self, presets: &std::collections::BTreeMap<String, S>) -> Result<S, EconError> {
+        match self {
+            Self::Spec(s) => Ok(s.clone()),
+            Self::Named(name) => presets
+                .get(name)
+                .cloned()
+                .ok_or_else(|| EconError::InvalidModel(format!("no model preset named {name}"))),
+        }
+    }
+}
+
+/// Which demand, share and cost models a run uses; each defaults to the built-in one.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ModelsCfg {
+    pub demand: ModelChoice<DemandSpec>,
+    pub share: ModelChoice<ShareSpec>,
+    pub cost: ModelChoice<CostSpec>,
+}
+
+impl ModelsCfg {
+    /// Check the parameters of the models given as specs; names are checked once the
+    /// presets are known, by [`Models::from_cfg`].
+    pub fn validate_specs(&self) -> Result<(), EconError> {
+        if let ModelChoice::Spec(s) = &self.demand {
+            s.build()?;
+        }
+        if let ModelChoice::Spec(s) = &self.share {
+            s.build()?;
+        }
+        if let ModelChoice::Spec(s) = &self.cost {
+            s.build()?;
+        }
+        Ok(())
+    }
+}
+
+/// Named model parameterizations, e.g. registered by mods, that a [`ModelsCfg`] can pick.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ModelPresets {
+    pub demand: std::collections::BTreeMap<String, DemandSpec>,
+    pub share: std::collections::BTreeMap<String, ShareSpec>,
+    pub cost: std::collections::BTreeMap<String, CostSpec>,
+}
+
+impl ModelPresets {
+    /// Check every preset's parameters.
+    pub fn validate(&self) -> Result<(), EconError> {
+        for s in self.demand.values() {
+            s.build()?;
+        }
+        for s in self.share.values() {
+            s.build()?;
+        }
+        for s in self.cost.values() {
+            s.build()?;
+        }
+        Ok(())
+    }
+
+    /// Add `other`'s presets; names already taken keep their first parameterization.
+    pub fn merge(&mut self, other: &ModelPresets) {
+        for (k, v) in &other.demand {
+            self.demand.entry(k.clone()).or_insert_with(|| v.clone());
+        }
+        for (k, v) in &other.share {
+            self.share.entry(k.clone()).or_insert_with(|| v.clone());
+        }
+        for (k, v) in &other.cost {
+            self.cost.entry(k.clone()).or_insert_with(|| v.clone());
+        }
+    }
+}
+
+/// The demand, share and cost models a run calls.
+#[derive(Clone, Debug)]
+pub struct Models {
+    pub demand: std::sync::Arc<dyn DemandModel>,
+    pub share: std::sync::Arc<dyn ShareModel>,
+    pub cost: std::sync::Arc<dyn CostModel>,
+}
+
+impl Default for Models {
+    fn default() -> Self {
+        Self {
+            demand: std::sync::Arc::new(ConstantElasticity),
+            share: std::sync::Arc::new(ProportionalShare::default()),
+            cost: std::sync::Arc::new(WaferYieldCost::default()),
+        }
+    }
+}
+
+impl Models {
+    /// Build the models `cfg` picks, looking names up in `presets`.
+    ///
+    /// Example:
+    /// let cfg: ModelsCfg = serde_yaml::from_str("demand: { type: linear }")?;
+    /// let models = Models::from_cfg(&cfg, &ModelPresets::default())?;
+    pub fn from_cfg(cfg: &ModelsCfg, presets: &ModelPresets) -> Result<Self, EconError> {
+        Ok(Self {
+            demand: cfg.demand.resolve(&presets.demand)?.build()?,
+            share: cfg.share.resolve(&presets.share)?.build()?,
+            cost: cfg.cost.resolve(&presets.cost)?.build()?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use proptest::prelude::*;
     use rust_decimal::Decimal;
 
+    #[test]
+    fn contract_portfolio_meets_service_level_cheaply() {
+        let offer = |id: &str, tiers: Vec<(u32, i64)>, max, reliability, lead| CapacityOffer {
+            foundry_id: id.into(),
+            tiers,
+            max_wafers_per_month: max,
+            durations: vec![6, 12, 24],
+            lead_months: lead,
+            reliability,
+            take_or_pay_frac: 1.0,
+        };
+        let mut problem = PortfolioProblem {
+            demand_wafers: vec![1_000; 12],
+            existing_wafers: vec![400; 12],
+            offers: vec![
+                offer("FND-A", vec![(100, 100), (500, 90)], 2_000, 1.0, 0),
+                offer("FND-B", vec![(100, 80)], 300, 0.8, 2),
+            ],
+            service_level: 0.95,
+        };
+        assert_eq!(problem.offers[0].price_for(499), Some(100));
+        assert_eq!(problem.offers[0].price_for(500), Some(90));
+        assert_eq!(problem.offers[0].price_for(99), None);
+        let plan = optimize_contract_portfolio(&problem).unwrap();
+        assert!(plan.met && plan.service_level >= 0.95, "{plan:?}");
+        // No dearer than one volume-tier contract sized for 95%
+        assert!(plan.expected_cost_cents <= 550 * 90 * 12, "{plan:?}");
+        assert!(plan
+            .contracts
+            .iter()
+            .all(|c| c.foundry_id == "FND-A" || c.start_month >= 2));
+        let proposed: Vec<(usize, ProposedContract)> = plan
+            .contracts
+            .iter()
+            .map(|c| (usize::from(c.foundry_id == "FND-B"), c.clone()))
+            .collect();
+        let (cost, _) = portfolio_outcome(&problem, &proposed);
+        assert_eq!(plan.expected_cost_cents, cost.round() as i64);
+
+        // Nothing needed, nothing proposed; too little on offer is flagged
+        problem.service_level = 0.3;
+        assert_eq!(optimize_contract_portfolio(&problem).unwrap().contracts, []);
+        problem.service_level = 1.0;
+        problem.offers.truncate(1);
+        problem.offers[0].max_wafers_per_month = 100;
+        let plan = optimize_contract_portfolio(&problem).unwrap();
+        assert!(!plan.met && plan.service_level < 1.0);
+        problem.offers[0].tiers = vec![(500, 90), (100, 100)];
+        assert!(matches!(
+            optimize_contract_portfolio(&problem),
+            Err(EconError::InvalidPortfolio(_))
+        ));
+    }
+
+    #[test]
+    fn attractiveness_weighs_price_and_fit() {
+        let w = AttractivenessWeights {
+            perf: 0.4,
+            price: 0.3,
+            appeal: 0.1,
+            tdp_fit: 0.1,
+            brand: 0.1,
+        };
+        let at = |price: i64, tdp: f32| ProductAttributes {
+            perf: 0.6,
+            price: price_score(Decimal::new(price, 0), Decimal::new(200, 0)),
+            appeal: 0.2,
+            tdp_fit: tdp_fit_score(tdp, Some((35.0, 95.0))),
+            brand: 0.5,
+        };
+        let base = w.score(&at(200, 65.0));
+        assert!((base - (0.24 + 0.15 + 0.02 + 0.1 + 0.05)).abs() < 1e-6);
+        // Cheaper than the reference helps, overpricing and running hot hurt
+        assert!(w.score(&at(150, 65.0)) > base);
+        assert!(w.score(&at(300, 65.0)) < base);
+        assert!(w.score(&at(200, 190.0)) < base);
+        assert_eq!(tdp_fit_score(10.0, Some((35.0, 95.0))), 10.0 / 35.0);
+        assert_eq!(tdp_fit_score(500.0, None), 1.0);
+    }
+
+    #[test]
+    fn reviews_reward_fast_cheap_solid_launches() {
+        let m = ReviewModel::default();
+        let par = ReviewInputs {
+            perf_rel: 1.0,
+            price: 0.5,
+            quality: 0.5,
+        };
+        assert!((m.score(&par, 0.0) - 50.0).abs() < 1e-4);
+        assert_eq!(m.appeal_swing(50.0), 0.0);
+        let great = ReviewInputs {
+            perf_rel: 1.5,
+            price: 0.6,
+            ..par
+        };
+        assert!(m.score(&great, 0.0) > 55.0);
+        assert!(m.appeal_swing(m.score(&great, 0.0)) > 0.0);
+        let slow = ReviewInputs {
+            perf_rel: 0.5,
+            ..par
+        };
+        assert!(m.appeal_swing(m.score(&slow, 0.0)) < 0.0);
+        // Noise moves the score by at most `noise_points`, which stays in [0, 100]
+        assert!((m.score(&par, 1.0) - 58.0).abs() < 1e-4);
+        assert!((m.score(&par, -5.0) - 42.0).abs() < 1e-4);
+        assert_eq!(m.appeal_swing(100.0), m.max_appeal_swing);
+        assert_eq!(m.score(&ReviewInputs::default(), -1.0), 0.0);
+    }
+
+    #[test]
+    fn stale_appeal_decays_toward_floor() {
+        let m = StalenessModel::default();
+        assert_eq!(m.decay(0.45, 0), 0.45);
+        assert_eq!(m.decay(0.45, m.grace_months), 0.45);
+        let mut appeal = 0.45;
+        for month in 1..=120 {
+            let next = m.decay(appeal, m.grace_months + month);
+            assert!(next < appeal && next > m.floor);
+            appeal = next;
+        }
+        assert!(appeal - m.floor < 0.001);
+        // Already under the floor, or staleness off: unchanged
+        assert_eq!(m.decay(0.01, 100), 0.01);
+        let off = StalenessModel {
+            decay_frac: 0.0,
+            ..m
+        };
+        assert_eq!(off.decay(0.45, 100), 0.45);
+    }
+
+    #[test]
+    fn clearing_splits_by_attractiveness_and_spills_over() {
+        let offer = |attractiveness: f32, available_units: u64| MarketOffer {
+            attractiveness,
+            available_units,
+        };
+        // Ample stock: shares follow attractiveness
+        assert_eq!(
+            clear_market(1_000, &[offer(3.0, 5_000), offer(1.0, 5_000)]),
+            vec![750, 250]
+        );
+        // The favourite runs short and the rest spills to the others in proportion
+        assert_eq!(
+            clear_market(
+                1_000,
+                &[offer(2.0, 200), offer(1.0, 5_000), offer(1.0, 5_000)]
+            ),
+            vec![200, 400, 400]
+        );
+        // Stock short all round leaves demand unmet; zero attractiveness sells nothing
+        assert_eq!(
+            clear_market(1_000, &[offer(1.0, 300), offer(1.0, 100), offer(0.0, 900)]),
+            vec![300, 100, 0]
+        );
+        assert_eq!(clear_market(0, &[offer(1.0, 10)]), vec![0]);
+        assert!(clear_market(10, &[]).is_empty());
+    }
+
+    #[test]
+    fn models_plug_in_by_spec_or_preset() {
+        let p0 = Decimal::new(100, 0);
+        // The defaults are the built-in functions
+        let models = Models::default();
+        for price in [50, 100, 150] {
+            let p = Decimal::new(price, 0);
+            assert_eq!(
+                models.demand.demand(1_000, p, p0, -1.5).unwrap(),
+                demand(1_000, p, p0, -1.5).unwrap()
+            );
+        }
+        let offers = [
+            MarketOffer {
+                attractiveness: 3.0,
+                available_units: 5_000,
+            },
+            MarketOffer {
+                attractiveness: 1.0,
+                available_units: 5_000,
+            },
+        ];
+        assert_eq!(
+            models.share.clear(1_000, &offers),
+            clear_market(1_000, &offers)
+        );
+
+        // Linear demand chokes off; saturating demand stops growing as prices fall
+        let linear = LinearDemand;
+        assert_eq!(linear.demand(1_000, p0, p0, -2.0).unwrap(), 1_000);
+        assert_eq!(
+            linear.demand(1_000, Decimal::new(75, 0), p0, -2.0).unwrap(),
+            1_500
+        );
+        assert_eq!(
+            linear
+                .demand(1_000, Decimal::new(200, 0), p0, -2.0)
+                .unwrap(),
+            0
+        );
+        let capped = SaturatingDemand { max_frac: 1.2 };
+        assert_eq!(
+            capped.demand(1_000, Decimal::new(10, 0), p0, -1.5).unwrap(),
+            1_200
+        );
+        // A sharper share exponent hands more to the favourite
+        assert_eq!(
+            ProportionalShare { exponent: 2.0 }.clear(1_000, &offers),
+            vec![900, 100]
+        );
+        // Edge loss raises the cost of a die
+        let die = DieCost {
+            wafer_cost: Decimal::new(1_000, 0),
+            usable_area_mm2: 1_000.0,
+            die_area_mm2: 100.0,
+            yield_frac: Decimal::ONE,
+        };
+        assert_eq!(
+            WaferYieldCost::default().unit_cost(&die),
+            Decimal::new(100, 0)
+        );
+        assert_eq!(
+            WaferYieldCost {
+                edge_loss_frac: 0.5
+            }
+            .unit_cost(&die),
+            Decimal::new(200, 0)
+        );
+
+        // Configs pick shipped models by spec or preset name; bad ones are refused
+        let mut presets = ModelPresets::default();
+        presets.demand.insert(
+            "elastic_cap".into(),
+            DemandSpec::Saturating { max_frac: 1.1 },
+        );
+        let cfg = ModelsCfg {
+            demand: ModelChoice::Named("elastic_cap".into()),
+            share: ModelChoice::Spec(ShareSpec::Proportional { exponent: 2.0 }),
+            ..Default::default()
+        };
+        let models = Models::from_cfg(&cfg, &presets).unwrap();
+        assert_eq!(
+            models
+                .demand
+                .demand(1_000, Decimal::new(10, 0), p0, -1.5)
+                .unwrap(),
+            1_100
+        );
+        assert_eq!(models.share.clear(1_000, &offers), vec![900, 100]);
+        assert!(matches!(
+            Models::from_cfg(&cfg, &ModelPresets::default()),
+            Err(EconError::InvalidModel(_))
+        ));
+        let bad = ModelsCfg {
+            demand: ModelChoice::Spec(DemandSpec::Saturating { max_frac: 0.5 }),
+            ..Default::default()
+        };
+        assert!(bad.validate_specs().is_err());
+        presets.cost.insert(
+            "lossy".into(),
+            CostSpec::WaferYield {
+                edge_loss_frac: 1.0,
+            },
+        );
+        assert!(presets.validate().is_err());
+
+        // Merging keeps the first parameterization of a name
+        let mut merged = ModelPresets::default();
+        merged.merge(&presets);
+        let mut other = ModelPresets::default();
+        other
+            .demand
+            .insert("elastic_cap".into(), DemandSpec::Linear);
+        merged.merge(&other);
+        assert_eq!(
+            merged.demand["elastic_cap"],
+            DemandSpec::Saturating { max_frac: 1.1 }
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn clearing_respects_demand_and_stock(
+            demand in 0u64..1_000_000,
+            offers in proptest::collection::vec((0.0f32..2.0, 0u64..500_000), 0..6),
+        ) {
+            let offers: Vec<MarketOffer> = offers
+                .into_iter()
+                .map(|(attractiveness, available_units)| MarketOffer { attractiveness, available_units })
+                .collect();
+            let sold = clear_market(demand, &offers);
+            let total: u64 = sold.iter().sum();
+            prop_assert!(total <= demand);
+            for (o, s) in offers.iter().zip(&sold) {
+                prop_assert!(*s <= o.available_units);
+            }
+            // Demand goes unmet only when every seller that can sell is sold out
+            let stock: u64 = offers
+                .iter()
+                .filter(|o| o.attractiveness > 0.0)
+                .map(|o| o.available_units)
+                .sum();
+            prop_assert_eq!(total, demand.min(stock));
+        }
+    }
+
     #[test]
     fn test_cost_plus() {
         let cost = Decimal::new(100, 2); // 1.00
@@ -267,6 +1792,95 @@ mod tests {
         assert_eq!(asp(&prices, &qty).unwrap(), Decimal::new(150, 2));
     }
 
+    #[test]
+    fn promo_split_cannibalizes_reached_buyers() {
+        let s = promo_demand_split(1000, 0.2, -2.0, 0.5).unwrap();
+        assert_eq!(s.full_price_units, 500);
+        assert_eq!(s.cannibalized_units, 500);
+        // 500 * 0.8^-2 = 781.25
+        assert_eq!(s.promo_units, 781);
+        assert_eq!(s.incremental_units(), 281);
+        let none = promo_demand_split(1000, 0.0, -2.0, 1.0).unwrap();
+        assert_eq!(none.incremental_units(), 0);
+        assert_eq!(none.full_price_units, 0);
+        assert!(promo_demand_split(1000, 1.0, -2.0, 1.0).is_err());
+        assert!(promo_demand_split(1000, 0.2, -2.0, 1.5).is_err());
+        assert!(promo_demand_split(1000, 0.2, 0.5, 1.0).is_err());
+    }
+
+    #[test]
+    fn clock_bins_trade_yield_for_speed() {
+        assert!((clock_bin_yield(1.0).unwrap() - 1.0).abs() < 1e-12);
+        let bins: Vec<f64> = [0.8, 0.9, 1.1, 1.2, 1.3]
+            .iter()
+            .map(|&c| clock_bin_yield(c).unwrap())
+            .collect();
+        assert!(bins.windows(2).all(|w| w[0] > w[1]));
+        // Slow bins salvage a few dies; fast bins lose many
+        assert!(bins[0] < 1.15);
+        assert!(bins[3] < 0.7);
+        assert!(clock_bin_yield(0.0).is_err());
+        assert!(clock_bin_yield(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn forecast_tracks_trend_and_seasonality() {
+        let cfg = ForecastCfg::default();
+        // Exact line: no residuals, zero-width band
+        let line: Vec<f64> = (0..8).map(|i| 100.0 + 10.0 * i as f64).collect();
+        let f = forecast(&line, 3, &cfg).unwrap();
+        assert!((f[0].mean - 180.0).abs() < 1e-9);
+        assert!((f[2].mean - 200.0).abs() < 1e-9);
+        assert_eq!(f[2].upper, f[2].mean);
+        // Trend plus a yearly bump in December, three seasons of history
+        let seasonal: Vec<f64> = (0..36)
+            .map(|i| 1000.0 + 5.0 * i as f64 + if i % 12 == 11 { 300.0 } else { 0.0 })
+            .collect();
+        let f = forecast(&seasonal, 12, &cfg).unwrap();
+        assert!(f[11].mean > f[10].mean + 200.0, "{:?}", &f[10..]);
+        // Noise widens the band with the horizon
+        let noisy: Vec<f64> = (0..20)
+            .map(|i| 50.0 + if i % 2 == 0 { 4.0 } else { -4.0 })
+            .collect();
+        let f = forecast(&noisy, 6, &cfg).unwrap();
+        assert!(f[0].upper > f[0].mean && f[0].lower < f[0].mean);
+        assert!(f[5].upper - f[5].lower > f[0].upper - f[0].lower);
+        assert!(matches!(
+            forecast(&[1.0], 3, &cfg),
+            Err(EconError::InsufficientHistory(1))
+        ));
+        let bad = ForecastCfg { alpha: 0.0, ..cfg };
+        assert!(matches!(
+            forecast(&line, 3, &bad),
+            Err(EconError::InvalidSmoothing)
+        ));
+    }
+
+    #[test]
+    fn bass_curve_is_s_shaped_and_capped() {
+        let params = BassParams {
+            innovation: 0.03,
+            imitation: 0.38,
+            market_size_units: 10_000_000,
+        };
+        assert_eq!(bass_adoption_frac(0.03, 0.38, -1.0).unwrap(), 0.0);
+        let monthly: Vec<u64> = (0..240)
+            .map(|m| bass_adopters(&params, m as f64 / 12.0, (m + 1) as f64 / 12.0).unwrap())
+            .collect();
+        // Adoption peaks at t* = ln(q/p)/(p+q) ≈ 6.2 years, not at launch or at the end
+        let peak = (0..monthly.len()).max_by_key(|&i| monthly[i]).unwrap();
+        assert!((70..80).contains(&peak), "peak month {peak}");
+        assert!(monthly[0] < monthly[peak] && monthly[239] < monthly[peak]);
+        let total: u64 = monthly.iter().sum();
+        assert!(total <= params.market_size_units);
+        assert!(total > params.market_size_units * 95 / 100, "total {total}");
+        assert_eq!(
+            bass_adoption_frac(0.0, 0.38, 1.0),
+            Err(EconError::InvalidDiffusion(0.0, 0.38))
+        );
+        assert!(bass_adoption_frac(0.03, f64::NAN, 1.0).is_err());
+    }
+
     proptest! {
         #[test]
         fn optimal_price_monotonic_in_cost(cents in 1u64..100_000) {
@@ -278,6 +1892,7 @@ mod tests {
         }
 
         #[test]
+        #[allow(clippy::unnecessary_cast)]
         fn demand_monotonic(base in 1u64..1_000_000, p in 10i64..10_000, e in -5.0f32..-1.1f32) {
             let price_low = Decimal::new(p as i64, 2);
             let price_high = Decimal::new((p+100) as i64, 2);
@@ -288,5 +1903,13 @@ mod tests {
             prop_assert!(qh <= base);
             prop_assert!(ql >= qh);
         }
+
+        #[test]
+        fn bass_fraction_monotonic_and_bounded(p in 0.001f64..0.1, q in 0.0f64..1.0, t in 0.0f64..50.0) {
+            let f0 = bass_adoption_frac(p, q, t).unwrap();
+            let f1 = bass_adoption_frac(p, q, t + 0.5).unwrap();
+            prop_assert!((0.0..=1.0).contains(&f0));
+            prop_assert!(f1 >= f0);
+        }
     }
 }
@@ -0,0 +1,16 @@
+-- IPC audit trail: commands applied to a game, written alongside each save
+
+CREATE TABLE IF NOT EXISTS ipc_audit (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  save_id INTEGER NOT NULL REFERENCES saves(id) ON DELETE CASCADE,
+  session_id TEXT NOT NULL,
+  command TEXT NOT NULL,
+  args_json TEXT NOT NULL,
+  duration_ms INTEGER NOT NULL,
+  ok INTEGER NOT NULL,
+  error TEXT,
+  suppressed INTEGER NOT NULL DEFAULT 0,
+  at_ms INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_ipc_audit_save ON ipc_audit(save_id);
@@ -0,0 +1,14 @@
+-- Promotional calendar: time-boxed discounts per market segment
+
+CREATE TABLE IF NOT EXISTS promos (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  save_id INTEGER NOT NULL REFERENCES saves(id) ON DELETE CASCADE,
+  promo_id INTEGER NOT NULL,
+  segment_id TEXT NOT NULL,
+  discount_frac REAL NOT NULL,
+  reach_frac REAL NOT NULL,
+  start TEXT NOT NULL,
+  months INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_promos_save ON promos(save_id);
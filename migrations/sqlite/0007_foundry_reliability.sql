@@ -0,0 +1,14 @@
+-- Foundry reliability: delivery-miss compensation on contracts and learned foundry scores
+
+ALTER TABLE foundry_contracts ADD COLUMN compensation_cents_per_wafer INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS foundry_scores (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  save_id INTEGER NOT NULL REFERENCES saves(id) ON DELETE CASCADE,
+  foundry_id TEXT NOT NULL,
+  score REAL NOT NULL,
+  months_observed INTEGER NOT NULL,
+  misses INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_foundry_scores_save ON foundry_scores(save_id);
@@ -0,0 +1,3 @@
+-- Capacity market: node-bound (trailing-edge) foundry contracts
+
+ALTER TABLE foundry_contracts ADD COLUMN node TEXT;
@@ -0,0 +1,11 @@
+-- Save integrity: snapshot blob checksums and per-table slice checksums recorded when a save completes
+
+ALTER TABLE snapshots ADD COLUMN checksum INTEGER;
+
+CREATE TABLE IF NOT EXISTS save_checksums (
+  save_id INTEGER NOT NULL REFERENCES saves(id) ON DELETE CASCADE,
+  table_name TEXT NOT NULL,
+  row_count INTEGER NOT NULL,
+  checksum INTEGER NOT NULL,
+  PRIMARY KEY (save_id, table_name)
+);
@@ -0,0 +1,10 @@
+-- Runtime resources saved as JSON by key (active mod effects, campaign and tutorial state)
+
+CREATE TABLE IF NOT EXISTS save_resources (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  save_id INTEGER NOT NULL REFERENCES saves(id) ON DELETE CASCADE,
+  key TEXT NOT NULL,
+  json TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_save_resources_save ON save_resources(save_id);
@@ -0,0 +1,4 @@
+-- Capacity ramps and minimum orders on foundry contracts
+
+ALTER TABLE foundry_contracts ADD COLUMN ramp_wafers TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE foundry_contracts ADD COLUMN min_order_wafers INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,4 @@
+-- Take-or-pay rollover: unused paid wafers banked as credits per contract
+
+ALTER TABLE foundry_contracts ADD COLUMN rollover INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE foundry_contracts ADD COLUMN banked_wafers INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,3 @@
+-- Stable contract ids, so a contract keeps its identity across saves
+
+ALTER TABLE foundry_contracts ADD COLUMN contract_id TEXT NOT NULL DEFAULT '';